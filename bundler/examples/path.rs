@@ -20,6 +20,7 @@ fn main() {
         Config {
             require: true,
             external_modules,
+            ..Default::default()
         },
     );
     let mut entries = HashMap::default();
@@ -42,7 +43,10 @@ fn main() {
 
     let wr = stdout();
     let mut emitter = Emitter {
-        cfg: swc_ecma_codegen::Config { minify: false },
+        cfg: swc_ecma_codegen::Config {
+            minify: false,
+            ..Default::default()
+        },
         cm: cm.clone(),
         comments: None,
         wr: Box::new(JsWriter::new(cm.clone(), "\n", wr.lock(), None)),
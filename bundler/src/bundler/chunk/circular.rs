@@ -1,10 +1,10 @@
 use super::merge::{LocalMarker, Unexporter};
 use crate::{bundler::load::TransformedModule, Bundler, Load, ModuleId, Resolve};
 use hygiene::top_level_ident_folder;
-use std::{borrow::Borrow, iter::once};
-use swc_common::{SyntaxContext, DUMMY_SP};
+use std::{collections::HashSet, iter::once};
+use swc_common::SyntaxContext;
 use swc_ecma_ast::*;
-use swc_ecma_visit::{noop_visit_type, FoldWith, Node, Visit, VisitMutWith, VisitWith};
+use swc_ecma_visit::{FoldWith, VisitMutWith};
 
 mod hygiene;
 
@@ -41,11 +41,22 @@ where
 
         let mut entry = self.process_circular_module(&modules, entry_module);
 
-        for &dep in &*circular_modules {
-            let new_module = self.merge_two_circular_modules(&modules, entry, dep);
+        // Modules in a cycle are evaluated in post-order of the import graph,
+        // so the entry of the cycle is evaluated last.
+        let mut body = vec![];
+        for id in evaluation_order(&modules, entry_id) {
+            if id == entry_id {
+                body.append(&mut entry.body);
+                continue;
+            }
 
-            entry = new_module;
+            let dep_info = self.scope.get_module(id).unwrap();
+            let dep = self
+                .process_circular_module(&modules, dep_info)
+                .fold_with(&mut Unexporter);
+            body.extend(dep.body);
         }
+        entry.body = body;
 
         // All circular modules are inlined
         circular_modules.clear();
@@ -54,29 +65,6 @@ where
         entry
     }
 
-    /// Merges `a` and `b` into one module.
-    fn merge_two_circular_modules(
-        &self,
-        circular_modules: &[TransformedModule],
-        mut entry: Module,
-        dep: ModuleId,
-    ) -> Module {
-        self.run(|| {
-            // print_hygiene("START: merge_two_circular_modules", &self.cm, &entry);
-
-            let dep_info = self.scope.get_module(dep).unwrap();
-            let mut dep = self.process_circular_module(circular_modules, dep_info);
-
-            dep = dep.fold_with(&mut Unexporter);
-
-            // Merge code
-            entry.body = merge_respecting_order(entry.body, dep.body);
-
-            // print_hygiene("END :merge_two_circular_modules", &self.cm, &entry);
-            entry
-        })
-    }
-
     ///
     ///  - Remove cicular imnports
     fn process_circular_module(
@@ -134,129 +122,51 @@ where
     }
 }
 
-/// Originally, this method should create a dependency graph, but
-fn merge_respecting_order(mut entry: Vec<ModuleItem>, mut dep: Vec<ModuleItem>) -> Vec<ModuleItem> {
-    let mut new = Vec::with_capacity(entry.len() + dep.len());
-
-    // While looping over items from entry, we check for dependency.
-    loop {
-        if entry.is_empty() {
-            log::debug!("entry is empty");
-            break;
-        }
-        let item = entry.drain(..=0).next().unwrap();
-
-        // Everything from  dep is injected
-        if dep.is_empty() {
-            log::trace!("dep is empty");
-            new.push(item);
-            new.extend(entry);
-            break;
-        }
-
-        // If the code of entry depends on dependency, we insert dependency source code
-        // at the position.
-        if let Some(pos) = dependency_index(&item, &dep) {
-            log::trace!("Found depndency: {}", pos);
-
-            new.extend(dep.drain(..=pos));
-            new.push(item);
-            continue;
-        }
-
-        // We checked the length of `dep`
-        if let Some(pos) = dependency_index(&dep[0], &[&item]) {
-            log::trace!("Found reverse depndency (index[0]): {}", pos);
-
-            new.extend(entry.drain(..=pos));
-            new.extend(dep.drain(..=0));
-            continue;
-        }
-
-        if let Some(pos) = dependency_index(&dep[0], &entry) {
-            log::trace!("Found reverse depndency: {}", pos);
-
-            new.extend(entry.drain(..=pos));
-            new.extend(dep.drain(..=0));
-            continue;
-        }
-
-        log::debug!("No dependency");
-
-        new.push(item);
-    }
-
-    // Append remaining statements.
-    new.extend(dep);
-
-    new
-}
-
-/// Searches for top level declaration which provides requirements for `deps`.
-fn dependency_index<T>(item: &ModuleItem, deps: &[T]) -> Option<usize>
-where
-    T: Borrow<ModuleItem>,
-{
-    let mut v = DepFinder { deps, idx: None };
-    item.visit_with(&Invalid { span: DUMMY_SP }, &mut v);
-    v.idx
-}
-
-struct DepFinder<'a, T>
-where
-    T: Borrow<ModuleItem>,
-{
-    deps: &'a [T],
-    idx: Option<usize>,
-}
-
-impl<T> Visit for DepFinder<'_, T>
-where
-    T: Borrow<ModuleItem>,
-{
-    noop_visit_type!();
-
-    fn visit_ident(&mut self, i: &Ident, _: &dyn Node) {
-        if self.idx.is_some() {
+/// Returns the order of evaluation of `modules`, as defined by the
+/// [spec](https://tc39.es/ecma262/#sec-innermoduleevaluation).
+///
+/// Modules are evaluated in post-order of a depth-first search starting from
+/// `entry`, following imports in source order. Modules which are not
+/// reachable from `entry` are evaluated before it.
+fn evaluation_order(modules: &[TransformedModule], entry: ModuleId) -> Vec<ModuleId> {
+    fn visit(
+        modules: &[TransformedModule],
+        id: ModuleId,
+        visited: &mut HashSet<ModuleId>,
+        order: &mut Vec<ModuleId>,
+    ) {
+        if !visited.insert(id) {
             return;
         }
-
-        for (idx, dep) in self.deps.iter().enumerate() {
-            match dep.borrow() {
-                ModuleItem::Stmt(Stmt::Decl(Decl::Class(decl))) => {
-                    log::trace!(
-                        "Decl (from dep) = {}{:?}, Ident = {}{:?}",
-                        decl.ident.sym,
-                        decl.ident.span.ctxt,
-                        i.sym,
-                        i.span.ctxt
-                    );
-                    if decl.ident.sym == i.sym && decl.ident.span.ctxt == i.span.ctxt {
-                        self.idx = Some(idx);
-                        break;
-                    }
-                }
-                _ => {}
-            }
+        let module = match modules.iter().find(|m| m.id == id) {
+            Some(v) => v,
+            None => return,
+        };
+
+        let deps = module
+            .imports
+            .specifiers
+            .iter()
+            .map(|(src, _)| src)
+            .chain(module.exports.reexports.iter().map(|(src, _)| src))
+            // `require` and `import()` are evaluated lazily.
+            .filter(|src| src.is_loaded_synchronously && src.is_unconditional);
+        for src in deps {
+            visit(modules, src.module_id, visited, order);
         }
+
+        order.push(id);
     }
 
-    fn visit_member_expr(&mut self, e: &MemberExpr, _: &dyn Node) {
-        e.obj.visit_with(e as _, self);
+    let mut visited = HashSet::new();
+    let mut order = vec![];
+    visit(modules, entry, &mut visited, &mut order);
 
-        if e.computed {
-            e.prop.visit_with(e as _, self)
-        }
+    let entry = order.pop();
+    for module in modules {
+        visit(modules, module.id, &mut visited, &mut order);
     }
+    order.extend(entry);
 
-    fn visit_class_member(&mut self, _: &ClassMember, _: &dyn Node) {}
-    fn visit_function(&mut self, _: &Function, _: &dyn Node) {}
-    fn visit_arrow_expr(&mut self, _: &ArrowExpr, _: &dyn Node) {}
-
-    /// We only search for top-level binding
-    #[inline]
-    fn visit_stmts(&mut self, _: &[Stmt], _: &dyn Node) {}
-    /// We only search for top-level binding
-    #[inline]
-    fn visit_block_stmt(&mut self, _: &BlockStmt, _: &dyn Node) {}
+    order
 }
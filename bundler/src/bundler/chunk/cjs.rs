@@ -1,6 +1,6 @@
 use super::merge::Unexporter;
-use crate::{bundler::load::TransformedModule, Bundler, Load, Resolve};
-use anyhow::Error;
+use crate::{bundler::load::TransformedModule, Bundler, Load, ModuleId, Resolve};
+use anyhow::{bail, Error};
 use std::{borrow::Cow, sync::atomic::Ordering};
 use swc_common::{Mark, SyntaxContext, DUMMY_SP};
 use swc_ecma_ast::{ModuleItem, *};
@@ -33,8 +33,11 @@ where
         entry: &mut Module,
         info: &TransformedModule,
         dep: Cow<Module>,
-        dep_mark: Mark,
+        dep_id: ModuleId,
     ) -> Result<(), Error> {
+        let dep_info = self.scope.get_module(dep_id).unwrap();
+        let dep_mark = dep_info.mark();
+
         // If src is none, all requires are transpiled
         let mut v = RequireReplacer {
             ctxt: SyntaxContext::empty().apply_mark(dep_mark),
@@ -44,6 +47,16 @@ where
         entry.body.visit_mut_with(&mut v);
 
         if v.replaced {
+            // `require()` cannot wait for the module.
+            if self.scope.is_async(dep_id) {
+                bail!(
+                    "`{}` is required by `{}`, but it uses top-level await or depends on a module \
+                     using it. Use `import` instead.",
+                    dep_info.fm.name,
+                    info.fm.name
+                )
+            }
+
             let load_var = v.load_var;
 
             {
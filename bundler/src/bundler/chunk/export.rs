@@ -1,11 +1,14 @@
 use super::merge::{LocalMarker, Unexporter};
-use crate::{bundler::load::TransformedModule, util, Bundler, Load, ModuleId, Resolve};
+use crate::{
+    bundler::{load::TransformedModule, tla::wrap_async_module},
+    util, Bundler, Load, ModuleId, Resolve,
+};
 use anyhow::{Context, Error};
 use std::mem::{replace, take};
 use swc_atoms::js_word;
 use swc_common::{Spanned, SyntaxContext, DUMMY_SP};
 use swc_ecma_ast::*;
-use swc_ecma_utils::find_ids;
+use swc_ecma_utils::{find_ids, private_ident};
 use swc_ecma_visit::{noop_visit_mut_type, FoldWith, VisitMut, VisitMutWith};
 
 impl<L, R> Bundler<'_, L, R>
@@ -18,12 +21,20 @@ where
         entry: &mut Module,
         info: &TransformedModule,
         targets: &mut Vec<ModuleId>,
-    ) -> Result<(), Error> {
+    ) -> Result<Vec<Ident>, Error> {
         entry.visit_mut_with(&mut DefaultRenamer);
 
+        // Promises of async modules, which should be awaited by `entry`.
+        let mut pending = vec![];
+
         for (src, specifiers) in &info.exports.reexports {
+            if self.scope.is_dropped(src.module_id) {
+                continue;
+            }
+
             let imported = self.scope.get_module(src.module_id).unwrap();
             assert!(imported.is_es6, "Reexports are es6 only");
+            let is_export_all = info.exports.reexports_all.contains(src);
 
             info.helpers.extend(&imported.helpers);
 
@@ -67,7 +78,14 @@ where
                                 )
                             })?;
 
-                        dep = self.drop_unused(dep, Some(&specifiers));
+                        dep = self.drop_unused(
+                            dep,
+                            if is_export_all {
+                                None
+                            } else {
+                                Some(&specifiers)
+                            },
+                        );
 
                         dep.visit_mut_with(&mut UnexportAsVar {
                             target_ctxt: SyntaxContext::empty().apply_mark(info.mark()),
@@ -86,15 +104,42 @@ where
                     })
                 },
             );
-            let dep = dep?;
+            let mut dep = dep?;
+
+            if self.scope.is_async(src.module_id) {
+                let promise = private_ident!("_evaluated");
+                dep.body = wrap_async_module(take(&mut dep.body), promise.clone());
+                pending.push(promise);
+            }
 
             // print_hygiene("entry:before-injection", &self.cm, &entry);
             // print_hygiene("dep:before-injection", &self.cm, &dep);
 
+            // `export * from './foo'` is replaced by an export of each name of
+            // `foo`, except names exported by `entry` itself.
+            let star_names = if is_export_all {
+                let ctxt = SyntaxContext::empty().apply_mark(info.mark());
+                self.star_exported_names(src.module_id, &mut vec![])
+                    .into_iter()
+                    .filter(|name| {
+                        !info
+                            .exports
+                            .items
+                            .iter()
+                            .chain(info.exports.reexports.values().flatten())
+                            .any(|s| s.local().sym() == name)
+                    })
+                    .map(|name| Ident::new(name, DUMMY_SP.with_ctxt(ctxt)))
+                    .collect()
+            } else {
+                vec![]
+            };
+
             // Replace import statement / require with module body
             let mut injector = ExportInjector {
                 imported: dep.body,
                 src: src.src.clone(),
+                star_names,
             };
             entry.body.visit_mut_with(&mut injector);
 
@@ -110,13 +155,15 @@ where
             assert_eq!(injector.imported, vec![]);
         }
 
-        Ok(())
+        Ok(pending)
     }
 }
 
 struct ExportInjector {
     imported: Vec<ModuleItem>,
     src: Str,
+    /// Names exported by `export * from src`.
+    star_names: Vec<Ident>,
 }
 
 impl VisitMut for ExportInjector {
@@ -135,7 +182,25 @@ impl VisitMut for ExportInjector {
                     if export.src.value == self.src.value =>
                 {
                     buf.extend(take(&mut self.imported));
-                    buf.push(item);
+                    if !self.star_names.is_empty() {
+                        buf.push(ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(
+                            NamedExport {
+                                span: export.span,
+                                specifiers: take(&mut self.star_names)
+                                    .into_iter()
+                                    .map(|orig| {
+                                        ExportSpecifier::Named(ExportNamedSpecifier {
+                                            span: DUMMY_SP,
+                                            orig,
+                                            exported: None,
+                                        })
+                                    })
+                                    .collect(),
+                                src: None,
+                                type_only: false,
+                            },
+                        )));
+                    }
                 }
 
                 ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(
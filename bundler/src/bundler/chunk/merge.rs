@@ -1,5 +1,9 @@
 use crate::{
-    bundler::{export::Exports, load::Specifier},
+    bundler::{
+        export::Exports,
+        load::Specifier,
+        tla::{await_stmt, hoist_imports, wrap_async_module},
+    },
     id::{Id, ModuleId},
     load::Load,
     resolve::Resolve,
@@ -15,7 +19,7 @@ use std::{
 use swc_atoms::{js_word, JsWord};
 use swc_common::{Mark, Spanned, SyntaxContext, DUMMY_SP};
 use swc_ecma_ast::*;
-use swc_ecma_utils::{find_ids, DestructuringFinder, StmtLike};
+use swc_ecma_utils::{find_ids, private_ident, DestructuringFinder, StmtLike};
 use swc_ecma_visit::{
     noop_fold_type, noop_visit_mut_type, Fold, FoldWith, VisitMut, VisitMutWith, VisitWith,
 };
@@ -43,24 +47,37 @@ where
             );
 
             let info = self.scope.get_module(entry).unwrap();
+            let mut entry: Module = (*info.module).clone();
+            self.remove_dropped_deps(&mut entry, &info);
+
             if targets.is_empty() {
-                return Ok((*info.module).clone());
+                return Ok(entry);
             }
 
             if is_circular {
                 log::info!("Circular dependency detected: ({})", info.fm.name);
                 // TODO: provide only circular imports.
-                return Ok(self.merge_circular_modules(entry, targets));
+                return Ok(self.merge_circular_modules(info.id, targets));
             }
 
-            let mut entry: Module = (*info.module).clone();
-
             log::info!("Merge: ({}){} <= {:?}", info.id, info.fm.name, targets);
 
-            self.merge_reexports(&mut entry, &info, targets)
+            // The body of an async module should wait for async dependencies.
+            let body_len = if self.scope.is_async(info.id) {
+                hoist_imports(&mut entry.body)
+            } else {
+                0
+            };
+
+            let mut pending = self
+                .merge_reexports(&mut entry, &info, targets)
                 .context("failed to merge reepxorts")?;
 
             for (src, specifiers) in &info.imports.specifiers {
+                if self.scope.is_dropped(src.module_id) {
+                    continue;
+                }
+
                 if !targets.contains(&src.module_id) {
                     // Already merged by recursive call to merge_modules.
                     log::debug!(
@@ -103,7 +120,7 @@ where
                         // Change require() call to load()
                         let dep = self.scope.get_module(src.module_id).unwrap();
 
-                        self.merge_cjs(&mut entry, &info, Cow::Borrowed(&dep.module), dep.mark())?;
+                        self.merge_cjs(&mut entry, &info, Cow::Borrowed(&dep.module), dep.id)?;
                     }
 
                     continue;
@@ -178,6 +195,14 @@ where
 
                         dep = dep.fold_with(&mut Unexporter);
 
+                        let promise = if self.scope.is_async(imported.id) {
+                            let promise = private_ident!("_evaluated");
+                            dep.body = wrap_async_module(take(&mut dep.body), promise.clone());
+                            Some(promise)
+                        } else {
+                            None
+                        };
+
                         if !specifiers.is_empty() {
                             entry.visit_mut_with(&mut LocalMarker {
                                 mark: imported.mark(),
@@ -207,13 +232,14 @@ where
                         // print_hygiene("entry:after:injection", &self.cm, &entry);
 
                         if injector.imported.is_empty() {
+                            pending.extend(promise);
                             continue;
                         }
                         dep.body = take(&mut injector.imported);
                     }
 
                     if self.config.require {
-                        self.merge_cjs(&mut entry, &info, Cow::Owned(dep), imported.mark())?;
+                        self.merge_cjs(&mut entry, &info, Cow::Owned(dep), imported.id)?;
                     }
 
                     // print_hygiene(
@@ -224,6 +250,12 @@ where
                 }
             }
 
+            if !pending.is_empty() {
+                let body = entry.body.split_off(entry.body.len() - body_len);
+                entry.body.extend(pending.into_iter().map(await_stmt));
+                entry.body.extend(body);
+            }
+
             if is_entry && self.config.require && !targets.is_empty() {
                 log::info!("Injectng remaining: {:?}", targets);
 
@@ -235,7 +267,7 @@ where
                     );
 
                     let dep = self.scope.get_module(target).unwrap();
-                    self.merge_cjs(&mut entry, &info, Cow::Borrowed(&dep.module), dep.mark())?;
+                    self.merge_cjs(&mut entry, &info, Cow::Borrowed(&dep.module), dep.id)?;
                 }
             }
 
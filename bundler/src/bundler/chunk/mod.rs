@@ -3,11 +3,13 @@ use crate::{
     id::ModuleId, load::Load, resolve::Resolve, util::IntoParallelIterator, Bundle, BundleKind,
 };
 use anyhow::{Context, Error};
-use petgraph::{graphmap::DiGraphMap, visit::Bfs};
+use petgraph::{algo::tarjan_scc, graphmap::DiGraphMap};
 #[cfg(feature = "rayon")]
 use rayon::iter::ParallelIterator;
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
+use swc_common::DUMMY_SP;
 use swc_ecma_transforms::{hygiene, optimization::simplify::dce};
+use swc_ecma_utils::HANDLER;
 use swc_ecma_visit::FoldWith;
 
 mod circular;
@@ -53,27 +55,36 @@ where
     ) -> Result<Vec<Bundle>, Error> {
         let entries = self.determine_entries(entries);
 
-        Ok(entries
+        entries
             .into_par_iter()
             .map(
                 |(kind, id, mut module_ids_to_merge): (BundleKind, ModuleId, _)| {
-                    self.run(|| {
+                    self.run(|| -> Result<_, Error> {
+                        self.scope.store_bundled_modules(
+                            id,
+                            std::iter::once(id)
+                                .chain(module_ids_to_merge.iter().copied())
+                                .collect(),
+                        );
+
                         let module = self
                             .merge_modules(id, true, &mut module_ids_to_merge)
-                            .context("failed to merge module")
-                            .unwrap(); // TODO
+                            .context("failed to merge module")?;
 
                         assert_eq!(module_ids_to_merge, vec![], "Everything should be merged");
 
                         let module = module
-                            .fold_with(&mut dce::dce(Default::default()))
+                            .fold_with(&mut dce::dce(dce::Config {
+                                comments: self.loader.comments(),
+                                ..Default::default()
+                            }))
                             .fold_with(&mut hygiene());
 
-                        Bundle { kind, id, module }
+                        Ok(Bundle { kind, id, module })
                     })
                 },
             )
-            .collect())
+            .collect()
     }
 
     fn determine_entries(
@@ -83,52 +94,74 @@ where
         let mut graph = ModuleGraph::default();
         let mut kinds = vec![];
 
+        let entry_ids = entries.values().map(|m| m.id).collect::<Vec<_>>();
+        self.hoist_cjs_modules(&entry_ids);
+        self.mark_unused_modules(&entry_ids);
+
         for (name, module) in entries.drain() {
             kinds.push((BundleKind::Named { name }, module.id));
             self.add_to_graph(&mut graph, module.id);
         }
+        self.mark_async_modules(&graph);
+
+        let entry_ids = kinds.iter().map(|(_, id)| *id).collect::<HashSet<_>>();
+
+        // Modules in cycles between entries. Each of them becomes a lib chunk
+        // which keeps its imports, so the cycle is evaluated in the same
+        // order as the original modules, whichever entry is loaded first.
+        let mut hoisted = HashSet::<ModuleId>::default();
+
+        for scc in tarjan_scc(&graph) {
+            if scc.len() < 2 {
+                continue;
+            }
+            for &id in &scc {
+                self.scope.mark_as_circular(id);
+            }
+            self.report_circular_dependency(&graph, &scc);
+
+            if scc.iter().filter(|id| entry_ids.contains(*id)).count() < 2 {
+                continue;
+            }
+
+            // Entries cannot include each other, so the remaining part of
+            // the cycle is shared by them.
+            hoisted.extend(scc.into_iter().filter(|id| !entry_ids.contains(id)));
+        }
 
         let mut metadata = HashMap::<ModuleId, Metadata>::default();
 
         // Draw dependency graph
         for (_, id) in &kinds {
-            let mut bfs = Bfs::new(&graph, *id);
-
-            while let Some(dep) = bfs.next(&graph) {
-                if dep == *id {
-                    // Useless
-                    continue;
-                }
-
+            for dep in reachable(&graph, *id, |dep| entry_ids.contains(&dep)) {
                 metadata.entry(dep).or_default().access_cnt += 1;
             }
         }
 
         // Promote modules to entry.
-        for (id, md) in &metadata {
-            if md.access_cnt > 1 {
-                // TODO: Dynamic import
-                let module = self.scope.get_module(*id).unwrap();
-                kinds.push((
-                    BundleKind::Lib {
-                        name: module.fm.name.to_string(),
-                    },
-                    *id,
-                ))
-            }
+        let libs = metadata
+            .iter()
+            .filter(|(id, md)| md.access_cnt > 1 || hoisted.contains(*id))
+            .map(|(id, _)| *id)
+            .collect::<Vec<_>>();
+
+        for id in libs {
+            // TODO: Dynamic import
+            let module = self.scope.get_module(id).unwrap();
+            kinds.push((
+                BundleKind::Lib {
+                    name: module.fm.name.to_string(),
+                },
+                id,
+            ))
         }
 
+        let roots = kinds.iter().map(|(_, id)| *id).collect::<HashSet<_>>();
         let mut chunks: HashMap<_, Vec<_>> = HashMap::default();
 
         for (_, id) in &kinds {
-            let mut bfs = Bfs::new(&graph, *id);
-
-            while let Some(dep) = bfs.next(&graph) {
-                if dep == *id {
-                    // Useless
-                    continue;
-                }
-
+            // Other chunks are imported, not merged.
+            for dep in reachable(&graph, *id, |dep| roots.contains(&dep)) {
                 if metadata.get(&dep).map(|md| md.access_cnt).unwrap_or(0) == 1 {
                     chunks.entry(*id).or_default().push(dep);
                     log::info!("Module dep: {} => {}", id, dep)
@@ -146,6 +179,36 @@ where
             .collect()
     }
 
+    /// Reports a cycle in `scc`, which is a strongly connected component of
+    /// `graph`, as a warning.
+    fn report_circular_dependency(&self, graph: &ModuleGraph, scc: &[ModuleId]) {
+        let path = find_cycle(graph, scc);
+
+        let names = path
+            .iter()
+            .map(|&id| self.scope.get_module(id).unwrap().fm.name.to_string())
+            .collect::<Vec<_>>();
+        let msg = format!("Circular dependency: {}", names.join(" -> "));
+
+        // Point to the import which closes the cycle.
+        let importer = self.scope.get_module(path[path.len() - 2]).unwrap();
+        let span = importer
+            .imports
+            .specifiers
+            .iter()
+            .map(|(src, _)| src)
+            .chain(importer.exports.reexports.iter().map(|(src, _)| src))
+            .find(|src| src.module_id == path[0])
+            .map(|src| src.src.span)
+            .unwrap_or(DUMMY_SP);
+
+        if HANDLER.is_set() {
+            HANDLER.with(|handler| handler.struct_span_warn(span, &msg).emit());
+        } else {
+            log::warn!("{}", msg);
+        }
+    }
+
     fn add_to_graph(&self, graph: &mut ModuleGraph, module_id: ModuleId) {
         let contains = graph.contains_node(module_id);
 
@@ -168,7 +231,9 @@ where
         }
 
         for (src, _) in &*m.imports.specifiers {
-            //
+            if self.scope.is_dropped(src.module_id) {
+                continue;
+            }
 
             self.add_to_graph(graph, src.module_id);
             graph.add_edge(
@@ -179,6 +244,10 @@ where
         }
 
         for (src, _) in &m.exports.reexports {
+            if self.scope.is_dropped(src.module_id) {
+                continue;
+            }
+
             self.add_to_graph(graph, src.module_id);
             graph.add_edge(
                 module_id,
@@ -189,11 +258,75 @@ where
     }
 }
 
+/// Returns modules reachable from `from` in breadth-first order, without
+/// passing through modules for which `is_boundary` returns true.
+fn reachable(
+    graph: &ModuleGraph,
+    from: ModuleId,
+    is_boundary: impl Fn(ModuleId) -> bool,
+) -> Vec<ModuleId> {
+    let mut visited = HashSet::new();
+    visited.insert(from);
+    let mut queue = VecDeque::new();
+    queue.push_back(from);
+    let mut result = vec![];
+
+    while let Some(id) = queue.pop_front() {
+        for dep in graph.neighbors(id) {
+            if is_boundary(dep) || !visited.insert(dep) {
+                continue;
+            }
+
+            result.push(dep);
+            queue.push_back(dep);
+        }
+    }
+
+    result
+}
+
+/// Finds an import path starting from and ending with the smallest module in
+/// `scc`.
+fn find_cycle(graph: &ModuleGraph, scc: &[ModuleId]) -> Vec<ModuleId> {
+    fn visit(
+        graph: &ModuleGraph,
+        scc: &[ModuleId],
+        visited: &mut HashSet<ModuleId>,
+        path: &mut Vec<ModuleId>,
+    ) -> bool {
+        let cur = *path.last().unwrap();
+
+        for dep in graph.neighbors(cur) {
+            if dep == path[0] {
+                path.push(dep);
+                return true;
+            }
+            if !scc.contains(&dep) || !visited.insert(dep) {
+                continue;
+            }
+
+            path.push(dep);
+            if visit(graph, scc, visited, path) {
+                return true;
+            }
+            path.pop();
+        }
+
+        false
+    }
+
+    let start = *scc.iter().min().unwrap();
+    let mut path = vec![start];
+    visit(graph, scc, &mut HashSet::new(), &mut path);
+    path
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::bundler::tests::suite;
+    use crate::bundler::tests::{collect_diagnostics, suite};
     use swc_common::FileName;
+    use swc_ecma_ast::*;
 
     #[test]
     fn es6_determine_entries() {
@@ -287,4 +420,129 @@ mod tests {
                 Ok(())
             });
     }
+
+    /// Evaluates `bundles` like an es module loader, starting from `entry`,
+    /// and returns the string literals passed to `console.log` in order.
+    fn evaluate(bundles: &[Bundle], entry: &str) -> Vec<String> {
+        fn visit(
+            bundles: &[Bundle],
+            name: &str,
+            visited: &mut HashSet<String>,
+            logs: &mut Vec<String>,
+        ) {
+            if !visited.insert(name.to_string()) {
+                return;
+            }
+            let bundle = bundles
+                .iter()
+                .find(|bundle| match &bundle.kind {
+                    BundleKind::Named { name: n } | BundleKind::Lib { name: n } => n == name,
+                    BundleKind::Dynamic => false,
+                })
+                .unwrap_or_else(|| panic!("failed to find chunk named {}", name));
+
+            for item in &bundle.module.body {
+                if let ModuleItem::ModuleDecl(ModuleDecl::Import(import)) = item {
+                    let dep = format!("{}.js", import.src.value.trim_start_matches("./"));
+                    visit(bundles, &dep, visited, logs);
+                }
+            }
+
+            for item in &bundle.module.body {
+                if let ModuleItem::Stmt(Stmt::Expr(ExprStmt { expr, .. })) = item {
+                    if let Expr::Call(CallExpr { args, .. }) = &**expr {
+                        if let Some(Expr::Lit(Lit::Str(s))) = args.first().map(|arg| &*arg.expr) {
+                            logs.push(s.value.to_string());
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut logs = vec![];
+        visit(bundles, entry, &mut HashSet::new(), &mut logs);
+        logs
+    }
+
+    #[test]
+    fn circular_entries() {
+        suite()
+            .file("a.js", "import './b'; console.log('a');")
+            .file("b.js", "import './a'; console.log('b');")
+            .run(|t| {
+                let mut entries = HashMap::default();
+                for name in &["a.js", "b.js"] {
+                    let module = t
+                        .bundler
+                        .load_transformed(&FileName::Real(name.into()))?
+                        .unwrap();
+                    entries.insert(name.to_string(), module);
+                }
+
+                let (bundles, warnings) = collect_diagnostics(|| t.bundler.chunk(entries));
+                let bundles = bundles?;
+
+                assert_eq!(bundles.len(), 2);
+                for bundle in &bundles {
+                    assert!(matches!(bundle.kind, BundleKind::Named { .. }));
+                }
+                assert_eq!(evaluate(&bundles, "a.js"), vec!["b", "a"]);
+                assert_eq!(evaluate(&bundles, "b.js"), vec!["a", "b"]);
+
+                assert_eq!(warnings.len(), 1, "{:?}", warnings);
+                assert!(
+                    warnings[0] == "Circular dependency: a.js -> b.js -> a.js"
+                        || warnings[0] == "Circular dependency: b.js -> a.js -> b.js",
+                    "{}",
+                    warnings[0]
+                );
+
+                Ok(())
+            });
+    }
+
+    #[test]
+    fn hoist_cycle_between_entries() {
+        suite()
+            .file("a.js", "import './common'; console.log('a');")
+            .file("b.js", "import './common'; console.log('b');")
+            .file(
+                "common.js",
+                "import './util'; import './b'; console.log('common');",
+            )
+            .file("util.js", "import './a'; console.log('util');")
+            .run(|t| {
+                let mut entries = HashMap::default();
+                for name in &["a.js", "b.js"] {
+                    let module = t
+                        .bundler
+                        .load_transformed(&FileName::Real(name.into()))?
+                        .unwrap();
+                    entries.insert(name.to_string(), module);
+                }
+
+                let (bundles, warnings) = collect_diagnostics(|| t.bundler.chunk(entries));
+                let bundles = bundles?;
+
+                assert_eq!(bundles.len(), 4);
+                let libs = bundles
+                    .iter()
+                    .filter(|bundle| matches!(bundle.kind, BundleKind::Lib { .. }))
+                    .count();
+                assert_eq!(libs, 2, "modules in the shared cycle should be hoisted");
+
+                // Same as the order of the original modules.
+                assert_eq!(evaluate(&bundles, "a.js"), vec!["util", "b", "common", "a"]);
+                assert_eq!(evaluate(&bundles, "b.js"), vec!["a", "util", "common", "b"]);
+
+                assert_eq!(warnings.len(), 1, "{:?}", warnings);
+                assert!(
+                    warnings[0].starts_with("Circular dependency: "),
+                    "{}",
+                    warnings[0]
+                );
+
+                Ok(())
+            });
+    }
 }
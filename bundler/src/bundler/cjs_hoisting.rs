@@ -0,0 +1,775 @@
+//! Scope hoisting for common js modules.
+//!
+//! Common js modules which only assign to `exports` at top level are
+//! converted to es modules, so they can be merged and tree-shaken like es
+//! modules instead of being wrapped with `__spack_require__`.
+
+use super::{
+    load::Specifier,
+    usage_analysis::{used_names, UsedExports},
+};
+use crate::{Bundler, Load, ModuleId, Resolve};
+use std::collections::HashSet;
+use swc_atoms::{js_word, JsWord};
+use swc_common::{sync::Lrc, Mark, SyntaxContext, DUMMY_SP};
+use swc_ecma_ast::*;
+use swc_ecma_transforms::optimization::simplify::dce;
+use swc_ecma_utils::{find_ids, ident::IdentLike, is_valid_ident, Id};
+use swc_ecma_visit::{noop_visit_type, Node, Visit, VisitWith};
+
+impl<L, R> Bundler<'_, L, R>
+where
+    L: Load,
+    R: Resolve,
+{
+    /// Converts es-like common js modules reachable from `entries` into es
+    /// modules.
+    ///
+    /// Entries and modules loaded using `require` or `import()` are not
+    /// converted, as they need the `exports` object. Modules without the
+    /// `__esModule` flag are not converted if the default export or the
+    /// namespace is imported, as it's `module.exports` for them.
+    pub(super) fn hoist_cjs_modules(&self, entries: &[ModuleId]) {
+        self.run(|| {
+            let mut required = HashSet::new();
+            let mut default_imported = HashSet::new();
+            let mut candidates = vec![];
+            let mut visited = HashSet::new();
+            let mut queue = entries.to_vec();
+
+            while let Some(id) = queue.pop() {
+                if !visited.insert(id) {
+                    continue;
+                }
+                let module = match self.scope.get_module(id) {
+                    Some(v) => v,
+                    None => continue,
+                };
+
+                if !module.is_es6 && !entries.contains(&id) {
+                    candidates.push(id);
+                }
+
+                for (src, specifiers) in &module.imports.specifiers {
+                    if !src.is_unconditional {
+                        required.insert(src.module_id);
+                    }
+                    if uses_default(specifiers) {
+                        default_imported.insert(src.module_id);
+                    }
+                    queue.push(src.module_id);
+                }
+                for (src, specifiers) in &module.exports.reexports {
+                    if uses_default(specifiers) {
+                        default_imported.insert(src.module_id);
+                    }
+                    queue.push(src.module_id);
+                }
+            }
+
+            let top_level_ctxt = SyntaxContext::empty().apply_mark(self.top_level_mark);
+
+            for id in candidates {
+                if required.contains(&id) {
+                    continue;
+                }
+
+                let mut info = self.scope.get_module(id).unwrap();
+                let (mut module, is_es_module) = match cjs_to_esm(&info.module, top_level_ctxt) {
+                    Some(v) => v,
+                    None => continue,
+                };
+                if !is_es_module && default_imported.contains(&id) {
+                    continue;
+                }
+
+                log::debug!("Hoisting common js module: {}", info.fm.name);
+
+                let exports = self.extract_export_info(&info.fm.name, &mut module);
+                let exports = match self.resolve_exports(&info.fm.name, exports) {
+                    Ok((exports, _)) => exports,
+                    Err(_) => continue,
+                };
+                let module = self.drop_unused(module, None);
+
+                info.has_side_effects =
+                    self.resolver
+                        .side_effects(&info.fm.name)
+                        .unwrap_or_else(|| {
+                            dce::module_has_side_effects(&module, self.loader.comments())
+                        });
+                info.module = Lrc::new(module);
+                info.exports = Lrc::new(exports);
+                info.is_es6 = true;
+
+                self.scope.store_module(info);
+            }
+        })
+    }
+}
+
+/// Returns true if `specifiers` use the default export or the namespace.
+fn uses_default(specifiers: &[Specifier]) -> bool {
+    match used_names(specifiers.iter()) {
+        UsedExports::All => true,
+        UsedExports::Some(names) => names.contains(&js_word!("default")),
+    }
+}
+
+enum Item {
+    /// `exports.foo = bar;`
+    Export(Ident, Box<Expr>),
+    /// `Object.defineProperty(exports, '__esModule', { value: true });`
+    EsModuleFlag,
+    Stmt(Stmt),
+}
+
+/// Returns [None] if `module` cannot be converted. Otherwise, returns the
+/// converted module and whether `module` has the `__esModule` flag.
+///
+/// `top_level_ctxt` is the syntax context of top-level bindings.
+fn cjs_to_esm(module: &Module, top_level_ctxt: SyntaxContext) -> Option<(Module, bool)> {
+    let mut items = vec![];
+    let mut assigns_exports = false;
+    let mut assigns_module_exports = false;
+
+    for item in &module.body {
+        let stmt = match item {
+            ModuleItem::Stmt(stmt) => stmt,
+            ModuleItem::ModuleDecl(..) => return None,
+        };
+
+        match stmt {
+            Stmt::Expr(ExprStmt { expr, .. }) if is_es_module_flag(expr) => {
+                items.push(Item::EsModuleFlag)
+            }
+            Stmt::Expr(ExprStmt { expr, .. }) => match &**expr {
+                Expr::Assign(AssignExpr {
+                    op: AssignOp::Assign,
+                    left,
+                    right,
+                    ..
+                }) => {
+                    let left = match left {
+                        PatOrExpr::Expr(e) => &**e,
+                        PatOrExpr::Pat(p) => match &**p {
+                            Pat::Expr(e) => &**e,
+                            _ => {
+                                items.push(Item::Stmt(stmt.clone()));
+                                continue;
+                            }
+                        },
+                    };
+
+                    if is_module_exports(left) {
+                        // `module.exports = { foo, bar: baz };`
+                        if assigns_module_exports {
+                            return None;
+                        }
+                        assigns_module_exports = true;
+
+                        let obj = match &**right {
+                            Expr::Object(obj) => obj,
+                            _ => return None,
+                        };
+
+                        for prop in &obj.props {
+                            match prop {
+                                PropOrSpread::Prop(prop) => match &**prop {
+                                    Prop::Shorthand(i) => {
+                                        items.push(Item::Export(
+                                            Ident::new(i.sym.clone(), DUMMY_SP),
+                                            Box::new(Expr::Ident(i.clone())),
+                                        ));
+                                    }
+                                    Prop::KeyValue(KeyValueProp { key, value }) => {
+                                        let sym = match key {
+                                            PropName::Ident(i) => i.sym.clone(),
+                                            PropName::Str(s) => s.value.clone(),
+                                            _ => return None,
+                                        };
+                                        items.push(Item::Export(
+                                            Ident::new(sym, DUMMY_SP),
+                                            value.clone(),
+                                        ));
+                                    }
+                                    _ => return None,
+                                },
+                                _ => return None,
+                            }
+                        }
+                        continue;
+                    }
+
+                    match exported_name(left) {
+                        Some(name) if name == *"__esModule" => items.push(Item::EsModuleFlag),
+                        Some(name) => {
+                            assigns_exports = true;
+                            items.push(Item::Export(Ident::new(name, DUMMY_SP), right.clone()))
+                        }
+                        None => items.push(Item::Stmt(stmt.clone())),
+                    }
+                }
+                _ => items.push(Item::Stmt(stmt.clone())),
+            },
+            _ => items.push(Item::Stmt(stmt.clone())),
+        }
+    }
+
+    // `module.exports = {}` replaces the object previously assigned to
+    // `exports`.
+    if assigns_exports && assigns_module_exports {
+        return None;
+    }
+
+    let mut exported = HashSet::new();
+    let mut usage = CjsUsageFinder {
+        found: false,
+        in_fn: false,
+        used: Default::default(),
+    };
+    // Functions are hoisted, so they can be exported before declarations.
+    let mut declared: HashSet<Id> = HashSet::new();
+    let mut assigned = AssignFinder::default();
+    let mut is_es_module = false;
+
+    for item in &items {
+        match item {
+            Item::Export(name, value) => {
+                if !exported.insert(name.sym.clone()) {
+                    return None;
+                }
+                value.visit_with(&Invalid { span: DUMMY_SP } as _, &mut usage);
+                value.visit_with(&Invalid { span: DUMMY_SP } as _, &mut assigned);
+            }
+            Item::EsModuleFlag => is_es_module = true,
+            Item::Stmt(stmt) => {
+                stmt.visit_with(&Invalid { span: DUMMY_SP } as _, &mut usage);
+                stmt.visit_with(&Invalid { span: DUMMY_SP } as _, &mut assigned);
+
+                if let Stmt::Decl(Decl::Fn(f)) = stmt {
+                    declared.insert(f.ident.to_id());
+                }
+            }
+        }
+    }
+
+    if usage.found || exported.is_empty() {
+        return None;
+    }
+
+    let mut body = Vec::with_capacity(items.len());
+
+    for item in items {
+        match item {
+            Item::Export(name, value) => {
+                if name.sym == js_word!("default") {
+                    body.push(ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(
+                        ExportDefaultExpr {
+                            span: DUMMY_SP,
+                            expr: value,
+                        },
+                    )));
+                    continue;
+                }
+
+                if !is_valid_ident(&name.sym) || name.sym.is_reserved_for_es3() {
+                    return None;
+                }
+
+                match *value {
+                    // `exports.foo = foo;`
+                    //
+                    // `export { foo }` is a live binding, so it's used only if `foo` is not
+                    // changed after the assignment to `exports.foo`.
+                    Expr::Ident(ref i)
+                        if i.sym == name.sym
+                            && i.span.ctxt == top_level_ctxt
+                            && declared.contains(&i.to_id())
+                            && !assigned.ids.contains(&i.to_id()) =>
+                    {
+                        body.push(export_named(i.clone(), None));
+                    }
+                    // `exports.foo = foo;`, where `foo` is changed later.
+                    //
+                    // The value is stored to a new binding, like
+                    //
+                    // const _foo = foo;
+                    // export { _foo as foo };
+                    Expr::Ident(ref i) if i.sym == name.sym => {
+                        let snapshot = Ident::new(
+                            format!("_{}", name.sym).into(),
+                            DUMMY_SP.apply_mark(Mark::fresh(Mark::root())),
+                        );
+                        body.push(ModuleItem::Stmt(Stmt::Decl(Decl::Var(VarDecl {
+                            span: DUMMY_SP,
+                            kind: VarDeclKind::Const,
+                            declare: false,
+                            decls: vec![VarDeclarator {
+                                span: DUMMY_SP,
+                                name: Pat::Ident(snapshot.clone()),
+                                init: Some(value),
+                                definite: false,
+                            }],
+                        }))));
+                        body.push(export_named(snapshot, Some(name)));
+                    }
+                    _ => {
+                        // We can't create a new binding if the name is used anywhere.
+                        if usage.used.contains(&name.sym) {
+                            return None;
+                        }
+
+                        body.push(ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                            span: DUMMY_SP,
+                            decl: Decl::Var(VarDecl {
+                                span: DUMMY_SP,
+                                kind: VarDeclKind::Const,
+                                declare: false,
+                                decls: vec![VarDeclarator {
+                                    span: DUMMY_SP,
+                                    name: Pat::Ident(Ident::new(
+                                        name.sym,
+                                        DUMMY_SP.with_ctxt(top_level_ctxt),
+                                    )),
+                                    init: Some(value),
+                                    definite: false,
+                                }],
+                            }),
+                        })));
+                    }
+                }
+            }
+            Item::EsModuleFlag => {}
+            Item::Stmt(stmt) => {
+                match &stmt {
+                    Stmt::Decl(Decl::Var(var)) => declared.extend(find_ids(&var.decls)),
+                    Stmt::Decl(Decl::Class(c)) => {
+                        declared.insert(c.ident.to_id());
+                    }
+                    _ => {}
+                }
+                body.push(ModuleItem::Stmt(stmt))
+            }
+        }
+    }
+
+    Some((
+        Module {
+            span: module.span,
+            body,
+            shebang: module.shebang.clone(),
+        },
+        is_es_module,
+    ))
+}
+
+/// `export { orig as exported }`
+fn export_named(orig: Ident, exported: Option<Ident>) -> ModuleItem {
+    ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(NamedExport {
+        span: DUMMY_SP,
+        specifiers: vec![ExportSpecifier::Named(ExportNamedSpecifier {
+            span: DUMMY_SP,
+            orig,
+            exported,
+        })],
+        src: None,
+        type_only: false,
+    }))
+}
+
+/// Returns true for `module.exports`.
+fn is_module_exports(e: &Expr) -> bool {
+    match e {
+        Expr::Member(MemberExpr {
+            obj: ExprOrSuper::Expr(obj),
+            prop,
+            computed: false,
+            ..
+        }) => match (&**obj, &**prop) {
+            (Expr::Ident(obj), Expr::Ident(prop)) => obj.sym == *"module" && prop.sym == *"exports",
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Returns `foo` for `exports.foo` and `module.exports.foo`.
+fn exported_name(e: &Expr) -> Option<JsWord> {
+    match e {
+        Expr::Member(MemberExpr {
+            obj: ExprOrSuper::Expr(obj),
+            prop,
+            computed,
+            ..
+        }) => {
+            let is_exports = match &**obj {
+                Expr::Ident(i) => i.sym == *"exports",
+                obj => is_module_exports(obj),
+            };
+            if !is_exports {
+                return None;
+            }
+
+            match &**prop {
+                Expr::Ident(i) if !*computed => Some(i.sym.clone()),
+                Expr::Lit(Lit::Str(s)) if *computed => Some(s.value.clone()),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Returns true for `Object.defineProperty(exports, '__esModule', { value: true
+/// })`.
+fn is_es_module_flag(e: &Expr) -> bool {
+    let call = match e {
+        Expr::Call(call) => call,
+        _ => return false,
+    };
+
+    match &call.callee {
+        ExprOrSuper::Expr(callee) => match &**callee {
+            Expr::Member(MemberExpr {
+                obj: ExprOrSuper::Expr(obj),
+                prop,
+                computed: false,
+                ..
+            }) => match (&**obj, &**prop) {
+                (Expr::Ident(obj), Expr::Ident(prop))
+                    if obj.sym == *"Object" && prop.sym == *"defineProperty" => {}
+                _ => return false,
+            },
+            _ => return false,
+        },
+        _ => return false,
+    }
+
+    match &*call.args {
+        [target, name, ..] => {
+            (match &*target.expr {
+                Expr::Ident(i) => i.sym == *"exports",
+                _ => false,
+            }) && (match &*name.expr {
+                Expr::Lit(Lit::Str(s)) => s.value == *"__esModule",
+                _ => false,
+            })
+        }
+        _ => false,
+    }
+}
+
+/// Finds bindings which are assigned or updated.
+#[derive(Default)]
+struct AssignFinder {
+    ids: HashSet<Id>,
+}
+
+impl Visit for AssignFinder {
+    noop_visit_type!();
+
+    fn visit_assign_expr(&mut self, e: &AssignExpr, _: &dyn Node) {
+        e.visit_children_with(self);
+
+        match &e.left {
+            PatOrExpr::Pat(pat) => {
+                let ids: Vec<Id> = find_ids(pat);
+                self.ids.extend(ids);
+            }
+            PatOrExpr::Expr(expr) => {
+                if let Expr::Ident(i) = &**expr {
+                    self.ids.insert(i.to_id());
+                }
+            }
+        }
+    }
+
+    fn visit_update_expr(&mut self, e: &UpdateExpr, _: &dyn Node) {
+        e.visit_children_with(self);
+
+        if let Expr::Ident(i) = &*e.arg {
+            self.ids.insert(i.to_id());
+        }
+    }
+}
+
+/// Finds usages of `module`, `exports`, `require` and top-level `this` which
+/// prevent conversion, and records all identifiers.
+struct CjsUsageFinder {
+    found: bool,
+    /// `this` in functions and classes does not refer to `exports`.
+    in_fn: bool,
+    used: HashSet<JsWord>,
+}
+
+impl Visit for CjsUsageFinder {
+    noop_visit_type!();
+
+    fn visit_ident(&mut self, i: &Ident, _: &dyn Node) {
+        match &*i.sym {
+            "module" | "exports" | "require" => self.found = true,
+            _ => {}
+        }
+
+        self.used.insert(i.sym.clone());
+    }
+
+    fn visit_this_expr(&mut self, _: &ThisExpr, _: &dyn Node) {
+        if !self.in_fn {
+            self.found = true;
+        }
+    }
+
+    fn visit_function(&mut self, f: &Function, _: &dyn Node) {
+        let old = self.in_fn;
+        self.in_fn = true;
+        f.visit_children_with(self);
+        self.in_fn = old;
+    }
+
+    fn visit_class(&mut self, c: &Class, _: &dyn Node) {
+        let old = self.in_fn;
+        self.in_fn = true;
+        c.visit_children_with(self);
+        self.in_fn = old;
+    }
+
+    fn visit_member_expr(&mut self, e: &MemberExpr, _: &dyn Node) {
+        e.obj.visit_with(e as _, self);
+
+        if e.computed {
+            e.prop.visit_with(e as _, self);
+        }
+    }
+
+    fn visit_prop_name(&mut self, n: &PropName, _: &dyn Node) {
+        match n {
+            PropName::Computed(n) => n.visit_with(n as _, self),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bundler::tests::suite;
+    use swc_common::FileName;
+
+    #[test]
+    fn convert_exports() {
+        suite().run(|t| {
+            let module = t.parse(
+                "
+                Object.defineProperty(exports, '__esModule', { value: true });
+                function foo() {}
+                exports.foo = foo;
+                exports.bar = 1;
+                exports.default = 2;
+                ",
+            );
+
+            let (module, is_es_module) = cjs_to_esm(&module, SyntaxContext::empty()).unwrap();
+            assert!(is_es_module);
+            t.assert_eq(
+                &module,
+                "
+                function foo() {}
+                export { foo };
+                export const bar = 1;
+                export default 2;
+                ",
+            );
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn convert_module_exports() {
+        suite().run(|t| {
+            let module = t.parse(
+                "
+                const foo = 1;
+                module.exports = { foo, bar: 2 };
+                ",
+            );
+
+            let (module, is_es_module) = cjs_to_esm(&module, SyntaxContext::empty()).unwrap();
+            assert!(!is_es_module);
+            t.assert_eq(
+                &module,
+                "
+                const foo = 1;
+                export { foo };
+                export const bar = 2;
+                ",
+            );
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn convert_reassigned_exports() {
+        suite().run(|t| {
+            let module = t.parse(
+                "
+                let count = 0;
+                exports.count = count;
+                function increment() {
+                    count++;
+                }
+                exports.increment = increment;
+                ",
+            );
+
+            let (module, _) = cjs_to_esm(&module, SyntaxContext::empty()).unwrap();
+            t.assert_eq(
+                &module,
+                "
+                let count = 0;
+                const _count = count;
+                export { _count as count };
+                function increment() {
+                    count++;
+                }
+                export { increment };
+                ",
+            );
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn no_convert_escaping_exports() {
+        suite().run(|t| {
+            assert!(cjs_to_esm(
+                &t.parse("exports.foo = 1; use(exports);"),
+                Default::default()
+            )
+            .is_none());
+            assert!(cjs_to_esm(
+                &t.parse("exports.foo = require('./foo');"),
+                Default::default()
+            )
+            .is_none());
+            assert!(cjs_to_esm(&t.parse("module.exports = foo;"), Default::default()).is_none());
+            assert!(cjs_to_esm(
+                &t.parse("this.foo = 1; exports.bar = 2;"),
+                Default::default()
+            )
+            .is_none());
+            assert!(cjs_to_esm(
+                &t.parse("exports.foo = 1; module.exports = { foo: 2 };"),
+                Default::default()
+            )
+            .is_none());
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn hoist_imported_cjs() {
+        suite()
+            .file(
+                "main.js",
+                "
+                import { a } from './a';
+                console.log(a);
+                ",
+            )
+            .file("a.js", "exports.a = 1;")
+            .run(|t| {
+                let module = t
+                    .bundler
+                    .load_transformed(&FileName::Real("main.js".into()))?
+                    .unwrap();
+                t.bundler.hoist_cjs_modules(&[module.id]);
+
+                let a = t
+                    .bundler
+                    .scope
+                    .get_module_by_path(&FileName::Real("a.js".into()))
+                    .unwrap();
+                assert!(a.is_es6);
+
+                Ok(())
+            });
+    }
+
+    #[test]
+    fn no_hoist_default_imported_cjs_without_flag() {
+        suite()
+            .file(
+                "main.js",
+                "
+                import a from './a';
+                import b from './b';
+                console.log(a, b);
+                ",
+            )
+            .file("a.js", "exports.a = 1;")
+            .file(
+                "b.js",
+                "
+                Object.defineProperty(exports, '__esModule', { value: true });
+                exports.default = 1;
+                ",
+            )
+            .run(|t| {
+                let module = t
+                    .bundler
+                    .load_transformed(&FileName::Real("main.js".into()))?
+                    .unwrap();
+                t.bundler.hoist_cjs_modules(&[module.id]);
+
+                let a = t
+                    .bundler
+                    .scope
+                    .get_module_by_path(&FileName::Real("a.js".into()))
+                    .unwrap();
+                assert!(!a.is_es6);
+                let b = t
+                    .bundler
+                    .scope
+                    .get_module_by_path(&FileName::Real("b.js".into()))
+                    .unwrap();
+                assert!(b.is_es6);
+
+                Ok(())
+            });
+    }
+
+    #[test]
+    fn no_hoist_required_cjs() {
+        suite()
+            .file(
+                "main.js",
+                "
+                const { a } = require('./a');
+                console.log(a);
+                ",
+            )
+            .file("a.js", "exports.a = 1;")
+            .run(|t| {
+                let module = t
+                    .bundler
+                    .load_transformed(&FileName::Real("main.js".into()))?
+                    .unwrap();
+                t.bundler.hoist_cjs_modules(&[module.id]);
+
+                let a = t
+                    .bundler
+                    .scope
+                    .get_module_by_path(&FileName::Real("a.js".into()))
+                    .unwrap();
+                assert!(!a.is_es6);
+
+                Ok(())
+            });
+    }
+}
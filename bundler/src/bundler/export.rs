@@ -2,8 +2,8 @@ use super::{
     load::{Source, Specifier},
     Bundler,
 };
-use crate::{id::Id, load::Load, resolve::Resolve};
-use std::collections::HashMap;
+use crate::{id::Id, load::Load, resolve::Resolve, ImportKind};
+use std::collections::{HashMap, HashSet};
 use swc_atoms::js_word;
 use swc_common::{FileName, SyntaxContext};
 use swc_ecma_ast::*;
@@ -45,12 +45,17 @@ where
 pub(super) struct RawExports {
     /// Key is None if it's exported from the module itself.
     pub items: HashMap<Option<Str>, Vec<Specifier>>,
+    /// Sources of `export * from 'foo'`.
+    pub all: Vec<Str>,
 }
 
 #[derive(Debug, Default)]
 pub(super) struct Exports {
     pub items: Vec<Specifier>,
     pub reexports: HashMap<Source, Vec<Specifier>>,
+    /// Sources of `export * from 'foo'`, which are also stored in
+    /// [Exports::reexports] without specifiers.
+    pub reexports_all: HashSet<Source>,
 }
 
 struct ExportFinder<'a, 'b, L, R>
@@ -79,7 +84,10 @@ where
         {
             return None;
         }
-        let path = self.bundler.resolve(self.file_name, src).ok()?;
+        let path = self
+            .bundler
+            .resolve(self.file_name, src, ImportKind::Import)
+            .ok()?;
         let (_, mark) = self.bundler.scope.module_id_gen.gen(&path);
         let ctxt = SyntaxContext::empty();
 
@@ -229,6 +237,7 @@ where
                     }
                     None => {}
                 }
+                self.info.all.push(all.src.clone());
             }
             _ => {}
         }
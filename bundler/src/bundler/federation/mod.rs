@@ -0,0 +1,731 @@
+use super::{helpers::federation_runtime, load::TransformedModule};
+use crate::{Bundle, BundleKind, Bundler, ImportKind, Load, OutputFormat, Resolve};
+use anyhow::{anyhow, bail, Context, Error};
+use std::{collections::HashMap, mem::take};
+use swc_atoms::{js_word, JsWord};
+use swc_common::{FileName, DUMMY_SP};
+use swc_ecma_ast::*;
+use swc_ecma_utils::{prepend_stmts, private_ident, quote_ident, quote_str, ExprFactory};
+use swc_ecma_visit::{
+    noop_visit_mut_type, noop_visit_type, Node, Visit, VisitMut, VisitMutWith, VisitWith,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Name of the runtime function injected by the bundler.
+const RUNTIME: &str = "__swc_federation__";
+
+/// Name of the export which marks a shared package written in common js.
+const CJS_MARKER: &str = "__swc_cjs__";
+
+/// Module federation, which allows independently built bundles to load modules
+/// from each other and to share packages at runtime.
+///
+/// Shared packages are loaded using top-level await, so this requires
+/// [OutputFormat::Es].
+#[derive(Debug, Clone)]
+pub struct FederationConfig {
+    /// Name of this build, which is recorded as the provider of shared
+    /// packages.
+    pub name: JsWord,
+    /// Name of the container entry, which exports `get` and `init`.
+    ///
+    /// The container entry is emitted only if there's an exposed module.
+    pub filename: String,
+    /// Modules exposed by the container, keyed by their public name like
+    /// `./Button`.
+    pub exposes: HashMap<JsWord, FileName>,
+    /// Packages shared with other builds, keyed by module specifier.
+    pub shared: HashMap<JsWord, SharedConfig>,
+    /// Name of the share scope, which is `default` for webpack.
+    pub share_scope: JsWord,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SharedConfig {
+    /// Version of the package provided by this build.
+    ///
+    /// If [None], the package is not bundled and another build should provide
+    /// it.
+    pub version: Option<JsWord>,
+    /// Versions accepted by this build, like `^17.0.0`. Any version is
+    /// accepted if [None].
+    pub required_version: Option<JsWord>,
+    /// If true, the highest version in the share scope is used even if it
+    /// does not satisfy [SharedConfig::required_version].
+    pub singleton: bool,
+}
+
+/// Name of the bundle for an exposed module, like `./Button`.
+fn exposed_chunk_name(name: &str) -> String {
+    format!("__federation_expose_{}.js", sanitize(name))
+}
+
+/// Name of the bundle for a shared package.
+fn shared_chunk_name(src: &str) -> String {
+    format!("__federation_shared_{}.js", sanitize(src))
+}
+
+fn sanitize(name: &str) -> String {
+    name.trim_start_matches("./")
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+impl<L, R> Bundler<'_, L, R>
+where
+    L: Load,
+    R: Resolve,
+{
+    /// Adds modules exposed by the container to `entries`.
+    pub(super) fn add_exposed_modules(
+        &self,
+        entries: &mut HashMap<String, FileName>,
+    ) -> Result<(), Error> {
+        let federation = match &self.config.federation {
+            Some(v) => v,
+            None => return Ok(()),
+        };
+        if self.config.format != OutputFormat::Es {
+            bail!("module federation is supported only by es modules")
+        }
+
+        for (name, path) in &federation.exposes {
+            entries.insert(exposed_chunk_name(name), path.clone());
+        }
+
+        Ok(())
+    }
+
+    /// Changes imports of shared packages to load them from the share scope.
+    ///
+    /// This should be called before the resolver.
+    pub(super) fn rewrite_shared_imports(
+        &self,
+        file_name: &FileName,
+        module: &mut Module,
+    ) -> Result<(), Error> {
+        let federation = match &self.config.federation {
+            Some(v) => v,
+            None => return Ok(()),
+        };
+
+        let mut v = SharedImportRewriter {
+            federation,
+            used: vec![],
+            error: None,
+        };
+        module.visit_mut_with(&mut v);
+        if let Some(err) = v.error {
+            return Err(err.context(format!("failed to rewrite shared imports of {}", file_name)));
+        }
+
+        for src in v.used {
+            self.scope.store_shared_import(src, file_name.clone());
+        }
+
+        Ok(())
+    }
+
+    /// Loads packages provided by this build. Each of them is bundled
+    /// separately, so that the share scope can load only the selected
+    /// version.
+    pub(super) fn load_shared_modules(
+        &self,
+        entries: &mut HashMap<String, TransformedModule>,
+    ) -> Result<(), Error> {
+        let federation = match &self.config.federation {
+            Some(v) => v,
+            None => return Ok(()),
+        };
+
+        // Shared packages may import other shared packages.
+        loop {
+            let mut pending = self
+                .scope
+                .shared_imports()
+                .into_iter()
+                .filter(|(src, _)| {
+                    federation.shared[src].version.is_some()
+                        && !entries.contains_key(&shared_chunk_name(src))
+                })
+                .collect::<Vec<_>>();
+            if pending.is_empty() {
+                return Ok(());
+            }
+            pending.sort_by(|a, b| (*a.0).cmp(&*b.0));
+
+            for (src, importer) in pending {
+                let path = self.resolve(&importer, &src, ImportKind::Import)?;
+                let module = self
+                    .load_transformed(&path)
+                    .with_context(|| format!("failed to load shared package `{}`", src))?
+                    .unwrap();
+                entries.insert(shared_chunk_name(&src), module);
+            }
+        }
+    }
+
+    /// Exports `module.exports` of a shared package written in common js as
+    /// the default export, because the share scope loads packages using
+    /// `import()`.
+    ///
+    /// ```js
+    /// var _module = { exports: {} };
+    /// (function (module, exports) {
+    ///     // ... body of the package
+    /// })(_module, _module.exports);
+    /// export default _module.exports;
+    /// export var __swc_cjs__ = true;
+    /// ```
+    ///
+    /// `__swc_cjs__` tells the runtime to expose properties of
+    /// `module.exports` as named exports.
+    pub(super) fn wrap_cjs_shared_module(&self, name: &str, module: &mut Module) {
+        let federation = match &self.config.federation {
+            Some(v) => v,
+            None => return,
+        };
+        if !federation
+            .shared
+            .keys()
+            .any(|src| shared_chunk_name(src) == name)
+        {
+            return;
+        }
+
+        let module_var = private_ident!("_module");
+        let exports = module_var.clone().make_member(quote_ident!("exports"));
+        let param = |name: &str| Param {
+            span: DUMMY_SP,
+            decorators: vec![],
+            pat: Pat::Ident(Ident::new(
+                name.into(),
+                DUMMY_SP.apply_mark(self.top_level_mark),
+            )),
+        };
+
+        // Imports of the package are kept at top level.
+        let (mut body, stmts): (Vec<_>, Vec<_>) = take(&mut module.body)
+            .into_iter()
+            .partition(|item| matches!(item, ModuleItem::ModuleDecl(..)));
+        let stmts = stmts
+            .into_iter()
+            .filter_map(|item| match item {
+                ModuleItem::Stmt(stmt) => Some(stmt),
+                ModuleItem::ModuleDecl(..) => None,
+            })
+            .collect::<Vec<_>>();
+
+        let factory = Expr::Fn(FnExpr {
+            ident: None,
+            function: Function {
+                params: vec![param("module"), param("exports")],
+                decorators: vec![],
+                span: DUMMY_SP,
+                body: Some(BlockStmt {
+                    span: DUMMY_SP,
+                    stmts,
+                }),
+                is_generator: false,
+                is_async: false,
+                type_params: None,
+                return_type: None,
+            },
+        });
+
+        body.push(ModuleItem::Stmt(Stmt::Decl(Decl::Var(var_decl(
+            VarDeclKind::Var,
+            vec![(
+                Pat::Ident(module_var.clone()),
+                Expr::Object(ObjectLit {
+                    span: DUMMY_SP,
+                    props: vec![PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                        key: PropName::Ident(quote_ident!("exports")),
+                        value: Box::new(Expr::Object(ObjectLit {
+                            span: DUMMY_SP,
+                            props: vec![],
+                        })),
+                    })))],
+                }),
+            )],
+        )))));
+        body.push(ModuleItem::Stmt(
+            Expr::Call(CallExpr {
+                span: DUMMY_SP,
+                callee: factory.wrap_with_paren().as_callee(),
+                args: vec![module_var.as_arg(), exports.clone().as_arg()],
+                type_args: None,
+            })
+            .into_stmt(),
+        ));
+        body.push(ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(
+            ExportDefaultExpr {
+                span: DUMMY_SP,
+                expr: Box::new(exports),
+            },
+        )));
+        body.push(ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+            span: DUMMY_SP,
+            decl: Decl::Var(var_decl(
+                VarDeclKind::Var,
+                vec![(
+                    Pat::Ident(quote_ident!(CJS_MARKER)),
+                    Expr::Lit(Lit::Bool(Bool {
+                        span: DUMMY_SP,
+                        value: true,
+                    })),
+                )],
+            )),
+        })));
+
+        module.body = body;
+    }
+
+    /// Injects the runtime and registers packages provided by this build, if
+    /// `module` uses the share scope.
+    pub(super) fn inject_federation_runtime(&self, module: &mut Module) {
+        let federation = match &self.config.federation {
+            Some(v) => v,
+            None => return,
+        };
+        let mut v = RuntimeFinder { found: false };
+        module.visit_with(&Invalid { span: DUMMY_SP } as _, &mut v);
+        if !v.found {
+            return;
+        }
+
+        let mut provided = self
+            .scope
+            .shared_imports()
+            .into_iter()
+            .filter_map(|(src, _)| {
+                let version = federation.shared[&src].version.clone()?;
+                Some((src, version))
+            })
+            .collect::<Vec<_>>();
+        provided.sort_by(|a, b| (*a.0).cmp(&*b.0));
+
+        let mut stmts = federation_runtime();
+        // __swc_federation__().register('default', 'react', '17.0.2', 'app', function
+        // () {     return import('./__federation_shared_react.js');
+        // });
+        stmts.extend(provided.into_iter().map(|(src, version)| {
+            ModuleItem::Stmt(
+                runtime_call(
+                    "register",
+                    vec![
+                        Lit::Str(quote_str!(federation.share_scope.clone())).as_arg(),
+                        Lit::Str(quote_str!(src.clone())).as_arg(),
+                        Lit::Str(quote_str!(version)).as_arg(),
+                        Lit::Str(quote_str!(federation.name.clone())).as_arg(),
+                        lazy_import(&shared_chunk_name(&src)).as_arg(),
+                    ],
+                )
+                .into_stmt(),
+            )
+        }));
+
+        prepend_stmts(&mut module.body, stmts.into_iter());
+    }
+
+    /// Creates the container entry, which exports `get` and `init`.
+    pub(super) fn container_entry(&self) -> Option<Bundle> {
+        let federation = self.config.federation.as_ref()?;
+        if federation.exposes.is_empty() {
+            return None;
+        }
+
+        self.run(|| {
+            let mut exposes = federation.exposes.keys().collect::<Vec<_>>();
+            exposes.sort_by(|a, b| (***a).cmp(&***b));
+
+            // { './Button': function () { return import('./__federation_expose_Button.js');
+            // } }
+            let modules = ObjectLit {
+                span: DUMMY_SP,
+                props: exposes
+                    .into_iter()
+                    .map(|name| {
+                        PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                            key: PropName::Str(quote_str!(name.clone())),
+                            value: Box::new(lazy_import(&exposed_chunk_name(name))),
+                        })))
+                    })
+                    .collect(),
+            };
+
+            // var _container = __swc_federation__().container('default', 'app', { ... });
+            // export var get = _container.get, init = _container.init;
+            let container = private_ident!("_container");
+            let body = vec![
+                ModuleItem::Stmt(Stmt::Decl(Decl::Var(var_decl(
+                    VarDeclKind::Var,
+                    vec![(
+                        Pat::Ident(container.clone()),
+                        runtime_call(
+                            "container",
+                            vec![
+                                Lit::Str(quote_str!(federation.share_scope.clone())).as_arg(),
+                                Lit::Str(quote_str!(federation.name.clone())).as_arg(),
+                                Expr::Object(modules).as_arg(),
+                            ],
+                        ),
+                    )],
+                )))),
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                    span: DUMMY_SP,
+                    decl: Decl::Var(var_decl(
+                        VarDeclKind::Var,
+                        ["get", "init"]
+                            .iter()
+                            .map(|name| {
+                                (
+                                    Pat::Ident(quote_ident!(*name)),
+                                    container.clone().make_member(quote_ident!(*name)),
+                                )
+                            })
+                            .collect(),
+                    )),
+                })),
+            ];
+
+            let mut module = Module {
+                span: DUMMY_SP,
+                body,
+                shebang: None,
+            };
+            self.inject_federation_runtime(&mut module);
+
+            let (id, _) = self.scope.module_id_gen.gen(&FileName::Custom(format!(
+                "federation:{}",
+                federation.filename
+            )));
+
+            Some(Bundle {
+                kind: BundleKind::Named {
+                    name: federation.filename.clone(),
+                },
+                id,
+                module,
+            })
+        })
+    }
+}
+
+/// `__swc_federation__().method(args)`
+fn runtime_call(method: &str, args: Vec<ExprOrSpread>) -> Expr {
+    let runtime = Expr::Call(CallExpr {
+        span: DUMMY_SP,
+        callee: quote_ident!(RUNTIME).as_callee(),
+        args: vec![],
+        type_args: None,
+    });
+
+    Expr::Call(CallExpr {
+        span: DUMMY_SP,
+        callee: runtime.make_member(quote_ident!(method)).as_callee(),
+        args,
+        type_args: None,
+    })
+}
+
+/// `function () { return import(src); }`
+fn lazy_import(file_name: &str) -> Expr {
+    let import = Expr::Call(CallExpr {
+        span: DUMMY_SP,
+        callee: quote_ident!("import").as_callee(),
+        args: vec![Lit::Str(quote_str!(format!("./{}", file_name))).as_arg()],
+        type_args: None,
+    });
+
+    Expr::Fn(FnExpr {
+        ident: None,
+        function: Function {
+            params: vec![],
+            decorators: vec![],
+            span: DUMMY_SP,
+            body: Some(BlockStmt {
+                span: DUMMY_SP,
+                stmts: vec![Stmt::Return(ReturnStmt {
+                    span: DUMMY_SP,
+                    arg: Some(Box::new(import)),
+                })],
+            }),
+            is_generator: false,
+            is_async: false,
+            type_params: None,
+            return_type: None,
+        },
+    })
+}
+
+fn var_decl(kind: VarDeclKind, decls: Vec<(Pat, Expr)>) -> VarDecl {
+    VarDecl {
+        span: DUMMY_SP,
+        kind,
+        declare: false,
+        decls: decls
+            .into_iter()
+            .map(|(name, init)| VarDeclarator {
+                span: DUMMY_SP,
+                name,
+                init: Some(Box::new(init)),
+                definite: false,
+            })
+            .collect(),
+    }
+}
+
+/// Binds exports of a shared package, which is loaded by `init`.
+///
+/// ```js
+/// const ns = await __swc_federation__().load(...);
+/// const { default: React, useState } = ns;
+/// ```
+fn bind_shared(
+    namespace: Option<Ident>,
+    props: Vec<(JsWord, Ident)>,
+    init: Expr,
+) -> Vec<ModuleItem> {
+    let destructure = |init: Expr| {
+        let pat = Pat::Object(ObjectPat {
+            span: DUMMY_SP,
+            props: props
+                .iter()
+                .map(|(key, local)| {
+                    ObjectPatProp::KeyValue(KeyValuePatProp {
+                        key: PropName::Ident(quote_ident!(key.clone())),
+                        value: Box::new(Pat::Ident(local.clone())),
+                    })
+                })
+                .collect(),
+            optional: false,
+            type_ann: None,
+        });
+        ModuleItem::Stmt(Stmt::Decl(Decl::Var(var_decl(
+            VarDeclKind::Const,
+            vec![(pat, init)],
+        ))))
+    };
+
+    match namespace {
+        Some(ns) => {
+            let mut items = vec![ModuleItem::Stmt(Stmt::Decl(Decl::Var(var_decl(
+                VarDeclKind::Const,
+                vec![(Pat::Ident(ns.clone()), init)],
+            ))))];
+            if !props.is_empty() {
+                items.push(destructure(Expr::Ident(ns)));
+            }
+            items
+        }
+        None if props.is_empty() => vec![ModuleItem::Stmt(init.into_stmt())],
+        None => vec![destructure(init)],
+    }
+}
+
+/// Changes imports, reexports, `require` calls and `import()` of shared
+/// packages to use the share scope.
+struct SharedImportRewriter<'a> {
+    federation: &'a FederationConfig,
+    /// Shared packages used by the module.
+    used: Vec<JsWord>,
+    error: Option<Error>,
+}
+
+impl SharedImportRewriter<'_> {
+    /// `__swc_federation__().method('default', 'react', '^17.0.0', true)`
+    fn call(&mut self, method: &str, src: &Str) -> Option<Expr> {
+        let config = self.federation.shared.get(&src.value)?;
+        if !self.used.contains(&src.value) {
+            self.used.push(src.value.clone());
+        }
+
+        Some(runtime_call(
+            method,
+            vec![
+                Lit::Str(quote_str!(self.federation.share_scope.clone())).as_arg(),
+                Lit::Str(quote_str!(src.value.clone())).as_arg(),
+                Lit::Str(quote_str!(config
+                    .required_version
+                    .clone()
+                    .unwrap_or_else(|| "*".into())))
+                .as_arg(),
+                Lit::Bool(Bool {
+                    span: DUMMY_SP,
+                    value: config.singleton,
+                })
+                .as_arg(),
+            ],
+        ))
+    }
+
+    /// `await __swc_federation__().load(...)`
+    fn load(&mut self, src: &Str) -> Option<Expr> {
+        let call = self.call("load", src)?;
+        Some(Expr::Await(AwaitExpr {
+            span: src.span,
+            arg: Box::new(call),
+        }))
+    }
+}
+
+impl VisitMut for SharedImportRewriter<'_> {
+    noop_visit_mut_type!();
+
+    fn visit_mut_expr(&mut self, e: &mut Expr) {
+        e.visit_mut_children_with(self);
+
+        let call = match e {
+            Expr::Call(call) if call.args.len() == 1 => call,
+            _ => return,
+        };
+        let method = match &call.callee {
+            ExprOrSuper::Expr(callee) => match &**callee {
+                Expr::Ident(Ident {
+                    sym: js_word!("require"),
+                    ..
+                }) => "require",
+                Expr::Ident(Ident {
+                    sym: js_word!("import"),
+                    ..
+                }) => "load",
+                _ => return,
+            },
+            _ => return,
+        };
+        let src = match &*call.args[0].expr {
+            Expr::Lit(Lit::Str(s)) if call.args[0].spread.is_none() => s.clone(),
+            _ => return,
+        };
+
+        if let Some(new) = self.call(method, &src) {
+            *e = new;
+        }
+    }
+
+    fn visit_mut_module_items(&mut self, items: &mut Vec<ModuleItem>) {
+        items.visit_mut_children_with(self);
+
+        let mut buf = Vec::with_capacity(items.len());
+        for item in take(items) {
+            match item {
+                ModuleItem::ModuleDecl(ModuleDecl::Import(import)) => {
+                    let init = match self.load(&import.src) {
+                        Some(v) => v,
+                        None => {
+                            buf.push(ModuleItem::ModuleDecl(ModuleDecl::Import(import)));
+                            continue;
+                        }
+                    };
+
+                    let mut namespace = None;
+                    let mut props = vec![];
+                    for s in import.specifiers {
+                        match s {
+                            ImportSpecifier::Named(s) => props
+                                .push((s.imported.unwrap_or_else(|| s.local.clone()).sym, s.local)),
+                            ImportSpecifier::Default(s) => {
+                                props.push((js_word!("default"), s.local))
+                            }
+                            ImportSpecifier::Namespace(s) => namespace = Some(s.local),
+                        }
+                    }
+
+                    buf.extend(bind_shared(namespace, props, init));
+                }
+
+                ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(export)) => {
+                    let init = match export.src.as_ref().and_then(|src| self.load(src)) {
+                        Some(v) => v,
+                        None => {
+                            buf.push(ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(export)));
+                            continue;
+                        }
+                    };
+
+                    let mut namespace = None;
+                    let mut props = vec![];
+                    let mut specifiers = vec![];
+                    for s in export.specifiers {
+                        let (local, exported) = match s {
+                            ExportSpecifier::Named(s) => {
+                                let local = private_ident!(s.orig.span, s.orig.sym.clone());
+                                props.push((s.orig.sym.clone(), local.clone()));
+                                (local, s.exported.unwrap_or(s.orig))
+                            }
+                            // export foo from 'react';
+                            ExportSpecifier::Default(s) => {
+                                let local = private_ident!(s.exported.span, s.exported.sym.clone());
+                                props.push((js_word!("default"), local.clone()));
+                                (local, s.exported)
+                            }
+                            ExportSpecifier::Namespace(s) => {
+                                let local = private_ident!(s.name.span, s.name.sym.clone());
+                                namespace = Some(local.clone());
+                                (local, s.name)
+                            }
+                        };
+                        specifiers.push(ExportSpecifier::Named(ExportNamedSpecifier {
+                            span: DUMMY_SP,
+                            orig: local,
+                            exported: Some(exported),
+                        }));
+                    }
+
+                    buf.extend(bind_shared(namespace, props, init));
+                    buf.push(ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(
+                        NamedExport {
+                            span: export.span,
+                            specifiers,
+                            src: None,
+                            type_only: false,
+                        },
+                    )));
+                }
+
+                ModuleItem::ModuleDecl(ModuleDecl::ExportAll(export))
+                    if self.federation.shared.contains_key(&export.src.value) =>
+                {
+                    // Exports of a shared package are not known until runtime.
+                    if self.error.is_none() {
+                        self.error = Some(anyhow!(
+                            "`export * from '{}'` is not supported for shared packages",
+                            export.src.value
+                        ));
+                    }
+                    buf.push(ModuleItem::ModuleDecl(ModuleDecl::ExportAll(export)));
+                }
+
+                _ => buf.push(item),
+            }
+        }
+
+        *items = buf;
+    }
+}
+
+struct RuntimeFinder {
+    found: bool,
+}
+
+impl Visit for RuntimeFinder {
+    noop_visit_type!();
+
+    fn visit_ident(&mut self, i: &Ident, _: &dyn Node) {
+        if &*i.sym == RUNTIME {
+            self.found = true;
+        }
+    }
+
+    fn visit_member_expr(&mut self, e: &MemberExpr, _: &dyn Node) {
+        e.obj.visit_with(e as _, self);
+
+        if e.computed {
+            e.prop.visit_with(e as _, self);
+        }
+    }
+}
@@ -0,0 +1,229 @@
+use super::{FederationConfig, SharedConfig};
+use crate::{bundler::tests::suite, Bundle, BundleKind, ImportKind, Plugin};
+use anyhow::Error;
+use std::collections::HashMap;
+use swc_common::FileName;
+use swc_ecma_ast::*;
+
+/// Resolves packages like `react` to `react.js`.
+struct Packages;
+
+impl Plugin for Packages {
+    fn name(&self) -> &str {
+        "packages"
+    }
+
+    fn resolve_id(
+        &self,
+        specifier: &str,
+        _: Option<&FileName>,
+        _: ImportKind,
+    ) -> Result<Option<FileName>, Error> {
+        if specifier.starts_with('.') {
+            return Ok(None);
+        }
+        Ok(Some(FileName::Real(format!("{}.js", specifier).into())))
+    }
+}
+
+fn config() -> FederationConfig {
+    let mut shared = HashMap::default();
+    shared.insert(
+        "react".into(),
+        SharedConfig {
+            version: Some("17.0.2".into()),
+            required_version: Some("^17.0.0".into()),
+            singleton: true,
+        },
+    );
+
+    FederationConfig {
+        name: "app".into(),
+        filename: "remoteEntry.js".into(),
+        exposes: Default::default(),
+        shared,
+        share_scope: "default".into(),
+    }
+}
+
+fn find<'a>(bundles: &'a [Bundle], name: &str) -> &'a Bundle {
+    bundles
+        .iter()
+        .find(|bundle| bundle.kind == BundleKind::Named { name: name.into() })
+        .unwrap_or_else(|| panic!("failed to find bundle named {}", name))
+}
+
+fn has_runtime(bundle: &Bundle) -> bool {
+    bundle.module.body.iter().any(|item| match item {
+        ModuleItem::Stmt(Stmt::Decl(Decl::Fn(f))) => &*f.ident.sym == "__swc_federation__",
+        _ => false,
+    })
+}
+
+#[test]
+fn shared_package_is_loaded_from_share_scope() {
+    suite()
+        .file(
+            "main.js",
+            "
+            import React, { useState } from 'react';
+            console.log(React, useState);
+            ",
+        )
+        .file(
+            "react.js",
+            "
+            export default 'react';
+            export const useState = 1;
+            ",
+        )
+        .plugin(Packages)
+        .federation(config())
+        .run(|t| {
+            let mut entries = HashMap::default();
+            entries.insert("main.js".to_string(), FileName::Real("main.js".into()));
+
+            let bundles = t.bundler.bundle(entries)?;
+            assert_eq!(bundles.len(), 2);
+
+            let main = find(&bundles, "main.js");
+            assert!(has_runtime(main));
+            assert!(
+                !main
+                    .module
+                    .body
+                    .iter()
+                    .any(|item| matches!(item, ModuleItem::ModuleDecl(ModuleDecl::Import(..)))),
+                "shared package should not be imported directly"
+            );
+
+            let react = find(&bundles, "__federation_shared_react.js");
+            assert!(!has_runtime(react));
+
+            Ok(())
+        });
+}
+
+#[test]
+fn container_entry() {
+    let mut config = config();
+    config
+        .exposes
+        .insert("./Button".into(), FileName::Real("button.js".into()));
+
+    suite()
+        .file(
+            "button.js",
+            "
+            import React from 'react';
+            export const Button = () => React;
+            ",
+        )
+        .file("react.js", "export default 'react';")
+        .plugin(Packages)
+        .federation(config)
+        .run(|t| {
+            let bundles = t.bundler.bundle(HashMap::default())?;
+            assert_eq!(bundles.len(), 3);
+
+            assert!(has_runtime(find(&bundles, "__federation_expose_Button.js")));
+            find(&bundles, "__federation_shared_react.js");
+
+            let container = find(&bundles, "remoteEntry.js");
+            assert!(has_runtime(container));
+            let exported = container
+                .module
+                .body
+                .iter()
+                .filter_map(|item| match item {
+                    ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                        decl: Decl::Var(v),
+                        ..
+                    })) => Some(v.decls.iter()),
+                    _ => None,
+                })
+                .flatten()
+                .map(|decl| match &decl.name {
+                    Pat::Ident(i) => &*i.sym,
+                    _ => unreachable!(),
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(exported, vec!["get", "init"]);
+
+            Ok(())
+        });
+}
+
+#[test]
+fn export_all_from_shared_package() {
+    suite()
+        .file("main.js", "export * from 'react';")
+        .file("react.js", "export default 'react';")
+        .plugin(Packages)
+        .federation(config())
+        .run(|t| {
+            let mut entries = HashMap::default();
+            entries.insert("main.js".to_string(), FileName::Real("main.js".into()));
+
+            assert!(t.bundler.bundle(entries).is_err());
+
+            Ok(())
+        });
+}
+
+#[test]
+fn cjs_shared_package_exports_module_exports() {
+    suite()
+        .file(
+            "main.js",
+            "
+            import React, { useState } from 'react';
+            console.log(React, useState);
+            ",
+        )
+        .file(
+            "react.js",
+            "
+            function useState() {}
+            exports.useState = useState;
+            exports.version = '17.0.2';
+            ",
+        )
+        .plugin(Packages)
+        .federation(config())
+        .run(|t| {
+            let mut entries = HashMap::default();
+            entries.insert("main.js".to_string(), FileName::Real("main.js".into()));
+
+            let bundles = t.bundler.bundle(entries)?;
+            let react = find(&bundles, "__federation_shared_react.js");
+
+            let exports = react
+                .module
+                .body
+                .iter()
+                .filter_map(|item| match item {
+                    ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(..)) => Some("default"),
+                    ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                        decl: Decl::Var(v),
+                        ..
+                    })) => match &v.decls[0].name {
+                        Pat::Ident(i) => Some(&*i.sym),
+                        _ => None,
+                    },
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(exports, vec!["default", "__swc_cjs__"]);
+
+            // The body of the package is wrapped, so `exports` is not a global.
+            assert!(!react.module.body.iter().any(|item| match item {
+                ModuleItem::Stmt(Stmt::Expr(ExprStmt { expr, .. })) => {
+                    matches!(&**expr, Expr::Assign(..))
+                }
+                _ => false,
+            }));
+
+            Ok(())
+        });
+}
@@ -1,4 +1,4 @@
-use crate::{hash::calc_hash, Bundle, BundleKind, Bundler, Load, Resolve};
+use crate::{hash::calc_hash, Bundle, BundleKind, Bundler, ImportKind, Load, Resolve};
 use anyhow::Error;
 use relative_path::RelativePath;
 use std::{
@@ -17,23 +17,52 @@ where
     /// This method do
     ///
     /// - inject helpers
+    /// - export `module.exports` of shared packages written in common js
+    /// - inject the runtime of module federation
     /// - rename chunks
+    /// - convert bundles to [Config::format]
+    ///
+    /// [Config::format]: crate::Config::format
     pub(super) fn finalize(&self, bundles: Vec<Bundle>) -> Result<Vec<Bundle>, Error> {
         self.run(|| {
             let mut new = Vec::with_capacity(bundles.len());
             let mut renamed = HashMap::default();
 
             for mut bundle in bundles {
+                if let BundleKind::Named { ref name } = bundle.kind {
+                    let is_es6 = self
+                        .scope
+                        .get_module(bundle.id)
+                        .map_or(true, |module| module.is_es6);
+                    if !is_es6 {
+                        self.wrap_cjs_shared_module(name, &mut bundle.module);
+                    }
+                }
+                self.inject_federation_runtime(&mut bundle.module);
+
                 match bundle.kind {
-                    BundleKind::Named { .. } => {
-                        // Inject helpers
-                        let helpers = self
+                    BundleKind::Named { ref name } => {
+                        let module = self
                             .scope
                             .get_module(bundle.id)
-                            .expect("module should exist at this point")
-                            .helpers;
+                            .expect("module should exist at this point");
+
+                        // Entries are imported by lib chunks if they are in a cycle.
+                        if let FileName::Real(path) = &module.fm.name {
+                            let mut file_name = PathBuf::from(name);
+                            if file_name.extension().is_none() {
+                                if let Some(ext) = path.extension() {
+                                    file_name.set_extension(ext);
+                                }
+                            }
+                            let new_name = path.with_file_name(file_name);
+                            renamed.insert(path.clone(), new_name.to_string_lossy().to_string());
+                        }
 
-                        helpers.append_to(&mut bundle.module.body);
+                        // Inject helpers
+                        module
+                            .helpers
+                            .append_to(self.helper_mark, &mut bundle.module.body);
 
                         new.push(Bundle { ..bundle });
                     }
@@ -79,61 +108,77 @@ where
                 }
             }
 
-            if new.len() == 1 {
-                return Ok(new);
+            if new.len() != 1 {
+                new = self.rename_imports(new, &renamed);
             }
 
-            new = new.move_map(|bundle| {
-                let path = match self.scope.get_module(bundle.id).unwrap().fm.name {
-                    FileName::Real(ref v) => v.clone(),
-                    _ => {
-                        log::error!("Cannot rename: not a real file");
-                        return bundle;
-                    }
-                };
+            new.into_iter()
+                .map(|bundle| {
+                    let module = self.apply_format(bundle.module)?;
+                    Ok(Bundle { module, ..bundle })
+                })
+                .collect()
+        })
+    }
 
-                let module = {
-                    // Change imports
-                    let mut v = Renamer {
-                        resolver: &self.resolver,
-                        base: &path,
-                        renamed: &renamed,
-                    };
-                    bundle.module.fold_with(&mut v)
-                };
+    /// Changes imports of renamed chunks.
+    fn rename_imports(
+        &self,
+        bundles: Vec<Bundle>,
+        renamed: &HashMap<PathBuf, String>,
+    ) -> Vec<Bundle> {
+        bundles.move_map(|bundle| {
+            let path = match self.scope.get_module(bundle.id).unwrap().fm.name {
+                FileName::Real(ref v) => v.clone(),
+                _ => {
+                    log::error!("Cannot rename: not a real file");
+                    return bundle;
+                }
+            };
 
-                Bundle { module, ..bundle }
-            });
+            let module = {
+                // Change imports
+                let mut v = Renamer {
+                    bundler: self,
+                    base: &path,
+                    renamed,
+                };
+                bundle.module.fold_with(&mut v)
+            };
 
-            Ok(new)
+            Bundle { module, ..bundle }
         })
     }
 }
 
 /// Import renamer. This pass changes import path.
-struct Renamer<'a, R>
+struct Renamer<'a, 'b, L, R>
 where
+    L: Load,
     R: Resolve,
 {
-    resolver: R,
+    bundler: &'a Bundler<'b, L, R>,
     base: &'a PathBuf,
     renamed: &'a HashMap<PathBuf, String>,
 }
 
-impl<R> Fold for Renamer<'_, R>
+impl<L, R> Fold for Renamer<'_, '_, L, R>
 where
+    L: Load,
     R: Resolve,
 {
     noop_fold_type!();
 
     fn fold_import_decl(&mut self, import: ImportDecl) -> ImportDecl {
-        let resolved = match self
-            .resolver
-            .resolve(&FileName::Real(self.base.clone()), &import.src.value)
-        {
-            Ok(v) => match v {
-                FileName::Real(v) => v,
-                _ => panic!("rename_bundles called with non-path module"),
+        let resolved = match self.bundler.resolve(
+            &FileName::Real(self.base.clone()),
+            &import.src.value,
+            ImportKind::Import,
+        ) {
+            Ok(v) => match &*v {
+                FileName::Real(v) => v.clone(),
+                // Virtual modules provided by plugins are not renamed.
+                _ => return import,
             },
             Err(_) => return import,
         };
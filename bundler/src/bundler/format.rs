@@ -0,0 +1,630 @@
+use super::tla::has_top_level_await;
+use crate::{Bundler, Load, OutputFormat, Resolve};
+use anyhow::{bail, Error};
+use swc_atoms::{js_word, JsWord};
+use swc_common::DUMMY_SP;
+use swc_ecma_ast::*;
+use swc_ecma_transforms::{
+    helpers::{inject_helpers, Helpers, HELPERS},
+    hygiene,
+    modules::common_js::common_js,
+};
+use swc_ecma_utils::{
+    find_ids, is_valid_ident, private_ident, quote_ident, quote_str, ExprFactory,
+};
+use swc_ecma_visit::FoldWith;
+
+impl<L, R> Bundler<'_, L, R>
+where
+    L: Load,
+    R: Resolve,
+{
+    /// Converts a bundle, which is an es module, to [Config::format].
+    ///
+    /// [Config::format]: crate::Config::format
+    pub(super) fn apply_format(&self, module: Module) -> Result<Module, Error> {
+        self.run(|| {
+            if self.config.format != OutputFormat::Es && has_top_level_await(&module) {
+                bail!("top-level await is supported only by es modules")
+            }
+
+            let module = match &self.config.format {
+                OutputFormat::Es => return Ok(module),
+                OutputFormat::CommonJs => HELPERS.set(&Helpers::new(false), || {
+                    module
+                        .fold_with(&mut common_js(self.top_level_mark, Default::default()))
+                        .fold_with(&mut inject_helpers())
+                }),
+                OutputFormat::Iife { global_name } => {
+                    let factory = into_factory(module)?;
+                    let args = factory
+                        .deps
+                        .iter()
+                        .map(|(src, _)| self.external_global(src, None).map(|e| e.as_arg()))
+                        .collect::<Result<_, _>>()?;
+
+                    // (function (_foo) { ... })(Foo)
+                    let call = Expr::Call(CallExpr {
+                        span: DUMMY_SP,
+                        callee: factory.into_fn_expr(global_name.is_some()).as_callee(),
+                        args,
+                        type_args: None,
+                    });
+
+                    let stmt = match global_name {
+                        // var Name = (function () { ...; return exports; })();
+                        Some(name) => Stmt::Decl(Decl::Var(VarDecl {
+                            span: DUMMY_SP,
+                            kind: VarDeclKind::Var,
+                            declare: false,
+                            decls: vec![VarDeclarator {
+                                span: DUMMY_SP,
+                                name: Pat::Ident(quote_ident!(name.clone())),
+                                init: Some(Box::new(call)),
+                                definite: false,
+                            }],
+                        })),
+                        None => call.into_stmt(),
+                    };
+
+                    Module {
+                        span: DUMMY_SP,
+                        body: vec![ModuleItem::Stmt(stmt)],
+                        shebang: None,
+                    }
+                }
+                OutputFormat::Umd { global_name } => {
+                    let factory = into_factory(module)?;
+                    let global = quote_ident!("global");
+
+                    let global_args = factory
+                        .deps
+                        .iter()
+                        .map(|(src, _)| {
+                            self.external_global(src, Some(&global)).map(|e| e.as_arg())
+                        })
+                        .collect::<Result<_, _>>()?;
+                    let require_args = factory
+                        .deps
+                        .iter()
+                        .map(|(src, _)| {
+                            CallExpr {
+                                span: DUMMY_SP,
+                                callee: quote_ident!("require").as_callee(),
+                                args: vec![Lit::Str(quote_str!(src.clone())).as_arg()],
+                                type_args: None,
+                            }
+                            .as_arg()
+                        })
+                        .collect();
+                    let define_deps = ArrayLit {
+                        span: DUMMY_SP,
+                        elems: factory
+                            .deps
+                            .iter()
+                            .map(|(src, _)| Some(Lit::Str(quote_str!(src.clone())).as_arg()))
+                            .collect(),
+                    };
+
+                    let call_factory = |args| {
+                        Box::new(Expr::Call(CallExpr {
+                            span: DUMMY_SP,
+                            callee: quote_ident!("factory").as_callee(),
+                            args,
+                            type_args: None,
+                        }))
+                    };
+                    let type_of = |name: &str, ty: &str| {
+                        UnaryExpr {
+                            span: DUMMY_SP,
+                            op: op!("typeof"),
+                            arg: Box::new(Expr::Ident(quote_ident!(name))),
+                        }
+                        .make_eq(Lit::Str(quote_str!(ty)))
+                    };
+                    let assign = |left: Expr, right: Box<Expr>| {
+                        AssignExpr {
+                            span: DUMMY_SP,
+                            op: op!("="),
+                            left: PatOrExpr::Expr(Box::new(left)),
+                            right,
+                        }
+                        .into_stmt()
+                    };
+
+                    // if (typeof exports === 'object' && typeof module !== 'undefined')
+                    //     module.exports = factory(require('foo'));
+                    // else if (typeof define === 'function' && define.amd)
+                    //     define(['foo'], factory);
+                    // else {
+                    //     global = global || self;
+                    //     global.Name = factory(global.Foo);
+                    // }
+                    let body = Stmt::If(IfStmt {
+                        span: DUMMY_SP,
+                        test: Box::new(
+                            type_of("exports", "object").make_bin(
+                                op!("&&"),
+                                UnaryExpr {
+                                    span: DUMMY_SP,
+                                    op: op!("typeof"),
+                                    arg: Box::new(Expr::Ident(quote_ident!("module"))),
+                                }
+                                .make_bin(op!("!=="), Lit::Str(quote_str!("undefined"))),
+                            ),
+                        ),
+                        cons: Box::new(assign(
+                            quote_ident!("module").make_member(quote_ident!("exports")),
+                            call_factory(require_args),
+                        )),
+                        alt: Some(Box::new(Stmt::If(IfStmt {
+                            span: DUMMY_SP,
+                            test: Box::new(type_of("define", "function").make_bin(
+                                op!("&&"),
+                                quote_ident!("define").make_member(quote_ident!("amd")),
+                            )),
+                            cons: Box::new(
+                                CallExpr {
+                                    span: DUMMY_SP,
+                                    callee: quote_ident!("define").as_callee(),
+                                    args: vec![
+                                        define_deps.as_arg(),
+                                        quote_ident!("factory").as_arg(),
+                                    ],
+                                    type_args: None,
+                                }
+                                .into_stmt(),
+                            ),
+                            alt: Some(Box::new(Stmt::Block(BlockStmt {
+                                span: DUMMY_SP,
+                                stmts: vec![
+                                    assign(
+                                        Expr::Ident(global.clone()),
+                                        Box::new(
+                                            global
+                                                .clone()
+                                                .make_bin(op!("||"), quote_ident!("self")),
+                                        ),
+                                    ),
+                                    assign(
+                                        global_member(&global, global_name),
+                                        call_factory(global_args),
+                                    ),
+                                ],
+                            }))),
+                        }))),
+                    });
+
+                    let wrapper = Expr::Fn(FnExpr {
+                        ident: None,
+                        function: Function {
+                            params: vec![param(global), param(quote_ident!("factory"))],
+                            decorators: vec![],
+                            span: DUMMY_SP,
+                            body: Some(BlockStmt {
+                                span: DUMMY_SP,
+                                stmts: vec![body],
+                            }),
+                            is_generator: false,
+                            is_async: false,
+                            type_params: None,
+                            return_type: None,
+                        },
+                    });
+
+                    // (function (global, factory) { ... })(this, function (_foo) { ... });
+                    let stmt = CallExpr {
+                        span: DUMMY_SP,
+                        callee: wrapper.as_callee(),
+                        args: vec![
+                            ThisExpr { span: DUMMY_SP }.as_arg(),
+                            factory.into_fn_expr(true).as_arg(),
+                        ],
+                        type_args: None,
+                    }
+                    .into_stmt();
+
+                    Module {
+                        span: DUMMY_SP,
+                        body: vec![ModuleItem::Stmt(stmt)],
+                        shebang: None,
+                    }
+                }
+            };
+
+            Ok(module.fold_with(&mut hygiene()))
+        })
+    }
+
+    /// Returns the global variable for an external module.
+    fn external_global(&self, src: &JsWord, global: Option<&Ident>) -> Result<Expr, Error> {
+        let name = match self.config.external_globals.get(src) {
+            Some(v) => v,
+            None => bail!(
+                "`{}` is imported by a bundle, but it's not an external module with a global \
+                 name. Note that `iife` and `umd` do not support code splitting.",
+                src
+            ),
+        };
+
+        Ok(match global {
+            Some(global) => global_member(global, name),
+            None => {
+                let mut parts = name.split('.');
+                let first = Expr::Ident(quote_ident!(parts.next().unwrap()));
+                parts.fold(first, |obj, part| obj.make_member(quote_ident!(part)))
+            }
+        })
+    }
+}
+
+/// A bundle converted to the body of a function.
+struct Factory {
+    /// Sources of external modules and the parameters for them.
+    deps: Vec<(JsWord, Ident)>,
+    body: Vec<Stmt>,
+    /// Exported names and values.
+    exports: Vec<(JsWord, Expr)>,
+}
+
+impl Factory {
+    /// Returns the parameter for `src`.
+    fn dep(&mut self, src: &JsWord) -> Ident {
+        if let Some((_, param)) = self.deps.iter().find(|(s, _)| s == src) {
+            return param.clone();
+        }
+
+        let name: String = src
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        let param = private_ident!(format!("_{}", name));
+        self.deps.push((src.clone(), param.clone()));
+        param
+    }
+
+    fn export(&mut self, name: JsWord, local: Ident) {
+        self.exports.push((name, Expr::Ident(local)))
+    }
+
+    /// If `return_exports` is true, the function returns an object containing
+    /// exports.
+    fn into_fn_expr(mut self, return_exports: bool) -> Expr {
+        if return_exports {
+            let props = self
+                .exports
+                .into_iter()
+                .map(|(name, value)| {
+                    let key = if is_valid_ident(&name) {
+                        PropName::Ident(quote_ident!(name))
+                    } else {
+                        PropName::Str(quote_str!(name))
+                    };
+                    PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                        key,
+                        value: Box::new(value),
+                    })))
+                })
+                .collect();
+
+            self.body.push(Stmt::Return(ReturnStmt {
+                span: DUMMY_SP,
+                arg: Some(Box::new(Expr::Object(ObjectLit {
+                    span: DUMMY_SP,
+                    props,
+                }))),
+            }));
+        }
+
+        Expr::Fn(FnExpr {
+            ident: None,
+            function: Function {
+                params: self.deps.into_iter().map(|(_, p)| param(p)).collect(),
+                decorators: vec![],
+                span: DUMMY_SP,
+                body: Some(BlockStmt {
+                    span: DUMMY_SP,
+                    stmts: self.body,
+                }),
+                is_generator: false,
+                is_async: false,
+                type_params: None,
+                return_type: None,
+            },
+        })
+    }
+}
+
+/// Converts imports and exports of `module` so that it can be used as a
+/// body of a function.
+fn into_factory(module: Module) -> Result<Factory, Error> {
+    let mut factory = Factory {
+        deps: vec![],
+        body: vec![],
+        exports: vec![],
+    };
+    let mut imports = vec![];
+
+    for item in module.body {
+        let decl = match item {
+            ModuleItem::Stmt(stmt) => {
+                factory.body.push(stmt);
+                continue;
+            }
+            ModuleItem::ModuleDecl(decl) => decl,
+        };
+
+        match decl {
+            ModuleDecl::Import(import) => {
+                let dep = factory.dep(&import.src.value);
+
+                for s in import.specifiers {
+                    // Globals and common js modules are used as a
+                    // namespace and the default export at the same time.
+                    let init = match s {
+                        ImportSpecifier::Named(s) => {
+                            let imported = s.imported.unwrap_or_else(|| s.local.clone());
+                            imports.push(var(
+                                s.local,
+                                dep.clone().make_member(quote_ident!(imported.sym)),
+                            ));
+                            continue;
+                        }
+                        ImportSpecifier::Default(s) => s.local,
+                        ImportSpecifier::Namespace(s) => s.local,
+                    };
+                    imports.push(var(init, Expr::Ident(dep.clone())));
+                }
+            }
+
+            ModuleDecl::ExportDecl(export) => {
+                match &export.decl {
+                    Decl::Class(c) => factory.export(c.ident.sym.clone(), c.ident.clone()),
+                    Decl::Fn(f) => factory.export(f.ident.sym.clone(), f.ident.clone()),
+                    Decl::Var(v) => {
+                        let ids: Vec<Ident> = find_ids(&v.decls);
+                        for id in ids {
+                            factory.export(id.sym.clone(), id);
+                        }
+                    }
+                    _ => {}
+                }
+                factory.body.push(Stmt::Decl(export.decl));
+            }
+
+            ModuleDecl::ExportDefaultDecl(export) => {
+                let decl = match export.decl {
+                    DefaultDecl::Class(c) => {
+                        let ident = c.ident.unwrap_or_else(|| private_ident!("_default"));
+                        factory.export(js_word!("default"), ident.clone());
+                        Decl::Class(ClassDecl {
+                            ident,
+                            class: c.class,
+                            declare: false,
+                        })
+                    }
+                    DefaultDecl::Fn(f) => {
+                        let ident = f.ident.unwrap_or_else(|| private_ident!("_default"));
+                        factory.export(js_word!("default"), ident.clone());
+                        Decl::Fn(FnDecl {
+                            ident,
+                            function: f.function,
+                            declare: false,
+                        })
+                    }
+                    DefaultDecl::TsInterfaceDecl(..) => continue,
+                };
+                factory.body.push(Stmt::Decl(decl));
+            }
+
+            ModuleDecl::ExportDefaultExpr(export) => {
+                let ident = private_ident!("_default");
+                factory.export(js_word!("default"), ident.clone());
+                factory.body.push(var(ident, *export.expr));
+            }
+
+            ModuleDecl::ExportNamed(export) => {
+                let dep = export.src.as_ref().map(|src| factory.dep(&src.value));
+
+                for s in export.specifiers {
+                    match s {
+                        ExportSpecifier::Named(s) => {
+                            let exported = s.exported.unwrap_or_else(|| s.orig.clone());
+                            match &dep {
+                                Some(dep) => factory.exports.push((
+                                    exported.sym,
+                                    dep.clone().make_member(quote_ident!(s.orig.sym)),
+                                )),
+                                None => factory.export(exported.sym, s.orig),
+                            }
+                        }
+                        ExportSpecifier::Namespace(s) => match &dep {
+                            Some(dep) => {
+                                factory.exports.push((s.name.sym, Expr::Ident(dep.clone())))
+                            }
+                            None => unreachable!("export * as foo requires a source"),
+                        },
+                        ExportSpecifier::Default(s) => bail!(
+                            "`export {} from` is not supported by `iife` and `umd`",
+                            s.exported.sym
+                        ),
+                    }
+                }
+            }
+
+            ModuleDecl::ExportAll(export) => bail!(
+                "`export * from '{}'` is not supported by `iife` and `umd`",
+                export.src.value
+            ),
+
+            _ => {}
+        }
+    }
+
+    factory.body.splice(0..0, imports);
+
+    Ok(factory)
+}
+
+/// `global.foo.bar` for `foo.bar`
+fn global_member(global: &Ident, name: &str) -> Expr {
+    name.split('.')
+        .fold(Expr::Ident(global.clone()), |obj, part| {
+            obj.make_member(quote_ident!(part))
+        })
+}
+
+fn param(i: Ident) -> Param {
+    Param {
+        span: DUMMY_SP,
+        decorators: Default::default(),
+        pat: Pat::Ident(i),
+    }
+}
+
+fn var(name: Ident, init: Expr) -> Stmt {
+    Stmt::Decl(Decl::Var(VarDecl {
+        span: DUMMY_SP,
+        kind: VarDeclKind::Var,
+        declare: false,
+        decls: vec![VarDeclarator {
+            span: DUMMY_SP,
+            name: Pat::Ident(name),
+            init: Some(Box::new(init)),
+            definite: false,
+        }],
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{bundler::tests::suite, OutputFormat};
+
+    #[test]
+    fn iife_with_global_name() {
+        suite().run(|t| {
+            t.bundler.config.format = OutputFormat::Iife {
+                global_name: Some("Lib".into()),
+            };
+            t.bundler
+                .config
+                .external_globals
+                .insert("jquery".into(), "jQuery".into());
+
+            let module = t.parse(
+                "
+                import $ from 'jquery';
+                export const a = $(1);
+                export default function () {}
+                ",
+            );
+            let module = t.bundler.apply_format(module)?;
+
+            t.assert_eq(
+                &module,
+                "
+                var Lib = function (_jquery) {
+                    var $ = _jquery;
+                    const a = $(1);
+                    function _default() {}
+                    return { a: a, default: _default };
+                }(jQuery);
+                ",
+            );
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn umd() {
+        suite().run(|t| {
+            t.bundler.config.format = OutputFormat::Umd {
+                global_name: "Lib".into(),
+            };
+            t.bundler
+                .config
+                .external_globals
+                .insert("jquery".into(), "jQuery".into());
+
+            let module = t.parse(
+                "
+                import $, { ajax } from 'jquery';
+                export const a = ajax($);
+                export default a;
+                ",
+            );
+            let module = t.bundler.apply_format(module)?;
+
+            t.assert_eq(
+                &module,
+                "
+                (function (global, factory) {
+                    if (typeof exports === 'object' && typeof module !== 'undefined')
+                        module.exports = factory(require('jquery'));
+                    else if (typeof define === 'function' && define.amd)
+                        define(['jquery'], factory);
+                    else {
+                        global = global || self;
+                        global.Lib = factory(global.jQuery);
+                    }
+                })(this, function (_jquery) {
+                    var $ = _jquery;
+                    var ajax = _jquery.ajax;
+                    const a = ajax($);
+                    var _default = a;
+                    return { a: a, default: _default };
+                });
+                ",
+            );
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn common_js() {
+        suite().run(|t| {
+            t.bundler.config.format = OutputFormat::CommonJs;
+
+            let module = t.parse(
+                "
+                import { ajax } from 'jquery';
+                export const a = ajax;
+                export default function () {}
+                ",
+            );
+            let module = t.bundler.apply_format(module)?;
+
+            t.assert_eq(
+                &module,
+                "
+                'use strict';
+                Object.defineProperty(exports, '__esModule', {
+                    value: true
+                });
+                exports.a = void 0;
+                var _jquery = require('jquery');
+                const a = _jquery.ajax;
+                exports.a = a;
+                function _default() {}
+                exports.default = _default;
+                ",
+            );
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn iife_without_global() {
+        suite().run(|t| {
+            t.bundler.config.format = OutputFormat::Iife { global_name: None };
+
+            let module = t.parse("import './foo';");
+            assert!(t.bundler.apply_format(module).is_err());
+
+            Ok(())
+        });
+    }
+}
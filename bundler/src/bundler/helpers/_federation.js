@@ -0,0 +1,172 @@
+function __swc_federation__() {
+    var root = typeof globalThis !== 'undefined' ? globalThis : self;
+    if (root.__swc_federation__) {
+        return root.__swc_federation__;
+    }
+
+    var scopes = {};
+
+    function parse(version) {
+        return String(version).split('-')[0].split('.').map(function (v) {
+            return parseInt(v, 10) || 0;
+        });
+    }
+
+    function compare(a, b) {
+        var l = parse(a);
+        var r = parse(b);
+        for (var i = 0; i < 3; i++) {
+            if ((l[i] || 0) !== (r[i] || 0)) {
+                return (l[i] || 0) < (r[i] || 0) ? -1 : 1;
+            }
+        }
+        return 0;
+    }
+
+    // Supports `*`, `1.2.3`, `^1.2.3`, `~1.2.3` and `>=1.2.3`.
+    function satisfies(version, range) {
+        if (!range || range === '*') {
+            return true;
+        }
+        var m = /^(\^|~|>=)?\s*(.*)$/.exec(range);
+        var v = parse(version);
+        var base = parse(m[2]);
+        var cmp = compare(version, m[2]);
+
+        switch (m[1]) {
+            case '>=':
+                return cmp >= 0;
+            case '^':
+                if (cmp < 0) {
+                    return false;
+                }
+                if (base[0] !== 0) {
+                    return v[0] === base[0];
+                }
+                if (base[1] !== 0) {
+                    return v[0] === 0 && v[1] === base[1];
+                }
+                return v[0] === 0 && v[1] === 0 && v[2] === base[2];
+            case '~':
+                return cmp >= 0 && v[0] === base[0] && v[1] === base[1];
+            default:
+                return cmp === 0;
+        }
+    }
+
+    function scope(name) {
+        return scopes[name] || (scopes[name] = {});
+    }
+
+    function find(scopeName, name, requiredVersion, singleton) {
+        var versions = scope(scopeName)[name] || {};
+        var available = Object.keys(versions).sort(compare).reverse();
+        if (!available.length) {
+            throw new Error('Shared module "' + name + '" is not provided by any container');
+        }
+
+        if (singleton) {
+            if (!satisfies(available[0], requiredVersion)) {
+                console.warn('Unsatisfied version ' + available[0] + ' of shared singleton module "' + name + '" (required ' + requiredVersion + ')');
+            }
+            return versions[available[0]];
+        }
+
+        for (var i = 0; i < available.length; i++) {
+            if (satisfies(available[i], requiredVersion)) {
+                return versions[available[i]];
+            }
+        }
+        throw new Error('No version of shared module "' + name + '" satisfies ' + requiredVersion + ' (available: ' + available.join(', ') + ')');
+    }
+
+    // Packages written in common js export `module.exports` as default, so
+    // its properties are exposed as named exports.
+    function interop(exports) {
+        var ns = {};
+        if (exports !== null && (typeof exports === 'object' || typeof exports === 'function')) {
+            Object.keys(exports).forEach(function (key) {
+                ns[key] = exports[key];
+            });
+        }
+        if (!exports || !exports.__esModule) {
+            ns.default = exports;
+        }
+        return ns;
+    }
+
+    function load(entry) {
+        if (!entry.loaded) {
+            entry.loaded = Promise.resolve(entry.get()).then(function (m) {
+                if (m.__swc_cjs__) {
+                    entry.exports = m.default;
+                    m = interop(m.default);
+                }
+                entry.module = m;
+                return m;
+            });
+        }
+        return entry.loaded;
+    }
+
+    var runtime = {
+        register: function (scopeName, name, version, from, get) {
+            var versions = scope(scopeName)[name] || (scope(scopeName)[name] = {});
+            if (!versions[version]) {
+                versions[version] = { get: get, from: from };
+            }
+        },
+        init: function (scopeName, shareScope) {
+            var own = scope(scopeName);
+            if (own === shareScope) {
+                return;
+            }
+            Object.keys(own).forEach(function (name) {
+                var versions = shareScope[name] || (shareScope[name] = {});
+                Object.keys(own[name]).forEach(function (version) {
+                    if (!versions[version]) {
+                        versions[version] = own[name][version];
+                    }
+                });
+            });
+            scopes[scopeName] = shareScope;
+        },
+        load: function (scopeName, name, requiredVersion, singleton) {
+            try {
+                return load(find(scopeName, name, requiredVersion, singleton));
+            } catch (e) {
+                return Promise.reject(e);
+            }
+        },
+        require: function (scopeName, name, requiredVersion, singleton) {
+            var entry = find(scopeName, name, requiredVersion, singleton);
+            if (!('module' in entry)) {
+                throw new Error('Shared module "' + name + '" should be imported before it is required');
+            }
+            if ('exports' in entry) {
+                return entry.exports;
+            }
+            var keys = Object.keys(entry.module);
+            return keys.length === 1 && keys[0] === 'default' ? entry.module.default : entry.module;
+        },
+        container: function (scopeName, name, modules) {
+            return {
+                get: function (module) {
+                    if (!Object.prototype.hasOwnProperty.call(modules, module)) {
+                        return Promise.reject(new Error('Module "' + module + '" does not exist in container "' + name + '"'));
+                    }
+                    return modules[module]().then(function (m) {
+                        return function () {
+                            return m;
+                        };
+                    });
+                },
+                init: function (shareScope) {
+                    runtime.init(scopeName, shareScope);
+                }
+            };
+        }
+    };
+
+    return root.__swc_federation__ = runtime;
+}
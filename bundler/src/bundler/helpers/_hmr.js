@@ -0,0 +1,158 @@
+function __swc_hmr__() {
+    var root = typeof globalThis !== 'undefined' ? globalThis : self;
+    if (root.__swc_hmr__) {
+        return root.__swc_hmr__;
+    }
+
+    var factories = {};
+    var cache = {};
+    // Data passed to dispose handlers and importers of disposed modules.
+    var data = {};
+    var parents = {};
+
+    function hot(id) {
+        return {
+            data: data[id],
+            selfAccepted: false,
+            errorHandler: undefined,
+            accepted: {},
+            disposeHandlers: [],
+            accept: function (deps, callback) {
+                if (deps === undefined || typeof deps === 'function') {
+                    this.selfAccepted = true;
+                    this.errorHandler = deps;
+                    return;
+                }
+                if (typeof deps === 'string') {
+                    deps = [deps];
+                }
+                for (var i = 0; i < deps.length; i++) {
+                    this.accepted[deps[i]] = callback || function () { };
+                }
+            },
+            dispose: function (callback) {
+                this.disposeHandlers.push(callback);
+            }
+        };
+    }
+
+    function load(id, parent) {
+        var module = cache[id];
+        if (!module) {
+            if (!Object.prototype.hasOwnProperty.call(factories, id)) {
+                throw new Error('Module "' + id + '" is not defined');
+            }
+            module = cache[id] = {
+                id: id,
+                exports: {},
+                parents: parents[id] || [],
+                hot: hot(id)
+            };
+            factories[id].call(module.exports, module, module.exports, function (dep) {
+                return load(dep, id);
+            });
+        }
+        if (parent && module.parents.indexOf(parent) === -1) {
+            module.parents.push(parent);
+        }
+        return module.exports;
+    }
+
+    function reload() {
+        if (typeof location !== 'undefined') {
+            location.reload();
+        }
+    }
+
+    var runtime = {
+        hash: undefined,
+        define: function (hash, modules) {
+            runtime.hash = hash;
+            Object.keys(modules).forEach(function (id) {
+                if (!Object.prototype.hasOwnProperty.call(factories, id)) {
+                    factories[id] = modules[id];
+                }
+            });
+        },
+        require: function (id) {
+            return load(id);
+        },
+        // Applies an update chunk. The page is reloaded if an updated module
+        // is not accepted by itself or its importers, or if the update is not
+        // based on the running build.
+        update: function (previousHash, hash, modules) {
+            if (runtime.hash !== previousHash) {
+                return reload();
+            }
+
+            var updated = Object.keys(modules);
+            var outdated = [];
+            var callbacks = [];
+            var queue = updated.slice();
+
+            while (queue.length) {
+                var id = queue.shift();
+                if (outdated.indexOf(id) !== -1) {
+                    continue;
+                }
+                outdated.push(id);
+
+                var module = cache[id];
+                if (!module || module.hot.selfAccepted) {
+                    continue;
+                }
+                if (!module.parents.length) {
+                    return reload();
+                }
+                for (var i = 0; i < module.parents.length; i++) {
+                    var parent = cache[module.parents[i]];
+                    if (parent && parent.hot.accepted[id]) {
+                        callbacks.push({ parent: parent.id, dep: id, callback: parent.hot.accepted[id] });
+                    } else {
+                        queue.push(module.parents[i]);
+                    }
+                }
+            }
+
+            var selfAccepted = [];
+            outdated.forEach(function (id) {
+                var module = cache[id];
+                if (!module) {
+                    return;
+                }
+                var d = {};
+                module.hot.disposeHandlers.forEach(function (handler) {
+                    handler(d);
+                });
+                data[id] = d;
+                parents[id] = module.parents;
+                if (module.hot.selfAccepted) {
+                    selfAccepted.push({ id: id, errorHandler: module.hot.errorHandler });
+                }
+                delete cache[id];
+            });
+
+            updated.forEach(function (id) {
+                factories[id] = modules[id];
+            });
+            runtime.hash = hash;
+
+            selfAccepted.forEach(function (m) {
+                try {
+                    load(m.id);
+                } catch (e) {
+                    if (!m.errorHandler) {
+                        throw e;
+                    }
+                    m.errorHandler(e);
+                }
+            });
+            callbacks.forEach(function (c) {
+                load(c.dep, c.parent);
+                c.callback([c.dep]);
+            });
+        }
+    };
+
+    return root.__swc_hmr__ = runtime;
+}
@@ -1,14 +1,31 @@
 use once_cell::sync::Lazy;
-use std::sync::atomic::{AtomicBool, Ordering::SeqCst};
-use swc_common::{FileName, FilePathMapping, SourceMap};
+use std::{
+    collections::HashMap,
+    sync::atomic::{AtomicBool, Ordering::SeqCst},
+};
+use swc_atoms::JsWord;
+use swc_common::{sync::Lock, FileName, FilePathMapping, Mark, SourceMap, SyntaxContext};
 use swc_ecma_ast::*;
 use swc_ecma_parser::{lexer::Lexer, Parser, StringInput};
-use swc_ecma_utils::{drop_span, prepend_stmts};
+use swc_ecma_transforms::helpers::{self as swc_helpers, helper_name};
+use swc_ecma_utils::{drop_span, id, prepend_stmts, Id};
+use swc_ecma_visit::{noop_visit_mut_type, VisitMut, VisitMutWith};
+
+#[cfg(test)]
+mod tests;
+
+/// Prefix of helpers imported with [swc_helpers::ImportStyle::PerFile].
+const SWC_HELPERS_PREFIX: &str = "@swc/helpers/_";
 
 #[derive(Debug, Default)]
 pub(super) struct Helpers {
     /// `__spack_require__`
     pub require: AtomicBool,
+
+    /// Helpers from `@swc/helpers`, like `class_call_check`.
+    ///
+    /// They are inlined once per bundle.
+    pub swc: Lock<Vec<JsWord>>,
 }
 
 macro_rules! define {
@@ -48,24 +65,140 @@ macro_rules! define {
     };
 }
 
-define!(require {
-    build: build_spack_require
-});
+define!(
+    require {
+        build: build_spack_require
+    }
+    federation {
+        build: build_federation_runtime
+    }
+    hmr {
+        build: build_hmr_runtime
+    }
+);
+
+/// Returns the runtime of module federation, `__swc_federation__`.
+///
+/// Unlike other helpers, it's injected into every bundle which uses a share
+/// scope, including lib chunks.
+pub(super) fn federation_runtime() -> Vec<ModuleItem> {
+    let mut buf = vec![];
+    build_federation_runtime(&mut buf);
+    buf
+}
+
+/// Returns the module registry used for hot module replacement, `__swc_hmr__`.
+pub(super) fn hmr_runtime() -> Vec<ModuleItem> {
+    let mut buf = vec![];
+    build_hmr_runtime(&mut buf);
+    buf
+}
 
 impl Helpers {
+    /// Removes imports like `import _classCallCheck from
+    /// '@swc/helpers/_class_call_check'` and makes references to them use the
+    /// helpers inlined by [Helpers::append_to].
+    ///
+    /// This should be called after the resolver.
+    pub fn extract_from(module: &mut Module, helper_mark: Mark) -> Self {
+        let helpers = Helpers::default();
+        let mut known: Option<swc_helpers::Helpers> = None;
+        let mut renamed: HashMap<Id, JsWord> = HashMap::new();
+
+        module.body.retain(|item| {
+            let import = match item {
+                ModuleItem::ModuleDecl(ModuleDecl::Import(import)) => import,
+                _ => return true,
+            };
+            if !import.src.value.starts_with(SWC_HELPERS_PREFIX) {
+                return true;
+            }
+            let name = &import.src.value[SWC_HELPERS_PREFIX.len()..];
+            let local = match &*import.specifiers {
+                [ImportSpecifier::Default(s)] => &s.local,
+                _ => return true,
+            };
+
+            // Unknown helpers are left as is.
+            if !known
+                .get_or_insert_with(|| swc_helpers::Helpers::new(false))
+                .enable(name)
+            {
+                return true;
+            }
+
+            renamed.insert(id(local), format!("_{}", helper_name(name)).into());
+            let mut swc = helpers.swc.lock();
+            if !swc.iter().any(|v| &**v == name) {
+                swc.push(name.into());
+            }
+            false
+        });
+
+        if !renamed.is_empty() {
+            module.visit_mut_with(&mut HelperRenamer {
+                ctxt: SyntaxContext::empty().apply_mark(helper_mark),
+                renamed,
+            });
+        }
+
+        helpers
+    }
+
     pub fn extend(&self, rhs: &Self) {
         if rhs.require.load(SeqCst) {
             self.require.store(true, SeqCst);
         }
+
+        let names = rhs.swc.lock().clone();
+        let mut swc = self.swc.lock();
+        for name in names {
+            if !swc.contains(&name) {
+                swc.push(name);
+            }
+        }
     }
 
-    pub fn append_to(&self, to: &mut Vec<ModuleItem>) {
+    pub fn append_to(&self, helper_mark: Mark, to: &mut Vec<ModuleItem>) {
         let mut buf = vec![];
 
         if self.require.load(SeqCst) {
             build_spack_require(&mut buf);
         }
 
+        let swc = self.swc.lock();
+        if !swc.is_empty() {
+            let helpers = swc_helpers::Helpers::new(false);
+            for name in swc.iter() {
+                helpers.enable(name);
+            }
+            buf.extend(helpers.definitions(helper_mark));
+        }
+
         prepend_stmts(to, buf.into_iter());
     }
 }
+
+struct HelperRenamer {
+    ctxt: SyntaxContext,
+    renamed: HashMap<Id, JsWord>,
+}
+
+impl VisitMut for HelperRenamer {
+    noop_visit_mut_type!();
+
+    fn visit_mut_ident(&mut self, i: &mut Ident) {
+        if let Some(sym) = self.renamed.get(&id(i)) {
+            i.sym = sym.clone();
+            i.span = i.span.with_ctxt(self.ctxt);
+        }
+    }
+
+    fn visit_mut_member_expr(&mut self, e: &mut MemberExpr) {
+        e.obj.visit_mut_with(self);
+
+        if e.computed {
+            e.prop.visit_mut_with(self);
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use crate::bundler::tests::suite;
+use std::collections::HashMap;
+use swc_common::FileName;
+use swc_ecma_ast::*;
+
+#[test]
+fn swc_helpers_are_inlined_once() {
+    suite()
+        .file(
+            "main.js",
+            "
+            import { A } from './a';
+            import { B } from './b';
+            console.log(new A(), new B());
+            ",
+        )
+        .file(
+            "a.js",
+            "
+            import _classCallCheck from '@swc/helpers/_class_call_check';
+            export var A = function A() {
+                _classCallCheck(this, A);
+            };
+            ",
+        )
+        .file(
+            "b.js",
+            "
+            import _classCallCheck from '@swc/helpers/_class_call_check';
+            export var B = function B() {
+                _classCallCheck(this, B);
+            };
+            ",
+        )
+        .run(|t| {
+            let mut entries = HashMap::default();
+            entries.insert("main.js".to_string(), FileName::Real("main.js".into()));
+
+            let bundles = t.bundler.bundle(entries)?;
+            assert_eq!(bundles.len(), 1);
+
+            let body = &bundles[0].module.body;
+            let definitions = body
+                .iter()
+                .filter(|item| match item {
+                    ModuleItem::Stmt(Stmt::Decl(Decl::Fn(f))) => &*f.ident.sym == "_classCallCheck",
+                    _ => false,
+                })
+                .count();
+            assert_eq!(definitions, 1);
+
+            let imports = body
+                .iter()
+                .filter(|item| match item {
+                    ModuleItem::ModuleDecl(ModuleDecl::Import(..)) => true,
+                    _ => false,
+                })
+                .count();
+            assert_eq!(imports, 0);
+
+            Ok(())
+        });
+}
@@ -0,0 +1,531 @@
+use super::{helpers::hmr_runtime, tla::has_top_level_await};
+use crate::{
+    hash::{calc_hash, calc_hash_of_str},
+    Bundle, BundleKind, Bundler, ImportKind, Load, Resolve,
+};
+use anyhow::{bail, Context, Error};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
+    path::PathBuf,
+};
+use swc_atoms::{js_word, JsWord};
+use swc_common::{FileName, Mark, DUMMY_SP};
+use swc_ecma_ast::*;
+use swc_ecma_transforms::{
+    helpers::{inject_helpers, Helpers, HELPERS},
+    hygiene,
+    modules::common_js::{self, common_js},
+    resolver_with_mark,
+};
+use swc_ecma_utils::{quote_ident, quote_str, ExprFactory};
+use swc_ecma_visit::{noop_visit_mut_type, FoldWith, VisitMut, VisitMutWith};
+
+#[cfg(test)]
+mod tests;
+
+/// Hot module replacement.
+///
+/// Instead of merging modules, each bundle registers modules to a registry,
+/// which is shared by all bundles. Modules can use `module.hot` or
+/// `import.meta.hot` to accept updates or to dispose resources.
+#[derive(Debug, Clone, Default)]
+pub struct HmrConfig {
+    /// Manifest of the previous build.
+    ///
+    /// If set, modules changed since the previous build are emitted as an
+    /// update chunk named `[hash].hot-update.js`.
+    pub previous: Option<HmrManifest>,
+    /// Root directory of the project.
+    ///
+    /// Ids of modules in the registry are paths relative to this directory.
+    pub root: PathBuf,
+}
+
+/// Content hashes of modules, which are compared by the next build to find
+/// changed modules.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HmrManifest {
+    /// Hash of the build.
+    pub hash: String,
+    /// Hashes of modules, keyed by ids used by the registry.
+    pub modules: BTreeMap<String, String>,
+}
+
+/// A module converted to a factory function of the registry.
+#[derive(Debug, Clone)]
+struct HotModule {
+    id: String,
+    /// `function (module, exports, require) { ... }`
+    factory: Expr,
+    hash: String,
+    deps: Vec<FileName>,
+}
+
+impl<L, R> Bundler<'_, L, R>
+where
+    L: Load,
+    R: Resolve,
+{
+    /// Creates a bundle with a module registry for each entry, and an update
+    /// chunk if [HmrConfig::previous] is set.
+    pub(super) fn bundle_hot(
+        &self,
+        entries: HashMap<String, FileName>,
+    ) -> Result<Vec<Bundle>, Error> {
+        self.run(|| {
+            let config = self.config.hmr.as_ref().unwrap();
+
+            let mut entries = entries.into_iter().collect::<Vec<_>>();
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+            let mut modules = HashMap::<FileName, HotModule>::default();
+            let mut chunks = vec![];
+            for (name, path) in entries {
+                let path = self.resolve_entry(path)?;
+                let included = self.load_hot_modules(&path, &mut modules)?;
+                chunks.push((name, path, included));
+            }
+
+            let manifest = {
+                let modules = modules
+                    .values()
+                    .map(|m| (m.id.clone(), m.hash.clone()))
+                    .collect::<BTreeMap<_, _>>();
+                let hashes = modules
+                    .iter()
+                    .map(|(id, hash)| format!("{}:{}", id, hash))
+                    .collect::<Vec<_>>();
+                HmrManifest {
+                    hash: calc_hash_of_str(&hashes.join("\n")),
+                    modules,
+                }
+            };
+
+            let mut bundles = vec![];
+            for (name, path, included) in chunks {
+                let (id, _) = self.scope.module_id_gen.gen(&path);
+                let factories = included.iter().map(|path| &modules[path]).collect();
+
+                // __swc_hmr__().define('hash', { 'a.js': function (module, exports, require) {
+                // ... } }); __swc_hmr__().require('main.js');
+                let mut body = hmr_runtime();
+                body.push(ModuleItem::Stmt(
+                    runtime_call(
+                        "define",
+                        vec![
+                            Lit::Str(quote_str!(&*manifest.hash)).as_arg(),
+                            factory_map(factories).as_arg(),
+                        ],
+                    )
+                    .into_stmt(),
+                ));
+                body.push(ModuleItem::Stmt(
+                    runtime_call(
+                        "require",
+                        vec![Lit::Str(quote_str!(&*modules[&path].id)).as_arg()],
+                    )
+                    .into_stmt(),
+                ));
+
+                let module = self.apply_format(Module {
+                    span: DUMMY_SP,
+                    body,
+                    shebang: None,
+                })?;
+                bundles.push(Bundle {
+                    kind: BundleKind::Named { name },
+                    id,
+                    module,
+                });
+            }
+
+            if let Some(previous) = &config.previous {
+                let mut changed = modules
+                    .values()
+                    .filter(|m| previous.modules.get(&m.id) != Some(&m.hash))
+                    .collect::<Vec<_>>();
+                changed.sort_by(|a, b| a.id.cmp(&b.id));
+
+                if !changed.is_empty() {
+                    // The runtime reloads the page if it's not running the previous build,
+                    // as modules changed by a missed build are not in the update.
+                    //
+                    // __swc_hmr__().update('previous', 'hash', { 'a.js': function (module,
+                    // exports, require) { ... } });
+                    let body = vec![ModuleItem::Stmt(
+                        runtime_call(
+                            "update",
+                            vec![
+                                Lit::Str(quote_str!(&*previous.hash)).as_arg(),
+                                Lit::Str(quote_str!(&*manifest.hash)).as_arg(),
+                                factory_map(changed).as_arg(),
+                            ],
+                        )
+                        .into_stmt(),
+                    )];
+                    let name = format!("{}.hot-update.js", manifest.hash);
+                    let (id, _) = self
+                        .scope
+                        .module_id_gen
+                        .gen(&FileName::Custom(format!("hmr:{}", name)));
+
+                    bundles.push(Bundle {
+                        kind: BundleKind::Named { name },
+                        id,
+                        module: Module {
+                            span: DUMMY_SP,
+                            body,
+                            shebang: None,
+                        },
+                    });
+                }
+            }
+
+            self.scope.store_hmr_manifest(manifest);
+
+            Ok(bundles)
+        })
+    }
+
+    /// Returns the manifest of the last build, which should be passed to the
+    /// next build as [HmrConfig::previous].
+    ///
+    /// Returns [None] if hot module replacement is not enabled.
+    pub fn hmr_manifest(&self) -> Option<HmrManifest> {
+        self.scope.get_hmr_manifest()
+    }
+
+    /// Converts `entry` and its dependencies to [HotModule]s.
+    ///
+    /// Returns modules reachable from `entry`, including itself.
+    fn load_hot_modules(
+        &self,
+        entry: &FileName,
+        modules: &mut HashMap<FileName, HotModule>,
+    ) -> Result<Vec<FileName>, Error> {
+        let mut included = vec![];
+        let mut visited = HashSet::new();
+        let mut queue = VecDeque::new();
+        visited.insert(entry.clone());
+        queue.push_back(entry.clone());
+
+        while let Some(file_name) = queue.pop_front() {
+            if !modules.contains_key(&file_name) {
+                let module = self
+                    .load_hot_module(&file_name)
+                    .with_context(|| format!("failed to load {} as a hot module", file_name))?;
+                modules.insert(file_name.clone(), module);
+            }
+
+            for dep in &modules[&file_name].deps {
+                if visited.insert(dep.clone()) {
+                    queue.push_back(dep.clone());
+                }
+            }
+            included.push(file_name);
+        }
+
+        Ok(included)
+    }
+
+    fn load_hot_module(&self, file_name: &FileName) -> Result<HotModule, Error> {
+        let (_, _, mut module) = self.load(file_name)?;
+        if has_top_level_await(&module) {
+            bail!("top-level await is not supported by hot module replacement")
+        }
+
+        let mut v = HotModuleRewriter {
+            bundler: self,
+            base: file_name,
+            resolved: Default::default(),
+            deps: vec![],
+            error: None,
+        };
+        module.visit_mut_with(&mut v);
+        if let Some(err) = v.error {
+            return Err(err);
+        }
+        let deps = v.deps;
+
+        let mark = Mark::fresh(Mark::root());
+        let module = HELPERS.set(&Helpers::new(false), || {
+            module
+                .fold_with(&mut resolver_with_mark(mark))
+                .fold_with(&mut common_js(
+                    mark,
+                    common_js::Config {
+                        // Specifiers are ids of the registry, which only `require` knows.
+                        ignore_dynamic: false,
+                        ..Default::default()
+                    },
+                ))
+                .fold_with(&mut inject_helpers())
+                .fold_with(&mut hygiene())
+        });
+        let hash = calc_hash(self.cm.clone(), &module)?;
+
+        let stmts: Vec<_> = module
+            .body
+            .into_iter()
+            .map(|item| match item {
+                ModuleItem::Stmt(stmt) => Ok(stmt),
+                ModuleItem::ModuleDecl(decl) => bail!("failed to convert {:?} to common js", decl),
+            })
+            .collect::<Result<_, Error>>()?;
+
+        let param = |name: &str| Param {
+            span: DUMMY_SP,
+            decorators: vec![],
+            pat: Pat::Ident(quote_ident!(name)),
+        };
+        let factory = Expr::Fn(FnExpr {
+            ident: None,
+            function: Function {
+                params: vec![param("module"), param("exports"), param("require")],
+                decorators: vec![],
+                span: DUMMY_SP,
+                body: Some(BlockStmt {
+                    span: DUMMY_SP,
+                    stmts,
+                }),
+                is_generator: false,
+                is_async: false,
+                type_params: None,
+                return_type: None,
+            },
+        });
+
+        Ok(HotModule {
+            id: self.hot_module_id(file_name),
+            factory,
+            hash,
+            deps,
+        })
+    }
+
+    /// Id of a module in the registry.
+    ///
+    /// Paths are relative to [HmrConfig::root], so that bundles do not
+    /// contain absolute paths.
+    fn hot_module_id(&self, file_name: &FileName) -> String {
+        if let FileName::Real(path) = file_name {
+            let root = &self.config.hmr.as_ref().unwrap().root;
+            if let Ok(path) = path.strip_prefix(root) {
+                return path
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/");
+            }
+        }
+
+        file_name.to_string()
+    }
+}
+
+/// `__swc_hmr__().method(args)`
+fn runtime_call(method: &str, args: Vec<ExprOrSpread>) -> Expr {
+    let runtime = Expr::Call(CallExpr {
+        span: DUMMY_SP,
+        callee: quote_ident!("__swc_hmr__").as_callee(),
+        args: vec![],
+        type_args: None,
+    });
+
+    Expr::Call(CallExpr {
+        span: DUMMY_SP,
+        callee: runtime.make_member(quote_ident!(method)).as_callee(),
+        args,
+        type_args: None,
+    })
+}
+
+/// `{ 'a.js': function (module, exports, require) { ... } }`
+fn factory_map(modules: Vec<&HotModule>) -> Expr {
+    Expr::Object(ObjectLit {
+        span: DUMMY_SP,
+        props: modules
+            .into_iter()
+            .map(|m| {
+                PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                    key: PropName::Str(quote_str!(&*m.id)),
+                    value: Box::new(m.factory.clone()),
+                })))
+            })
+            .collect(),
+    })
+}
+
+fn is_ident(e: &Expr, name: &str) -> bool {
+    match e {
+        Expr::Ident(i) => &*i.sym == name,
+        _ => false,
+    }
+}
+
+/// Returns true for `obj.name`.
+fn is_member(e: &Expr, name: &str) -> bool {
+    match e {
+        Expr::Member(MemberExpr {
+            prop,
+            computed: false,
+            ..
+        }) => is_ident(prop, name),
+        _ => false,
+    }
+}
+
+fn is_import_meta(e: &Expr) -> bool {
+    match e {
+        Expr::MetaProp(MetaPropExpr { meta, prop }) => {
+            meta.sym == js_word!("import") && &*prop.sym == "meta"
+        }
+        _ => false,
+    }
+}
+
+/// Changes module specifiers to ids of the registry and `import.meta.hot` to
+/// `module.hot`.
+struct HotModuleRewriter<'a, 'b, L, R>
+where
+    L: Load,
+    R: Resolve,
+{
+    bundler: &'a Bundler<'b, L, R>,
+    base: &'a FileName,
+    resolved: HashMap<JsWord, JsWord>,
+    deps: Vec<FileName>,
+    error: Option<Error>,
+}
+
+impl<L, R> HotModuleRewriter<'_, '_, L, R>
+where
+    L: Load,
+    R: Resolve,
+{
+    fn rewrite(&mut self, src: &mut Str, kind: ImportKind) {
+        if self.bundler.config.external_modules.contains(&src.value) {
+            return;
+        }
+        if let Some(id) = self.resolved.get(&src.value) {
+            src.value = id.clone();
+            return;
+        }
+
+        match self.bundler.resolve(self.base, &src.value, kind) {
+            Ok(file_name) => {
+                let id: JsWord = self.bundler.hot_module_id(&file_name).into();
+                self.resolved.insert(src.value.clone(), id.clone());
+                src.value = id;
+                src.has_escape = false;
+                if !self.deps.contains(&*file_name) {
+                    self.deps.push((*file_name).clone());
+                }
+            }
+            Err(err) => {
+                if self.error.is_none() {
+                    self.error = Some(err);
+                }
+            }
+        }
+    }
+
+    /// Handles dependencies passed to `hot.accept`, which may be a string or
+    /// an array of strings.
+    fn rewrite_accepted(&mut self, e: &mut Expr) {
+        match e {
+            Expr::Lit(Lit::Str(s)) => self.rewrite(s, ImportKind::Import),
+            Expr::Array(arr) => {
+                for elem in arr.elems.iter_mut().flatten() {
+                    if let Expr::Lit(Lit::Str(s)) = &mut *elem.expr {
+                        self.rewrite(s, ImportKind::Import);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl<L, R> VisitMut for HotModuleRewriter<'_, '_, L, R>
+where
+    L: Load,
+    R: Resolve,
+{
+    noop_visit_mut_type!();
+
+    fn visit_mut_import_decl(&mut self, import: &mut ImportDecl) {
+        self.rewrite(&mut import.src, ImportKind::Import);
+    }
+
+    fn visit_mut_named_export(&mut self, export: &mut NamedExport) {
+        if let Some(src) = &mut export.src {
+            self.rewrite(src, ImportKind::Import);
+        }
+    }
+
+    fn visit_mut_export_all(&mut self, export: &mut ExportAll) {
+        self.rewrite(&mut export.src, ImportKind::Import);
+    }
+
+    fn visit_mut_call_expr(&mut self, call: &mut CallExpr) {
+        call.visit_mut_children_with(self);
+
+        let callee = match &call.callee {
+            ExprOrSuper::Expr(callee) => &**callee,
+            _ => return,
+        };
+        let kind = match callee {
+            Expr::Ident(Ident {
+                sym: js_word!("require"),
+                ..
+            }) => ImportKind::Require,
+            Expr::Ident(Ident {
+                sym: js_word!("import"),
+                ..
+            }) => ImportKind::DynamicImport,
+            // module.hot.accept('./dep', callback)
+            Expr::Member(MemberExpr {
+                obj: ExprOrSuper::Expr(obj),
+                prop,
+                computed: false,
+                ..
+            }) => {
+                if !is_member(obj, "hot") || !is_ident(prop, "accept") {
+                    return;
+                }
+                if let Some(arg) = call.args.first_mut() {
+                    self.rewrite_accepted(&mut arg.expr);
+                }
+                return;
+            }
+            _ => return,
+        };
+
+        if call.args.len() != 1 || call.args[0].spread.is_some() {
+            return;
+        }
+        if let Expr::Lit(Lit::Str(src)) = &mut *call.args[0].expr {
+            self.rewrite(src, kind);
+        }
+    }
+
+    fn visit_mut_expr(&mut self, e: &mut Expr) {
+        e.visit_mut_children_with(self);
+
+        // import.meta.hot => module.hot
+        if let Expr::Member(MemberExpr {
+            obj: ExprOrSuper::Expr(obj),
+            prop,
+            computed: false,
+            ..
+        }) = e
+        {
+            if is_import_meta(obj) && is_ident(prop, "hot") {
+                *obj = Box::new(Expr::Ident(quote_ident!("module")));
+            }
+        }
+    }
+}
@@ -0,0 +1,217 @@
+use super::{HmrConfig, HmrManifest};
+use crate::{bundler::tests::suite, Bundle, BundleKind};
+use std::collections::HashMap;
+use swc_common::{FileName, DUMMY_SP};
+use swc_ecma_ast::*;
+use swc_ecma_visit::{noop_visit_type, Node, Visit, VisitWith};
+
+fn entries() -> HashMap<String, FileName> {
+    let mut entries = HashMap::default();
+    entries.insert("main.js".to_string(), FileName::Real("main.js".into()));
+    entries
+}
+
+/// Returns ids of modules passed to `__swc_hmr__().define` or
+/// `__swc_hmr__().update`.
+fn registered(bundle: &Bundle) -> Vec<String> {
+    bundle
+        .module
+        .body
+        .iter()
+        .filter_map(|item| match item {
+            ModuleItem::Stmt(Stmt::Expr(ExprStmt { expr, .. })) => match &**expr {
+                Expr::Call(CallExpr { args, .. }) => match args.last().map(|arg| &*arg.expr) {
+                    Some(Expr::Object(obj)) => Some(obj.props.iter()),
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        })
+        .flatten()
+        .map(|prop| match prop {
+            PropOrSpread::Prop(prop) => match &**prop {
+                Prop::KeyValue(KeyValueProp {
+                    key: PropName::Str(s),
+                    ..
+                }) => s.value.to_string(),
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        })
+        .collect()
+}
+
+#[test]
+fn modules_are_registered_instead_of_merged() {
+    suite()
+        .file(
+            "main.js",
+            "
+            import { a } from './a';
+            console.log(a);
+            ",
+        )
+        .file(
+            "a.js",
+            "
+            export const a = 1;
+            import.meta.hot.accept();
+            ",
+        )
+        .hmr(Default::default())
+        .run(|t| {
+            let bundles = t.bundler.bundle(entries())?;
+            assert_eq!(bundles.len(), 1);
+
+            let body = &bundles[0].module.body;
+            assert!(body.iter().any(|item| match item {
+                ModuleItem::Stmt(Stmt::Decl(Decl::Fn(f))) => &*f.ident.sym == "__swc_hmr__",
+                _ => false,
+            }));
+            assert!(!body
+                .iter()
+                .any(|item| matches!(item, ModuleItem::ModuleDecl(..))));
+            assert_eq!(registered(&bundles[0]), vec!["main.js", "a.js"]);
+
+            let manifest = t.bundler.hmr_manifest().unwrap();
+            assert_eq!(manifest.modules.len(), 2);
+
+            Ok(())
+        });
+}
+
+/// Collects string arguments of calls to `callee`.
+struct CallFinder {
+    callee: &'static str,
+    args: Vec<String>,
+}
+
+impl Visit for CallFinder {
+    noop_visit_type!();
+
+    fn visit_call_expr(&mut self, call: &CallExpr, _: &dyn Node) {
+        call.visit_children_with(self);
+
+        match &call.callee {
+            ExprOrSuper::Expr(callee) => match &**callee {
+                Expr::Ident(i) if &*i.sym == self.callee => {}
+                _ => return,
+            },
+            _ => return,
+        }
+        if let Some(Expr::Lit(Lit::Str(s))) = call.args.first().map(|arg| &*arg.expr) {
+            self.args.push(s.value.to_string());
+        }
+    }
+}
+
+fn calls(bundle: &Bundle, callee: &'static str) -> Vec<String> {
+    let mut v = CallFinder {
+        callee,
+        args: vec![],
+    };
+    bundle
+        .module
+        .visit_with(&Invalid { span: DUMMY_SP } as _, &mut v);
+    v.args
+}
+
+#[test]
+fn dynamic_import_uses_registry() {
+    suite()
+        .file(
+            "main.js",
+            "
+            import('./a').then(({ a }) => console.log(a));
+            ",
+        )
+        .file("a.js", "export const a = 1;")
+        .hmr(Default::default())
+        .run(|t| {
+            let bundles = t.bundler.bundle(entries())?;
+            assert_eq!(bundles.len(), 1);
+            assert_eq!(registered(&bundles[0]), vec!["main.js", "a.js"]);
+
+            // The specifier is an id of the registry, which the browser can't fetch.
+            assert_eq!(calls(&bundles[0], "import"), Vec::<String>::new());
+            assert!(calls(&bundles[0], "require").contains(&"a.js".to_string()));
+
+            Ok(())
+        });
+}
+
+#[test]
+fn update_contains_changed_modules() {
+    let mut previous: Option<HmrManifest> = None;
+
+    suite()
+        .file("main.js", "import './a'; import './b';")
+        .file("a.js", "console.log('a');")
+        .file("b.js", "console.log('b');")
+        .hmr(Default::default())
+        .run(|t| {
+            t.bundler.bundle(entries())?;
+            previous = t.bundler.hmr_manifest();
+            Ok(())
+        });
+    let previous = previous.unwrap();
+
+    suite()
+        .file("main.js", "import './a'; import './b';")
+        .file("a.js", "console.log('a was changed');")
+        .file("b.js", "console.log('b');")
+        .hmr(HmrConfig {
+            previous: Some(previous.clone()),
+            ..Default::default()
+        })
+        .run(|t| {
+            let bundles = t.bundler.bundle(entries())?;
+            assert_eq!(bundles.len(), 2);
+
+            let manifest = t.bundler.hmr_manifest().unwrap();
+            assert_ne!(manifest.hash, previous.hash);
+            assert_eq!(manifest.modules["b.js"], previous.modules["b.js"]);
+
+            let name = format!("{}.hot-update.js", manifest.hash);
+            let update = bundles
+                .iter()
+                .find(|bundle| bundle.kind == BundleKind::Named { name: name.clone() })
+                .unwrap();
+            assert_eq!(registered(update), vec!["a.js"]);
+
+            // The runtime compares the hash of the previous build with the running one.
+            match &update.module.body[0] {
+                ModuleItem::Stmt(Stmt::Expr(ExprStmt { expr, .. })) => match &**expr {
+                    Expr::Call(CallExpr { args, .. }) => match &*args[0].expr {
+                        Expr::Lit(Lit::Str(s)) => assert_eq!(*s.value, *previous.hash),
+                        _ => unreachable!(),
+                    },
+                    _ => unreachable!(),
+                },
+                _ => unreachable!(),
+            }
+
+            Ok(())
+        });
+}
+
+#[test]
+fn module_ids_are_relative_to_root() {
+    let mut entries = HashMap::default();
+    entries.insert("main.js".to_string(), FileName::Real("src/main.js".into()));
+
+    suite()
+        .file("src/main.js", "import './src/a';")
+        .file("src/a.js", "console.log('a');")
+        .hmr(HmrConfig {
+            root: "src".into(),
+            ..Default::default()
+        })
+        .run(|t| {
+            let bundles = t.bundler.bundle(entries)?;
+            assert_eq!(registered(&bundles[0]), vec!["main.js", "a.js"]);
+
+            Ok(())
+        });
+}
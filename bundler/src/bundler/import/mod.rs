@@ -1,5 +1,5 @@
 use super::Bundler;
-use crate::{load::Load, resolve::Resolve};
+use crate::{load::Load, resolve::Resolve, ImportKind};
 use anyhow::{Context, Error};
 use std::{
     collections::{HashMap, HashSet},
@@ -51,12 +51,49 @@ where
         })
     }
 
+    /// Resolves `module_specifier` imported by `base`.
+    ///
+    /// The result is cached, so plugins are called only once for each pair of
+    /// `base` and `module_specifier`, with the kind of the first request.
     pub(super) fn resolve(
         &self,
         base: &FileName,
         module_specifier: &str,
+        kind: ImportKind,
+    ) -> Result<Lrc<FileName>, Error> {
+        if let Some(path) = self.scope.get_resolved(base, module_specifier) {
+            return Ok(path);
+        }
+
+        let path = self.resolve_uncached(base, module_specifier, kind)?;
+        self.scope
+            .store_resolved(base, module_specifier, path.clone());
+        Ok(path)
+    }
+
+    fn resolve_uncached(
+        &self,
+        base: &FileName,
+        module_specifier: &str,
+        kind: ImportKind,
     ) -> Result<Lrc<FileName>, Error> {
         self.run(|| {
+            for plugin in &self.config.plugins {
+                let path = plugin
+                    .resolve_id(module_specifier, Some(base), kind)
+                    .with_context(|| {
+                        format!(
+                            "plugin `{}` failed to resolve {} from {}",
+                            plugin.name(),
+                            module_specifier,
+                            base
+                        )
+                    })?;
+                if let Some(path) = path {
+                    return Ok(Lrc::new(path));
+                }
+            }
+
             let path = self
                 .resolver
                 .resolve(base, module_specifier)
@@ -119,7 +156,7 @@ where
     L: Load,
     R: Resolve,
 {
-    fn ctxt_for(&self, src: &str) -> Option<SyntaxContext> {
+    fn ctxt_for(&self, src: &str, kind: ImportKind) -> Option<SyntaxContext> {
         // Don't apply mark if it's a core module.
         if self
             .bundler
@@ -130,7 +167,7 @@ where
         {
             return None;
         }
-        let path = self.bundler.resolve(self.path, src).ok()?;
+        let path = self.bundler.resolve(self.path, src, kind).ok()?;
         let (_, mark) = self.bundler.scope.module_id_gen.gen(&path);
         let ctxt = SyntaxContext::empty();
 
@@ -161,7 +198,7 @@ where
             return import;
         }
 
-        if let Some(ctxt) = self.ctxt_for(&import.src.value) {
+        if let Some(ctxt) = self.ctxt_for(&import.src.value, ImportKind::Import) {
             import.span = import.span.with_ctxt(ctxt);
         }
 
@@ -299,7 +336,7 @@ where
 
                                     false
                                 }) {
-                                    let mark = self.ctxt_for(&import.src.value);
+                                    let mark = self.ctxt_for(&import.src.value, ImportKind::Import);
                                     let ctxt = match mark {
                                         None => return e.into(),
                                         Some(mark) => mark,
@@ -389,7 +426,12 @@ where
                     {
                         match &mut **callee {
                             Expr::Ident(i) => {
-                                if let Some(ctxt) = self.ctxt_for(&src.value) {
+                                let kind = if self.top_level {
+                                    ImportKind::Import
+                                } else {
+                                    ImportKind::Require
+                                };
+                                if let Some(ctxt) = self.ctxt_for(&src.value, kind) {
                                     i.span = i.span.with_ctxt(ctxt);
                                 }
                             }
@@ -478,7 +520,7 @@ where
 
                     match &mut **callee {
                         Expr::Ident(i) => {
-                            if let Some(mark) = self.ctxt_for(&src.value) {
+                            if let Some(mark) = self.ctxt_for(&src.value, ImportKind::Require) {
                                 i.span = i.span.with_ctxt(mark);
                             }
                         }
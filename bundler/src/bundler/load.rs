@@ -1,10 +1,10 @@
-use super::{export::Exports, helpers::Helpers, Bundler};
+use super::{export::Exports, helpers::Helpers, tla, Bundler};
 use crate::{
     bundler::{export::RawExports, import::RawImports},
     id::{Id, ModuleId},
     util,
     util::IntoParallelIterator,
-    Load, Resolve,
+    ImportKind, Load, Resolve,
 };
 use anyhow::{Context, Error};
 use is_macro::Is;
@@ -16,7 +16,7 @@ use swc_ecma_ast::{
     CallExpr, Expr, ExprOrSuper, Ident, ImportDecl, ImportSpecifier, Invalid, MemberExpr, Module,
     ModuleDecl, Str,
 };
-use swc_ecma_transforms::resolver_with_mark;
+use swc_ecma_transforms::{optimization::simplify::dce, resolver_with_mark};
 use swc_ecma_visit::{noop_visit_type, FoldWith, Node, Visit, VisitWith};
 /// Module after applying transformations.
 #[derive(Debug, Clone)]
@@ -30,6 +30,13 @@ pub(super) struct TransformedModule {
     /// If false, the module will be wrapped with a small helper function.
     pub is_es6: bool,
 
+    /// True if evaluating the module itself may have side effects.
+    /// Dependencies are not considered.
+    pub has_side_effects: bool,
+
+    /// True if the module uses `await` on top level.
+    pub has_top_level_await: bool,
+
     /// Used helpers
     pub helpers: Lrc<Helpers>,
 
@@ -92,14 +99,21 @@ where
         })
     }
 
-    fn load(&self, file_name: &FileName) -> Result<(ModuleId, Lrc<SourceFile>, Module), Error> {
+    pub(super) fn load(
+        &self,
+        file_name: &FileName,
+    ) -> Result<(ModuleId, Lrc<SourceFile>, Module), Error> {
         self.run(|| {
             let (module_id, _) = self.scope.module_id_gen.gen(file_name);
 
-            let (fm, module) = self
-                .loader
-                .load(&file_name)
-                .with_context(|| format!("Bundler.loader.load({}) failed", file_name))?;
+            let (fm, module) = match self.load_from_plugins(&file_name)? {
+                Some(v) => v,
+                None => self
+                    .loader
+                    .load(&file_name)
+                    .with_context(|| format!("Bundler.loader.load({}) failed", file_name))?,
+            };
+            let module = self.transform_with_plugins(&file_name, module)?;
             self.scope.mark_as_loaded(module_id);
             Ok((module_id, fm, module))
         })
@@ -114,7 +128,9 @@ where
     ) -> Result<(TransformedModule, Vec<(Source, Lrc<FileName>)>), Error> {
         self.run(|| {
             log::trace!("transform_module({})", fm.name);
+            self.rewrite_shared_imports(file_name, &mut module)?;
             module = module.fold_with(&mut resolver_with_mark(self.top_level_mark));
+            let helpers = Helpers::extract_from(&mut module, self.helper_mark);
 
             let (id, mark) = self.scope.module_id_gen.gen(file_name);
 
@@ -166,6 +182,14 @@ where
                 module = self.drop_unused(module, None);
             }
 
+            // `sideEffects` of package.json takes precedence over the analysis.
+            let has_side_effects = !is_es6
+                || self.resolver.side_effects(file_name).unwrap_or_else(|| {
+                    dce::module_has_side_effects(&module, self.loader.comments())
+                });
+
+            let has_top_level_await = tla::has_top_level_await(&module);
+
             let (imports, exports) = util::join(
                 || self.resolve_imports(file_name, imports),
                 || self.resolve_exports(file_name, exports),
@@ -184,7 +208,9 @@ where
                     imports: Lrc::new(imports),
                     exports: Lrc::new(exports),
                     is_es6,
-                    helpers: Default::default(),
+                    has_side_effects,
+                    has_top_level_await,
+                    helpers: Lrc::new(helpers),
                     mark,
                 },
                 import_files,
@@ -193,7 +219,7 @@ where
     }
 
     /// Resolve re-exports.
-    fn resolve_exports(
+    pub(super) fn resolve_exports(
         &self,
         base: &FileName,
         raw: RawExports,
@@ -211,7 +237,7 @@ where
                     self.run(|| {
                         let info = match src {
                             Some(src) => {
-                                let name = self.resolve(base, &src.value)?;
+                                let name = self.resolve(base, &src.value, ImportKind::Import)?;
                                 let (id, _) = self.scope.module_id_gen.gen(&name);
                                 Some((id, name, src))
                             }
@@ -246,6 +272,20 @@ where
                 }
             }
 
+            for src in raw.all {
+                let name = self.resolve(base, &src.value, ImportKind::Import)?;
+                let (id, _) = self.scope.module_id_gen.gen(&name);
+                let src = Source {
+                    is_loaded_synchronously: true,
+                    is_unconditional: false,
+                    module_id: id,
+                    src,
+                };
+                exports.reexports.entry(src.clone()).or_default();
+                exports.reexports_all.insert(src.clone());
+                files.push((src, name));
+            }
+
             Ok((exports, files))
         })
     }
@@ -286,7 +326,14 @@ where
                 .map(|(decl, dynamic, unconditional)| -> Result<_, Error> {
                     self.run(|| {
                         //
-                        let file_name = self.resolve(base, &decl.src.value)?;
+                        let kind = if dynamic {
+                            ImportKind::DynamicImport
+                        } else if unconditional {
+                            ImportKind::Import
+                        } else {
+                            ImportKind::Require
+                        };
+                        let file_name = self.resolve(base, &decl.src.value, kind)?;
                         let (id, _) = self.scope.module_id_gen.gen(&file_name);
 
                         Ok((id, file_name, decl, dynamic, unconditional))
@@ -1,5 +1,12 @@
 use self::scope::Scope;
-use crate::{Load, ModuleId, Resolve};
+pub use self::{
+    federation::{FederationConfig, SharedConfig},
+    stats::{
+        AssetStats, BundleStats, ChunkModuleStats, ChunkStats, EntrypointStats, Issuer,
+        ModuleStats, Reason,
+    },
+};
+use crate::{Load, ModuleId, Plugin, Resolve};
 use anyhow::{Context, Error};
 use std::collections::HashMap;
 use swc_atoms::JsWord;
@@ -7,22 +14,72 @@ use swc_common::{sync::Lrc, FileName, Globals, Mark, SourceMap, DUMMY_SP, GLOBAL
 use swc_ecma_ast::Module;
 
 mod chunk;
+mod cjs_hoisting;
 mod export;
+mod federation;
 mod finalize;
+mod format;
 mod helpers;
+mod hmr;
 mod import;
 mod load;
+mod plugin;
 mod scope;
+mod stats;
 #[cfg(test)]
 mod tests;
+mod tla;
 mod usage_analysis;
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct Config {
     /// If it's true, [Bundler] searches for require calls.
     pub require: bool,
     /// List of modules which should be preserved.
     pub external_modules: Vec<JsWord>,
+    /// Module format of bundles.
+    pub format: OutputFormat,
+    /// Global variables for external modules, like `jquery` => `$`.
+    ///
+    /// Used only by [OutputFormat::Iife] and [OutputFormat::Umd].
+    pub external_globals: HashMap<JsWord, JsWord>,
+    /// Plugins, which are called in order.
+    pub plugins: Vec<Box<dyn Plugin>>,
+    /// Exposed modules and shared packages for module federation.
+    pub federation: Option<FederationConfig>,
+    /// If set, modules are registered to a registry instead of being merged,
+    /// so that they can be replaced at runtime.
+    pub hmr: Option<HmrConfig>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Es module.
+    ///
+    /// This is the only format which supports modules using top-level await.
+    Es,
+    /// Common js module.
+    CommonJs,
+    /// A function expression which is invoked immediately, for `<script>`
+    /// tags.
+    Iife {
+        /// If provided, exports of the entry are assigned to a global variable
+        /// with this name.
+        global_name: Option<JsWord>,
+    },
+    /// Universal module definition, which works as an amd module, a common js
+    /// module or a `<script>` tag.
+    Umd {
+        /// Name of the global variable used if neither amd nor common js is
+        /// available.
+        global_name: JsWord,
+    },
+}
+
+impl Default for OutputFormat {
+    fn default() -> Self {
+        OutputFormat::Es
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -62,6 +119,8 @@ where
     used_mark: Mark,
     /// [Mark] used while tree shaking
     top_level_mark: Mark,
+    /// [Mark] applied to helpers inlined by the bundler.
+    helper_mark: Mark,
 
     scope: Scope,
 }
@@ -86,6 +145,7 @@ where
                 "top-level mark: {:?}",
                 DUMMY_SP.apply_mark(top_level_mark).ctxt()
             );
+            let helper_mark = Mark::fresh(Mark::root());
 
             Bundler {
                 cm,
@@ -93,6 +153,7 @@ where
                 resolver,
                 used_mark,
                 top_level_mark,
+                helper_mark,
                 scope: Default::default(),
                 globals,
                 config,
@@ -103,13 +164,31 @@ where
     ///
     ///
     ///
-    /// Note: This method will panic if entries references each other in
-    /// circular manner. However, it applies only to the provided `entries`, and
-    /// dependencies with circular reference is ok.
-    pub fn bundle(&self, entries: HashMap<String, FileName>) -> Result<Vec<Bundle>, Error> {
+    /// Note: Entries are never merged into other bundles. If entries reference
+    /// each other in circular manner, other modules in the cycle are hoisted
+    /// into a lib bundle imported by the entries.
+    ///
+    /// Circular dependencies are reported as warnings using
+    /// [swc_ecma_utils::HANDLER], if it's set.
+    ///
+    /// If [Config::federation] is set, exposed modules and shared packages are
+    /// bundled separately, and the container entry is emitted as a named
+    /// bundle.
+    ///
+    /// If [Config::hmr] is set, bundles contain a module registry and modules
+    /// are not merged. See [Bundler::hmr_manifest].
+    pub fn bundle(&self, mut entries: HashMap<String, FileName>) -> Result<Vec<Bundle>, Error> {
+        if self.config.hmr.is_some() {
+            let bundles = self.bundle_hot(entries)?;
+            return self.render_with_plugins(bundles);
+        }
+
+        self.add_exposed_modules(&mut entries)?;
+
         let results = entries
             .into_iter()
             .map(|(name, path)| -> Result<_, Error> {
+                let path = self.resolve_entry(path)?;
                 let res = self
                     .load_transformed(&path)
                     .context("load_transformed failed")?;
@@ -120,7 +199,7 @@ where
         // We collect at here to handle dynamic imports
         // TODO: Handle dynamic imports
 
-        let local = {
+        let mut local = {
             let mut output = HashMap::default();
 
             for res in results {
@@ -132,10 +211,14 @@ where
 
             output
         };
+        self.load_shared_modules(&mut local)?;
 
         let bundles = self.chunk(local)?;
 
-        let bundles = self.finalize(bundles)?;
+        let mut bundles = self.finalize(bundles)?;
+        bundles.extend(self.container_entry());
+
+        let bundles = self.render_with_plugins(bundles)?;
         Ok(bundles)
     }
 
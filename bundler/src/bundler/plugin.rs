@@ -0,0 +1,313 @@
+use crate::{Bundle, Bundler, ImportKind, Load, LoadResult, Resolve};
+use anyhow::{anyhow, Context, Error};
+use swc_common::{sync::Lrc, FileName, SourceFile};
+use swc_ecma_ast::Module;
+use swc_ecma_parser::{lexer::Lexer, EsConfig, JscTarget, Parser, StringInput, Syntax};
+use swc_ecma_utils::HANDLER;
+
+/// Methods to run hooks of [Config::plugins].
+///
+/// [Config::plugins]: crate::Config::plugins
+impl<L, R> Bundler<'_, L, R>
+where
+    L: Load,
+    R: Resolve,
+{
+    pub(super) fn resolve_entry(&self, file_name: FileName) -> Result<FileName, Error> {
+        let specifier = file_name.to_string();
+        for plugin in &self.config.plugins {
+            let resolved = plugin
+                .resolve_id(&specifier, None, ImportKind::Entry)
+                .with_context(|| {
+                    format!(
+                        "plugin `{}` failed to resolve entry {}",
+                        plugin.name(),
+                        specifier
+                    )
+                })?;
+            if let Some(resolved) = resolved {
+                return Ok(resolved);
+            }
+        }
+
+        Ok(file_name)
+    }
+
+    /// Returns [None] if no plugin provides `file_name`.
+    pub(super) fn load_from_plugins(
+        &self,
+        file_name: &FileName,
+    ) -> Result<Option<(Lrc<SourceFile>, Module)>, Error> {
+        for plugin in &self.config.plugins {
+            let loaded = plugin.load(file_name).with_context(|| {
+                format!("plugin `{}` failed to load {}", plugin.name(), file_name)
+            })?;
+
+            match loaded {
+                Some(LoadResult::Code(code)) => {
+                    let fm = self.cm.new_source_file(file_name.clone(), code);
+                    let lexer = Lexer::new(
+                        Syntax::Es(EsConfig {
+                            dynamic_import: true,
+                            top_level_await: true,
+                            ..Default::default()
+                        }),
+                        JscTarget::Es2020,
+                        StringInput::from(&*fm),
+                        None,
+                    );
+                    let mut parser = Parser::new_from(lexer);
+                    let module = parser.parse_module().map_err(|err| {
+                        let msg = format!(
+                            "failed to parse {} loaded by plugin `{}`",
+                            file_name,
+                            plugin.name()
+                        );
+                        if HANDLER.is_set() {
+                            HANDLER.with(|handler| err.into_diagnostic(handler).emit());
+                            Error::msg(msg)
+                        } else {
+                            anyhow!("{}: {:?}", msg, err)
+                        }
+                    })?;
+
+                    return Ok(Some((fm, module)));
+                }
+                Some(LoadResult::Module(fm, module)) => return Ok(Some((fm, module))),
+                None => {}
+            }
+        }
+
+        Ok(None)
+    }
+
+    pub(super) fn transform_with_plugins(
+        &self,
+        file_name: &FileName,
+        mut module: Module,
+    ) -> Result<Module, Error> {
+        for plugin in &self.config.plugins {
+            module = plugin.transform(file_name, module).with_context(|| {
+                format!(
+                    "plugin `{}` failed to transform {}",
+                    plugin.name(),
+                    file_name
+                )
+            })?;
+        }
+
+        Ok(module)
+    }
+
+    /// Runs `render_chunk` and `generate_bundle` hooks.
+    pub(super) fn render_with_plugins(&self, bundles: Vec<Bundle>) -> Result<Vec<Bundle>, Error> {
+        let mut bundles = bundles
+            .into_iter()
+            .map(|bundle| {
+                self.config
+                    .plugins
+                    .iter()
+                    .try_fold(bundle, |bundle, plugin| {
+                        let id = bundle.id;
+                        plugin.render_chunk(bundle).with_context(|| {
+                            format!("plugin `{}` failed to render chunk {}", plugin.name(), id)
+                        })
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for plugin in &self.config.plugins {
+            plugin
+                .generate_bundle(&mut bundles)
+                .with_context(|| format!("plugin `{}` failed to generate bundle", plugin.name()))?;
+        }
+
+        Ok(bundles)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{bundler::tests::suite, Bundle, ImportKind, LoadResult, Plugin};
+    use anyhow::Error;
+    use std::{
+        collections::HashMap,
+        sync::{
+            atomic::{AtomicBool, Ordering::SeqCst},
+            Arc, Mutex,
+        },
+    };
+    use swc_common::{FileName, DUMMY_SP};
+    use swc_ecma_ast::*;
+    use swc_ecma_utils::quote_str;
+
+    struct VirtualEnv {
+        generated: Arc<AtomicBool>,
+    }
+
+    impl Plugin for VirtualEnv {
+        fn name(&self) -> &str {
+            "virtual-env"
+        }
+
+        fn resolve_id(
+            &self,
+            specifier: &str,
+            _: Option<&FileName>,
+            kind: ImportKind,
+        ) -> Result<Option<FileName>, Error> {
+            if specifier == "virtual:env" {
+                assert_eq!(kind, ImportKind::Import);
+                return Ok(Some(FileName::Custom(specifier.into())));
+            }
+            Ok(None)
+        }
+
+        fn load(&self, file: &FileName) -> Result<Option<LoadResult>, Error> {
+            match file {
+                FileName::Custom(name) if name == "virtual:env" => Ok(Some(LoadResult::Code(
+                    "export const mode = 'production';".into(),
+                ))),
+                _ => Ok(None),
+            }
+        }
+
+        fn generate_bundle(&self, bundles: &mut Vec<Bundle>) -> Result<(), Error> {
+            assert_eq!(bundles.len(), 1);
+            self.generated.store(true, SeqCst);
+            Ok(())
+        }
+    }
+
+    struct Banner(&'static str);
+
+    impl Plugin for Banner {
+        fn name(&self) -> &str {
+            "banner"
+        }
+
+        fn render_chunk(&self, mut bundle: Bundle) -> Result<Bundle, Error> {
+            bundle.module.body.insert(
+                0,
+                ModuleItem::Stmt(Stmt::Expr(ExprStmt {
+                    span: DUMMY_SP,
+                    expr: Box::new(Expr::Lit(Lit::Str(quote_str!(self.0)))),
+                })),
+            );
+            Ok(bundle)
+        }
+    }
+
+    fn banner(item: &ModuleItem) -> &str {
+        match item {
+            ModuleItem::Stmt(Stmt::Expr(ExprStmt { expr, .. })) => match &**expr {
+                Expr::Lit(Lit::Str(s)) => &*s.value,
+                _ => panic!("not a banner: {:?}", item),
+            },
+            _ => panic!("not a banner: {:?}", item),
+        }
+    }
+
+    #[test]
+    fn virtual_module_and_banner() {
+        let generated = Arc::new(AtomicBool::new(false));
+
+        suite()
+            .file(
+                "main.js",
+                "
+                import { mode } from 'virtual:env';
+                console.log(mode);
+                ",
+            )
+            .plugin(VirtualEnv {
+                generated: generated.clone(),
+            })
+            .plugin(Banner("a"))
+            .plugin(Banner("b"))
+            .run(|t| {
+                let mut entries = HashMap::default();
+                entries.insert("main".to_string(), FileName::Real("main.js".into()));
+
+                let bundles = t.bundler.bundle(entries)?;
+                assert_eq!(bundles.len(), 1);
+
+                let body = &bundles[0].module.body;
+                assert_eq!(banner(&body[0]), "b");
+                assert_eq!(banner(&body[1]), "a");
+                assert!(
+                    !body
+                        .iter()
+                        .any(|item| matches!(item, ModuleItem::ModuleDecl(ModuleDecl::Import(..)))),
+                    "virtual module should be merged"
+                );
+
+                Ok(())
+            });
+
+        assert!(generated.load(SeqCst));
+    }
+
+    /// Records calls to `resolve_id`.
+    struct ResolveRecorder {
+        calls: Arc<Mutex<Vec<(String, ImportKind)>>>,
+    }
+
+    impl Plugin for ResolveRecorder {
+        fn name(&self) -> &str {
+            "resolve-recorder"
+        }
+
+        fn resolve_id(
+            &self,
+            specifier: &str,
+            importer: Option<&FileName>,
+            kind: ImportKind,
+        ) -> Result<Option<FileName>, Error> {
+            if importer.is_some() {
+                self.calls.lock().unwrap().push((specifier.into(), kind));
+            }
+            Ok(None)
+        }
+    }
+
+    #[test]
+    fn resolve_id_is_called_once_with_kind() {
+        let calls = Arc::new(Mutex::new(vec![]));
+
+        suite()
+            .file(
+                "main.js",
+                "
+                import { a } from './a';
+                function load() {
+                    return require('./b');
+                }
+                console.log(a, load());
+                ",
+            )
+            .file("a.js", "export const a = 1;")
+            .file("b.js", "module.exports = 2;")
+            .plugin(ResolveRecorder {
+                calls: calls.clone(),
+            })
+            .run(|t| {
+                let mut entries = HashMap::default();
+                entries.insert("main".to_string(), FileName::Real("main.js".into()));
+
+                t.bundler.bundle(entries)?;
+
+                Ok(())
+            });
+
+        let mut calls = calls.lock().unwrap().clone();
+        calls.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            calls,
+            vec![
+                ("./a".to_string(), ImportKind::Import),
+                ("./b".to_string(), ImportKind::Require)
+            ]
+        );
+    }
+}
@@ -1,19 +1,40 @@
-use super::load::TransformedModule;
+use super::{hmr::HmrManifest, load::TransformedModule};
 use crate::{
     id::{ModuleId, ModuleIdGenerator},
     util::CloneMap,
 };
-use swc_common::FileName;
+use std::collections::HashMap;
+use swc_atoms::JsWord;
+use swc_common::{
+    sync::{Lock, Lrc},
+    FileName,
+};
 
 #[derive(Debug, Default)]
 pub(super) struct Scope {
     pub module_id_gen: ModuleIdGenerator,
 
     circular_modules: CloneMap<ModuleId, ()>,
+    /// Modules using top-level await, and their importers.
+    async_modules: CloneMap<ModuleId, ()>,
     loaded_modules: CloneMap<ModuleId, ()>,
+    /// Modules which are not included in any bundle.
+    dropped_modules: CloneMap<ModuleId, ()>,
 
     /// Cached after applying basical transformations.
     transformed_modules: CloneMap<ModuleId, TransformedModule>,
+
+    /// Modules merged into a bundle, keyed by the id of the bundle.
+    bundled_modules: CloneMap<ModuleId, Vec<ModuleId>>,
+
+    /// Resolved modules, keyed by importers and module specifiers.
+    resolved: CloneMap<(FileName, JsWord), Lrc<FileName>>,
+
+    /// Shared packages of module federation, mapped to one of their importers.
+    shared_imports: Lock<HashMap<JsWord, FileName>>,
+
+    /// Manifest of the last build with hot module replacement.
+    hmr_manifest: Lock<Option<HmrManifest>>,
 }
 
 impl Scope {
@@ -25,6 +46,22 @@ impl Scope {
         self.circular_modules.insert(id, ());
     }
 
+    pub fn is_async(&self, id: ModuleId) -> bool {
+        self.async_modules.get(&id).is_some()
+    }
+
+    pub fn mark_as_async(&self, id: ModuleId) {
+        self.async_modules.insert(id, ());
+    }
+
+    pub fn is_dropped(&self, id: ModuleId) -> bool {
+        self.dropped_modules.get(&id).is_some()
+    }
+
+    pub fn mark_as_dropped(&self, id: ModuleId) {
+        self.dropped_modules.insert(id, ());
+    }
+
     pub fn mark_as_loaded(&self, id: ModuleId) {
         self.loaded_modules.insert(id, ());
     }
@@ -44,4 +81,44 @@ impl Scope {
     pub fn get_module(&self, id: ModuleId) -> Option<TransformedModule> {
         Some(self.transformed_modules.get(&id)?.clone())
     }
+
+    pub fn store_bundled_modules(&self, bundle: ModuleId, modules: Vec<ModuleId>) {
+        self.bundled_modules.insert(bundle, modules);
+    }
+
+    /// Returns modules merged into the bundle, including the entry.
+    pub fn get_bundled_modules(&self, bundle: ModuleId) -> Vec<ModuleId> {
+        self.bundled_modules.get(&bundle).unwrap_or_default()
+    }
+
+    pub fn get_resolved(&self, base: &FileName, specifier: &str) -> Option<Lrc<FileName>> {
+        self.resolved.get(&(base.clone(), specifier.into()))
+    }
+
+    pub fn store_resolved(&self, base: &FileName, specifier: &str, resolved: Lrc<FileName>) {
+        self.resolved
+            .insert((base.clone(), specifier.into()), resolved);
+    }
+
+    pub fn store_shared_import(&self, src: JsWord, importer: FileName) {
+        self.shared_imports.lock().entry(src).or_insert(importer);
+    }
+
+    /// Returns shared packages imported by any module, with one of their
+    /// importers.
+    pub fn shared_imports(&self) -> Vec<(JsWord, FileName)> {
+        self.shared_imports
+            .lock()
+            .iter()
+            .map(|(src, importer)| (src.clone(), importer.clone()))
+            .collect()
+    }
+
+    pub fn store_hmr_manifest(&self, manifest: HmrManifest) {
+        *self.hmr_manifest.lock() = Some(manifest);
+    }
+
+    pub fn get_hmr_manifest(&self) -> Option<HmrManifest> {
+        self.hmr_manifest.lock().clone()
+    }
 }
@@ -0,0 +1,576 @@
+use super::{
+    load::TransformedModule,
+    usage_analysis::{used_names, UsedExports},
+};
+use crate::{Bundle, BundleKind, Bundler, Load, ModuleId, Resolve};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use swc_atoms::JsWord;
+use swc_common::{FileName, Span, Spanned, DUMMY_SP};
+use swc_ecma_ast::*;
+use swc_ecma_visit::{noop_visit_type, Node, Visit, VisitWith};
+
+/// Statistics of bundles, which can be serialized as a json file compatible
+/// with stats of webpack.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleStats {
+    pub assets: Vec<AssetStats>,
+    /// Chunks required to load each entry, keyed by the name of the entry.
+    pub entrypoints: BTreeMap<String, EntrypointStats>,
+    pub chunks: Vec<ChunkStats>,
+    pub modules: Vec<ModuleStats>,
+    /// Modules included in more than one chunk.
+    pub duplicated_modules: Vec<ModuleId>,
+}
+
+/// A file emitted for a chunk.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetStats {
+    pub name: String,
+    /// Same as the size of the chunk.
+    pub size: usize,
+    pub chunks: Vec<ModuleId>,
+    pub chunk_names: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EntrypointStats {
+    /// Chunks in the order of evaluation. The chunk of the entry is the last
+    /// one.
+    pub chunks: Vec<ModuleId>,
+    /// Files of `chunks`.
+    pub assets: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChunkStats {
+    /// Id of the entry module of the chunk.
+    pub id: ModuleId,
+    pub names: Vec<String>,
+    pub files: Vec<String>,
+    /// False for chunks shared by other chunks.
+    pub entry: bool,
+    pub initial: bool,
+    /// Size of the code in the chunk, in bytes.
+    pub size: usize,
+    pub modules: Vec<ChunkModuleStats>,
+}
+
+/// A module included in a chunk.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChunkModuleStats {
+    pub id: ModuleId,
+    pub name: String,
+    /// Size of the code of the module in the chunk, in bytes.
+    pub size: usize,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModuleStats {
+    pub id: ModuleId,
+    pub name: String,
+    /// Size of the source file, in bytes.
+    pub size: usize,
+    /// Size of the code left in bundles after tree shaking, in bytes.
+    ///
+    /// Code generated by the bundler is not counted.
+    pub tree_shaken_size: usize,
+    /// Chunks containing this module. Empty if the module is removed because
+    /// it's not used.
+    pub chunks: Vec<ModuleId>,
+    /// Modules which import this module.
+    pub reasons: Vec<Reason>,
+    /// The import chain from an entry to this module, excluding this module.
+    pub issuer_path: Vec<Issuer>,
+    pub provided_exports: Vec<String>,
+    /// [None] if all exports are used.
+    pub used_exports: Option<Vec<String>>,
+    /// Exports removed by tree shaking.
+    pub removed_exports: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Reason {
+    pub module_id: ModuleId,
+    pub module_name: String,
+    /// `harmony import`, `harmony export imported specifier`, `cjs require`
+    /// or `import()`.
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    /// The import path used by the importer.
+    pub user_request: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Issuer {
+    pub id: ModuleId,
+    pub name: String,
+}
+
+impl<L, R> Bundler<'_, L, R>
+where
+    L: Load,
+    R: Resolve,
+{
+    /// Computes statistics of `bundles`, which should be the result of
+    /// [Bundler::bundle].
+    pub fn stats(&self, bundles: &[Bundle]) -> BundleStats {
+        self.run(|| {
+            let mut modules: HashMap<ModuleId, TransformedModule> = HashMap::default();
+            let mut order = vec![];
+            let mut issuers = HashMap::<ModuleId, ModuleId>::default();
+            let mut reasons = HashMap::<ModuleId, Vec<Reason>>::default();
+            let mut used = HashMap::<ModuleId, UsedExports>::default();
+
+            let mut queue = bundles.iter().map(|b| b.id).collect::<VecDeque<_>>();
+            for &id in &queue {
+                used.insert(id, UsedExports::All);
+            }
+
+            while let Some(id) = queue.pop_front() {
+                if modules.contains_key(&id) {
+                    continue;
+                }
+                let module = match self.scope.get_module(id) {
+                    Some(v) => v,
+                    None => continue,
+                };
+
+                let deps = module
+                    .imports
+                    .specifiers
+                    .iter()
+                    .map(|(src, specifiers)| {
+                        let (kind, names) = if !src.is_loaded_synchronously {
+                            ("import()", UsedExports::All)
+                        } else if !src.is_unconditional {
+                            ("cjs require", UsedExports::All)
+                        } else {
+                            ("harmony import", used_names(specifiers.iter()))
+                        };
+                        (src, kind, names)
+                    })
+                    .chain(module.exports.reexports.iter().map(|(src, specifiers)| {
+                        (
+                            src,
+                            "harmony export imported specifier",
+                            used_names(specifiers.iter()),
+                        )
+                    }));
+
+                for (src, kind, names) in deps {
+                    let dep = src.module_id;
+
+                    reasons.entry(dep).or_default().push(Reason {
+                        module_id: id,
+                        module_name: module.fm.name.to_string(),
+                        kind,
+                        user_request: src.src.value.to_string(),
+                    });
+                    match used.get_mut(&dep) {
+                        Some(v) => {
+                            v.extend(names);
+                        }
+                        None => {
+                            used.insert(dep, names);
+                        }
+                    }
+
+                    // Issuer paths start from the entry of a chunk.
+                    if !issuers.contains_key(&dep) && bundles.iter().all(|b| b.id != dep) {
+                        issuers.insert(dep, id);
+                    }
+                    queue.push_back(dep);
+                }
+
+                order.push(id);
+                modules.insert(id, module);
+            }
+
+            let files = modules
+                .values()
+                .map(|m| (m.fm.name.clone(), m.id))
+                .collect::<HashMap<_, _>>();
+            let mut sizes = HashMap::<ModuleId, usize>::default();
+            let mut chunk_of = HashMap::<ModuleId, Vec<ModuleId>>::default();
+
+            let chunks = bundles
+                .iter()
+                .map(|bundle| {
+                    let mut v = SizeCalculator {
+                        files: &files,
+                        bundler: self,
+                        sizes: HashMap::default(),
+                    };
+                    bundle
+                        .module
+                        .visit_with(&Invalid { span: DUMMY_SP } as _, &mut v);
+
+                    for (id, size) in &v.sizes {
+                        *sizes.entry(*id).or_default() += size;
+                    }
+
+                    let bundled = self.scope.get_bundled_modules(bundle.id);
+                    for &id in &bundled {
+                        chunk_of.entry(id).or_default().push(bundle.id);
+                    }
+
+                    let names = match &bundle.kind {
+                        BundleKind::Named { name } | BundleKind::Lib { name } => vec![name.clone()],
+                        BundleKind::Dynamic => vec![],
+                    };
+
+                    ChunkStats {
+                        id: bundle.id,
+                        files: names.clone(),
+                        names,
+                        // Chunks shared by entries are imported by them.
+                        entry: !reasons.contains_key(&bundle.id),
+                        initial: !matches!(bundle.kind, BundleKind::Dynamic),
+                        size: v.sizes.values().sum(),
+                        modules: bundled
+                            .into_iter()
+                            .filter_map(|id| {
+                                Some(ChunkModuleStats {
+                                    id,
+                                    name: modules.get(&id)?.fm.name.to_string(),
+                                    size: v.sizes.get(&id).copied().unwrap_or(0),
+                                })
+                            })
+                            .collect(),
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            let assets = chunks
+                .iter()
+                .flat_map(|chunk| {
+                    chunk.files.iter().map(move |file| AssetStats {
+                        name: file.clone(),
+                        size: chunk.size,
+                        chunks: vec![chunk.id],
+                        chunk_names: chunk.names.clone(),
+                    })
+                })
+                .collect();
+
+            let entrypoints = chunks
+                .iter()
+                .filter(|chunk| chunk.entry)
+                .filter_map(|chunk| {
+                    let name = chunk.names.first()?.clone();
+
+                    let mut ids = vec![];
+                    visit_chunk_deps(&modules, &chunks, chunk.id, &mut ids);
+
+                    let assets = ids
+                        .iter()
+                        .filter_map(|id| chunks.iter().find(|c| c.id == *id))
+                        .flat_map(|c| c.files.clone())
+                        .collect();
+
+                    Some((
+                        name,
+                        EntrypointStats {
+                            chunks: ids,
+                            assets,
+                        },
+                    ))
+                })
+                .collect();
+
+            let mut duplicated_modules = vec![];
+            let modules = order
+                .into_iter()
+                .map(|id| {
+                    let module = &modules[&id];
+
+                    let provided_exports = module
+                        .exports
+                        .items
+                        .iter()
+                        .chain(module.exports.reexports.values().flatten())
+                        .map(|s| s.local().sym().to_string())
+                        .collect::<Vec<_>>();
+                    let used = used
+                        .remove(&id)
+                        .unwrap_or(UsedExports::Some(HashSet::new()));
+                    let (used_exports, removed_exports) = match used {
+                        UsedExports::All => (None, vec![]),
+                        UsedExports::Some(names) => {
+                            let removed = provided_exports
+                                .iter()
+                                .filter(|s| !names.contains(&JsWord::from(s.as_str())))
+                                .cloned()
+                                .collect();
+                            let mut names =
+                                names.into_iter().map(|s| s.to_string()).collect::<Vec<_>>();
+                            names.sort();
+                            (Some(names), removed)
+                        }
+                    };
+
+                    let mut issuer_path = vec![];
+                    let mut cur = id;
+                    while let Some(&issuer) = issuers.get(&cur) {
+                        if issuer_path.iter().any(|i: &Issuer| i.id == issuer) {
+                            break;
+                        }
+                        issuer_path.push(Issuer {
+                            id: issuer,
+                            name: modules[&issuer].fm.name.to_string(),
+                        });
+                        cur = issuer;
+                    }
+                    issuer_path.reverse();
+
+                    let chunks = chunk_of.remove(&id).unwrap_or_default();
+                    if chunks.len() > 1 {
+                        duplicated_modules.push(id);
+                    }
+
+                    ModuleStats {
+                        id,
+                        name: module.fm.name.to_string(),
+                        size: module.fm.src.len(),
+                        tree_shaken_size: sizes.get(&id).copied().unwrap_or(0),
+                        chunks,
+                        reasons: reasons.remove(&id).unwrap_or_default(),
+                        issuer_path,
+                        provided_exports,
+                        used_exports,
+                        removed_exports,
+                    }
+                })
+                .collect();
+
+            BundleStats {
+                assets,
+                entrypoints,
+                chunks,
+                modules,
+                duplicated_modules,
+            }
+        })
+    }
+}
+
+/// Adds chunks imported by the chunk `id` and then `id` itself to `ids`.
+fn visit_chunk_deps(
+    modules: &HashMap<ModuleId, TransformedModule>,
+    chunks: &[ChunkStats],
+    id: ModuleId,
+    ids: &mut Vec<ModuleId>,
+) {
+    if ids.contains(&id) {
+        return;
+    }
+    let chunk = match chunks.iter().find(|c| c.id == id) {
+        Some(v) => v,
+        None => return,
+    };
+    // Prevent infinite loop on chunks importing each other.
+    ids.push(id);
+
+    for module in chunk.modules.iter().filter_map(|m| modules.get(&m.id)) {
+        let deps = module
+            .imports
+            .specifiers
+            .iter()
+            .map(|(src, _)| src)
+            .chain(module.exports.reexports.iter().map(|(src, _)| src))
+            .filter(|src| src.is_loaded_synchronously);
+        for src in deps {
+            if src.module_id != id && chunks.iter().any(|c| c.id == src.module_id) {
+                visit_chunk_deps(modules, chunks, src.module_id, ids);
+            }
+        }
+    }
+
+    // Dependencies are evaluated first.
+    let pos = ids.iter().position(|&v| v == id).unwrap();
+    ids.remove(pos);
+    ids.push(id);
+}
+
+/// Attributes code in a bundle to modules using spans.
+struct SizeCalculator<'a, 'b, L, R>
+where
+    L: Load,
+    R: Resolve,
+{
+    files: &'a HashMap<FileName, ModuleId>,
+    bundler: &'a Bundler<'b, L, R>,
+    sizes: HashMap<ModuleId, usize>,
+}
+
+impl<L, R> SizeCalculator<'_, '_, L, R>
+where
+    L: Load,
+    R: Resolve,
+{
+    /// Returns true if `span` is attributed to a module.
+    fn add(&mut self, span: Span) -> bool {
+        if span.is_dummy() {
+            return false;
+        }
+
+        let fm = self.bundler.cm.lookup_source_file(span.lo);
+        match self.files.get(&fm.name) {
+            Some(&id) => {
+                *self.sizes.entry(id).or_default() += (span.hi.0 - span.lo.0) as usize;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl<L, R> Visit for SizeCalculator<'_, '_, L, R>
+where
+    L: Load,
+    R: Resolve,
+{
+    noop_visit_type!();
+
+    fn visit_module_item(&mut self, item: &ModuleItem, _: &dyn Node) {
+        if !self.add(item.span()) {
+            item.visit_children_with(self)
+        }
+    }
+
+    fn visit_stmt(&mut self, stmt: &Stmt, _: &dyn Node) {
+        if !self.add(stmt.span()) {
+            stmt.visit_children_with(self)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bundler::tests::suite;
+    use std::collections::HashMap;
+    use swc_common::FileName;
+
+    #[test]
+    fn removed_exports() {
+        suite()
+            .file(
+                "main.js",
+                "
+                import { a } from './a';
+                console.log(a);
+                ",
+            )
+            .file(
+                "a.js",
+                "
+                export const a = 1;
+                export const b = 2;
+                ",
+            )
+            .run(|t| {
+                let mut entries = HashMap::default();
+                entries.insert("main".to_string(), FileName::Real("main.js".into()));
+
+                let bundles = t.bundler.bundle(entries)?;
+                let stats = t.bundler.stats(&bundles);
+
+                assert_eq!(stats.chunks.len(), 1);
+                assert!(stats.chunks[0].entry);
+                assert_eq!(stats.modules.len(), 2);
+
+                let a = stats
+                    .modules
+                    .iter()
+                    .find(|m| m.name == "a.js")
+                    .expect("a.js should be in stats");
+                assert_eq!(a.used_exports, Some(vec!["a".to_string()]));
+                assert_eq!(a.removed_exports, vec!["b".to_string()]);
+                assert_eq!(a.chunks, vec![stats.chunks[0].id]);
+                assert_eq!(a.reasons.len(), 1);
+                assert_eq!(a.reasons[0].kind, "harmony import");
+                assert_eq!(a.reasons[0].user_request, "./a");
+                assert_eq!(a.issuer_path.len(), 1);
+                assert_eq!(a.issuer_path[0].name, "main.js");
+                assert!(stats.duplicated_modules.is_empty());
+
+                Ok(())
+            });
+    }
+
+    #[test]
+    fn two_entries() {
+        suite()
+            .file(
+                "a.js",
+                "
+                import { shared } from './shared';
+                console.log('a', shared);
+                ",
+            )
+            .file(
+                "b.js",
+                "
+                import { shared } from './shared';
+                console.log('b', shared);
+                ",
+            )
+            .file("shared.js", "export const shared = 1;")
+            .run(|t| {
+                let mut entries = HashMap::default();
+                entries.insert("a".to_string(), FileName::Real("a.js".into()));
+                entries.insert("b".to_string(), FileName::Real("b.js".into()));
+
+                let bundles = t.bundler.bundle(entries)?;
+                let stats = t.bundler.stats(&bundles);
+
+                assert_eq!(stats.chunks.len(), 3);
+                assert_eq!(stats.assets.len(), 3);
+
+                // The module used by both entries is hoisted into a chunk
+                // instead of being duplicated.
+                let shared = stats
+                    .modules
+                    .iter()
+                    .find(|m| m.name == "shared.js")
+                    .expect("shared.js should be in stats");
+                assert_eq!(shared.chunks.len(), 1);
+                assert!(stats.duplicated_modules.is_empty());
+
+                let lib = stats
+                    .chunks
+                    .iter()
+                    .find(|c| c.id == shared.chunks[0])
+                    .unwrap();
+                assert!(!lib.entry);
+                assert_eq!(lib.files.len(), 1);
+                assert!(lib.files[0].starts_with("shared-"), "{}", lib.files[0]);
+                assert_eq!(
+                    lib.modules.iter().map(|m| &*m.name).collect::<Vec<_>>(),
+                    vec!["shared.js"]
+                );
+                assert!(stats
+                    .assets
+                    .iter()
+                    .any(|a| a.name == lib.files[0] && a.chunks == vec![lib.id]));
+
+                assert_eq!(stats.entrypoints.keys().collect::<Vec<_>>(), vec!["a", "b"]);
+                for (name, entrypoint) in &stats.entrypoints {
+                    assert_eq!(entrypoint.chunks.len(), 2);
+                    assert_eq!(entrypoint.chunks[0], lib.id);
+                    assert_eq!(entrypoint.assets, vec![lib.files[0].clone(), name.clone()]);
+                }
+
+                Ok(())
+            });
+    }
+}
@@ -1,13 +1,22 @@
 //! Utilities for testing.
-use super::{Bundler, Config};
-use crate::{util::HygieneRemover, Load, Resolve};
+use super::{Bundler, Config, FederationConfig, HmrConfig};
+use crate::{util::HygieneRemover, Load, Plugin, Resolve};
 use anyhow::Error;
-use std::{collections::HashMap, path::PathBuf};
-use swc_common::{sync::Lrc, FileName, SourceFile, SourceMap, GLOBALS};
+use std::{
+    collections::HashMap,
+    mem::replace,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+use swc_common::{
+    errors::{DiagnosticBuilder, Emitter, Handler},
+    sync::Lrc,
+    FileName, SourceFile, SourceMap, DUMMY_SP, GLOBALS,
+};
 use swc_ecma_ast::*;
-use swc_ecma_parser::{lexer::Lexer, JscTarget, Parser, StringInput};
-use swc_ecma_utils::drop_span;
-use swc_ecma_visit::VisitMutWith;
+use swc_ecma_parser::{lexer::Lexer, EsConfig, JscTarget, Parser, StringInput, Syntax};
+use swc_ecma_utils::{drop_span, HANDLER};
+use swc_ecma_visit::{noop_visit_mut_type, VisitMut, VisitMutWith};
 
 pub(super) struct Tester<'a> {
     pub cm: Lrc<SourceMap>,
@@ -27,12 +36,7 @@ impl Load for Loader {
 
         let fm = self.cm.new_source_file(f.clone(), v.to_string());
 
-        let lexer = Lexer::new(
-            Default::default(),
-            JscTarget::Es2020,
-            StringInput::from(&*fm),
-            None,
-        );
+        let lexer = Lexer::new(syntax(), JscTarget::Es2020, StringInput::from(&*fm), None);
 
         let mut parser = Parser::new_from(lexer);
         let module = parser.parse_module().unwrap();
@@ -41,6 +45,15 @@ impl Load for Loader {
     }
 }
 
+fn syntax() -> Syntax {
+    Syntax::Es(EsConfig {
+        dynamic_import: true,
+        top_level_await: true,
+        import_meta: true,
+        ..Default::default()
+    })
+}
+
 #[derive(Debug, Default)]
 pub struct Resolver;
 
@@ -71,12 +84,7 @@ impl<'a> Tester<'a> {
             .cm
             .new_source_file(FileName::Real(PathBuf::from("input.js")), s.into());
 
-        let lexer = Lexer::new(
-            Default::default(),
-            Default::default(),
-            StringInput::from(&*fm),
-            None,
-        );
+        let lexer = Lexer::new(syntax(), Default::default(), StringInput::from(&*fm), None);
         let mut parser = Parser::new_from(lexer);
         parser.parse_module().unwrap()
     }
@@ -84,8 +92,12 @@ impl<'a> Tester<'a> {
     pub fn assert_eq(&self, m: &Module, expected: &str) {
         let expected = self.parse(expected);
 
+        let mut expected = expected;
+        expected.visit_mut_with(&mut ParenRemover);
+
         let mut m = m.clone();
         m.visit_mut_with(&mut HygieneRemover);
+        m.visit_mut_with(&mut ParenRemover);
 
         let m = drop_span(m);
         let expected = drop_span(expected);
@@ -93,6 +105,49 @@ impl<'a> Tester<'a> {
         assert_eq!(m, expected)
     }
 }
+
+/// Removes parentheses, which are required to write some expected outputs.
+struct ParenRemover;
+
+impl VisitMut for ParenRemover {
+    noop_visit_mut_type!();
+
+    fn visit_mut_expr(&mut self, e: &mut Expr) {
+        e.visit_mut_children_with(self);
+
+        if let Expr::Paren(paren) = e {
+            let expr = replace(
+                &mut paren.expr,
+                Box::new(Expr::Invalid(Invalid { span: DUMMY_SP })),
+            );
+            *e = *expr;
+        }
+    }
+}
+
+/// Runs `op` with [HANDLER] set, and returns the messages of diagnostics
+/// emitted by it.
+pub(super) fn collect_diagnostics<F, Ret>(op: F) -> (Ret, Vec<String>)
+where
+    F: FnOnce() -> Ret,
+{
+    let diagnostics = Diagnostics::default();
+    let handler = Handler::with_emitter(true, false, Box::new(diagnostics.clone()));
+    let ret = HANDLER.set(&handler, op);
+
+    let messages = diagnostics.0.lock().unwrap().clone();
+    (ret, messages)
+}
+
+#[derive(Clone, Default)]
+struct Diagnostics(Arc<Mutex<Vec<String>>>);
+
+impl Emitter for Diagnostics {
+    fn emit(&mut self, db: &DiagnosticBuilder) {
+        self.0.lock().unwrap().push(db.message());
+    }
+}
+
 pub(super) fn suite() -> TestBuilder {
     TestBuilder::default()
 }
@@ -100,6 +155,9 @@ pub(super) fn suite() -> TestBuilder {
 #[derive(Default)]
 pub(super) struct TestBuilder {
     files: HashMap<String, String>,
+    plugins: Vec<Box<dyn Plugin>>,
+    federation: Option<FederationConfig>,
+    hmr: Option<HmrConfig>,
 }
 
 impl TestBuilder {
@@ -108,6 +166,21 @@ impl TestBuilder {
         self
     }
 
+    pub fn plugin(mut self, plugin: impl Plugin + 'static) -> Self {
+        self.plugins.push(Box::new(plugin));
+        self
+    }
+
+    pub fn federation(mut self, federation: FederationConfig) -> Self {
+        self.federation = Some(federation);
+        self
+    }
+
+    pub fn hmr(mut self, hmr: HmrConfig) -> Self {
+        self.hmr = Some(hmr);
+        self
+    }
+
     pub fn run<F>(self, op: F)
     where
         F: FnOnce(&mut Tester) -> Result<(), Error>,
@@ -125,6 +198,10 @@ impl TestBuilder {
                     Config {
                         require: true,
                         external_modules: vec![],
+                        plugins: self.plugins,
+                        federation: self.federation,
+                        hmr: self.hmr,
+                        ..Default::default()
                     },
                 );
 
@@ -0,0 +1,418 @@
+use super::chunk::ModuleGraph;
+use crate::{Bundler, Load, ModuleId, Resolve};
+use std::{
+    collections::{HashMap, VecDeque},
+    mem::take,
+};
+use swc_common::DUMMY_SP;
+use swc_ecma_ast::*;
+use swc_ecma_utils::{find_ids, ExprFactory};
+use swc_ecma_visit::{noop_visit_type, Node, Visit, VisitWith};
+
+impl<L, R> Bundler<'_, L, R>
+where
+    L: Load,
+    R: Resolve,
+{
+    /// Marks modules using top-level await and their importers as async.
+    ///
+    /// As an importer of an async module should wait for it, async modules are
+    /// wrapped with async functions while merging.
+    pub(super) fn mark_async_modules(&self, graph: &ModuleGraph) {
+        let mut importers = HashMap::<ModuleId, Vec<ModuleId>>::default();
+        let mut queue = VecDeque::new();
+
+        for id in graph.nodes() {
+            let module = match self.scope.get_module(id) {
+                Some(v) => v,
+                None => continue,
+            };
+            if module.has_top_level_await {
+                queue.push_back(id);
+            }
+
+            // `require` and `import()` do not wait for the module.
+            let deps = module
+                .imports
+                .specifiers
+                .iter()
+                .map(|(src, _)| src)
+                .filter(|src| src.is_loaded_synchronously && src.is_unconditional)
+                .chain(module.exports.reexports.iter().map(|(src, _)| src));
+            for src in deps {
+                importers.entry(src.module_id).or_default().push(id);
+            }
+        }
+
+        while let Some(id) = queue.pop_front() {
+            if self.scope.is_async(id) {
+                continue;
+            }
+            log::debug!("Async module: {}", id);
+            self.scope.mark_as_async(id);
+
+            queue.extend(importers.get(&id).into_iter().flatten().copied());
+        }
+    }
+}
+
+/// Returns true if `module` uses `await` or `for await` on top level.
+pub(super) fn has_top_level_await(module: &Module) -> bool {
+    let mut v = TopLevelAwaitFinder { found: false };
+    module.visit_with(&Invalid { span: DUMMY_SP } as _, &mut v);
+    v.found
+}
+
+/// Moves imports and reexports in `body` to the front, as dependencies are
+/// evaluated before the body of a module.
+///
+/// Returns the number of other items, which are the body of the module.
+pub(super) fn hoist_imports(body: &mut Vec<ModuleItem>) -> usize {
+    let (mut imports, others): (Vec<_>, Vec<_>) = take(body)
+        .into_iter()
+        .filter(|item| !matches!(item, ModuleItem::Stmt(Stmt::Empty(..))))
+        .partition(|item| match item {
+            ModuleItem::ModuleDecl(ModuleDecl::Import(..))
+            | ModuleItem::ModuleDecl(ModuleDecl::ExportAll(..)) => true,
+            ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(export)) => export.src.is_some(),
+            _ => false,
+        });
+
+    let len = others.len();
+    imports.extend(others);
+    *body = imports;
+    len
+}
+
+/// Wraps `body` of an async module with an async function, which is invoked
+/// immediately. The returned promise is stored in `promise`.
+///
+/// Imports, function declarations and bindings of other declarations are
+/// hoisted out of the function, so the importer can access them.
+///
+/// # Example
+///
+/// ```js
+/// export const data = await fetch();
+/// ```
+///
+/// becomes
+///
+/// ```js
+/// let data;
+/// const promise = (async () => {
+///     data = await fetch();
+/// })();
+/// ```
+pub(super) fn wrap_async_module(body: Vec<ModuleItem>, promise: Ident) -> Vec<ModuleItem> {
+    let mut items = vec![];
+    let mut vars = vec![];
+    let mut lets = vec![];
+    let mut stmts = vec![];
+
+    for item in body {
+        match item {
+            ModuleItem::ModuleDecl(..) | ModuleItem::Stmt(Stmt::Decl(Decl::Fn(..))) => {
+                items.push(item)
+            }
+
+            ModuleItem::Stmt(Stmt::Decl(Decl::Class(ClassDecl { ident, class, .. }))) => {
+                lets.push(declarator(Pat::Ident(ident.clone()), None));
+                stmts.push(assign(
+                    Pat::Ident(ident.clone()),
+                    Box::new(Expr::Class(ClassExpr {
+                        ident: Some(ident),
+                        class,
+                    })),
+                ));
+            }
+
+            ModuleItem::Stmt(Stmt::Decl(Decl::Var(var))) => {
+                for decl in var.decls {
+                    let ids: Vec<Ident> = find_ids(&decl.name);
+                    let names = ids.into_iter().map(|id| declarator(Pat::Ident(id), None));
+                    match var.kind {
+                        VarDeclKind::Var => vars.extend(names),
+                        VarDeclKind::Let | VarDeclKind::Const => lets.extend(names),
+                    }
+
+                    if let Some(init) = decl.init {
+                        stmts.push(assign(decl.name, init));
+                    }
+                }
+            }
+
+            ModuleItem::Stmt(stmt) => stmts.push(stmt),
+        }
+    }
+
+    let evaluate = ArrowExpr {
+        span: DUMMY_SP,
+        params: vec![],
+        body: BlockStmtOrExpr::BlockStmt(BlockStmt {
+            span: DUMMY_SP,
+            stmts,
+        }),
+        is_async: true,
+        is_generator: false,
+        type_params: None,
+        return_type: None,
+    };
+
+    if !vars.is_empty() {
+        items.push(var_decl(VarDeclKind::Var, vars));
+    }
+    if !lets.is_empty() {
+        items.push(var_decl(VarDeclKind::Let, lets));
+    }
+    items.push(var_decl(
+        VarDeclKind::Const,
+        vec![declarator(
+            Pat::Ident(promise),
+            Some(Box::new(Expr::Call(CallExpr {
+                span: DUMMY_SP,
+                callee: evaluate.as_callee(),
+                args: vec![],
+                type_args: None,
+            }))),
+        )],
+    ));
+
+    items
+}
+
+/// `await promise;`
+pub(super) fn await_stmt(promise: Ident) -> ModuleItem {
+    ModuleItem::Stmt(Stmt::Expr(ExprStmt {
+        span: DUMMY_SP,
+        expr: Box::new(Expr::Await(AwaitExpr {
+            span: DUMMY_SP,
+            arg: Box::new(Expr::Ident(promise)),
+        })),
+    }))
+}
+
+fn declarator(name: Pat, init: Option<Box<Expr>>) -> VarDeclarator {
+    VarDeclarator {
+        span: DUMMY_SP,
+        name,
+        init,
+        definite: false,
+    }
+}
+
+fn var_decl(kind: VarDeclKind, decls: Vec<VarDeclarator>) -> ModuleItem {
+    ModuleItem::Stmt(Stmt::Decl(Decl::Var(VarDecl {
+        span: DUMMY_SP,
+        kind,
+        declare: false,
+        decls,
+    })))
+}
+
+fn assign(left: Pat, right: Box<Expr>) -> Stmt {
+    Stmt::Expr(ExprStmt {
+        span: DUMMY_SP,
+        expr: Box::new(Expr::Assign(AssignExpr {
+            span: DUMMY_SP,
+            op: op!("="),
+            left: PatOrExpr::Pat(Box::new(left)),
+            right,
+        })),
+    })
+}
+
+struct TopLevelAwaitFinder {
+    found: bool,
+}
+
+impl Visit for TopLevelAwaitFinder {
+    noop_visit_type!();
+
+    fn visit_await_expr(&mut self, _: &AwaitExpr, _: &dyn Node) {
+        self.found = true;
+    }
+
+    fn visit_for_of_stmt(&mut self, s: &ForOfStmt, _: &dyn Node) {
+        if s.await_token.is_some() {
+            self.found = true;
+        }
+        s.visit_children_with(self);
+    }
+
+    /// Functions and classes create a new scope for `await`.
+    fn visit_function(&mut self, _: &Function, _: &dyn Node) {}
+
+    fn visit_arrow_expr(&mut self, _: &ArrowExpr, _: &dyn Node) {}
+
+    fn visit_class(&mut self, _: &Class, _: &dyn Node) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bundler::tests::suite;
+    use swc_common::FileName;
+
+    #[test]
+    fn detect_top_level_await() {
+        suite().run(|t| {
+            assert!(has_top_level_await(&t.parse("await foo();")));
+            assert!(has_top_level_await(&t.parse("for await (const a of b) {}")));
+            assert!(!has_top_level_await(
+                &t.parse("async function foo() { await bar(); }")
+            ));
+            assert!(!has_top_level_await(
+                &t.parse("const foo = async () => await bar();")
+            ));
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn mark_importers_as_async() {
+        suite()
+            .file("main.js", "import { a } from './a'; console.log(a);")
+            .file("a.js", "import { b } from './b'; export const a = b;")
+            .file("b.js", "export const b = await fetch();")
+            .file("c.js", "export const c = 1;")
+            .run(|t| {
+                let mut entries = HashMap::default();
+                entries.insert("main".to_string(), FileName::Real("main.js".into()));
+                entries.insert("c".to_string(), FileName::Real("c.js".into()));
+
+                t.bundler.bundle(entries)?;
+
+                let (main, _) = t
+                    .bundler
+                    .scope
+                    .module_id_gen
+                    .gen(&FileName::Real("main.js".into()));
+                let (c, _) = t
+                    .bundler
+                    .scope
+                    .module_id_gen
+                    .gen(&FileName::Real("c.js".into()));
+                assert!(t.bundler.scope.is_async(main));
+                assert!(!t.bundler.scope.is_async(c));
+
+                Ok(())
+            });
+    }
+
+    /// Returns the kind and names of a variable declaration.
+    fn declared(item: &ModuleItem) -> (VarDeclKind, Vec<String>) {
+        match item {
+            ModuleItem::Stmt(Stmt::Decl(Decl::Var(var))) => (
+                var.kind,
+                var.decls
+                    .iter()
+                    .map(|decl| match &decl.name {
+                        Pat::Ident(i) => i.sym.to_string(),
+                        _ => panic!("hoisted binding should be an identifier"),
+                    })
+                    .collect(),
+            ),
+            _ => panic!("not a variable declaration: {:?}", item),
+        }
+    }
+
+    #[test]
+    fn wrap_bindings() {
+        suite().run(|t| {
+            let module = t.parse(
+                "
+                import './a';
+                function foo() {}
+                class Foo {}
+                const { a, b } = await foo();
+                var c;
+                console.log(a, b, c);
+                ",
+            );
+            let promise = Ident::new("promise".into(), DUMMY_SP);
+            let wrapped = wrap_async_module(module.body, promise);
+
+            assert_eq!(wrapped.len(), 5);
+            assert!(matches!(
+                wrapped[0],
+                ModuleItem::ModuleDecl(ModuleDecl::Import(..))
+            ));
+            assert!(matches!(
+                wrapped[1],
+                ModuleItem::Stmt(Stmt::Decl(Decl::Fn(..)))
+            ));
+            assert_eq!(
+                declared(&wrapped[2]),
+                (VarDeclKind::Var, vec!["c".to_string()])
+            );
+            assert_eq!(
+                declared(&wrapped[3]),
+                (
+                    VarDeclKind::Let,
+                    vec!["Foo".to_string(), "a".to_string(), "b".to_string()]
+                )
+            );
+            assert_eq!(
+                declared(&wrapped[4]),
+                (VarDeclKind::Const, vec!["promise".to_string()])
+            );
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn await_async_dependency() {
+        suite()
+            .file("main.js", "import { a } from './a'; console.log(a);")
+            .file("a.js", "export const a = await fetch();")
+            .run(|t| {
+                let mut entries = HashMap::default();
+                entries.insert("main".to_string(), FileName::Real("main.js".into()));
+
+                let bundles = t.bundler.bundle(entries)?;
+                assert_eq!(bundles.len(), 1);
+
+                let body = &bundles[0].module.body;
+                assert_eq!(
+                    declared(&body[0]),
+                    (VarDeclKind::Let, vec!["a".to_string()])
+                );
+                assert_eq!(declared(&body[1]).0, VarDeclKind::Const);
+                match &body[2] {
+                    ModuleItem::Stmt(Stmt::Expr(ExprStmt { expr, .. })) => {
+                        assert!(matches!(**expr, Expr::Await(..)))
+                    }
+                    _ => panic!("main.js should wait for a.js: {:?}", body[2]),
+                }
+                assert_eq!(body.len(), 4);
+
+                Ok(())
+            });
+    }
+
+    #[test]
+    fn require_async_module() {
+        suite()
+            .file("main.js", "const { a } = require('./a'); console.log(a);")
+            .file("a.js", "export const a = await fetch();")
+            .run(|t| {
+                let mut entries = HashMap::default();
+                entries.insert("main".to_string(), FileName::Real("main.js".into()));
+
+                let err = t
+                    .bundler
+                    .bundle(entries)
+                    .expect_err("require() cannot wait for an async module");
+                assert!(
+                    format!("{:?}", err).contains("uses top-level await"),
+                    "{:?}",
+                    err
+                );
+
+                Ok(())
+            });
+    }
+}
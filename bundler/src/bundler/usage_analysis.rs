@@ -1,11 +1,50 @@
-use super::load::Specifier;
-use crate::{Bundler, Load, Resolve};
-use std::borrow::Cow;
+use super::load::{Specifier, TransformedModule};
+use crate::{Bundler, Load, ModuleId, Resolve};
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+};
+use swc_atoms::{js_word, JsWord};
 use swc_ecma_ast::*;
 use swc_ecma_transforms::optimization::simplify::dce;
 use swc_ecma_utils::ident::IdentLike;
 use swc_ecma_visit::FoldWith;
 
+/// Exports of a module used by other modules.
+#[derive(Debug, Clone)]
+pub(super) enum UsedExports {
+    All,
+    Some(HashSet<JsWord>),
+}
+
+impl UsedExports {
+    /// Returns true if `self` is changed.
+    pub(super) fn extend(&mut self, other: UsedExports) -> bool {
+        match other {
+            UsedExports::All => {
+                let changed = !matches!(self, UsedExports::All);
+                *self = UsedExports::All;
+                changed
+            }
+            UsedExports::Some(other) => match self {
+                UsedExports::All => false,
+                UsedExports::Some(v) => {
+                    let len = v.len();
+                    v.extend(other);
+                    v.len() != len
+                }
+            },
+        }
+    }
+
+    pub(super) fn contains(&self, sym: &JsWord) -> bool {
+        match self {
+            UsedExports::All => true,
+            UsedExports::Some(v) => v.contains(sym),
+        }
+    }
+}
+
 impl<L, R> Bundler<'_, L, R>
 where
     L: Load,
@@ -41,10 +80,354 @@ where
                     None
                 },
                 used_mark,
+                comments: self.loader.comments(),
             });
 
             let node = node.fold_with(&mut v);
             node
         })
     }
+
+    /// Marks modules which can be removed from bundles entirely.
+    ///
+    /// A module is dropped if neither it nor its dependencies have side
+    /// effects and none of its exports are used. Re-exports which are not used
+    /// are not followed, so unused modules re-exported by a barrel module are
+    /// dropped as well.
+    pub(super) fn mark_unused_modules(&self, entries: &[ModuleId]) {
+        let mut side_effect_free = HashMap::default();
+        let mut used: HashMap<ModuleId, UsedExports> = HashMap::default();
+        let mut queue = vec![];
+        let mut seen = HashSet::new();
+
+        for &id in entries {
+            used.insert(id, UsedExports::All);
+            queue.push(id);
+        }
+
+        while let Some(id) = queue.pop() {
+            let module = match self.scope.get_module(id) {
+                Some(v) => v,
+                None => continue,
+            };
+            let exports = used[&id].clone();
+
+            let deps = module
+                .imports
+                .specifiers
+                .iter()
+                .map(|(src, specifiers)| {
+                    // `require` and `import()` use the whole exports object.
+                    let names = if src.is_loaded_synchronously && src.is_unconditional {
+                        used_names(specifiers.iter())
+                    } else {
+                        UsedExports::All
+                    };
+                    (src.module_id, names)
+                })
+                .chain(module.exports.reexports.iter().map(|(src, specifiers)| {
+                    // Exports used through `export * from 'foo'` are looked up in
+                    // `foo`, too.
+                    let names = if module.exports.reexports_all.contains(src) {
+                        match &exports {
+                            UsedExports::All => UsedExports::All,
+                            UsedExports::Some(used) => UsedExports::Some(
+                                self.star_exported_names(src.module_id, &mut vec![])
+                                    .into_iter()
+                                    .filter(|name| used.contains(name))
+                                    .collect(),
+                            ),
+                        }
+                    } else {
+                        used_names(
+                            specifiers
+                                .iter()
+                                .filter(|s| exports.contains(s.local().sym())),
+                        )
+                    };
+                    (src.module_id, names)
+                }));
+
+            for (dep, names) in deps {
+                seen.insert(dep);
+
+                let is_empty = match &names {
+                    UsedExports::All => false,
+                    UsedExports::Some(v) => v.is_empty(),
+                };
+                if is_empty && self.is_side_effect_free(dep, &mut side_effect_free, &mut vec![]) {
+                    continue;
+                }
+
+                let changed = match used.get_mut(&dep) {
+                    Some(v) => v.extend(names),
+                    None => {
+                        used.insert(dep, names);
+                        true
+                    }
+                };
+                if changed {
+                    queue.push(dep);
+                }
+            }
+        }
+
+        for id in seen {
+            if !used.contains_key(&id) {
+                log::debug!("Dropping unused module without side effects: {}", id);
+                self.scope.mark_as_dropped(id);
+            }
+        }
+    }
+
+    /// Removes imports and re-exports of modules dropped by
+    /// [Bundler::mark_unused_modules].
+    pub(super) fn remove_dropped_deps(&self, module: &mut Module, info: &TransformedModule) {
+        let dropped = info
+            .imports
+            .specifiers
+            .iter()
+            .map(|(src, _)| src)
+            .chain(info.exports.reexports.keys())
+            .filter(|src| self.scope.is_dropped(src.module_id))
+            .map(|src| src.src.value.clone())
+            .collect::<HashSet<_>>();
+        if dropped.is_empty() {
+            return;
+        }
+
+        module.body.retain(|item| match item {
+            ModuleItem::ModuleDecl(ModuleDecl::Import(import)) => {
+                !dropped.contains(&import.src.value)
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(NamedExport {
+                src: Some(src), ..
+            })) => !dropped.contains(&src.value),
+            ModuleItem::ModuleDecl(ModuleDecl::ExportAll(export)) => {
+                !dropped.contains(&export.src.value)
+            }
+            _ => true,
+        });
+    }
+
+    /// Names exported by `export * from 'foo'`, where `id` is the id of `foo`.
+    ///
+    /// `default` is never re-exported by `export *`.
+    pub(super) fn star_exported_names(
+        &self,
+        id: ModuleId,
+        stack: &mut Vec<ModuleId>,
+    ) -> Vec<JsWord> {
+        let module = match self.scope.get_module(id) {
+            Some(v) => v,
+            None => return vec![],
+        };
+        if stack.contains(&id) {
+            return vec![];
+        }
+        stack.push(id);
+
+        let mut names = module
+            .exports
+            .items
+            .iter()
+            .chain(module.exports.reexports.values().flatten())
+            .map(|s| s.local().sym().clone())
+            .filter(|sym| *sym != js_word!("default"))
+            .collect::<Vec<_>>();
+        for src in &module.exports.reexports_all {
+            names.extend(self.star_exported_names(src.module_id, stack));
+        }
+        names.sort_by(|a, b| (**a).cmp(&**b));
+        names.dedup();
+
+        stack.pop();
+        names
+    }
+
+    /// Returns true if the module and its dependencies are free of side
+    /// effects.
+    fn is_side_effect_free(
+        &self,
+        id: ModuleId,
+        cache: &mut HashMap<ModuleId, bool>,
+        stack: &mut Vec<ModuleId>,
+    ) -> bool {
+        if let Some(&v) = cache.get(&id) {
+            return v;
+        }
+        // Circular dependencies are treated as impure.
+        if stack.contains(&id) {
+            return false;
+        }
+
+        let module = match self.scope.get_module(id) {
+            Some(v) => v,
+            None => return false,
+        };
+
+        stack.push(id);
+        let pure = !module.has_side_effects
+            && module
+                .imports
+                .specifiers
+                .iter()
+                .map(|(src, _)| src)
+                .chain(module.exports.reexports.keys())
+                .all(|src| self.is_side_effect_free(src.module_id, cache, stack));
+        stack.pop();
+
+        cache.insert(id, pure);
+        pure
+    }
+}
+
+/// Names of exports of the dependency referenced by `specifiers`.
+pub(super) fn used_names<'a>(specifiers: impl Iterator<Item = &'a Specifier>) -> UsedExports {
+    let mut names = HashSet::new();
+
+    for s in specifiers {
+        match s {
+            Specifier::Specific { local, alias } => {
+                names.insert(alias.as_ref().unwrap_or(local).sym().clone());
+            }
+            Specifier::Namespace { .. } => return UsedExports::All,
+        }
+    }
+
+    UsedExports::Some(names)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bundler::tests::suite;
+    use swc_common::FileName;
+
+    #[test]
+    fn drop_side_effect_free_import() {
+        suite()
+            .file(
+                "main.js",
+                "
+                import './a';
+                import './b';
+                ",
+            )
+            .file("a.js", "export const a = 1;")
+            .file("b.js", "console.log('b');")
+            .run(|t| {
+                let module = t
+                    .bundler
+                    .load_transformed(&FileName::Real("main.js".into()))?
+                    .unwrap();
+                t.bundler.mark_unused_modules(&[module.id]);
+
+                let (a, _) = t
+                    .bundler
+                    .scope
+                    .module_id_gen
+                    .gen(&FileName::Real("a.js".into()));
+                let (b, _) = t
+                    .bundler
+                    .scope
+                    .module_id_gen
+                    .gen(&FileName::Real("b.js".into()));
+                assert!(t.bundler.scope.is_dropped(a));
+                assert!(!t.bundler.scope.is_dropped(b));
+
+                Ok(())
+            });
+    }
+
+    #[test]
+    fn drop_unused_reexport() {
+        suite()
+            .file(
+                "main.js",
+                "
+                import { a } from './lib';
+                console.log(a);
+                ",
+            )
+            .file(
+                "lib.js",
+                "
+                export { a } from './a';
+                export { b } from './b';
+                ",
+            )
+            .file("a.js", "export const a = 1;")
+            .file(
+                "b.js",
+                "export const b = function () { console.log('b'); };",
+            )
+            .run(|t| {
+                let module = t
+                    .bundler
+                    .load_transformed(&FileName::Real("main.js".into()))?
+                    .unwrap();
+                t.bundler.mark_unused_modules(&[module.id]);
+
+                let (a, _) = t
+                    .bundler
+                    .scope
+                    .module_id_gen
+                    .gen(&FileName::Real("a.js".into()));
+                let (b, _) = t
+                    .bundler
+                    .scope
+                    .module_id_gen
+                    .gen(&FileName::Real("b.js".into()));
+                assert!(!t.bundler.scope.is_dropped(a));
+                assert!(t.bundler.scope.is_dropped(b));
+
+                Ok(())
+            });
+    }
+
+    #[test]
+    fn drop_unused_export_all() {
+        suite()
+            .file(
+                "main.js",
+                "
+                import { a } from './lib';
+                console.log(a);
+                ",
+            )
+            .file(
+                "lib.js",
+                "
+                export * from './a';
+                export * from './b';
+                ",
+            )
+            .file("a.js", "export const a = 1;")
+            .file(
+                "b.js",
+                "export const b = function () { console.log('b'); };",
+            )
+            .run(|t| {
+                let module = t
+                    .bundler
+                    .load_transformed(&FileName::Real("main.js".into()))?
+                    .unwrap();
+                t.bundler.mark_unused_modules(&[module.id]);
+
+                let (a, _) = t
+                    .bundler
+                    .scope
+                    .module_id_gen
+                    .gen(&FileName::Real("a.js".into()));
+                let (b, _) = t
+                    .bundler
+                    .scope
+                    .module_id_gen
+                    .gen(&FileName::Real("b.js".into()));
+                assert!(!t.bundler.scope.is_dropped(a));
+                assert!(t.bundler.scope.is_dropped(b));
+
+                Ok(())
+            });
+    }
 }
@@ -27,6 +27,12 @@ pub(crate) fn calc_hash(cm: Lrc<SourceMap>, m: &Module) -> Result<String, Error>
     Ok(radix_fmt::radix(result, 36).to_string())
 }
 
+pub(crate) fn calc_hash_of_str(s: &str) -> String {
+    let mut digest = crc64::Digest::new(crc64::ECMA);
+    digest.write(s.as_bytes());
+    radix_fmt::radix(digest.sum64(), 36).to_string()
+}
+
 struct Hasher {
     digest: Digest,
 }
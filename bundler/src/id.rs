@@ -1,3 +1,4 @@
+use serde::Serialize;
 use std::{
     collections::HashMap,
     fmt,
@@ -8,7 +9,7 @@ use swc_common::{sync::Lock, FileName, Mark, SyntaxContext, DUMMY_SP};
 use swc_ecma_ast::Ident;
 use swc_ecma_utils::ident::IdentLike;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
 pub struct ModuleId(u64);
 
 impl fmt::Display for ModuleId {
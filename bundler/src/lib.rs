@@ -1,7 +1,12 @@
 pub use self::{
-    bundler::{Bundle, BundleKind, Bundler, Config},
+    bundler::{
+        AssetStats, Bundle, BundleKind, BundleStats, Bundler, ChunkModuleStats, ChunkStats, Config,
+        EntrypointStats, FederationConfig, HmrConfig, HmrManifest, Issuer, ModuleStats,
+        OutputFormat, Reason, SharedConfig,
+    },
     id::ModuleId,
     load::Load,
+    plugin::{ImportKind, LoadResult, Plugin},
     resolve::Resolve,
 };
 
@@ -10,5 +15,6 @@ mod debug;
 mod hash;
 mod id;
 mod load;
+mod plugin;
 mod resolve;
 mod util;
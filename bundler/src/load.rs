@@ -1,5 +1,5 @@
 use anyhow::Error;
-use swc_common::{sync::Lrc, FileName, SourceFile};
+use swc_common::{comments::Comments, sync::Lrc, FileName, SourceFile};
 use swc_ecma_ast::Module;
 
 /// Responsible for providing files to the bundler.
@@ -12,16 +12,32 @@ use swc_ecma_ast::Module;
 /// This trait is designed to allow passing pre-parsed module.
 pub trait Load: swc_common::sync::Send + swc_common::sync::Sync {
     fn load(&self, file: &FileName) -> Result<(Lrc<SourceFile>, Module), Error>;
+
+    /// Comments of loaded modules.
+    ///
+    /// If provided, calls annotated with `/*#__PURE__*/` are treated as free of
+    /// side effects.
+    fn comments(&self) -> Option<&dyn Comments> {
+        None
+    }
 }
 
 impl<T: ?Sized + Load> Load for Box<T> {
     fn load(&self, file: &FileName) -> Result<(Lrc<SourceFile>, Module), Error> {
         (**self).load(file)
     }
+
+    fn comments(&self) -> Option<&dyn Comments> {
+        (**self).comments()
+    }
 }
 
 impl<'a, T: ?Sized + Load> Load for &'a T {
     fn load(&self, file: &FileName) -> Result<(Lrc<SourceFile>, Module), Error> {
         (**self).load(file)
     }
+
+    fn comments(&self) -> Option<&dyn Comments> {
+        (**self).comments()
+    }
 }
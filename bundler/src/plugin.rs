@@ -0,0 +1,89 @@
+use crate::Bundle;
+use anyhow::Error;
+use std::fmt;
+use swc_common::{sync::Lrc, FileName, SourceFile};
+use swc_ecma_ast::Module;
+
+/// How a module is requested.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportKind {
+    /// Entry provided by user.
+    Entry,
+    /// `import` or `export from` declarations, or `require` on top level.
+    Import,
+    /// `require` calls which are not on top level.
+    Require,
+    /// `import()`
+    DynamicImport,
+}
+
+/// Source of a module provided by [Plugin::load].
+pub enum LoadResult {
+    /// Code which will be parsed as an ecmascript module.
+    Code(String),
+    /// Module parsed by the plugin.
+    Module(Lrc<SourceFile>, Module),
+}
+
+/// Hooks to customize the bundler, modeled after plugins of rollup.
+///
+/// Plugins are called in the order of [Config::plugins]. For `resolve_id` and
+/// `load`, the first plugin returning [Some] wins and the bundler falls back to
+/// [Resolve] and [Load] if all plugins return [None]. Other hooks are chained,
+/// which means a plugin gets the output of previous plugins.
+///
+/// [Config::plugins]: crate::Config::plugins
+/// [Resolve]: crate::Resolve
+/// [Load]: crate::Load
+pub trait Plugin: swc_common::sync::Send + swc_common::sync::Sync {
+    /// Used for error messages.
+    fn name(&self) -> &str;
+
+    /// Resolves `specifier`, which is imported by `importer`.
+    ///
+    /// `importer` is [None] for entries, and `specifier` is the name of the
+    /// entry file in the case.
+    ///
+    /// Like rollup, this is called once for each pair of `importer` and
+    /// `specifier`, and the result is reused. If a module imports the same
+    /// specifier in different ways, `kind` is the kind of the first import.
+    fn resolve_id(
+        &self,
+        _specifier: &str,
+        _importer: Option<&FileName>,
+        _kind: ImportKind,
+    ) -> Result<Option<FileName>, Error> {
+        Ok(None)
+    }
+
+    /// Provides the source of `file`. Useful for virtual modules.
+    fn load(&self, _file: &FileName) -> Result<Option<LoadResult>, Error> {
+        Ok(None)
+    }
+
+    /// Called for each module after loading it, before the bundler analyzes
+    /// the module.
+    fn transform(&self, _file: &FileName, module: Module) -> Result<Module, Error> {
+        Ok(module)
+    }
+
+    /// Called for each bundle after finalizing it.
+    fn render_chunk(&self, bundle: Bundle) -> Result<Bundle, Error> {
+        Ok(bundle)
+    }
+
+    /// Called with all bundles, right before [Bundler::bundle] returns.
+    ///
+    /// [Bundler::bundle]: crate::Bundler::bundle
+    fn generate_bundle(&self, _bundles: &mut Vec<Bundle>) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+impl fmt::Debug for dyn Plugin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Plugin")
+            .field("name", &self.name())
+            .finish()
+    }
+}
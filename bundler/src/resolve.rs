@@ -3,16 +3,32 @@ use swc_common::FileName;
 
 pub trait Resolve: swc_common::sync::Send + swc_common::sync::Sync {
     fn resolve(&self, base: &FileName, module_specifier: &str) -> Result<FileName, Error>;
+
+    /// Returns whether the module at `path` has side effects, if it's known
+    /// without parsing the module. (e.g. `sideEffects` in package.json)
+    ///
+    /// If [None] is returned, the bundler analyzes the module by itself.
+    fn side_effects(&self, _path: &FileName) -> Option<bool> {
+        None
+    }
 }
 
 impl<T: ?Sized + Resolve> Resolve for Box<T> {
     fn resolve(&self, base: &FileName, module_specifier: &str) -> Result<FileName, Error> {
         (**self).resolve(base, module_specifier)
     }
+
+    fn side_effects(&self, path: &FileName) -> Option<bool> {
+        (**self).side_effects(path)
+    }
 }
 
 impl<'a, T: ?Sized + Resolve> Resolve for &'a T {
     fn resolve(&self, base: &FileName, module_specifier: &str) -> Result<FileName, Error> {
         (**self).resolve(base, module_specifier)
     }
+
+    fn side_effects(&self, path: &FileName) -> Option<bool> {
+        (**self).side_effects(path)
+    }
 }
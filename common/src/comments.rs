@@ -1,6 +1,6 @@
 use crate::{
     pos::Spanned,
-    syntax_pos::{BytePos, Span},
+    syntax_pos::{BytePos, Span, DUMMY_SP},
 };
 use fxhash::FxHashMap;
 use std::{cell::RefCell, rc::Rc, sync::Arc};
@@ -28,11 +28,74 @@ pub trait Comments {
     fn move_leading(&self, from: BytePos, to: BytePos);
     fn take_leading(&self, pos: BytePos) -> Option<Vec<Comment>>;
 
+    /// Returns leading comments of `pos` without removing them.
+    ///
+    /// The default implementation takes comments and adds them back.
+    fn get_leading(&self, pos: BytePos) -> Option<Vec<Comment>> {
+        let cmts = self.take_leading(pos)?;
+        self.add_leading_comments(pos, cmts.clone());
+        Some(cmts)
+    }
+
     fn add_trailing(&self, pos: BytePos, cmt: Comment);
     fn add_trailing_comments(&self, pos: BytePos, comments: Vec<Comment>);
     fn has_trailing(&self, pos: BytePos) -> bool;
     fn move_trailing(&self, from: BytePos, to: BytePos);
     fn take_trailing(&self, pos: BytePos) -> Option<Vec<Comment>>;
+
+    /// Returns trailing comments of `pos` without removing them.
+    ///
+    /// The default implementation takes comments and adds them back.
+    fn get_trailing(&self, pos: BytePos) -> Option<Vec<Comment>> {
+        let cmts = self.take_trailing(pos)?;
+        self.add_trailing_comments(pos, cmts.clone());
+        Some(cmts)
+    }
+
+    /// Returns `true` if a leading comment of `pos` is an annotation like
+    /// `/*#__PURE__*/` or `/*@__PURE__*/`, where `flag` is `PURE`.
+    fn has_flag(&self, pos: BytePos, flag: &str) -> bool {
+        match self.get_leading(pos) {
+            Some(cmts) => cmts.iter().any(|cmt| is_annotation(cmt, flag)),
+            None => false,
+        }
+    }
+
+    /// Marks the node at `pos` as pure by adding `/*#__PURE__*/` in front of
+    /// it.
+    ///
+    /// Does nothing for [BytePos(0)](BytePos), as comments of it would be
+    /// emitted for every synthesized node. Use
+    /// [Span::dummy_with_cmt](crate::Span::dummy_with_cmt) for the span of
+    /// a synthesized node which should be annotated.
+    fn add_pure_comment(&self, pos: BytePos) {
+        if pos == BytePos(0) || self.has_flag(pos, "PURE") {
+            return;
+        }
+
+        self.add_leading(
+            pos,
+            Comment {
+                kind: CommentKind::Block,
+                span: DUMMY_SP,
+                text: "#__PURE__".into(),
+            },
+        )
+    }
+}
+
+/// Returns `true` for block comments like `#__PURE__` or `@__PURE__`, where
+/// `flag` is `PURE`.
+pub fn is_annotation(cmt: &Comment, flag: &str) -> bool {
+    if cmt.kind != CommentKind::Block {
+        return false;
+    }
+
+    let text = cmt.text.trim();
+    (text.starts_with('#') || text.starts_with('@'))
+        && text[1..].starts_with("__")
+        && text[3..].starts_with(flag)
+        && &text[3 + flag.len()..] == "__"
 }
 
 macro_rules! delegate {
@@ -57,6 +120,10 @@ macro_rules! delegate {
             (**self).take_leading(pos)
         }
 
+        fn get_leading(&self, pos: BytePos) -> Option<Vec<Comment>> {
+            (**self).get_leading(pos)
+        }
+
         fn add_trailing(&self, pos: BytePos, cmt: Comment) {
             (**self).add_trailing(pos, cmt)
         }
@@ -76,6 +143,10 @@ macro_rules! delegate {
         fn take_trailing(&self, pos: BytePos) -> Option<Vec<Comment>> {
             (**self).take_trailing(pos)
         }
+
+        fn get_trailing(&self, pos: BytePos) -> Option<Vec<Comment>> {
+            (**self).get_trailing(pos)
+        }
     };
 }
 
@@ -145,6 +216,10 @@ impl Comments for SingleThreadedComments {
         self.leading.borrow_mut().remove(&pos)
     }
 
+    fn get_leading(&self, pos: BytePos) -> Option<Vec<Comment>> {
+        self.leading.borrow().get(&pos).cloned()
+    }
+
     fn add_trailing(&self, pos: BytePos, cmt: Comment) {
         self.trailing.borrow_mut().entry(pos).or_default().push(cmt);
     }
@@ -176,6 +251,10 @@ impl Comments for SingleThreadedComments {
     fn take_trailing(&self, pos: BytePos) -> Option<Vec<Comment>> {
         self.trailing.borrow_mut().remove(&pos)
     }
+
+    fn get_trailing(&self, pos: BytePos) -> Option<Vec<Comment>> {
+        self.trailing.borrow().get(&pos).cloned()
+    }
 }
 
 impl SingleThreadedComments {
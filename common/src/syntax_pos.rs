@@ -7,7 +7,10 @@ use std::{
     hash::{Hash, Hasher},
     ops::{Add, Sub},
     path::PathBuf,
-    sync::Mutex,
+    sync::{
+        atomic::{AtomicU32, Ordering::SeqCst},
+        Mutex,
+    },
 };
 
 mod analyze_source_file;
@@ -40,17 +43,38 @@ pub const DUMMY_SP: Span = Span {
     ctxt: SyntaxContext::empty(),
 };
 
-#[derive(Default)]
+/// Positions from this one are not used by source files, and are handed out
+/// by [Span::dummy_with_cmt] instead.
+pub(crate) const DUMMY_RESERVE: u32 = u32::MAX - 2_u32.pow(16);
+
 pub struct Globals {
     hygiene_data: Mutex<hygiene::HygieneData>,
+    dummy_cnt: AtomicU32,
 }
 
 impl Globals {
     pub fn new() -> Globals {
         Globals {
             hygiene_data: Mutex::new(hygiene::HygieneData::new()),
+            dummy_cnt: AtomicU32::new(DUMMY_RESERVE),
         }
     }
+
+    /// Makes positions handed out by [Span::dummy_with_cmt] available again.
+    ///
+    /// Comments attached to those positions should be removed before calling
+    /// this, and no other file should be transformed with these globals at the
+    /// same time. Tools processing files one by one can call this for each
+    /// file, as only 2^16 positions are reserved.
+    pub fn reset_dummy_cmt(&self) {
+        self.dummy_cnt.store(DUMMY_RESERVE, SeqCst);
+    }
+}
+
+impl Default for Globals {
+    fn default() -> Self {
+        Globals::new()
+    }
 }
 
 // scoped_thread_local!(pub static GLOBALS: Globals);
@@ -184,6 +208,37 @@ impl Span {
         self.lo.0 == 0 && self.hi.0 == 0
     }
 
+    /// Creates a new dummy span which can have comments.
+    ///
+    /// Comments of [DUMMY_SP] are shared by every synthesized node, so a
+    /// transform which annotates a node it creates (e.g. with
+    /// `/*#__PURE__*/`) should use a span from this method instead.
+    ///
+    /// Requires [GLOBALS] to be configured.
+    pub fn dummy_with_cmt() -> Self {
+        GLOBALS.with(|globals| {
+            let lo = globals.dummy_cnt.fetch_add(1, SeqCst);
+            if lo < DUMMY_RESERVE || lo >= u32::MAX - 1 {
+                // Ran out of reserved positions. Comments of the node are dropped, as comments
+                // of DUMMY_SP are not emitted.
+                if lo == u32::MAX - 1 {
+                    log::warn!(
+                        "Span::dummy_with_cmt: all reserved positions are used, so comments of \
+                         synthesized nodes are dropped. Call `Globals::reset_dummy_cmt` for each \
+                         file to reuse positions."
+                    );
+                }
+                return DUMMY_SP;
+            }
+
+            Span {
+                lo: BytePos(lo),
+                hi: BytePos(lo),
+                ctxt: SyntaxContext::empty(),
+            }
+        })
+    }
+
     /// Returns a new span representing an empty span at the beginning of this
     /// span
     #[inline]
@@ -761,6 +816,14 @@ impl Pos for BytePos {
     }
 }
 
+impl BytePos {
+    /// Returns `true` if this position was created by [Span::dummy_with_cmt],
+    /// which means it does not point into a source file.
+    pub fn is_reserved_for_comments(self) -> bool {
+        self.0 >= DUMMY_RESERVE && self.0 != u32::MAX
+    }
+}
+
 impl Add for BytePos {
     type Output = BytePos;
 
@@ -930,7 +993,30 @@ fn lookup_line(lines: &[BytePos], pos: BytePos) -> isize {
 
 #[cfg(test)]
 mod tests {
-    use super::{lookup_line, BytePos, Span};
+    use super::{lookup_line, BytePos, Globals, Span, DUMMY_SP, GLOBALS};
+
+    #[test]
+    fn dummy_with_cmt_reset() {
+        let globals = Globals::new();
+        GLOBALS.set(&globals, || {
+            let first = Span::dummy_with_cmt();
+            assert!(first.lo.is_reserved_for_comments());
+            assert_ne!(Span::dummy_with_cmt(), first);
+
+            globals.reset_dummy_cmt();
+            assert_eq!(Span::dummy_with_cmt(), first);
+
+            // Exhausted
+            globals
+                .dummy_cnt
+                .store(u32::MAX - 1, std::sync::atomic::Ordering::SeqCst);
+            assert_eq!(Span::dummy_with_cmt(), DUMMY_SP);
+            assert_eq!(Span::dummy_with_cmt(), DUMMY_SP);
+
+            globals.reset_dummy_cmt();
+            assert_eq!(Span::dummy_with_cmt(), first);
+        });
+    }
 
     #[test]
     fn test_lookup_line() {
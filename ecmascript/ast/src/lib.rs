@@ -47,6 +47,7 @@ pub use self::{
         ExprStmt, ForInStmt, ForOfStmt, ForStmt, IfStmt, LabeledStmt, ReturnStmt, Stmt, SwitchCase,
         SwitchStmt, ThrowStmt, TryStmt, VarDeclOrExpr, VarDeclOrPat, WhileStmt, WithStmt,
     },
+    target::JscTarget,
     typescript::{
         Accessibility, TruePlusMinus, TsArrayType, TsAsExpr, TsCallSignatureDecl,
         TsConditionalType, TsConstAssertion, TsConstructSignatureDecl, TsConstructorType,
@@ -81,6 +82,7 @@ mod operators;
 mod pat;
 mod prop;
 mod stmt;
+mod target;
 mod typescript;
 
 /// Represents a invalid node.
@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+/// The version of ecmascript.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialOrd, Ord, PartialEq, Eq, Hash)]
+pub enum JscTarget {
+    #[serde(rename = "es3")]
+    Es3,
+    #[serde(rename = "es5")]
+    Es5,
+    #[serde(rename = "es2015")]
+    Es2015,
+    #[serde(rename = "es2016")]
+    Es2016,
+    #[serde(rename = "es2017")]
+    Es2017,
+    #[serde(rename = "es2018")]
+    Es2018,
+    #[serde(rename = "es2019")]
+    Es2019,
+    #[serde(rename = "es2020")]
+    Es2020,
+}
+
+impl Default for JscTarget {
+    fn default() -> Self {
+        JscTarget::Es5
+    }
+}
+
+impl Display for JscTarget {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            JscTarget::Es3 => "es3",
+            JscTarget::Es5 => "es5",
+            JscTarget::Es2015 => "es2015",
+            JscTarget::Es2016 => "es2016",
+            JscTarget::Es2017 => "es2017",
+            JscTarget::Es2018 => "es2018",
+            JscTarget::Es2019 => "es2019",
+            JscTarget::Es2020 => "es2020",
+        })
+    }
+}
@@ -1,40 +1,50 @@
 use super::*;
-use swc_common::comments::CommentKind;
+use swc_common::comments::{Comment, CommentKind};
 
-macro_rules! write_comments {
-    ($e:expr, $prefix_space:expr, $cmts:expr) => {{
-        let cmts = match $cmts {
-            Some(v) => v,
-            None => return Ok(()),
-        };
+/// Maximum number of non-trivia characters between two adjacent nodes.
+///
+/// Anything longer is not a gap between siblings (e.g. the spans come from
+/// different places after a transform), so it is not scanned for comments.
+const MAX_GAP_TOKENS_LEN: usize = 32;
 
-        for cmt in cmts.iter() {
+impl<'a> Emitter<'a> {
+    /// Writes `cmts`, breaking the line after each of them.
+    ///
+    /// If `inline_blocks` is true, block comments are kept on the current line
+    /// so the token after them follows on the same line.
+    fn write_comments(
+        &mut self,
+        prefix_space: bool,
+        cmts: &[Comment],
+        inline_blocks: bool,
+    ) -> Result {
+        for cmt in cmts {
             match cmt.kind {
                 CommentKind::Line => {
-                    if $prefix_space {
-                        $e.wr.write_comment(cmt.span, " ")?;
+                    if prefix_space {
+                        self.wr.write_comment(cmt.span, " ")?;
                     }
-                    $e.wr.write_comment(cmt.span, "//")?;
-                    $e.wr.write_comment(cmt.span, &cmt.text)?;
-                    $e.wr.write_line()?;
+                    self.wr.write_comment(cmt.span, "//")?;
+                    self.wr.write_comment(cmt.span, &cmt.text)?;
+                    self.wr.write_line()?;
                 }
                 CommentKind::Block => {
-                    if $prefix_space {
-                        $e.wr.write_comment(cmt.span, " ")?;
+                    if prefix_space {
+                        self.wr.write_comment(cmt.span, " ")?;
+                    }
+                    self.wr.write_comment(cmt.span, "/*")?;
+                    self.wr.write_comment(cmt.span, &cmt.text)?;
+                    self.wr.write_comment(cmt.span, "*/")?;
+                    if !inline_blocks {
+                        self.wr.write_line()?;
                     }
-                    $e.wr.write_comment(cmt.span, "/*")?;
-                    $e.wr.write_comment(cmt.span, &cmt.text)?;
-                    $e.wr.write_comment(cmt.span, "*/")?;
-                    $e.wr.write_line()?;
                 }
             }
         }
 
-        return Ok(());
-    }};
-}
+        Ok(())
+    }
 
-impl<'a> Emitter<'a> {
     pub(super) fn emit_trailing_comments_of_pos(
         &mut self,
         pos: BytePos,
@@ -49,9 +59,10 @@ impl<'a> Emitter<'a> {
             None => return Ok(()),
         };
 
-        let cmts = comments.take_trailing(pos);
-
-        write_comments!(self, prefix_space, &cmts)
+        match comments.take_trailing(pos) {
+            Some(cmts) => self.write_comments(prefix_space, &cmts, false),
+            None => Ok(()),
+        }
     }
 
     pub(super) fn emit_leading_comments_of_pos(&mut self, pos: BytePos) -> Result {
@@ -64,6 +75,162 @@ impl<'a> Emitter<'a> {
             None => return Ok(()),
         };
 
-        write_comments!(self, false, comments.take_leading(pos))
+        match comments.take_leading(pos) {
+            Some(cmts) => self.write_comments(false, &cmts, false),
+            None => Ok(()),
+        }
+    }
+
+    /// Emits comments attached to the tokens between two adjacent nodes.
+    ///
+    /// Tokens like `else` or a closing bracket are not nodes, so nothing else
+    /// looks up the comments in front of them. Block comments stay on the
+    /// same line as the token, e.g. `foo(a /* b */)`.
+    pub(super) fn emit_comments_between(&mut self, lo: BytePos, hi: BytePos) -> Result {
+        let cmts = self.take_comments_between(lo, hi);
+        let prefix_space = self.wr.line_pos() != 0;
+        self.write_comments(prefix_space, &cmts, true)
+    }
+
+    fn take_comments_between(&self, lo: BytePos, hi: BytePos) -> Vec<Comment> {
+        let comments = match self.comments {
+            Some(comments) => comments,
+            None => return vec![],
+        };
+        let tokens = match self.token_starts_between(lo, hi) {
+            Some(tokens) => tokens,
+            None => return vec![],
+        };
+
+        let mut cmts = comments.take_trailing(lo).unwrap_or_default();
+        for (pos, c) in tokens {
+            cmts.extend(comments.take_leading(pos).unwrap_or_default());
+            // Comments after the token at `hi` belong to the node after the gap.
+            if c == '}' && pos + BytePos(1) < hi {
+                cmts.extend(comments.take_trailing(pos + BytePos(1)).unwrap_or_default());
+            }
+        }
+        cmts
+    }
+
+    /// Emits comments in front of the `)` which closes `children`.
+    ///
+    /// `lo` is used as the start of the gap if there are no children.
+    pub(super) fn emit_comments_before_closing_paren<N: Spanned>(
+        &mut self,
+        lo: BytePos,
+        children: &[N],
+        hi: BytePos,
+    ) -> Result {
+        let lo = children.last().map(|n| n.span().hi()).unwrap_or(lo);
+        self.emit_comments_between(lo, hi)
+    }
+
+    /// Emits comments in front of the `}` which closes a block-like node,
+    /// indented like its body.
+    ///
+    /// Unlike [Emitter::emit_comments_between], the line is broken after each
+    /// comment so the `}` starts a new line.
+    pub(super) fn emit_comments_before_closing_brace(
+        &mut self,
+        lo: BytePos,
+        hi: BytePos,
+    ) -> Result {
+        let cmts = self.take_comments_between(lo, hi);
+        let prefix_space = self.wr.line_pos() != 0;
+        if self.cfg.minify {
+            return self.write_comments(prefix_space, &cmts, false);
+        }
+
+        self.wr.increase_indent()?;
+        self.write_comments(prefix_space, &cmts, false)?;
+        self.wr.decrease_indent()
+    }
+
+    /// Emits all comments within `span` which were not emitted yet.
+    ///
+    /// This is called after each top-level item, so a comment which no node
+    /// picked up is moved after the item instead of being dropped.
+    pub(super) fn emit_leftover_comments(&mut self, span: Span) -> Result {
+        let comments = match self.comments {
+            Some(comments) => comments,
+            None => return Ok(()),
+        };
+        if span.is_dummy() {
+            return Ok(());
+        }
+        let src = match self.cm.span_to_snippet(span) {
+            Ok(src) => src,
+            Err(_) => return Ok(()),
+        };
+
+        let mut cmts = vec![];
+        for (i, c) in src.char_indices() {
+            if c.is_whitespace() {
+                continue;
+            }
+            let pos = span.lo() + BytePos(i as u32);
+            cmts.extend(comments.take_leading(pos).unwrap_or_default());
+            cmts.extend(
+                comments
+                    .take_trailing(pos + BytePos(c.len_utf8() as u32))
+                    .unwrap_or_default(),
+            );
+        }
+        cmts.sort_by_key(|cmt| cmt.span.lo());
+
+        let prefix_space = self.wr.line_pos() != 0;
+        self.write_comments(prefix_space, &cmts, false)
+    }
+
+    /// Returns the positions of non-trivia characters between `lo` and `hi`.
+    fn token_starts_between(&self, lo: BytePos, hi: BytePos) -> Option<Vec<(BytePos, char)>> {
+        if lo == BytePos(0) || hi <= lo {
+            return None;
+        }
+        let src = self
+            .cm
+            .span_to_snippet(Span::new(lo, hi, Default::default()))
+            .ok()?;
+
+        let mut tokens = vec![];
+        let mut chars = src.char_indices().peekable();
+        while let Some((i, c)) = chars.next() {
+            if c.is_whitespace() {
+                continue;
+            }
+
+            if c == '/' {
+                match chars.peek() {
+                    Some(&(_, '/')) => {
+                        for (_, c) in &mut chars {
+                            if c == '\n' {
+                                break;
+                            }
+                        }
+                        continue;
+                    }
+                    Some(&(_, '*')) => {
+                        chars.next();
+                        let mut was_star = false;
+                        for (_, c) in &mut chars {
+                            if was_star && c == '/' {
+                                break;
+                            }
+                            was_star = c == '*';
+                        }
+                        continue;
+                    }
+                    _ => {}
+                }
+            }
+
+            tokens.push((lo + BytePos(i as u32), c));
+            if tokens.len() > MAX_GAP_TOKENS_LEN {
+                return None;
+            }
+        }
+
+        Some(tokens)
     }
 }
@@ -1,4 +1,96 @@
-#[derive(Debug, Default, Clone, Copy)]
+use swc_ecma_ast::JscTarget;
+
+#[derive(Debug, Clone, Copy)]
 pub struct Config {
     pub minify: bool,
+
+    /// Literals are printed in a form the target understands.
+    ///
+    /// Defaults to the latest version.
+    pub target: JscTarget,
+
+    /// Make syntax which [Config::target] does not support an error.
+    ///
+    /// Such syntax is printed as-is if disabled, which is the default.
+    pub check_target: bool,
+
+    /// String used for one level of indentation.
+    ///
+    /// Defaults to four spaces.
+    pub indent: &'static str,
+
+    /// Quotes used for string literals.
+    pub quote_style: QuoteStyle,
+
+    /// Escape non-ascii characters of string literals, templates, regexes
+    /// and identifiers.
+    ///
+    /// Emitting fails for tagged templates with non-ascii characters, as
+    /// escaping changes their raw strings, and for identifiers with characters
+    /// outside of the BMP if the target is older than es2015.
+    pub ascii_only: bool,
+
+    pub semicolons: Semicolons,
+
+    /// Add a trailing comma to comma-separated lists which are printed over
+    /// multiple lines.
+    ///
+    /// Argument lists only get one if the target is es2017 or later.
+    pub trailing_commas: bool,
+
+    /// Soft limit of the line width.
+    ///
+    /// Argument lists and array literals which would exceed it are printed
+    /// with one element per line. Nothing is broken if `None`.
+    pub max_line_len: Option<usize>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            minify: false,
+            target: JscTarget::Es2020,
+            check_target: false,
+            indent: "    ",
+            quote_style: Default::default(),
+            ascii_only: false,
+            semicolons: Default::default(),
+            trailing_commas: false,
+            max_line_len: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuoteStyle {
+    /// Use the quotes of the original source. Synthesized strings use single
+    /// quotes.
+    Preserve,
+    /// Prefer single quotes, unless double quotes need less escaping.
+    Single,
+    /// Prefer double quotes, unless single quotes need less escaping.
+    Double,
+}
+
+impl Default for QuoteStyle {
+    fn default() -> Self {
+        QuoteStyle::Preserve
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Semicolons {
+    /// Terminate every statement with a semicolon.
+    Always,
+    /// Omit semicolons which automatic semicolon insertion restores.
+    ///
+    /// If the next line starts with a token which would continue the
+    /// statement, the semicolon is written at the start of that line instead.
+    AsNeeded,
+}
+
+impl Default for Semicolons {
+    fn default() -> Self {
+        Semicolons::Always
+    }
 }
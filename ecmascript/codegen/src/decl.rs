@@ -13,6 +13,10 @@ impl<'a> Emitter<'a> {
             Decl::Var(ref n) => {
                 emit!(n);
                 semi!(); // VarDecl is also used for for-loops
+
+                if let Some(last) = n.decls.last() {
+                    self.emit_comments_between(last.span.hi(), n.span.hi())?;
+                }
             }
             Decl::TsEnum(ref n) => emit!(n),
             Decl::TsInterface(ref n) => emit!(n),
@@ -76,6 +80,10 @@ impl<'a> Emitter<'a> {
             space!();
         }
 
+        if node.kind != VarDeclKind::Var {
+            self.ensure_target(node.span, JscTarget::Es2015, "`let` and `const`")?;
+        }
+
         keyword!(node.kind.as_str());
         space!();
 
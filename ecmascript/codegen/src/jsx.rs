@@ -7,6 +7,8 @@ use swc_ecma_codegen_macros::emitter;
 impl<'a> Emitter<'a> {
     #[emitter]
     fn emit_jsx_element(&mut self, node: &JSXElement) -> Result {
+        self.emit_leading_comments_of_pos(node.span().lo())?;
+
         emit!(node.opening);
         self.emit_list(
             node.span(),
@@ -28,6 +30,13 @@ impl<'a> Emitter<'a> {
             Some(&node.attrs),
             ListFormat::JsxElementAttributes,
         )?;
+        {
+            let lo = match node.attrs.last() {
+                Some(attr) => attr.span().hi(),
+                None => node.name.span().hi(),
+            };
+            self.emit_comments_between(lo, node.span.hi())?;
+        }
 
         if node.self_closing {
             punct!("/");
@@ -46,6 +55,8 @@ impl<'a> Emitter<'a> {
 
     #[emitter]
     fn emit_jsx_attr(&mut self, node: &JSXAttr) -> Result {
+        self.emit_leading_comments_of_pos(node.span().lo())?;
+
         emit!(node.name);
 
         if let Some(ref value) = node.value {
@@ -103,6 +114,14 @@ impl<'a> Emitter<'a> {
     fn emit_jsx_expr_container(&mut self, node: &JSXExprContainer) -> Result {
         punct!("{");
         emit!(node.expr);
+        {
+            // e.g. `{/* foo */}`
+            let lo = match node.expr {
+                JSXExpr::Expr(ref expr) => expr.span().hi(),
+                JSXExpr::JSXEmptyExpr(..) => node.span.lo(),
+            };
+            self.emit_comments_between(lo, node.span.hi())?;
+        }
         punct!("}");
     }
 
@@ -123,6 +142,8 @@ impl<'a> Emitter<'a> {
 
     #[emitter]
     fn emit_jsx_fragment(&mut self, node: &JSXFragment) -> Result {
+        self.emit_leading_comments_of_pos(node.span().lo())?;
+
         emit!(node.opening);
 
         self.emit_list(
@@ -1,7 +1,7 @@
 #![recursion_limit = "1024"]
 #![allow(unused_variables)]
 
-pub use self::config::Config;
+pub use self::config::{Config, QuoteStyle, Semicolons};
 use self::{
     list::ListFormat,
     text_writer::WriteJs,
@@ -58,6 +58,8 @@ pub struct Emitter<'a> {
 impl<'a> Emitter<'a> {
     #[emitter]
     pub fn emit_program(&mut self, node: &Program) -> Result {
+        self.wr.configure(&self.cfg);
+
         match *node {
             Program::Module(ref m) => emit!(m),
             Program::Script(ref s) => emit!(s),
@@ -66,6 +68,8 @@ impl<'a> Emitter<'a> {
 
     #[emitter]
     pub fn emit_module(&mut self, node: &Module) -> Result {
+        self.wr.configure(&self.cfg);
+
         if let Some(ref shebang) = node.shebang {
             punct!("#!");
             self.wr.write_str_lit(DUMMY_SP, &*shebang)?;
@@ -73,11 +77,14 @@ impl<'a> Emitter<'a> {
         }
         for stmt in &node.body {
             emit!(stmt);
+            self.emit_leftover_comments(stmt.span())?;
         }
     }
 
     #[emitter]
     pub fn emit_script(&mut self, node: &Script) -> Result {
+        self.wr.configure(&self.cfg);
+
         if let Some(ref shebang) = node.shebang {
             punct!("#!");
             self.wr.write_str_lit(DUMMY_SP, &*shebang)?;
@@ -85,6 +92,7 @@ impl<'a> Emitter<'a> {
         }
         for stmt in &node.body {
             emit!(stmt);
+            self.emit_leftover_comments(stmt.span())?;
         }
     }
 
@@ -363,8 +371,14 @@ impl<'a> Emitter<'a> {
             Lit::BigInt(ref s) => emit!(s),
             Lit::Num(ref n) => emit!(n),
             Lit::Regex(ref n) => {
+                self.ensure_regex_supported(n)?;
+
                 punct!("/");
-                self.wr.write_str(&n.exp)?;
+                if self.cfg.ascii_only {
+                    self.wr.write_str(&escape_regex_non_ascii(&n.exp))?;
+                } else {
+                    self.wr.write_str(&n.exp)?;
+                }
                 punct!("/");
                 self.wr.write_str(&n.flags)?;
             }
@@ -382,17 +396,34 @@ impl<'a> Emitter<'a> {
     fn emit_str_lit(&mut self, node: &Str) -> Result {
         self.emit_leading_comments_of_pos(node.span().lo())?;
 
-        let single_quote = if let Ok(s) = self.cm.span_to_snippet(node.span) {
-            s.starts_with("'")
-        } else {
-            true
+        let single_quote = match self.cfg.quote_style {
+            QuoteStyle::Preserve => {
+                if let Ok(s) = self.cm.span_to_snippet(node.span) {
+                    s.starts_with("'")
+                } else {
+                    true
+                }
+            }
+            // Pick the quote which needs less escaping.
+            QuoteStyle::Single | QuoteStyle::Double => {
+                let singles = node.value.matches('\'').count();
+                let doubles = node.value.matches('"').count();
+                if singles == doubles {
+                    self.cfg.quote_style == QuoteStyle::Single
+                } else {
+                    singles < doubles
+                }
+            }
         };
 
         // if let Some(s) = get_text_of_node(&self.cm, node, false) {
         //     self.wr.write_str_lit(node.span, &s)?;
         //     return Ok(());
         // }
-        let value = escape(&node.value);
+        let mut value = escape_for_target(escape(&node.value), self.cfg.target);
+        if self.cfg.ascii_only {
+            value = escape_non_ascii(value, self.cfg.target);
+        }
         // let value = node.value.replace("\n", "\\n");
 
         if single_quote {
@@ -430,6 +461,7 @@ impl<'a> Emitter<'a> {
     #[emitter]
     fn emit_big_lit(&mut self, v: &BigInt) -> Result {
         self.emit_leading_comments_of_pos(v.span.lo())?;
+        self.ensure_target(v.span, JscTarget::Es2020, "BigInt literals")?;
 
         self.wr.write_lit(v.span, &v.value.to_string())?;
         self.wr.write_lit(v.span, "n")?;
@@ -518,6 +550,7 @@ impl<'a> Emitter<'a> {
     #[emitter]
     fn emit_opt_chain(&mut self, n: &OptChainExpr) -> Result {
         self.emit_leading_comments_of_pos(n.span().lo())?;
+        self.ensure_target(n.span, JscTarget::Es2020, "optional chaining")?;
 
         match *n.expr {
             Expr::Member(ref e) => {
@@ -538,6 +571,11 @@ impl<'a> Emitter<'a> {
 
                 punct!("(");
                 self.emit_expr_or_spreads(n.span(), &e.args, ListFormat::CallExpressionArguments)?;
+                self.emit_comments_before_closing_paren(
+                    e.callee.span().hi(),
+                    &e.args,
+                    n.span().hi(),
+                )?;
                 punct!(")");
             }
             _ => {}
@@ -559,6 +597,11 @@ impl<'a> Emitter<'a> {
 
         punct!("(");
         self.emit_expr_or_spreads(node.span(), &node.args, ListFormat::CallExpressionArguments)?;
+        self.emit_comments_before_closing_paren(
+            node.callee.span().hi(),
+            &node.args,
+            node.span().hi(),
+        )?;
         punct!(")");
     }
 
@@ -573,6 +616,11 @@ impl<'a> Emitter<'a> {
         if let Some(ref args) = node.args {
             punct!("(");
             self.emit_expr_or_spreads(node.span(), args, ListFormat::NewExpressionArguments)?;
+            self.emit_comments_before_closing_paren(
+                node.callee.span().hi(),
+                args,
+                node.span().hi(),
+            )?;
             punct!(")");
         }
     }
@@ -632,6 +680,10 @@ impl<'a> Emitter<'a> {
     #[emitter]
     fn emit_arrow_expr(&mut self, node: &ArrowExpr) -> Result {
         self.emit_leading_comments_of_pos(node.span().lo())?;
+        self.ensure_target(node.span, JscTarget::Es2015, "arrow functions")?;
+        if node.is_async {
+            self.ensure_target(node.span, JscTarget::Es2017, "async functions")?;
+        }
 
         if node.is_async {
             keyword!("async");
@@ -642,6 +694,13 @@ impl<'a> Emitter<'a> {
         }
         punct!("(");
         self.emit_list(node.span, Some(&node.params), ListFormat::CommaListElements)?;
+        {
+            let hi = match &node.return_type {
+                Some(ty) => ty.span.lo(),
+                None => node.body.span().lo(),
+            };
+            self.emit_comments_before_closing_paren(node.span.lo(), &node.params, hi)?;
+        }
         punct!(")");
 
         punct!("=>");
@@ -651,6 +710,11 @@ impl<'a> Emitter<'a> {
     #[emitter]
     fn emit_meta_prop_expr(&mut self, node: &MetaPropExpr) -> Result {
         self.emit_leading_comments_of_pos(node.span().lo())?;
+        if &*node.meta.sym == "import" {
+            self.ensure_target(node.span(), JscTarget::Es2020, "`import.meta`")?;
+        } else {
+            self.ensure_target(node.span(), JscTarget::Es2015, "`new.target`")?;
+        }
 
         emit!(node.meta);
         punct!(".");
@@ -678,6 +742,9 @@ impl<'a> Emitter<'a> {
     #[emitter]
     fn emit_assign_expr(&mut self, node: &AssignExpr) -> Result {
         self.emit_leading_comments_of_pos(node.span().lo())?;
+        if node.op == op!("**=") {
+            self.ensure_target(node.span, JscTarget::Es2016, "`**=`")?;
+        }
 
         emit!(node.left);
         formatting_space!();
@@ -690,6 +757,12 @@ impl<'a> Emitter<'a> {
     fn emit_bin_expr(&mut self, node: &BinExpr) -> Result {
         self.emit_leading_comments_of_pos(node.span().lo())?;
 
+        match node.op {
+            op!("**") => self.ensure_target(node.span, JscTarget::Es2016, "`**`")?,
+            op!("??") => self.ensure_target(node.span, JscTarget::Es2020, "`??`")?,
+            _ => {}
+        }
+
         // let indent_before_op = needs_indention(node, &node.left, node.op);
         // let indent_after_op = needs_indention(node, node.op, &node.right);
         let need_space = match node.op {
@@ -758,6 +831,8 @@ impl<'a> Emitter<'a> {
 
     #[emitter]
     fn emit_class_trailing(&mut self, node: &Class) -> Result {
+        self.ensure_target(node.span, JscTarget::Es2015, "classes")?;
+
         if node.super_class.is_some() {
             keyword!("extends");
             space!();
@@ -767,6 +842,14 @@ impl<'a> Emitter<'a> {
 
         punct!("{");
         self.emit_list(node.span, Some(&node.body), ListFormat::ClassMembers)?;
+        {
+            let lo = match (node.body.last(), &node.super_class) {
+                (Some(member), _) => member.span().hi(),
+                (None, Some(super_class)) => super_class.span().hi(),
+                (None, None) => node.span.lo(),
+            };
+            self.emit_comments_before_closing_brace(lo, node.span.hi())?;
+        }
         punct!("}");
     }
 
@@ -1003,6 +1086,8 @@ impl<'a> Emitter<'a> {
 
     #[emitter]
     fn emit_computed_prop_name(&mut self, node: &ComputedPropName) -> Result {
+        self.ensure_target(node.span, JscTarget::Es2015, "computed property names")?;
+
         punct!("[");
         emit!(node.expr);
         punct!("]");
@@ -1047,12 +1132,27 @@ impl<'a> Emitter<'a> {
     /// prints `(b){}` from `function a(b){}`
     #[emitter]
     fn emit_fn_trailing(&mut self, node: &Function) -> Result {
+        match (node.is_async, node.is_generator) {
+            (true, true) => self.ensure_target(node.span, JscTarget::Es2018, "async generators")?,
+            (true, false) => self.ensure_target(node.span, JscTarget::Es2017, "async functions")?,
+            (false, true) => self.ensure_target(node.span, JscTarget::Es2015, "generators")?,
+            (false, false) => {}
+        }
+
         if let Some(type_params) = &node.type_params {
             emit!(type_params);
         }
 
         punct!("(");
         self.emit_list(node.span, Some(&node.params), ListFormat::CommaListElements)?;
+        {
+            let hi = match (&node.return_type, &node.body) {
+                (Some(ty), _) => ty.span.lo(),
+                (None, Some(body)) => body.span.lo(),
+                (None, None) => node.span.hi(),
+            };
+            self.emit_comments_before_closing_paren(node.span.lo(), &node.params, hi)?;
+        }
         punct!(")");
 
         if let Some(ty) = &node.return_type {
@@ -1094,6 +1194,7 @@ impl<'a> Emitter<'a> {
         debug_assert!(node.quasis.len() == node.exprs.len() + 1);
 
         self.emit_leading_comments_of_pos(node.span().lo())?;
+        self.ensure_target(node.span, JscTarget::Es2015, "template literals")?;
 
         punct!("`");
         let i = 0;
@@ -1116,6 +1217,15 @@ impl<'a> Emitter<'a> {
         debug_assert!(node.quasis.len() == node.exprs.len() + 1);
 
         self.emit_leading_comments_of_pos(node.span().lo())?;
+        self.ensure_target(node.span, JscTarget::Es2015, "template literals")?;
+
+        // Escapes would change the raw strings passed to the tag.
+        if self.cfg.ascii_only && node.quasis.iter().any(|q| !q.raw.value.is_ascii()) {
+            return Err(self.invalid_input(
+                node.span,
+                "cannot escape non-ascii characters of tagged templates".into(),
+            ));
+        }
 
         emit!(node.tag);
         emit!(node.type_params);
@@ -1137,8 +1247,11 @@ impl<'a> Emitter<'a> {
 
     #[emitter]
     fn emit_quasi(&mut self, node: &TplElement) -> Result {
-        self.wr
-            .write_str_lit(node.span, &unescape(&node.raw.value))?;
+        let mut value = Cow::Owned(unescape(&node.raw.value));
+        if self.cfg.ascii_only {
+            value = escape_non_ascii(value, self.cfg.target);
+        }
+        self.wr.write_str_lit(node.span, &value)?;
         return Ok(());
     }
 
@@ -1212,7 +1325,8 @@ impl<'a> Emitter<'a> {
     fn emit_expr_or_spread(&mut self, node: &ExprOrSpread) -> Result {
         self.emit_leading_comments_of_pos(node.span().lo())?;
 
-        if node.spread.is_some() {
+        if let Some(spread) = node.spread {
+            self.ensure_target(spread, JscTarget::Es2015, "spread elements")?;
             punct!("...");
         }
 
@@ -1240,6 +1354,13 @@ impl<'a> Emitter<'a> {
             Some(&node.elems),
             ListFormat::ArrayLiteralExpressionElements,
         )?;
+        {
+            let lo = match node.elems.iter().rev().flatten().next() {
+                Some(elem) => elem.span().hi(),
+                None => node.span.lo(),
+            };
+            self.emit_comments_between(lo, node.span.hi())?;
+        }
         punct!("]");
     }
 
@@ -1259,18 +1380,31 @@ impl<'a> Emitter<'a> {
         if !self.cfg.minify {
             self.wr.write_line()?;
         }
+        {
+            let lo = match node.props.last() {
+                Some(prop) => prop.span().hi(),
+                None => node.span.lo(),
+            };
+            self.emit_comments_before_closing_brace(lo, node.span.hi())?;
+        }
         punct!("}");
     }
 
     #[emitter]
     fn emit_prop(&mut self, node: &Prop) -> Result {
         match *node {
-            Prop::Shorthand(ref n) => emit!(n),
+            Prop::Shorthand(ref n) => {
+                self.ensure_target(n.span, JscTarget::Es2015, "shorthand properties")?;
+                emit!(n)
+            }
             Prop::KeyValue(ref n) => emit!(n),
             Prop::Assign(ref n) => emit!(n),
             Prop::Getter(ref n) => emit!(n),
             Prop::Setter(ref n) => emit!(n),
-            Prop::Method(ref n) => emit!(n),
+            Prop::Method(ref n) => {
+                self.ensure_target(n.function.span, JscTarget::Es2015, "methods")?;
+                emit!(n)
+            }
         }
     }
 
@@ -1373,7 +1507,24 @@ impl<'a> Emitter<'a> {
             unimplemented!()
         } else {
             // TODO: span
-            self.wr.write_symbol(ident.span, &ident.sym)?;
+            if self.cfg.ascii_only {
+                // Surrogate pairs are not valid in identifiers.
+                if self.cfg.target < JscTarget::Es2015 && ident.sym.chars().any(|c| c > '\u{FFFF}')
+                {
+                    return Err(self.invalid_input(
+                        ident.span,
+                        format!(
+                            "cannot escape identifier `{}` for {}: characters outside of the BMP \
+                             require es2015",
+                            ident.sym, self.cfg.target
+                        ),
+                    ));
+                }
+                let sym = escape_non_ascii(Cow::Borrowed(&*ident.sym), self.cfg.target);
+                self.wr.write_symbol(ident.span, &sym)?;
+            } else {
+                self.wr.write_symbol(ident.span, &ident.sym)?;
+            }
             if ident.optional {
                 punct!("?");
             }
@@ -1428,6 +1579,13 @@ impl<'a> Emitter<'a> {
             return Ok(());
         }
 
+        let format = match children {
+            Some(children) if !is_empty => {
+                self.break_long_list(parent_node, &children[start..start + count], format)?
+            }
+            _ => format,
+        };
+
         if format.contains(ListFormat::BracketsMask) {
             self.wr.write_punct(format.opening_bracket())?;
 
@@ -1563,9 +1721,21 @@ impl<'a> Emitter<'a> {
                 }
             };
 
+            let should_write_closing_line_terminator = self
+                .cm
+                .should_write_closing_line_terminator(parent_node, children, format);
+
             if has_trailing_comma && format.contains(ListFormat::CommaDelimited) {
                 self.wr.write_punct(",")?;
                 formatting_space!(self);
+            } else if self.cfg.trailing_commas
+                && !self.cfg.minify
+                && should_write_closing_line_terminator
+                && format.contains(ListFormat::CommaDelimited)
+                && (self.cfg.target >= JscTarget::Es2017
+                    || !format.intersects(ListFormat::Parenthesis))
+            {
+                self.wr.write_punct(",")?;
             }
 
             {
@@ -1600,10 +1770,7 @@ impl<'a> Emitter<'a> {
             }
 
             // Write the closing line terminator or closing whitespace.
-            if self
-                .cm
-                .should_write_closing_line_terminator(parent_node, children, format)
-            {
+            if should_write_closing_line_terminator {
                 if !self.cfg.minify {
                     self.wr.write_line()?;
                 }
@@ -1656,6 +1823,7 @@ impl<'a> Emitter<'a> {
     #[emitter]
     fn emit_rest_pat(&mut self, node: &RestPat) -> Result {
         self.emit_leading_comments_of_pos(node.span().lo())?;
+        self.ensure_target(node.span, JscTarget::Es2015, "rest elements")?;
 
         punct!("...");
         emit!(node.arg);
@@ -1678,6 +1846,7 @@ impl<'a> Emitter<'a> {
     #[emitter]
     fn emit_spread_element(&mut self, node: &SpreadElement) -> Result {
         self.emit_leading_comments_of_pos(node.span().lo())?;
+        self.ensure_target(node.span(), JscTarget::Es2018, "object spread")?;
 
         punct!("...");
         emit!(node.expr)
@@ -1694,6 +1863,7 @@ impl<'a> Emitter<'a> {
     #[emitter]
     fn emit_array_pat(&mut self, node: &ArrayPat) -> Result {
         self.emit_leading_comments_of_pos(node.span().lo())?;
+        self.ensure_target(node.span, JscTarget::Es2015, "destructuring")?;
 
         punct!("[");
         self.emit_list(
@@ -1716,6 +1886,7 @@ impl<'a> Emitter<'a> {
     #[emitter]
     fn emit_assign_pat(&mut self, node: &AssignPat) -> Result {
         self.emit_leading_comments_of_pos(node.span().lo())?;
+        self.ensure_target(node.span, JscTarget::Es2015, "default values")?;
 
         emit!(node.left);
         formatting_space!();
@@ -1727,6 +1898,7 @@ impl<'a> Emitter<'a> {
     #[emitter]
     fn emit_object_pat(&mut self, node: &ObjectPat) -> Result {
         self.emit_leading_comments_of_pos(node.span().lo())?;
+        self.ensure_target(node.span, JscTarget::Es2015, "destructuring")?;
 
         punct!("{");
         self.emit_list(
@@ -1751,7 +1923,10 @@ impl<'a> Emitter<'a> {
         match *node {
             ObjectPatProp::KeyValue(ref node) => emit!(node),
             ObjectPatProp::Assign(ref node) => emit!(node),
-            ObjectPatProp::Rest(ref node) => emit!(node),
+            ObjectPatProp::Rest(ref node) => {
+                self.ensure_target(node.span, JscTarget::Es2018, "object rest")?;
+                emit!(node)
+            }
         }
     }
 
@@ -1816,7 +1991,12 @@ impl<'a> Emitter<'a> {
             Stmt::ForOf(ref e) => emit!(e),
             Stmt::Decl(ref e) => emit!(e),
         }
-        self.emit_trailing_comments_of_pos(node.span().hi(), true)?;
+        // e.g. `return /* foo */;`
+        let hi = node.span().hi();
+        if hi != BytePos(0) {
+            self.emit_comments_between(hi - BytePos(1), hi)?;
+        }
+        self.emit_trailing_comments_of_pos(hi, true)?;
 
         if !self.cfg.minify {
             self.wr.write_line()?;
@@ -1827,6 +2007,8 @@ impl<'a> Emitter<'a> {
     fn emit_expr_stmt(&mut self, e: &ExprStmt) -> Result {
         emit!(e.expr);
         semi!();
+
+        self.emit_comments_between(e.expr.span().hi(), e.span.hi())?;
     }
 
     #[emitter]
@@ -1839,6 +2021,13 @@ impl<'a> Emitter<'a> {
             Some(&node.stmts),
             ListFormat::MultiLineBlockStatements,
         )?;
+        {
+            let lo = match node.stmts.last() {
+                Some(stmt) => stmt.span().hi(),
+                None => node.span.lo(),
+            };
+            self.emit_comments_before_closing_brace(lo, node.span.hi())?;
+        }
         punct!("}");
     }
 
@@ -1846,7 +2035,8 @@ impl<'a> Emitter<'a> {
     fn emit_empty_stmt(&mut self, node: &EmptyStmt) -> Result {
         self.emit_leading_comments_of_pos(node.span().lo())?;
 
-        punct!(";");
+        // This is the statement itself, so it can't be omitted.
+        self.wr.write_punct(";")?;
     }
 
     #[emitter]
@@ -1946,7 +2136,8 @@ impl<'a> Emitter<'a> {
         emit!(node.cons);
 
         if let Some(ref alt) = node.alt {
-            if is_cons_block {
+            self.emit_comments_between(node.cons.span().hi(), alt.span().lo())?;
+            if is_cons_block && self.wr.line_pos() != 0 {
                 formatting_space!();
             }
             keyword!("else");
@@ -1971,6 +2162,13 @@ impl<'a> Emitter<'a> {
 
         punct!("{");
         self.emit_list(node.span(), Some(&node.cases), ListFormat::CaseBlockClauses)?;
+        {
+            let lo = match node.cases.last() {
+                Some(case) => case.span().hi(),
+                None => node.discriminant.span().hi(),
+            };
+            self.emit_comments_before_closing_brace(lo, node.span.hi())?;
+        }
         punct!("}");
     }
 
@@ -1978,6 +2176,10 @@ impl<'a> Emitter<'a> {
     fn emit_catch_clause(&mut self, node: &CatchClause) -> Result {
         self.emit_leading_comments_of_pos(node.span().lo())?;
 
+        if node.param.is_none() {
+            self.ensure_target(node.span, JscTarget::Es2019, "optional catch binding")?;
+        }
+
         keyword!("catch");
         formatting_space!();
 
@@ -2043,12 +2245,22 @@ impl<'a> Emitter<'a> {
         emit!(node.block);
 
         if let Some(ref catch) = node.handler {
-            formatting_space!();
+            self.emit_comments_between(node.block.span.hi(), catch.span.lo())?;
+            if self.wr.line_pos() != 0 {
+                formatting_space!();
+            }
             emit!(catch);
         }
 
         if let Some(ref finally) = node.finalizer {
-            formatting_space!();
+            let lo = match node.handler {
+                Some(ref catch) => catch.span.hi(),
+                None => node.block.span.hi(),
+            };
+            self.emit_comments_between(lo, finally.span.lo())?;
+            if self.wr.line_pos() != 0 {
+                formatting_space!();
+            }
             keyword!("finally");
             // space!();
             emit!(finally);
@@ -2080,6 +2292,7 @@ impl<'a> Emitter<'a> {
         }
         emit!(node.body);
 
+        self.emit_comments_between(node.body.span().hi(), node.test.span().lo())?;
         keyword!("while");
 
         formatting_space!();
@@ -2124,6 +2337,10 @@ impl<'a> Emitter<'a> {
     #[emitter]
     fn emit_for_of_stmt(&mut self, node: &ForOfStmt) -> Result {
         self.emit_leading_comments_of_pos(node.span().lo())?;
+        match node.await_token {
+            Some(span) => self.ensure_target(span, JscTarget::Es2018, "`for await...of`")?,
+            None => self.ensure_target(node.span, JscTarget::Es2015, "`for...of`")?,
+        }
 
         keyword!("for");
         if node.await_token.is_some() {
@@ -2143,6 +2360,128 @@ impl<'a> Emitter<'a> {
 }
 
 impl<'a> Emitter<'a> {
+    /// Returns an error if `syntax` can't be emitted because it's not
+    /// supported by the target.
+    ///
+    /// Nothing is checked unless [Config::check_target] is enabled.
+    fn ensure_target(&self, span: Span, required: JscTarget, syntax: &str) -> Result {
+        if !self.cfg.check_target || self.cfg.target >= required {
+            return Ok(());
+        }
+
+        Err(self.invalid_input(
+            span,
+            format!(
+                "cannot emit {} for {}: it requires {}",
+                syntax, self.cfg.target, required
+            ),
+        ))
+    }
+
+    /// Creates an error for a node which can't be emitted with the current
+    /// config.
+    fn invalid_input(&self, span: Span, msg: String) -> io::Error {
+        let loc = if span.is_dummy() {
+            String::new()
+        } else {
+            format!(" (at {})", self.cm.span_to_string(span))
+        };
+        io::Error::new(io::ErrorKind::InvalidInput, format!("{}{}", msg, loc))
+    }
+
+    fn ensure_regex_supported(&self, regex: &Regex) -> Result {
+        for flag in regex.flags.chars() {
+            match flag {
+                'u' => {
+                    self.ensure_target(regex.span, JscTarget::Es2015, "the `u` flag of regexes")?
+                }
+                'y' => {
+                    self.ensure_target(regex.span, JscTarget::Es2015, "the `y` flag of regexes")?
+                }
+                's' => {
+                    self.ensure_target(regex.span, JscTarget::Es2018, "the `s` flag of regexes")?
+                }
+                _ => {}
+            }
+        }
+
+        // `(?<name>`, `(?<=` and `(?<!` which are not escaped.
+        let mut escaped = false;
+        for (i, c) in regex.exp.char_indices() {
+            if escaped {
+                escaped = false;
+                continue;
+            }
+            match c {
+                '\\' => escaped = true,
+                '(' if regex.exp[i..].starts_with("(?<") => {
+                    let syntax = match regex.exp[i + 3..].chars().next() {
+                        Some('=') | Some('!') => "lookbehind assertions",
+                        _ => "named capturing groups",
+                    };
+                    self.ensure_target(regex.span, JscTarget::Es2018, syntax)?;
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the format to print an argument list or an array literal with
+    /// one element per line, if it would exceed [Config::max_line_len] on a
+    /// single line.
+    fn break_long_list<N: Node>(
+        &mut self,
+        parent_node: Span,
+        children: &[N],
+        format: ListFormat,
+    ) -> io::Result<ListFormat> {
+        let max_line_len = match self.cfg.max_line_len {
+            Some(max_line_len) if !self.cfg.minify => max_line_len,
+            _ => return Ok(format),
+        };
+        if format != ListFormat::CallExpressionArguments
+            && format != ListFormat::NewExpressionArguments
+            && format != ListFormat::ArrayLiteralExpressionElements
+        {
+            return Ok(format);
+        }
+
+        let single_line = format - ListFormat::LinesMask;
+        let mut buf = vec![];
+        {
+            let mut e = Emitter {
+                cfg: Config {
+                    max_line_len: None,
+                    ..self.cfg
+                },
+                cm: self.cm.clone(),
+                comments: None,
+                wr: Box::new(text_writer::JsWriter::new(
+                    self.cm.clone(),
+                    "\n",
+                    &mut buf,
+                    None,
+                )),
+            };
+            e.wr.configure(&e.cfg);
+            e.emit_list(parent_node, Some(children), single_line)?;
+        }
+        let width = String::from_utf8_lossy(&buf)
+            .lines()
+            .next()
+            .map(|line| line.chars().count())
+            .unwrap_or(0);
+
+        // The closing bracket is written by the caller.
+        if self.wr.line_pos() + width + 1 > max_line_len {
+            Ok(single_line | ListFormat::MultiLine | ListFormat::Indented)
+        } else {
+            Ok(format)
+        }
+    }
+
     fn write_delim(&mut self, f: ListFormat) -> Result {
         match f & ListFormat::DelimitersMask {
             ListFormat::None => {}
@@ -2365,6 +2704,88 @@ fn unescape(s: &str) -> String {
     result
 }
 
+/// Escapes characters of a string literal which can't be written as is for
+/// `target`.
+///
+/// Line separators are not allowed in string literals before es2019, and
+/// characters outside of the BMP are written as surrogate pairs before es2015.
+fn escape_for_target(s: Cow<str>, target: JscTarget) -> Cow<str> {
+    let needs_escape = |c: char| match c {
+        '\u{2028}' | '\u{2029}' => target < JscTarget::Es2019,
+        _ => target < JscTarget::Es2015 && c > '\u{FFFF}',
+    };
+    if !s.chars().any(needs_escape) {
+        return s;
+    }
+
+    let mut buf = String::with_capacity(s.len());
+    for c in s.chars() {
+        if needs_escape(c) {
+            for unit in c.encode_utf16(&mut [0; 2]) {
+                write!(buf, "\\u{:04x}", unit).unwrap();
+            }
+        } else {
+            buf.push(c);
+        }
+    }
+    Cow::Owned(buf)
+}
+
+/// Escapes all characters outside of the ascii range.
+///
+/// Characters outside of the BMP are written as code point escapes, which
+/// are only valid since es2015, and as surrogate pairs before it.
+fn escape_non_ascii(s: Cow<str>, target: JscTarget) -> Cow<str> {
+    if s.is_ascii() {
+        return s;
+    }
+
+    let mut buf = String::with_capacity(s.len());
+    for c in s.chars() {
+        if c.is_ascii() {
+            buf.push(c);
+        } else if c <= '\u{FFFF}' || target < JscTarget::Es2015 {
+            for unit in c.encode_utf16(&mut [0; 2]) {
+                write!(buf, "\\u{:04x}", unit).unwrap();
+            }
+        } else {
+            write!(buf, "\\u{{{:x}}}", c as u32).unwrap();
+        }
+    }
+    Cow::Owned(buf)
+}
+
+/// Escapes all characters outside of the ascii range in the source of a
+/// regex.
+///
+/// Characters outside of the BMP are written as surrogate pairs, which match
+/// the same string with and without the `u` flag.
+fn escape_regex_non_ascii(exp: &str) -> Cow<str> {
+    if exp.is_ascii() {
+        return Cow::Borrowed(exp);
+    }
+
+    let mut buf = String::with_capacity(exp.len());
+    let mut escaped = false;
+    for c in exp.chars() {
+        if c.is_ascii() {
+            escaped = !escaped && c == '\\';
+            buf.push(c);
+            continue;
+        }
+
+        // An identity escape like `\é` matches the character itself.
+        if escaped {
+            buf.pop();
+            escaped = false;
+        }
+        for unit in c.encode_utf16(&mut [0; 2]) {
+            write!(buf, "\\u{:04x}", unit).unwrap();
+        }
+    }
+    Cow::Owned(buf)
+}
+
 fn escape(s: &str) -> Cow<str> {
     // let patterns = &[
     //     "\\", "\u{0008}", "\u{000C}", "\n", "\r", "\t", "\u{000B}", "\00", "\01",
@@ -32,19 +32,23 @@ impl Builder {
         ret
     }
 
-    pub fn text<F>(self, src: &str, op: F) -> String
+    pub fn text<F>(self, src: &str, op: F) -> io::Result<String>
     where
-        F: FnOnce(&mut Emitter<'_>),
+        F: FnOnce(&mut Emitter<'_>) -> Result,
     {
         let mut buf = vec![];
 
-        self.with(src, &mut buf, op);
+        self.with(src, &mut buf, op)?;
 
-        String::from_utf8(buf).unwrap()
+        Ok(String::from_utf8(buf).unwrap())
     }
 }
 
 fn parse_then_emit(from: &str, cfg: Config, syntax: Syntax) -> String {
+    try_parse_then_emit(from, cfg, syntax).unwrap()
+}
+
+fn try_parse_then_emit(from: &str, cfg: Config, syntax: Syntax) -> io::Result<String> {
     ::testing::run_test(false, |cm, handler| {
         let src = cm.new_source_file(FileName::Real("custom.js".into()), from.to_string());
         println!(
@@ -52,7 +56,7 @@ fn parse_then_emit(from: &str, cfg: Config, syntax: Syntax) -> String {
             from, src.start_pos, src.end_pos
         );
 
-        let comments = Default::default();
+        let comments = SingleThreadedComments::default();
         let res = {
             let mut parser = Parser::new(syntax, StringInput::from(&*src), Some(&comments));
             let res = parser
@@ -66,20 +70,63 @@ fn parse_then_emit(from: &str, cfg: Config, syntax: Syntax) -> String {
             res?
         };
 
-        let out = Builder { cfg, cm, comments }.text(from, |e| e.emit_module(&res).unwrap());
+        let out = Builder {
+            cfg,
+            cm,
+            comments: comments.clone(),
+        }
+        .text(from, |e| e.emit_module(&res));
+        if out.is_ok() {
+            assert_all_comments_emitted(comments);
+        }
+
         Ok(out)
     })
     .unwrap()
 }
 
+/// The emitter takes comments from the storage as it emits them, so anything
+/// left over was dropped.
+fn assert_all_comments_emitted(comments: SingleThreadedComments) {
+    let (leading, trailing) = comments.take_all();
+    let leading = leading.borrow();
+    let trailing = trailing.borrow();
+
+    let dropped = leading
+        .values()
+        .chain(trailing.values())
+        .flatten()
+        .map(|cmt| &*cmt.text)
+        .collect::<Vec<_>>();
+    assert!(
+        dropped.is_empty(),
+        "comments were not emitted: {:?}",
+        dropped
+    );
+}
+
 pub(crate) fn assert_min(from: &str, to: &str) {
-    let out = parse_then_emit(from, Config { minify: true }, Syntax::default());
+    let out = parse_then_emit(
+        from,
+        Config {
+            minify: true,
+            ..Default::default()
+        },
+        Syntax::default(),
+    );
 
     assert_eq!(DebugUsingDisplay(out.trim()), DebugUsingDisplay(to),);
 }
 
 pub(crate) fn assert_pretty(from: &str, to: &str) {
-    let out = parse_then_emit(from, Config { minify: false }, Syntax::default());
+    let out = parse_then_emit(
+        from,
+        Config {
+            minify: false,
+            ..Default::default()
+        },
+        Syntax::default(),
+    );
 
     assert_eq!(DebugUsingDisplay(&out.trim()), DebugUsingDisplay(to),);
 }
@@ -188,7 +235,10 @@ fn empty_named_export_min() {
     test_from_to_custom_config(
         "export { }",
         "export{};",
-        Config { minify: true },
+        Config {
+            minify: true,
+            ..Default::default()
+        },
         Default::default(),
     );
 }
@@ -203,7 +253,10 @@ fn empty_named_export_from_min() {
     test_from_to_custom_config(
         "export { } from 'foo';",
         "export{}from'foo';",
-        Config { minify: true },
+        Config {
+            minify: true,
+            ..Default::default()
+        },
         Default::default(),
     );
 }
@@ -218,7 +271,10 @@ fn named_export_from_min() {
     test_from_to_custom_config(
         "export { bar } from 'foo';",
         "export{bar}from'foo';",
-        Config { minify: true },
+        Config {
+            minify: true,
+            ..Default::default()
+        },
         Default::default(),
     );
 }
@@ -241,7 +297,10 @@ fn export_namespace_from_min() {
     test_from_to_custom_config(
         "export * as Foo from 'foo';",
         "export*as Foo from'foo';",
-        Config { minify: true },
+        Config {
+            minify: true,
+            ..Default::default()
+        },
         Syntax::Es(EsConfig {
             export_namespace_from: true,
             ..EsConfig::default()
@@ -267,7 +326,10 @@ fn named_and_namespace_export_from_min() {
     test_from_to_custom_config(
         "export * as Foo, { bar } from 'foo';",
         "export*as Foo,{bar}from'foo';",
-        Config { minify: true },
+        Config {
+            minify: true,
+            ..Default::default()
+        },
         Syntax::Es(EsConfig {
             export_namespace_from: true,
             ..EsConfig::default()
@@ -423,6 +485,392 @@ fn issue_915_4() {
     test_identical(r#"`\\r\\n--${this.boundary}`;"#);
 }
 
+fn es5() -> Config {
+    Config {
+        target: JscTarget::Es5,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn target_string_escapes() {
+    let src = r"'\u2028\u{1F600}';";
+
+    test_from_to_custom_config(src, r"'\u2028\ud83d\ude00';", es5(), Default::default());
+    test_from_to_custom_config(
+        src,
+        "'\\u2028\u{1F600}';",
+        Config {
+            target: JscTarget::Es2015,
+            ..Default::default()
+        },
+        Default::default(),
+    );
+    test_from_to(src, "'\u{2028}\u{1F600}';");
+}
+
+#[test]
+fn target_unsupported_syntax() {
+    fn assert_err(src: &str, syntax: Syntax) {
+        let cfg = Config {
+            check_target: true,
+            ..es5()
+        };
+        let err = try_parse_then_emit(src, cfg, syntax).expect_err(src);
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput, "{}", src);
+        assert!(err.to_string().contains("for es5"), "{}", err);
+    }
+
+    assert_err("a ** b;", Default::default());
+    assert_err("const a = 1;", Default::default());
+    assert_err("a => a;", Default::default());
+    assert_err("class A {}", Default::default());
+    assert_err("`a`;", Default::default());
+    assert_err("try {} catch {}", Default::default());
+    assert_err("/a/u;", Default::default());
+    assert_err("/(?<a>b)/;", Default::default());
+    assert_err(
+        "a ?? b;",
+        Syntax::Es(EsConfig {
+            nullish_coalescing: true,
+            ..Default::default()
+        }),
+    );
+    assert_err(
+        "a?.b;",
+        Syntax::Es(EsConfig {
+            optional_chaining: true,
+            ..Default::default()
+        }),
+    );
+}
+
+#[test]
+fn target_unchecked_by_default() {
+    test_from_to_custom_config(
+        "const a = 1n ** b;
+import.meta;",
+        "const a = 1n ** b;
+import.meta;",
+        es5(),
+        Syntax::Es(EsConfig {
+            import_meta: true,
+            ..Default::default()
+        }),
+    );
+}
+
+/// Module syntax is not lowered for a target, but converted by module passes
+/// if configured.
+#[test]
+fn target_allows_module_syntax() {
+    test_from_to_custom_config(
+        "import a from 'a';\nexport default a;\nimport('b');",
+        "import a from 'a';\nexport default a;\nimport('b');",
+        Config {
+            check_target: true,
+            ..es5()
+        },
+        Syntax::Es(EsConfig {
+            dynamic_import: true,
+            ..Default::default()
+        }),
+    );
+}
+
+#[test]
+fn target_supported_syntax() {
+    test_from_to_custom_config(
+        "var a = function () {};\n/(?:a)\\(?<b/gim;",
+        "var a = function() {\n};\n/(?:a)\\(?<b/gim;",
+        es5(),
+        Default::default(),
+    );
+}
+
+#[test]
+fn indent_str() {
+    test_from_to_custom_config(
+        "if (a) {\n    b();\n}",
+        "if (a) {\n\tb();\n}",
+        Config {
+            indent: "\t",
+            ..Default::default()
+        },
+        Default::default(),
+    );
+}
+
+#[test]
+fn quote_style() {
+    let src = "'a';\n\"b\";\n'it\\'s';\n\"say \\\"hi\\\"\";";
+
+    test_from_to_custom_config(
+        src,
+        "'a';\n'b';\n\"it's\";\n'say \"hi\"';",
+        Config {
+            quote_style: QuoteStyle::Single,
+            ..Default::default()
+        },
+        Default::default(),
+    );
+    test_from_to_custom_config(
+        src,
+        "\"a\";\n\"b\";\n\"it's\";\n'say \"hi\"';",
+        Config {
+            quote_style: QuoteStyle::Double,
+            ..Default::default()
+        },
+        Default::default(),
+    );
+}
+
+#[test]
+fn ascii_only() {
+    let src = "'\u{e9}\u{1F600}';\ncaf\u{e9};";
+
+    test_from_to_custom_config(
+        src,
+        r"'\u00e9\u{1f600}';
+caf\u00e9;",
+        Config {
+            ascii_only: true,
+            ..Default::default()
+        },
+        Default::default(),
+    );
+    test_from_to_custom_config(
+        src,
+        r"'\u00e9\ud83d\ude00';
+caf\u00e9;",
+        Config {
+            ascii_only: true,
+            ..es5()
+        },
+        Default::default(),
+    );
+
+    test_from_to_custom_config(
+        "`caf\u{e9} ${a}`;\n/caf\\\u{e9}[\u{1F600}]/;",
+        r"`caf\u00e9 ${a}`;
+/caf\u00e9[\ud83d\ude00]/;",
+        Config {
+            ascii_only: true,
+            ..Default::default()
+        },
+        Default::default(),
+    );
+
+    let assert_err = |src: &str, cfg: Config| {
+        let err = try_parse_then_emit(src, cfg, Default::default()).expect_err(src);
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput, "{}", src);
+    };
+    assert_err(
+        "tag`caf\u{e9}`;",
+        Config {
+            ascii_only: true,
+            ..Default::default()
+        },
+    );
+    assert_err(
+        "\u{10480};",
+        Config {
+            ascii_only: true,
+            ..es5()
+        },
+    );
+    test_from_to_custom_config(
+        "\u{10480};",
+        r"\u{10480};",
+        Config {
+            ascii_only: true,
+            ..Default::default()
+        },
+        Default::default(),
+    );
+}
+
+#[test]
+fn semicolons_as_needed() {
+    test_from_to_custom_config(
+        "a = 1;
+(b || c)();
+let d = [1];
+[d] = e;
+if (a) ;
+for(;;);
+function f() {
+    return 1;
+}
+x = `t`;",
+        "a = 1
+;(b || c)()
+let d = [1]
+;[d] = e
+if (a) ;
+for(;;);
+function f() {
+    return 1
+}
+x = `t`",
+        Config {
+            semicolons: Semicolons::AsNeeded,
+            ..Default::default()
+        },
+        Default::default(),
+    );
+}
+
+#[test]
+fn max_line_len() {
+    let src = "foo(aaaaaaaaaa, bbbbbbbbbb, cccccccccc, dddddddddd);
+foo(a, b);
+var x = [1, 2];
+var y = [aaaaaaaaaa, bbbbbbbbbb, cccccccccc, dddddddddd];";
+
+    test_from_to_custom_config(
+        src,
+        "foo(
+    aaaaaaaaaa,
+    bbbbbbbbbb,
+    cccccccccc,
+    dddddddddd,
+);
+foo(a, b);
+var x = [1, 2];
+var y = [
+    aaaaaaaaaa,
+    bbbbbbbbbb,
+    cccccccccc,
+    dddddddddd,
+];",
+        Config {
+            trailing_commas: true,
+            max_line_len: Some(40),
+            ..Default::default()
+        },
+        Default::default(),
+    );
+
+    // Trailing commas in arguments require es2017.
+    test_from_to_custom_config(
+        "foo(aaaaaaaaaa, bbbbbbbbbb, cccccccccc, dddddddddd);",
+        "foo(
+    aaaaaaaaaa,
+    bbbbbbbbbb,
+    cccccccccc,
+    dddddddddd
+);",
+        Config {
+            trailing_commas: true,
+            max_line_len: Some(40),
+            ..es5()
+        },
+        Default::default(),
+    );
+}
+
+#[test]
+fn comment_in_empty_block() {
+    test_from_to(
+        "function foo() {
+    // TODO
+}",
+        "function foo() {
+    // TODO
+}",
+    );
+}
+
+#[test]
+fn comment_before_else() {
+    test_from_to(
+        "if (a) {
+    b();
+} // c
+else {
+    d();
+}",
+        "if (a) {
+    b();
+} // c
+else {
+    d();
+}",
+    );
+}
+
+#[test]
+fn block_comment_before_else() {
+    test_from_to(
+        "if (a) {
+    b();
+} /* c */ else {
+    d();
+}",
+        "if (a) {
+    b();
+} /* c */ else {
+    d();
+}",
+    );
+}
+
+#[test]
+fn comment_in_args() {
+    test_from_to(
+        "foo(/* webpackChunkName: \"x\" */ './x', a, /* b */);",
+        "foo(/* webpackChunkName: \"x\" */
+'./x', a /* b */);",
+    );
+}
+
+#[test]
+fn comment_before_semi() {
+    test_from_to(
+        "function f() {
+    var a = 1 /* one */;
+    return /* nothing */;
+}",
+        "function f() {
+    var a = 1; /* one */
+    return; /* nothing */
+}",
+    );
+}
+
+#[test]
+fn comment_in_jsx_expr_container() {
+    test_from_to_custom_config(
+        "<div>{/* c */}</div>;",
+        "<div>{ /* c */}</div>;",
+        Default::default(),
+        Syntax::Es(EsConfig {
+            jsx: true,
+            ..Default::default()
+        }),
+    );
+}
+
+#[test]
+fn comment_in_empty_enum() {
+    test_from_to_custom_config(
+        "enum E {
+    // nothing
+}",
+        "enum E {
+    // nothing
+}",
+        Default::default(),
+        Syntax::Typescript(Default::default()),
+    );
+}
+
+#[test]
+fn comment_leftover() {
+    test_from_to("foo(a /* c */ + b);", "foo(a + b);\n/* c */");
+}
+
 #[derive(Debug, Clone)]
 struct Buf(Arc<RwLock<Vec<u8>>>);
 impl Write for Buf {
@@ -35,6 +35,19 @@ pub trait WriteJs {
     fn write_symbol(&mut self, span: Span, s: &str) -> Result;
 
     fn write_punct(&mut self, s: &'static str) -> Result;
+
+    /// Applies the options of `cfg` which are handled by the writer, like the
+    /// indentation and the semicolon policy.
+    ///
+    /// [Emitter] calls this before emitting a program, a module or a script.
+    fn configure(&mut self, _cfg: &Config) {}
+
+    /// Number of bytes written to the current line.
+    ///
+    /// Writers which do not track lines return zero.
+    fn line_pos(&self) -> usize {
+        0
+    }
 }
 
 impl<W> WriteJs for Box<W>
@@ -93,4 +106,11 @@ where
     fn write_punct(&mut self, s: &'static str) -> Result {
         (**self).write_punct(s)
     }
+
+    fn configure(&mut self, cfg: &Config) {
+        (**self).configure(cfg)
+    }
+    fn line_pos(&self) -> usize {
+        (**self).line_pos()
+    }
 }
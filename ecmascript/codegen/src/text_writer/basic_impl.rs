@@ -1,4 +1,4 @@
-use super::{Result, WriteJs};
+use super::{Config, Result, Semicolons, WriteJs};
 use std::io::{self, Write};
 use swc_common::{sync::Lrc, BytePos, LineCol, SourceMap, Span};
 
@@ -12,6 +12,10 @@ pub struct JsWriter<'a, W: Write> {
     /// We may use this in future...
     _cm: Lrc<SourceMap>,
     indent: usize,
+    indent_str: &'static str,
+    semicolons: Semicolons,
+    /// A semicolon which was omitted and may be required by the next token.
+    pending_semi: bool,
     line_start: bool,
     line_count: usize,
     line_pos: usize,
@@ -31,6 +35,9 @@ impl<'a, W: Write> JsWriter<'a, W> {
         JsWriter {
             _cm: cm,
             indent: Default::default(),
+            indent_str: "    ",
+            semicolons: Default::default(),
+            pending_semi: false,
             line_start: true,
             line_count: 0,
             line_pos: Default::default(),
@@ -42,11 +49,11 @@ impl<'a, W: Write> JsWriter<'a, W> {
     }
 
     fn write_indent_string(&mut self) -> io::Result<usize> {
-        const INDENT: &[u8] = b"    ";
+        let indent = self.indent_str.as_bytes();
 
         let mut cnt = 0;
         for _ in 0..self.indent {
-            cnt += self.raw_write(INDENT)?;
+            cnt += self.raw_write(indent)?;
         }
 
         Ok(cnt)
@@ -63,6 +70,13 @@ impl<'a, W: Write> JsWriter<'a, W> {
         let mut cnt = 0;
 
         if !data.is_empty() {
+            if self.pending_semi {
+                self.pending_semi = false;
+                if self.requires_semi_before(data) {
+                    cnt += self.write(None, ";")?;
+                }
+            }
+
             if let Some(span) = span {
                 if !span.is_dummy() {
                     self.srcmap(span.lo())
@@ -85,6 +99,24 @@ impl<'a, W: Write> JsWriter<'a, W> {
         Ok(cnt)
     }
 
+    /// Returns true if the omitted semicolon must be written before `data`.
+    ///
+    /// Automatic semicolon insertion only happens at a line break, and only if
+    /// the next line can't continue the statement.
+    fn requires_semi_before(&self, data: &str) -> bool {
+        if data.starts_with('}') {
+            return false;
+        }
+        if !self.line_start {
+            return true;
+        }
+
+        match data.as_bytes()[0] {
+            b'(' | b'[' | b'`' | b'+' | b'-' | b'/' | b'*' | b'<' => true,
+            _ => false,
+        }
+    }
+
     fn srcmap(&mut self, byte_pos: BytePos) {
         if let Some(ref mut srcmap) = self.srcmap {
             srcmap.push((
@@ -109,7 +141,18 @@ impl<'a, W: Write> WriteJs for JsWriter<'a, W> {
     }
 
     fn write_semi(&mut self) -> Result {
-        self.write(None, ";")?;
+        match self.semicolons {
+            Semicolons::Always => {
+                self.write(None, ";")?;
+            }
+            Semicolons::AsNeeded => {
+                if self.pending_semi {
+                    self.pending_semi = false;
+                    self.write(None, ";")?;
+                }
+                self.pending_semi = true;
+            }
+        }
         Ok(())
     }
     fn write_space(&mut self) -> Result {
@@ -164,7 +207,11 @@ impl<'a, W: Write> WriteJs for JsWriter<'a, W> {
     }
 
     fn write_comment(&mut self, span: Span, s: &str) -> Result {
+        // Comments don't decide whether an omitted semicolon is required.
+        let pending_semi = self.pending_semi;
+        self.pending_semi = false;
         self.write(Some(span), s)?;
+        self.pending_semi = pending_semi;
         Ok(())
     }
 
@@ -187,6 +234,15 @@ impl<'a, W: Write> WriteJs for JsWriter<'a, W> {
         self.write(None, s)?;
         Ok(())
     }
+
+    fn configure(&mut self, cfg: &Config) {
+        self.indent_str = cfg.indent;
+        self.semicolons = cfg.semicolons;
+    }
+
+    fn line_pos(&self) -> usize {
+        self.line_pos
+    }
 }
 
 fn compute_line_starts(s: &str) -> Vec<usize> {
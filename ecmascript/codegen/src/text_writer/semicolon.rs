@@ -1,4 +1,4 @@
-use super::{Result, WriteJs};
+use super::{Config, Result, WriteJs};
 use swc_common::Span;
 
 pub fn omit_trailing_semi<W: WriteJs>(w: W) -> impl WriteJs {
@@ -54,6 +54,14 @@ impl<W: WriteJs> WriteJs for OmitTrailingSemi<W> {
     with_semi!(write_str(s: &str));
     with_semi!(write_symbol(span: Span, s: &str));
     with_semi!(write_punct(s: &'static str));
+
+    fn configure(&mut self, cfg: &Config) {
+        self.inner.configure(cfg)
+    }
+
+    fn line_pos(&self) -> usize {
+        self.inner.line_pos()
+    }
 }
 
 impl<W: WriteJs> OmitTrailingSemi<W> {
@@ -136,6 +136,13 @@ impl<'a> Emitter<'a> {
         punct!("{");
 
         self.emit_list(n.span, Some(&n.members), ListFormat::EnumMembers)?;
+        {
+            let lo = match n.members.last() {
+                Some(member) => member.span.hi(),
+                None => n.id.span.hi(),
+            };
+            self.emit_comments_before_closing_brace(lo, n.span.hi())?;
+        }
 
         punct!("}");
     }
@@ -270,6 +277,13 @@ impl<'a> Emitter<'a> {
         punct!("{");
 
         self.emit_list(n.span, Some(&n.body), ListFormat::InterfaceMembers)?;
+        {
+            let lo = match n.body.last() {
+                Some(member) => member.span().hi(),
+                None => n.span.lo(),
+            };
+            self.emit_comments_before_closing_brace(lo, n.span.hi())?;
+        }
 
         punct!("}");
     }
@@ -811,6 +825,13 @@ impl<'a> Emitter<'a> {
             Some(&n.members),
             ListFormat::MultiLineTypeLiteralMembers,
         )?;
+        {
+            let lo = match n.members.last() {
+                Some(member) => member.span().hi(),
+                None => n.span.lo(),
+            };
+            self.emit_comments_before_closing_brace(lo, n.span.hi())?;
+        }
         punct!("}");
     }
 
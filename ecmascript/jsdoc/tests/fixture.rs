@@ -160,6 +160,10 @@ impl Comments for SwcComments {
         self.leading.remove(&pos).map(|v| v.1)
     }
 
+    fn get_leading(&self, pos: BytePos) -> Option<Vec<Comment>> {
+        self.leading.get(&pos).map(|v| v.value().clone())
+    }
+
     fn add_trailing(&self, pos: BytePos, cmt: Comment) {
         self.trailing.entry(pos).or_default().push(cmt)
     }
@@ -183,4 +187,8 @@ impl Comments for SwcComments {
     fn take_trailing(&self, pos: BytePos) -> Option<Vec<Comment>> {
         self.trailing.remove(&pos).map(|v| v.1)
     }
+
+    fn get_trailing(&self, pos: BytePos) -> Option<Vec<Comment>> {
+        self.trailing.get(&pos).map(|v| v.value().clone())
+    }
 }
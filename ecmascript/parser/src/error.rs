@@ -1,14 +1,17 @@
 #![allow(dead_code)]
 
+pub use self::explain::explain;
 use self::SyntaxError::*;
 use crate::token::Token;
 use std::{borrow::Cow, fmt::Debug};
 use swc_atoms::JsWord;
 use swc_common::{
-    errors::{DiagnosticBuilder, Handler},
+    errors::{DiagnosticBuilder, DiagnosticId, Handler},
     Span, Spanned,
 };
 
+mod explain;
+
 /// Note: this struct is 8 bytes.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Error {
@@ -203,12 +206,155 @@ pub enum SyntaxError {
     TS2703,
 }
 
+impl SyntaxError {
+    /// Returns the stable diagnostic code of this error.
+    ///
+    /// Codes are never reused or reassigned, so tools can use them to link to
+    /// documentation or to suppress a specific error. Errors which mirror a
+    /// diagnostic of `tsc` use the code of typescript compiler.
+    ///
+    /// See [explain] for a long-form explanation of a code.
+    pub fn code(&self) -> &'static str {
+        match *self {
+            Eof => "E0001",
+            TopLevelAwait => "E0002",
+            LegacyDecimal => "E0003",
+            LegacyOctal => "E0004",
+            InvalidIdentChar => "E0005",
+            ExpectedDigit { .. } => "E0006",
+            RestPatInSetter => "E0007",
+            UnterminatedBlockComment => "E0008",
+            UnterminatedStrLit => "E0009",
+            ExpectedUnicodeEscape => "E0010",
+            EscapeInReservedWord { .. } => "E0011",
+            UnterminatedRegxp => "E0012",
+            UnterminatedTpl => "E0013",
+            IdentAfterNum => "E0014",
+            UnexpectedChar { .. } => "E0015",
+            InvalidStrEscape => "E0016",
+            InvalidUnicodeEscape => "E0017",
+            InvalidCodePoint => "E0018",
+            ExpectedHexChars { .. } => "E0019",
+            NumLitTerminatedWithExp => "E0020",
+            LegacyCommentInModule => "E0021",
+            InvalidIdentInStrict => "E0022",
+            EvalAndArgumentsInStrict => "E0023",
+            UnaryInExp { .. } => "E0024",
+            Hash => "E0025",
+            LineBreakInThrow => "E0026",
+            LineBreakBeforeArrow => "E0027",
+            Unexpected { .. } => "E0028",
+            ReservedWordInImport => "E0029",
+            AssignProperty => "E0030",
+            Expected(..) => "E0031",
+            ExpectedSemiForExprStmt { .. } => "E0032",
+            AwaitStar => "E0033",
+            ReservedWordInObjShorthandOrPat => "E0034",
+            NullishCoalescingWithLogicalOp => "E0035",
+            NullishCoalescingNotEnabled => "E0036",
+            MultipleDefault { .. } => "E0037",
+            CommaAfterRestElement => "E0038",
+            NonLastRestParam => "E0039",
+            SpreadInParenExpr => "E0040",
+            EmptyParenExpr => "E0041",
+            InvalidPat => "E0042",
+            InvalidExpr => "E0043",
+            NotSimpleAssign => "E0044",
+            ExpectedIdent => "E0045",
+            ExpctedSemi => "E0046",
+            DuplicateLabel(..) => "E0047",
+            AsyncGenerator => "E0048",
+            NonTopLevelImportExport => "E0049",
+            ImportExportInScript => "E0050",
+            PatVarWithoutInit => "E0051",
+            WithInStrict => "E0052",
+            ReturnNotAllowed => "E0053",
+            TooManyVarInForInHead => "E0054",
+            VarInitializerInForInHead => "E0055",
+            LabelledGenerator => "E0056",
+            YieldParamInGen => "E0057",
+            AwaitForStmt => "E0058",
+            UnterminatedJSXContents => "E0059",
+            EmptyJSXAttr => "E0060",
+            InvalidJSXValue => "E0061",
+            JSXExpectedClosingTagForLtGt => "E0062",
+            JSXExpectedClosingTag { .. } => "E0063",
+            InvalidLeadingDecorator => "E0064",
+            DecoratorOnExport => "E0065",
+            TsRequiredAfterOptional => "E0066",
+            TsInvalidParamPropPat => "E0067",
+            SpaceBetweenHashAndIdent => "E0068",
+            AsyncConstructor => "E0069",
+            PropertyNamedConstructor => "E0070",
+            DeclarePrivateIdentifier => "E0071",
+            ClassProperty => "E0072",
+            ReadOnlyMethod => "E0073",
+            TsBindingPatCannotBeOptional => "E0074",
+            TrailingCommaInsideImport => "E0075",
+            DynamicImport => "E0076",
+            ExportDefaultWithOutFrom => "E0077",
+            ExportNamespaceFrom => "E0078",
+            DotsWithoutIdentifier => "E0079",
+            NumericSeparatorIsAllowedOnlyBetweenTwoDigits => "E0080",
+            TS1003 => "TS1003",
+            TS1005 => "TS1005",
+            TS1009 => "TS1009",
+            TS1014 => "TS1014",
+            TS1015 => "TS1015",
+            TS1031 => "TS1031",
+            TS1038 => "TS1038",
+            TS1042 => "TS1042",
+            TS1047 => "TS1047",
+            TS1048 => "TS1048",
+            TS1056 => "TS1056",
+            TS1085 => "TS1085",
+            TS1089 => "TS1089",
+            TS1092 => "TS1092",
+            TS1096 => "TS1096",
+            TS1098 => "TS1098",
+            TS1100 => "TS1100",
+            TS1102 => "TS1102",
+            TS1105 => "TS1105",
+            TS1107 => "TS1107",
+            TS1109 => "TS1109",
+            TS1110 => "TS1110",
+            TS1114 => "TS1114",
+            TS1115 => "TS1115",
+            TS1116 => "TS1116",
+            TS1123 => "TS1123",
+            TS1141 => "TS1141",
+            TS1162 => "TS1162",
+            TS1164 => "TS1164",
+            TS1171 => "TS1171",
+            TS1172 => "TS1172",
+            TS1174 => "TS1174",
+            TS1175 => "TS1175",
+            TS1183 => "TS1183",
+            TS1093 => "TS1093",
+            TS1094 => "TS1094",
+            TS1196 => "TS1196",
+            TS1242 => "TS1242",
+            TS2369 => "TS2369",
+            TS2371 => "TS2371",
+            TS2406 => "TS2406",
+            TS2410 => "TS2410",
+            TS2414 => "TS2414",
+            TS2427 => "TS2427",
+            TS2452 => "TS2452",
+            TS2483 => "TS2483",
+            TS2491 => "TS2491",
+            TS2703 => "TS2703",
+        }
+    }
+}
+
 impl Error {
     #[cold]
     pub fn into_diagnostic(self, handler: &Handler) -> DiagnosticBuilder {
         let span = self.span();
 
         let kind = self.kind();
+        let code = DiagnosticId::Error(kind.code().into());
         let msg: Cow<'static, _> = match kind {
             TopLevelAwait => "top level await requires target to es2017 or higher and \
                               topLevelAwait:true for ecmascript"
@@ -369,8 +515,7 @@ impl Error {
             _ => format!("{:?}", kind).into(),
         };
 
-        let mut db = handler.struct_err(&msg);
-        db.set_span(span);
+        let mut db = handler.struct_span_err_with_code(span, &msg, code);
 
         match kind {
             ExpectedSemiForExprStmt { expr } => {
@@ -393,3 +538,26 @@ impl Error {
 fn size_of_error() {
     assert_eq!(std::mem::size_of::<Error>(), 8);
 }
+
+#[test]
+fn every_code_is_explained() {
+    // `SyntaxError::code` matches exhaustively, so its string literals are the
+    // codes of all error kinds.
+    let src = include_str!("error.rs");
+    let start = src.find("pub fn code(&self)").unwrap();
+    let end = start + src[start..].find("\n    }\n").unwrap();
+    let codes = src[start..end]
+        .split('"')
+        .skip(1)
+        .step_by(2)
+        .collect::<Vec<_>>();
+
+    assert!(codes.iter().any(|code| code.starts_with("E")));
+    assert!(codes.iter().any(|code| code.starts_with("TS")));
+
+    let mut seen = std::collections::HashSet::new();
+    for code in codes {
+        assert!(seen.insert(code), "{} is used twice", code);
+        assert!(explain(code).is_some(), "{} is not explained", code);
+    }
+}
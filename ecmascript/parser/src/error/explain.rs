@@ -0,0 +1,910 @@
+//! Long-form explanations of diagnostic codes.
+//!
+//! Each entry describes when the error is emitted and contains an example
+//! which triggers it.
+
+/// Returns a long-form explanation of the diagnostic code, with examples.
+///
+/// Returns `None` if `code` is not a known diagnostic code.
+///
+/// # Example
+///
+/// ```
+/// use swc_ecma_parser::error::{explain, SyntaxError};
+///
+/// assert!(explain(SyntaxError::Eof.code()).is_some());
+/// assert!(explain("E9999").is_none());
+/// ```
+pub fn explain(code: &str) -> Option<&'static str> {
+    Some(match code {
+        "E0001" => {
+            r#"The input ended while the parser expected more tokens.
+
+Erroneous code example:
+
+    function foo() {
+
+Close every block, parenthesis and bracket before the end of the file."#
+        }
+        "E0002" => {
+            r#"`await` was used at the top level of a module, but top level await is not enabled.
+
+Erroneous code example:
+
+    const data = await fetch(url);
+
+Set `jsc.target` to `es2017` or higher and `jsc.parser.topLevelAwait` to
+`true`, or move the expression into an async function."#
+        }
+        "E0003" => {
+            r#"A decimal literal with a leading zero was used in strict mode.
+
+Erroneous code example:
+
+    'use strict';
+    var a = 08;
+
+Remove the leading zero."#
+        }
+        "E0004" => {
+            r#"A legacy octal literal or octal escape was used in strict mode.
+
+Erroneous code example:
+
+    'use strict';
+    var a = 010;
+    var b = '\01';
+
+Use `0o10` for octal literals and `\x01` or `\u0001` for escapes."#
+        }
+        "E0005" => {
+            r#"An identifier contains a character which is not allowed in identifiers.
+
+Erroneous code example:
+
+    var a b = 1;
+
+Identifiers may only contain characters with the `ID_Continue` property, `$`,
+ZWNJ and ZWJ."#
+        }
+        "E0006" => {
+            r#"A number literal is missing a digit after its prefix.
+
+Erroneous code example:
+
+    var a = 0x;
+    var b = 0b2;
+
+Add at least one digit valid for the radix of the literal."#
+        }
+        "E0007" => {
+            r#"A setter has a rest parameter.
+
+Erroneous code example:
+
+    var o = { set a(...v) {} };
+
+Setters take exactly one parameter, which cannot be a rest element."#
+        }
+        "E0008" => {
+            r#"A block comment is never closed.
+
+Erroneous code example:
+
+    /* this comment never ends
+
+Add `*/` at the end of the comment."#
+        }
+        "E0009" => {
+            r#"A string literal is never closed.
+
+Erroneous code example:
+
+    var a = 'foo;
+
+String literals cannot span multiple lines. Add the closing quote, or use a
+template literal for multi-line strings."#
+        }
+        "E0010" => {
+            r#"A backslash in an identifier is not followed by a unicode escape.
+
+Erroneous code example:
+
+    var \x61 = 1;
+
+Only `\uXXXX` and `\u{X...}` escapes are allowed in identifiers."#
+        }
+        "E0011" => {
+            r#"A reserved word is written using unicode escapes.
+
+Erroneous code example:
+
+    var if = 1; // `if`
+
+Keywords cannot contain escape sequences. Rename the binding."#
+        }
+        "E0012" => {
+            r#"A regular expression literal is never closed.
+
+Erroneous code example:
+
+    var re = /foo;
+
+Add the closing `/` before the end of the line."#
+        }
+        "E0013" => {
+            r#"A template literal is never closed.
+
+Erroneous code example:
+
+    var s = `foo;
+
+Add the closing backtick."#
+        }
+        "E0014" => {
+            r#"An identifier starts immediately after a number literal.
+
+Erroneous code example:
+
+    var a = 3in [];
+
+Separate the number and the identifier with whitespace."#
+        }
+        "E0015" => {
+            r#"The lexer found a character which cannot start any token.
+
+Erroneous code example:
+
+    var a = 1 @ 2;
+
+Remove the character, or put it in a string or a comment."#
+        }
+        "E0016" => {
+            r#"A string contains an invalid escape sequence.
+
+Erroneous code example:
+
+    var a = '\x1';
+
+Hexadecimal escapes require exactly two hex digits."#
+        }
+        "E0017" => {
+            r#"A unicode escape sequence is malformed.
+
+Erroneous code example:
+
+    var a = '\u{}';
+
+Use `\uXXXX` with four hex digits or `\u{X...}` with at least one hex digit."#
+        }
+        "E0018" => {
+            r#"A unicode escape refers to a code point larger than `0x10FFFF`.
+
+Erroneous code example:
+
+    var a = '\u{110000}';
+
+Code points must be in the range `0` to `0x10FFFF`."#
+        }
+        "E0019" => {
+            r#"An escape sequence does not contain the expected number of hex digits.
+
+Erroneous code example:
+
+    var a = '\u00';
+
+`\x` requires two hex digits and `\u` requires four."#
+        }
+        "E0020" => {
+            r#"A number literal ends with an exponent marker without digits.
+
+Erroneous code example:
+
+    var a = 1e;
+
+Add digits after `e`, optionally preceded by `+` or `-`."#
+        }
+        "E0021" => {
+            r#"An HTML-like comment (`<!--` or `-->`) was used in module code.
+
+Erroneous code example:
+
+    <!-- comment
+    export default 1;
+
+HTML-like comments are only allowed in scripts. Use `//` instead."#
+        }
+        "E0022" => {
+            r#"A reserved word of strict mode was used as an identifier.
+
+Erroneous code example:
+
+    'use strict';
+    var let = 1;
+
+`implements`, `interface`, `let`, `package`, `private`, `protected`, `public`,
+`static` and `yield` cannot be used as identifiers in strict mode code. Rename
+the binding."#
+        }
+        "E0023" => {
+            r#"`eval` or `arguments` was used as a binding identifier in strict mode.
+
+Erroneous code example:
+
+    'use strict';
+    function eval() {}
+
+Rename the binding."#
+        }
+        "E0024" => {
+            r#"The left operand of `**` is a unary expression.
+
+Erroneous code example:
+
+    var a = -2 ** 2;
+
+The result would be ambiguous. Wrap the unary expression in parentheses, like
+`(-2) ** 2`."#
+        }
+        "E0025" => {
+            r#"A `#` was found where a private name is not allowed.
+
+Erroneous code example:
+
+    var a = #foo;
+
+Private names can only be used as class members or in member expressions like
+`this.#foo`."#
+        }
+        "E0026" => {
+            r#"A line break follows `throw`.
+
+Erroneous code example:
+
+    throw
+    new Error();
+
+Automatic semicolon insertion would make this `throw;`, which is invalid. Put
+the expression on the same line as `throw`."#
+        }
+        "E0027" => {
+            r#"A line break was found between the parameters of an arrow function and `=>`.
+
+Erroneous code example:
+
+    var f = (a, b)
+        => a + b;
+
+Move `=>` to the line of the parameters."#
+        }
+        "E0028" => {
+            r#"The parser found a token which is not valid at this position.
+
+Erroneous code example:
+
+    var a = (1 2);
+
+Check the code around the token for a missing operator or punctuator."#
+        }
+        "E0029" => {
+            r#"A reserved word was used as a local binding of an import.
+
+Erroneous code example:
+
+    import { default } from 'foo';
+
+Rename the binding, like `import { default as foo } from 'foo'`."#
+        }
+        "E0030" => {
+            r#"An object literal contains an assignment property.
+
+Erroneous code example:
+
+    var o = { a = 1 };
+
+`{ a = 1 }` is only valid as a destructuring pattern. Use `{ a: 1 }` for object
+literals."#
+        }
+        "E0031" => {
+            r#"The parser expected a specific token but found another one.
+
+Erroneous code example:
+
+    if (a { }
+
+Insert the expected token."#
+        }
+        "E0032" => {
+            r#"An expression statement is not terminated.
+
+Erroneous code example:
+
+    a b
+
+Expression statements must be followed by `;`, `}`, the end of input or a line
+break."#
+        }
+        "E0033" => {
+            r#"`await*` was used.
+
+Erroneous code example:
+
+    async function f() { await* [a, b]; }
+
+`await*` has been removed from the async functions proposal. Use
+`await Promise.all([a, b])` instead."#
+        }
+        "E0034" => {
+            r#"A reserved word was used as a shorthand property.
+
+Erroneous code example:
+
+    var { if } = obj;
+
+Use the long form, like `{ if: value }`."#
+        }
+        "E0035" => {
+            r#"The nullish coalescing operator is mixed with `&&` or `||` without parentheses.
+
+Erroneous code example:
+
+    var a = b || c ?? d;
+
+Add parentheses to make the precedence explicit, like `(b || c) ?? d`."#
+        }
+        "E0036" => {
+            r#"The nullish coalescing operator is used while it is disabled.
+
+Erroneous code example:
+
+    var a = b ?? c;
+
+Set `jsc.parser.nullishCoalescing` to `true`."#
+        }
+        "E0037" => {
+            r#"A switch statement has multiple `default` clauses.
+
+Erroneous code example:
+
+    switch (a) {
+        default:
+        default:
+    }
+
+Remove one of the `default` clauses."#
+        }
+        "E0038" => {
+            r#"A rest element is followed by a trailing comma.
+
+Erroneous code example:
+
+    var [...a,] = b;
+
+Remove the trailing comma."#
+        }
+        "E0039" => {
+            r#"A rest element or rest parameter is not the last element.
+
+Erroneous code example:
+
+    function f(...a, b) {}
+
+Move the rest element to the end of the list."#
+        }
+        "E0040" => {
+            r#"A parenthesized expression contains a spread element.
+
+Erroneous code example:
+
+    var a = (...b);
+
+Spread elements are only allowed in arrays, calls and arrow function
+parameters."#
+        }
+        "E0041" => {
+            r#"A parenthesized expression is empty.
+
+Erroneous code example:
+
+    var a = ();
+
+`()` is only valid as the parameter list of an arrow function."#
+        }
+        "E0042" => {
+            r#"An expression was used where a pattern is required.
+
+Erroneous code example:
+
+    [a + b] = c;
+
+Only identifiers, member expressions and destructuring patterns can be assigned
+to."#
+        }
+        "E0043" => {
+            r#"A pattern was used where an expression is required.
+
+Erroneous code example:
+
+    var a = { b = 1 };
+
+Remove the default value, or use the code as a destructuring pattern."#
+        }
+        "E0044" => {
+            r#"The target of an assignment or an update expression is not assignable.
+
+Erroneous code example:
+
+    a() = 1;
+    1++;
+
+Only identifiers and member expressions can be assigned to."#
+        }
+        "E0045" => {
+            r#"The parser expected an identifier.
+
+Erroneous code example:
+
+    var 1 = 2;
+
+Use a valid identifier."#
+        }
+        "E0046" => {
+            r#"A statement is not terminated.
+
+Erroneous code example:
+
+    var a = 1 var b = 2;
+
+Add `;` or a line break between the statements."#
+        }
+        "E0047" => {
+            r#"A label is declared while a label with the same name is in scope.
+
+Erroneous code example:
+
+    a: a: for (;;) {}
+
+Rename one of the labels."#
+        }
+        "E0048" => {
+            r#"An async function is a generator while async generators are not supported.
+
+Erroneous code example:
+
+    async function* f() {}
+
+Enable a syntax which supports async generators, or remove `*`."#
+        }
+        "E0049" => {
+            r#"An import or export declaration is not at the top level of a module.
+
+Erroneous code example:
+
+    if (a) {
+        import 'foo';
+    }
+
+Move the declaration to the top level, or use dynamic `import()`."#
+        }
+        "E0050" => {
+            r#"An import or export declaration was used in a script.
+
+Erroneous code example:
+
+    // parsed with `parse_script`
+    import 'foo';
+
+Parse the file as a module."#
+        }
+        "E0051" => {
+            r#"A destructuring binding has no initializer.
+
+Erroneous code example:
+
+    var { a };
+
+Add an initializer, like `var { a } = obj;`."#
+        }
+        "E0052" => {
+            r#"A `with` statement was used in strict mode.
+
+Erroneous code example:
+
+    'use strict';
+    with (obj) {}
+
+`with` is not allowed in strict mode code, including modules and classes."#
+        }
+        "E0053" => {
+            r#"A `return` statement was used outside of a function.
+
+Erroneous code example:
+
+    return 1;
+
+`return` is only allowed in function bodies."#
+        }
+        "E0054" => {
+            r#"The head of a `for-in` or `for-of` statement declares more than one variable.
+
+Erroneous code example:
+
+    for (var a, b of c) {}
+
+Declare exactly one variable."#
+        }
+        "E0055" => {
+            r#"The variable in the head of a `for-in` or `for-of` statement has an initializer.
+
+Erroneous code example:
+
+    for (let a = 1 of b) {}
+
+Remove the initializer."#
+        }
+        "E0056" => {
+            r#"A generator declaration is labelled.
+
+Erroneous code example:
+
+    label: function* g() {}
+
+Remove the label."#
+        }
+        "E0057" => {
+            r#"`yield` was used as a parameter name of a generator.
+
+Erroneous code example:
+
+    function* g(yield) {}
+
+Rename the parameter."#
+        }
+        "E0058" => {
+            r#"`for await` was used with a statement other than `for-of`.
+
+Erroneous code example:
+
+    async function f() { for await (var i = 0; i < 1; i++) {} }
+
+`for await` is only valid as `for await (... of ...)`."#
+        }
+        "E0059" => {
+            r#"The contents of a JSX element are never closed.
+
+Erroneous code example:
+
+    var a = <div>foo
+
+Add the closing tag."#
+        }
+        "E0060" => {
+            r#"A JSX attribute is assigned an empty expression.
+
+Erroneous code example:
+
+    var a = <div foo={} />;
+
+Assign a non-empty expression, or remove the braces."#
+        }
+        "E0061" => {
+            r#"The value of a JSX attribute is neither an expression container nor a string.
+
+Erroneous code example:
+
+    var a = <div foo=bar />;
+
+Use `foo="bar"` or `foo={bar}`."#
+        }
+        "E0062" => {
+            r#"A JSX fragment is closed with a named closing tag.
+
+Erroneous code example:
+
+    var a = <>foo</div>;
+
+Close the fragment with `</>`."#
+        }
+        "E0063" => {
+            r#"A JSX element is closed with a closing tag of a different name.
+
+Erroneous code example:
+
+    var a = <div>foo</span>;
+
+Make the closing tag match the opening tag."#
+        }
+        "E0064" => {
+            r#"Decorators are attached to something other than a class declaration.
+
+Erroneous code example:
+
+    @dec
+    function f() {}
+
+Decorators can only be applied to classes and class members."#
+        }
+        "E0065" => {
+            r#"The `export` keyword is placed between a decorator and a class.
+
+Erroneous code example:
+
+    @dec
+    export class Foo {}
+
+Use `export @dec class Foo {}`, or set `jsc.parser.decoratorsBeforeExport` to
+`true`."#
+        }
+        "E0066" => {
+            r#"A required element of a tuple type follows an optional element.
+
+Erroneous code example:
+
+    let x: [string?, number];
+
+Make the following elements optional, or reorder the elements."#
+        }
+        "E0067" => {
+            r#"A parameter property uses a destructuring pattern.
+
+Erroneous code example:
+
+    class Foo {
+        constructor(private { a }) {}
+    }
+
+Parameter properties must be identifiers or assignment patterns."#
+        }
+        "E0068" => {
+            r#"There is whitespace between `#` and the name of a private member.
+
+Erroneous code example:
+
+    class Foo { # a = 1 }
+
+Remove the whitespace, like `#a`."#
+        }
+        "E0069" => {
+            r#"A class constructor is declared as `async`.
+
+Erroneous code example:
+
+    class Foo { async constructor() {} }
+
+Constructors cannot be async. Use an async factory method instead."#
+        }
+        "E0070" => {
+            r#"A class has a non-static field named `constructor`.
+
+Erroneous code example:
+
+    class Foo { constructor = 1 }
+
+Rename the field."#
+        }
+        "E0071" => {
+            r#"The `declare` modifier is used with a private identifier.
+
+Erroneous code example:
+
+    class Foo { declare #a: string }
+
+Remove `declare`, or use a non-private name."#
+        }
+        "E0072" => {
+            r#"A class property was used while class properties are disabled.
+
+Erroneous code example:
+
+    class Foo { a = 1 }
+
+Set `jsc.parser.classProperty` to `true`."#
+        }
+        "E0073" => {
+            r#"A method is marked as `readonly`.
+
+Erroneous code example:
+
+    class Foo { readonly foo() {} }
+
+`readonly` can only be applied to properties and index signatures."#
+        }
+        "E0074" => {
+            r#"A destructuring parameter is marked as optional in an implementation.
+
+Erroneous code example:
+
+    function f({ a }?) {}
+
+Only parameters of signatures without bodies may use `?` with a binding
+pattern. Use a default value instead."#
+        }
+        "E0075" => {
+            r#"The arguments of `import(...)` end with a trailing comma.
+
+Erroneous code example:
+
+    import('foo',);
+
+Remove the trailing comma."#
+        }
+        "E0076" => {
+            r#"`import(...)` was used while dynamic import is disabled.
+
+Erroneous code example:
+
+    import('foo');
+
+Set `jsc.parser.dynamicImport` to `true`."#
+        }
+        "E0077" => {
+            r#"`export default from` is not followed by `from`.
+
+Erroneous code example:
+
+    export v;
+
+Add the source module, like `export v from 'mod';`."#
+        }
+        "E0078" => {
+            r#"`export * as ns from` was used while it is disabled.
+
+Erroneous code example:
+
+    export * as ns from 'foo';
+
+Set `jsc.parser.exportNamespaceFrom` to `true`."#
+        }
+        "E0079" => {
+            r#"`...` is not followed by an identifier in a declaration context.
+
+Erroneous code example:
+
+    function f(...) {}
+
+Add the name of the rest parameter."#
+        }
+        "E0080" => {
+            r#"A numeric separator is not placed between two digits.
+
+Erroneous code example:
+
+    var a = 1__000;
+    var b = 1_;
+    var c = 0_1;
+
+Numeric separators may only appear between two digits."#
+        }
+
+        "TS1003" => "Identifier expected.",
+        "TS1005" => "A specific token was expected, like '=' or ','.",
+        "TS1009" => {
+            r#"Trailing comma not allowed.
+
+Erroneous code example:
+
+    var a, ;"#
+        }
+        "TS1014" => "A rest parameter must be last in a parameter list.",
+        "TS1015" => {
+            r#"Parameter cannot have question mark and initializer.
+
+Erroneous code example:
+
+    function f(a?: number = 1) {}"#
+        }
+        "TS1031" => "A modifier cannot appear on a class element.",
+        "TS1038" => "A 'declare' modifier cannot be used in an already ambient context.",
+        "TS1042" => "A modifier cannot be used here.",
+        "TS1047" => {
+            r#"A rest parameter cannot be optional.
+
+Erroneous code example:
+
+    function f(...a?: number[]) {}"#
+        }
+        "TS1048" => {
+            r#"A rest parameter cannot have an initializer.
+
+Erroneous code example:
+
+    function f(...a = []) {}"#
+        }
+        "TS1056" => {
+            r#"Accessors are only available when targeting ECMAScript 5 and higher.
+
+Set `jsc.target` to `es5` or higher."#
+        }
+        "TS1085" => "Octal literals are not available when targeting ECMAScript 5 and higher.",
+        "TS1089" => "A modifier cannot appear on a constructor declaration.",
+        "TS1092" => "Type parameters cannot appear on a constructor declaration.",
+        "TS1093" => "Type annotation cannot appear on a constructor declaration.",
+        "TS1094" => {
+            r#"An accessor cannot have type parameters.
+
+Erroneous code example:
+
+    class Foo { get a<T>() {} }"#
+        }
+        "TS1096" => "An index signature must have exactly one parameter.",
+        "TS1098" => {
+            r#"Type parameter list cannot be empty.
+
+Erroneous code example:
+
+    function f<>() {}"#
+        }
+        "TS1100" => "Invalid use of 'eval' or 'arguments' in strict mode.",
+        "TS1102" => "'delete' cannot be called on an identifier in strict mode.",
+        "TS1105" => {
+            "A 'break' statement can only be used within an enclosing iteration or switch \
+             statement."
+        }
+        "TS1107" => "Jump target cannot cross function boundary.",
+        "TS1109" => {
+            r#"Expression expected.
+
+Erroneous code example:
+
+    var a = ;"#
+        }
+        "TS1110" => {
+            r#"Type expected.
+
+Erroneous code example:
+
+    let a: = 1;"#
+        }
+        "TS1114" => "Duplicate label.",
+        "TS1115" => {
+            "A 'continue' statement can only jump to a label of an enclosing iteration statement."
+        }
+        "TS1116" => "A 'break' statement can only jump to a label of an enclosing statement.",
+        "TS1123" => "Variable declaration list cannot be empty.",
+        "TS1141" => {
+            r#"String literal expected.
+
+Erroneous code example:
+
+    type A = import(1);"#
+        }
+        "TS1162" => "An object member cannot be declared optional.",
+        "TS1164" => "Computed property names are not allowed in enums.",
+        "TS1171" => "A comma expression is not allowed in a computed property name.",
+        "TS1172" => "'extends' clause already seen.",
+        "TS1174" => "Classes can only extend a single class.",
+        "TS1175" => "'implements' clause already seen.",
+        "TS1183" => "An implementation cannot be declared in ambient contexts.",
+        "TS1196" => "Catch clause variable cannot have a type annotation.",
+        "TS1242" => {
+            "'abstract' modifier can only appear on a class, method, or property declaration."
+        }
+        "TS2369" => "A parameter property is only allowed in a constructor implementation.",
+        "TS2371" => {
+            "A parameter initializer is only allowed in a function or constructor implementation."
+        }
+        "TS2406" => {
+            r#"The left-hand side of a 'for...in' statement must be a variable or a property access.
+
+Erroneous code example:
+
+    for (a() in b) {}"#
+        }
+        "TS2410" => "The 'with' statement is not supported.",
+        "TS2414" => "Class name cannot be a reserved type name.",
+        "TS2427" => "Interface name cannot be a reserved type name.",
+        "TS2452" => "An enum member cannot have a numeric name.",
+        "TS2483" => "The left-hand side of a 'for...of' statement cannot use a type annotation.",
+        "TS2491" => {
+            "The left-hand side of a 'for...in' statement cannot be a destructuring pattern."
+        }
+        "TS2703" => {
+            r#"The operand of a delete operator must be a property reference.
+
+Erroneous code example:
+
+    delete a;"#
+        }
+        _ => return None,
+    })
+}
@@ -0,0 +1,498 @@
+//! Incremental reparsing.
+//!
+//! When a file is edited, most of its top-level items are not affected by the
+//! edit. [reparse_module] reuses those items from the previous [Module] and
+//! only reparses the smallest range of top-level items enclosing the edit, or
+//! only the body of a top-level function if the edit is inside of it.
+//!
+//! Spans of reused nodes are shifted to point the new source file, so the
+//! result is identical to the result of parsing the new file from scratch.
+//!
+//! Comments are not collected while reparsing.
+
+use crate::{error::Error, lexer::Lexer, JscTarget, PResult, Parser, StringInput, Syntax};
+use std::ops::Range;
+use swc_common::{BytePos, SourceFile, Span, Spanned};
+use swc_ecma_ast::*;
+use swc_ecma_visit::{Fold, FoldWith};
+
+/// A replacement of a range of text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit {
+    /// Byte range in the old file, relative to the start of the file.
+    pub range: Range<usize>,
+    /// Replacement of the range.
+    pub text: String,
+}
+
+#[derive(Debug)]
+pub struct ReparsedModule {
+    pub module: Module,
+    /// Recoverable errors of the reparsed code.
+    pub errors: Vec<Error>,
+    /// Number of top-level items reused from the old module.
+    ///
+    /// Items containing a reparsed function body are not counted.
+    pub reused: usize,
+}
+
+/// Reparses `new_fm`, which is `old_fm` with `edit` applied.
+///
+/// `old` should be the result of `Parser::parse_module` for `old_fm`, parsed
+/// with same `syntax` and `target`.
+///
+/// This falls back to parsing the whole file if the edit can't be handled
+/// incrementally, so the result is always identical to the result of
+/// `Parser::parse_module` for `new_fm`.
+pub fn reparse_module(
+    syntax: Syntax,
+    target: JscTarget,
+    old: &Module,
+    old_fm: &SourceFile,
+    new_fm: &SourceFile,
+    edit: &TextEdit,
+) -> PResult<ReparsedModule> {
+    debug_assert_eq!(
+        new_fm.src.len(),
+        old_fm.src.len() - edit.range.len() + edit.text.len(),
+        "reparse_module: `new_fm` should be `old_fm` with `edit` applied"
+    );
+
+    let shift = Shift {
+        edit_lo: old_fm.start_pos + BytePos(edit.range.start as _),
+        edit_hi: old_fm.start_pos + BytePos(edit.range.end as _),
+        delta: edit.text.len() as i64 - edit.range.len() as i64,
+        file_delta: new_fm.start_pos.0 as i64 - old_fm.start_pos.0 as i64,
+    };
+    let r = Reparser {
+        syntax,
+        target,
+        old_fm,
+        new_fm,
+        shift,
+    };
+
+    if let Some(m) = r.reparse_fn_body(old) {
+        return Ok(m);
+    }
+    if let Some(m) = r.reparse_items(old) {
+        return Ok(m);
+    }
+
+    r.parse(new_fm.start_pos, new_fm.end_pos)
+        .map(|(module, errors)| ReparsedModule {
+            module,
+            errors,
+            reused: 0,
+        })
+}
+
+/// Maps a position in the old file to the new file.
+#[derive(Debug, Clone, Copy)]
+struct Shift {
+    edit_lo: BytePos,
+    edit_hi: BytePos,
+    /// Difference of the length of the file.
+    delta: i64,
+    /// Difference of start positions of files.
+    file_delta: i64,
+}
+
+impl Shift {
+    /// `is_hi` should be true if `pos` is the end of a span, so it's not
+    /// affected by code inserted at `pos`.
+    fn pos(self, pos: BytePos, is_hi: bool) -> BytePos {
+        let delta = if pos > self.edit_hi || (pos == self.edit_hi && !is_hi) {
+            self.file_delta + self.delta
+        } else {
+            self.file_delta
+        };
+
+        BytePos((pos.0 as i64 + delta) as u32)
+    }
+}
+
+impl Fold for Shift {
+    fn fold_span(&mut self, span: Span) -> Span {
+        if span.is_dummy() {
+            return span;
+        }
+
+        Span::new(
+            self.pos(span.lo(), false),
+            self.pos(span.hi(), true),
+            span.ctxt(),
+        )
+    }
+}
+
+struct Reparser<'a> {
+    syntax: Syntax,
+    target: JscTarget,
+    old_fm: &'a SourceFile,
+    new_fm: &'a SourceFile,
+    shift: Shift,
+}
+
+impl Reparser<'_> {
+    /// Parses `lo..hi` of the new file as a module.
+    fn parse(&self, lo: BytePos, hi: BytePos) -> PResult<(Module, Vec<Error>)> {
+        let src = self.new_src(lo, hi);
+        let lexer = Lexer::new(
+            self.syntax,
+            self.target,
+            StringInput::new(src, lo, hi),
+            None,
+        );
+        let mut parser = Parser::new_from(lexer);
+        let module = parser.parse_module()?;
+
+        Ok((module, parser.take_errors()))
+    }
+
+    fn new_src(&self, lo: BytePos, hi: BytePos) -> &str {
+        let start = self.new_fm.start_pos;
+        &self.new_fm.src[(lo - start).0 as usize..(hi - start).0 as usize]
+    }
+
+    /// Reparses only the body of a top-level function if the edit is strictly
+    /// inside of it.
+    fn reparse_fn_body(&self, old: &Module) -> Option<ReparsedModule> {
+        let Shift {
+            edit_lo, edit_hi, ..
+        } = self.shift;
+
+        let idx = match old.body.iter().position(|item| {
+            let span = item.span();
+            span.lo() < edit_lo && edit_hi < span.hi()
+        }) {
+            Some(idx) => idx,
+            None => return None,
+        };
+
+        let (body_span, is_async, is_generator) = match fn_of(&old.body[idx]) {
+            Some(Function {
+                body: Some(body),
+                is_async,
+                is_generator,
+                ..
+            }) => (body.span, *is_async, *is_generator),
+            _ => return None,
+        };
+        // Braces of the body should not be touched.
+        if edit_lo <= body_span.lo() || body_span.hi() <= edit_hi {
+            return None;
+        }
+
+        // Code inserted right after `{` or right before `}` belongs to the body.
+        let lo = self.shift.pos(body_span.lo() + BytePos(1), true);
+        let hi = self.shift.pos(body_span.hi() - BytePos(1), false);
+        let src = self.new_src(lo, hi);
+        let lexer = Lexer::new(
+            self.syntax,
+            self.target,
+            StringInput::new(src, lo, hi),
+            None,
+        );
+        let mut parser = Parser::new_from(lexer);
+        let stmts = match parser.parse_fn_body_stmts(is_async, is_generator) {
+            Ok(stmts) => stmts,
+            // The edit may have changed the structure of the file, like adding a
+            // `}`.
+            Err(..) => return None,
+        };
+        let errors = parser.take_errors();
+
+        let mut module = old.clone().fold_with(&mut self.shift.clone());
+        if let Some(Function {
+            body: Some(body), ..
+        }) = fn_of_mut(&mut module.body[idx])
+        {
+            body.stmts = stmts;
+        }
+
+        Some(ReparsedModule {
+            reused: module.body.len() - 1,
+            module,
+            errors,
+        })
+    }
+
+    /// Reparses the smallest range of top-level items enclosing the edit.
+    fn reparse_items(&self, old: &Module) -> Option<ReparsedModule> {
+        let Shift {
+            edit_lo, edit_hi, ..
+        } = self.shift;
+        let items = &old.body;
+        if items.is_empty() {
+            return None;
+        }
+
+        // First item which may be affected by the edit.
+        let mut first = items
+            .iter()
+            .position(|item| item.span().hi() >= edit_lo)
+            .unwrap_or(items.len() - 1);
+        // Last item which may be affected by the edit.
+        let mut last = items
+            .iter()
+            .rposition(|item| item.span().lo() <= edit_hi)
+            .unwrap_or(0)
+            .max(first);
+
+        // If the previous item is not terminated, reparsed code may be merged into
+        // it by automatic semicolon insertion.
+        while first > 0 && !is_terminated(&items[first - 1], self.old_fm) {
+            first -= 1;
+        }
+
+        loop {
+            if first == 0 && last == items.len() - 1 {
+                return None;
+            }
+
+            let lo = if first == 0 {
+                self.new_fm.start_pos
+            } else {
+                self.shift.pos(items[first - 1].span().hi(), true)
+            };
+            let hi = if last == items.len() - 1 {
+                self.new_fm.end_pos
+            } else {
+                self.shift.pos(items[last + 1].span().lo(), false)
+            };
+
+            // Shebang is only allowed at the start of a file.
+            if first != 0 && self.new_src(lo, hi).trim_start().starts_with("#!") {
+                return None;
+            }
+
+            let (reparsed, errors) = match self.parse(lo, hi) {
+                Ok(v) => v,
+                Err(..) => return None,
+            };
+
+            // Same as above, but for the next item.
+            if last != items.len() - 1 {
+                if let Some(item) = reparsed.body.last() {
+                    if !is_terminated(item, self.new_fm) {
+                        last += 1;
+                        continue;
+                    }
+                }
+            }
+
+            let is_empty = reparsed.body.is_empty() && reparsed.shebang.is_none();
+            let mut shift = self.shift;
+            let mut body =
+                Vec::with_capacity(items.len() - (last - first + 1) + reparsed.body.len());
+            body.extend(
+                items[..first]
+                    .iter()
+                    .cloned()
+                    .map(|i| i.fold_with(&mut shift)),
+            );
+            body.extend(reparsed.body);
+            body.extend(
+                items[last + 1..]
+                    .iter()
+                    .cloned()
+                    .map(|i| i.fold_with(&mut shift)),
+            );
+
+            // Span of a module starts at the first token and ends at the last token.
+            let span = shift.fold_span(old.span);
+            let span = if first == 0 {
+                if is_empty {
+                    span.with_lo(body[0].span().lo())
+                } else {
+                    span.with_lo(reparsed.span.lo())
+                }
+            } else if last == items.len() - 1 {
+                if is_empty {
+                    span.with_hi(body[body.len() - 1].span().hi())
+                } else {
+                    span.with_hi(reparsed.span.hi())
+                }
+            } else {
+                span
+            };
+
+            return Some(ReparsedModule {
+                reused: items.len() - (last - first + 1),
+                module: Module {
+                    span,
+                    body,
+                    shebang: if first == 0 {
+                        reparsed.shebang
+                    } else {
+                        old.shebang.clone()
+                    },
+                },
+                errors,
+            });
+        }
+    }
+}
+
+/// Returns true if no following code can be a part of `item`.
+fn is_terminated(item: &ModuleItem, fm: &SourceFile) -> bool {
+    match *item {
+        ModuleItem::Stmt(Stmt::Decl(Decl::Fn(..)))
+        | ModuleItem::Stmt(Stmt::Decl(Decl::Class(..)))
+        | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+            decl: Decl::Fn(..), ..
+        }))
+        | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+            decl: Decl::Class(..),
+            ..
+        }))
+        | ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(..)) => return true,
+        _ => {}
+    }
+
+    let hi = item.span().hi();
+    if hi <= fm.start_pos {
+        return false;
+    }
+    let idx = (hi - fm.start_pos).0 as usize;
+    fm.src[..idx].ends_with(';')
+}
+
+fn fn_of(item: &ModuleItem) -> Option<&Function> {
+    match *item {
+        ModuleItem::Stmt(Stmt::Decl(Decl::Fn(FnDecl { ref function, .. })))
+        | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+            decl: Decl::Fn(FnDecl { ref function, .. }),
+            ..
+        }))
+        | ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(ExportDefaultDecl {
+            decl: DefaultDecl::Fn(FnExpr { ref function, .. }),
+            ..
+        })) => Some(function),
+        _ => None,
+    }
+}
+
+fn fn_of_mut(item: &mut ModuleItem) -> Option<&mut Function> {
+    match *item {
+        ModuleItem::Stmt(Stmt::Decl(Decl::Fn(FnDecl {
+            ref mut function, ..
+        })))
+        | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+            decl: Decl::Fn(FnDecl {
+                ref mut function, ..
+            }),
+            ..
+        }))
+        | ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(ExportDefaultDecl {
+            decl: DefaultDecl::Fn(FnExpr {
+                ref mut function, ..
+            }),
+            ..
+        })) => Some(function),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swc_common::{FileName, SourceMap};
+
+    fn parse(fm: &SourceFile) -> Module {
+        let lexer = Lexer::new(
+            Default::default(),
+            Default::default(),
+            StringInput::from(fm),
+            None,
+        );
+        Parser::new_from(lexer)
+            .parse_module()
+            .expect("failed to parse")
+    }
+
+    /// Asserts that the result of incremental reparsing is identical to a fresh
+    /// parse, and returns the number of reused items.
+    fn check(src: &str, range: Range<usize>, text: &str) -> usize {
+        let cm = SourceMap::default();
+        let old_fm = cm.new_source_file(FileName::Anon, src.into());
+        let old = parse(&old_fm);
+
+        let mut new_src = src.to_string();
+        new_src.replace_range(range.clone(), text);
+        let new_fm = cm.new_source_file(FileName::Anon, new_src);
+        let expected = parse(&new_fm);
+
+        let edit = TextEdit {
+            range,
+            text: text.into(),
+        };
+        let actual = reparse_module(
+            Default::default(),
+            Default::default(),
+            &old,
+            &old_fm,
+            &new_fm,
+            &edit,
+        )
+        .expect("failed to reparse");
+
+        assert_eq!(actual.module, expected);
+        actual.reused
+    }
+
+    #[test]
+    fn replace_in_middle() {
+        let src = "var a = 1;\nvar b = 2;\nvar c = 3;\n";
+        assert_eq!(check(src, 19..20, "42"), 2);
+    }
+
+    #[test]
+    fn insert_item() {
+        let src = "var a = 1;\nvar b = 2;\n";
+        assert_eq!(check(src, 11..11, "foo();\n"), 1);
+    }
+
+    #[test]
+    fn edit_first() {
+        let src = "var a = 1;\nvar b = 2;\nvar c = 3;\n";
+        assert_eq!(check(src, 4..5, "abc"), 2);
+    }
+
+    #[test]
+    fn edit_last() {
+        let src = "var a = 1;\nvar b = 2;\nvar c = 3;\n";
+        assert_eq!(check(src, 30..31, "100"), 2);
+    }
+
+    #[test]
+    fn fn_body() {
+        let src = "var a = 1;\nfunction foo() {\n  return a;\n}\nvar b = 2;\n";
+        assert_eq!(check(src, 37..38, "b + 1"), 2);
+    }
+
+    #[test]
+    fn async_fn_body() {
+        let src = "async function foo() {\n  await a;\n}\nfoo();\n";
+        assert_eq!(check(src, 31..32, "bar()"), 1);
+    }
+
+    #[test]
+    fn unterminated_prev() {
+        let src = "var a = b\nvar c = 1;\n";
+        check(src, 10..10, "(d)\n");
+    }
+
+    #[test]
+    fn remove_semi() {
+        let src = "a = b;\n(c);\nfoo();\n";
+        check(src, 5..6, "");
+    }
+
+    #[test]
+    fn close_fn_body() {
+        let src = "function foo() {\n  a();\n}\n";
+        check(src, 24..24, "}\nfunction bar() {\n");
+    }
+}
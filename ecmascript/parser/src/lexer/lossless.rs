@@ -0,0 +1,265 @@
+//! Lossless lexing, for tools like syntax highlighters and formatters.
+use super::{input::Input, util::CharExt, Lexer};
+use crate::{
+    error::Error,
+    token::{Token, TokenKind},
+};
+use std::{cmp::max, collections::VecDeque, mem::take};
+use swc_common::{BytePos, Span};
+
+/// A token or a trivia yielded by [LosslessLexer].
+#[derive(Debug, Clone, PartialEq)]
+pub struct LosslessToken {
+    pub kind: TokenKind,
+    pub span: Span,
+    /// [None] if this is a trivia.
+    pub token: Option<Token>,
+}
+
+/// Wraps a [Lexer] and yields white spaces, newlines and comments in addition
+/// to the tokens.
+///
+/// Spans of yielded items are contiguous and cover the whole input, so
+/// concatenating the source text of each span reproduces the original text.
+pub struct LosslessLexer<'a, I: Input> {
+    lexer: Lexer<'a, I>,
+    /// End of the last item pushed to `buf`.
+    last_hi: BytePos,
+    buf: VecDeque<LosslessToken>,
+    done: bool,
+}
+
+impl<'a, I: Input> LosslessLexer<'a, I> {
+    pub fn new(mut lexer: Lexer<'a, I>) -> Self {
+        let last_hi = lexer.input.cur_pos();
+
+        LosslessLexer {
+            lexer,
+            last_hi,
+            buf: Default::default(),
+            done: false,
+        }
+    }
+
+    /// Errors which were recovered while lexing.
+    ///
+    /// Fatal errors are yielded as tokens of kind [TokenKind::Error].
+    pub fn take_errors(&mut self) -> Vec<Error> {
+        take(&mut self.lexer.errors.borrow_mut())
+    }
+
+    /// Pushes trivia between the last item and `hi`.
+    fn push_trivia(&mut self, hi: BytePos) {
+        let lo = self.last_hi;
+        if lo >= hi {
+            return;
+        }
+
+        // `slice` moves the cursor of the input, so we restore it.
+        let pos = self.lexer.input.cur_pos();
+        let src = self.lexer.input.slice(lo, hi);
+        split_trivia(src, lo, &mut self.buf);
+        self.lexer.input.reset_to(pos);
+
+        self.last_hi = hi;
+    }
+}
+
+impl<I: Input> Iterator for LosslessLexer<'_, I> {
+    type Item = LosslessToken;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.buf.pop_front() {
+                return Some(item);
+            }
+            if self.done {
+                return None;
+            }
+
+            match self.lexer.next() {
+                Some(t) => {
+                    self.push_trivia(t.span.lo);
+                    self.last_hi = max(self.last_hi, t.span.hi);
+
+                    self.buf.push_back(LosslessToken {
+                        kind: t.token.kind(),
+                        span: t.span,
+                        token: Some(t.token),
+                    });
+                }
+                None => {
+                    let end = self.lexer.input.cur_pos();
+                    self.push_trivia(end);
+                    self.done = true;
+                }
+            }
+        }
+    }
+}
+
+/// Splits text skipped by the lexer into trivia.
+fn split_trivia(src: &str, start: BytePos, buf: &mut VecDeque<LosslessToken>) {
+    let bytes = src.as_bytes();
+    let mut chars = src.char_indices().peekable();
+
+    while let Some((lo, c)) = chars.next() {
+        let kind = if c.is_line_break() {
+            if c == '\r' && bytes.get(lo + 1) == Some(&b'\n') {
+                chars.next();
+            }
+            TokenKind::Newline
+        } else if c.is_ws() {
+            while chars.peek().map(|&(_, c)| c.is_ws()).unwrap_or(false) {
+                chars.next();
+            }
+            TokenKind::Whitespace
+        } else if src[lo..].starts_with("//") {
+            while chars
+                .peek()
+                .map(|&(_, c)| !c.is_line_break())
+                .unwrap_or(false)
+            {
+                chars.next();
+            }
+            TokenKind::LineComment
+        } else if src[lo..].starts_with("/*") {
+            chars.next();
+            let end = src[lo + 2..]
+                .find("*/")
+                .map(|i| lo + 2 + i + 2)
+                .unwrap_or(src.len());
+            while chars.peek().map(|&(i, _)| i < end).unwrap_or(false) {
+                chars.next();
+            }
+            TokenKind::BlockComment
+        } else {
+            // The lexer only skips white spaces and comments, so this should be
+            // unreachable. We still yield it to keep the output lossless.
+            TokenKind::Error
+        };
+
+        let hi = chars.peek().map(|&(i, _)| i).unwrap_or(src.len());
+        buf.push_back(LosslessToken {
+            kind,
+            span: Span::new(
+                start + BytePos(lo as u32),
+                start + BytePos(hi as u32),
+                Default::default(),
+            ),
+            token: None,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Syntax;
+
+    fn kinds(src: &'static str) -> Vec<(TokenKind, &'static str)> {
+        crate::with_test_sess(src, |_, fm| {
+            let lexer = Lexer::new(Syntax::default(), Default::default(), fm, None);
+
+            Ok(LosslessLexer::new(lexer)
+                .map(|t| (t.kind, &src[t.span.lo.0 as usize..t.span.hi.0 as usize]))
+                .collect())
+        })
+        .unwrap()
+    }
+
+    fn assert_lossless(src: &'static str) {
+        let text: String = kinds(src).into_iter().map(|(_, s)| s).collect();
+        assert_eq!(text, src);
+    }
+
+    #[test]
+    fn trivia() {
+        assert_eq!(
+            kinds("a  // b\r\n/* c */c"),
+            vec![
+                (TokenKind::Ident, "a"),
+                (TokenKind::Whitespace, "  "),
+                (TokenKind::LineComment, "// b"),
+                (TokenKind::Newline, "\r\n"),
+                (TokenKind::BlockComment, "/* c */"),
+                (TokenKind::Ident, "c"),
+            ]
+        );
+    }
+
+    #[test]
+    fn leading_and_trailing_trivia() {
+        assert_eq!(
+            kinds("\n\n x; \t"),
+            vec![
+                (TokenKind::Newline, "\n"),
+                (TokenKind::Newline, "\n"),
+                (TokenKind::Whitespace, " "),
+                (TokenKind::Ident, "x"),
+                (TokenKind::Punct, ";"),
+                (TokenKind::Whitespace, " \t"),
+            ]
+        );
+    }
+
+    #[test]
+    fn only_trivia() {
+        assert_eq!(
+            kinds("/** a */"),
+            vec![(TokenKind::BlockComment, "/** a */")]
+        );
+    }
+
+    #[test]
+    fn classification() {
+        assert_eq!(
+            kinds("if(true)x+=/a/g"),
+            vec![
+                (TokenKind::Keyword, "if"),
+                (TokenKind::Punct, "("),
+                (TokenKind::Bool, "true"),
+                (TokenKind::Punct, ")"),
+                (TokenKind::Ident, "x"),
+                (TokenKind::Operator, "+="),
+                (TokenKind::Regex, "/a/g"),
+            ]
+        );
+    }
+
+    #[test]
+    fn template() {
+        assert_eq!(
+            kinds("`a${ b }`"),
+            vec![
+                (TokenKind::Punct, "`"),
+                (TokenKind::Template, "a"),
+                (TokenKind::Punct, "${"),
+                (TokenKind::Whitespace, " "),
+                (TokenKind::Ident, "b"),
+                (TokenKind::Whitespace, " "),
+                (TokenKind::Punct, "}"),
+                (TokenKind::Template, ""),
+                (TokenKind::Punct, "`"),
+            ]
+        );
+    }
+
+    #[test]
+    fn shebang() {
+        assert_eq!(
+            kinds("#!/usr/bin/env node\nfoo"),
+            vec![
+                (TokenKind::Shebang, "#!/usr/bin/env node"),
+                (TokenKind::Newline, "\n"),
+                (TokenKind::Ident, "foo"),
+            ]
+        );
+    }
+
+    #[test]
+    fn lossless() {
+        assert_lossless("function  foo ( a ,b ) {\n\treturn a /* x */ + b; // y\n}\n");
+        assert_lossless("var s = 'a\\nb', n = 1e3 \u{2028} ;");
+    }
+}
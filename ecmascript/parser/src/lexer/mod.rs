@@ -2,6 +2,7 @@
 
 pub use self::{
     input::Input,
+    lossless::{LosslessLexer, LosslessToken},
     state::{TokenContext, TokenContexts},
 };
 use self::{state::State, util::*};
@@ -21,6 +22,7 @@ use swc_common::{
 
 pub mod input;
 mod jsx;
+mod lossless;
 mod number;
 mod state;
 #[cfg(test)]
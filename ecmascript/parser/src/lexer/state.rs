@@ -197,7 +197,7 @@ impl<'a, I: Input> Iterator for Lexer<'a, I> {
 
             self.state.start = start;
 
-            if self.syntax.typescript() && self.ctx.in_type {
+            if self.syntax.typescript_or_flow() && self.ctx.in_type {
                 if c == '<' {
                     self.input.bump();
                     return Ok(Some(tok!('<')));
@@ -9,13 +9,16 @@
 //! ## Error reporting
 //!
 //! ```sh
-//! error: 'implements', 'interface', 'let', 'package', 'private', 'protected',  'public', 'static', or 'yield' cannot be used as an identifier in strict mode
+//! error[E0022]: 'implements', 'interface', 'let', 'package', 'private', 'protected',  'public', 'static', or 'yield' cannot be used as an identifier in strict mode
 //!  --> invalid.js:3:10
 //!   |
 //! 3 | function yield() {
 //!   |          ^^^^^
 //! ```
 //!
+//! Every error has a stable code, which can be explained using
+//! [error::explain].
+//!
 //! ## Error recovery
 //!
 //! The parser can recover from some parsing erros. For example, parser returns
@@ -30,6 +33,10 @@
 //! }
 //! ```
 //!
+//! For editor tooling, `Parser::with_recovery(true)` makes the parser recover
+//! from all errors at statement, class member and object member boundaries.
+//! Nodes which failed to parse are replaced by `Invalid` nodes.
+//!
 //! # Example (lexer)
 //!
 //! See `lexer.rs` in examples directory.
@@ -98,10 +105,12 @@ pub use self::{
 };
 use serde::{Deserialize, Serialize};
 use swc_common::Span;
+pub use swc_ecma_ast::JscTarget;
 
 #[macro_use]
 mod macros;
 pub mod error;
+pub mod incremental;
 pub mod lexer;
 mod parser;
 pub mod token;
@@ -114,6 +123,8 @@ pub enum Syntax {
     Es(EsConfig),
     #[serde(rename = "typescript")]
     Typescript(TsConfig),
+    #[serde(rename = "flow")]
+    Flow(FlowConfig),
 }
 
 impl Default for Syntax {
@@ -127,7 +138,8 @@ impl Syntax {
     pub fn jsx(self) -> bool {
         match self {
             Syntax::Es(EsConfig { jsx: true, .. })
-            | Syntax::Typescript(TsConfig { tsx: true, .. }) => true,
+            | Syntax::Typescript(TsConfig { tsx: true, .. })
+            | Syntax::Flow(FlowConfig { jsx: true, .. }) => true,
             _ => false,
         }
     }
@@ -145,6 +157,10 @@ impl Syntax {
             | Syntax::Typescript(TsConfig {
                 dynamic_import: true,
                 ..
+            })
+            | Syntax::Flow(FlowConfig {
+                dynamic_import: true,
+                ..
             }) => true,
             _ => false,
         }
@@ -168,6 +184,9 @@ impl Syntax {
             })
             | Syntax::Typescript(TsConfig {
                 decorators: true, ..
+            })
+            | Syntax::Flow(FlowConfig {
+                decorators: true, ..
             }) => true,
             _ => false,
         }
@@ -183,7 +202,8 @@ impl Syntax {
                 class_private_props: true,
                 ..
             })
-            | Syntax::Typescript(..) => true,
+            | Syntax::Typescript(..)
+            | Syntax::Flow(..) => true,
             _ => false,
         }
     }
@@ -211,6 +231,22 @@ impl Syntax {
         }
     }
 
+    /// Should we parse flow?
+    pub fn flow(self) -> bool {
+        match self {
+            Syntax::Flow(..) => true,
+            _ => false,
+        }
+    }
+
+    /// Should we parse type annotations?
+    ///
+    /// Flow types are parsed into the same nodes as typescript types, so most
+    /// of the typescript parser is shared with flow.
+    pub(crate) fn typescript_or_flow(self) -> bool {
+        self.typescript() || self.flow()
+    }
+
     pub fn export_default_from(self) -> bool {
         match self {
             Syntax::Es(EsConfig {
@@ -269,7 +305,7 @@ impl Syntax {
     pub(crate) fn early_errors(self) -> bool {
         match self {
             Syntax::Typescript(t) => !t.no_early_errors,
-            Syntax::Es(..) => true,
+            Syntax::Es(..) | Syntax::Flow(..) => true,
         }
     }
 }
@@ -294,30 +330,17 @@ pub struct TsConfig {
     pub no_early_errors: bool,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialOrd, Ord, PartialEq, Eq)]
-pub enum JscTarget {
-    #[serde(rename = "es3")]
-    Es3,
-    #[serde(rename = "es5")]
-    Es5,
-    #[serde(rename = "es2015")]
-    Es2015,
-    #[serde(rename = "es2016")]
-    Es2016,
-    #[serde(rename = "es2017")]
-    Es2017,
-    #[serde(rename = "es2018")]
-    Es2018,
-    #[serde(rename = "es2019")]
-    Es2019,
-    #[serde(rename = "es2020")]
-    Es2020,
-}
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct FlowConfig {
+    #[serde(default)]
+    pub jsx: bool,
 
-impl Default for JscTarget {
-    fn default() -> Self {
-        JscTarget::Es5
-    }
+    #[serde(default)]
+    pub decorators: bool,
+
+    #[serde(default)]
+    pub dynamic_import: bool,
 }
 
 #[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
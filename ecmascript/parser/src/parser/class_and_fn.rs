@@ -1,4 +1,4 @@
-use super::{ident::MaybeOptionalIdentParser, *};
+use super::{ident::MaybeOptionalIdentParser, recovery::Boundary, *};
 use crate::{error::SyntaxError, Tokens};
 use either::Either;
 use swc_atoms::js_word;
@@ -89,7 +89,7 @@ impl<'a, I: Tokens> Parser<I> {
                 p.emit_err(span, SyntaxError::TS2414);
             }
 
-            let type_params = if p.input.syntax().typescript() {
+            let type_params = if p.input.syntax().typescript_or_flow() {
                 p.try_parse_ts_type_params()?
             } else {
                 None
@@ -97,7 +97,7 @@ impl<'a, I: Tokens> Parser<I> {
 
             let (mut super_class, mut super_type_params) = if eat!("extends") {
                 let super_class = p.parse_lhs_expr().map(Some)?;
-                let super_type_params = if p.input.syntax().typescript() && is!('<') {
+                let super_type_params = if p.input.syntax().typescript_or_flow() && is!('<') {
                     Some(p.parse_ts_type_args()?)
                 } else {
                     None
@@ -121,12 +121,12 @@ impl<'a, I: Tokens> Parser<I> {
                 p.emit_err(p.input.prev_span(), SyntaxError::TS1172);
 
                 p.parse_lhs_expr()?;
-                if p.input.syntax().typescript() && is!('<') {
+                if p.input.syntax().typescript_or_flow() && is!('<') {
                     p.parse_ts_type_args()?;
                 }
             };
 
-            let implements = if p.input.syntax().typescript() && eat!("implements") {
+            let implements = if p.input.syntax().typescript_or_flow() && eat!("implements") {
                 p.parse_ts_heritage_clause()?
             } else {
                 vec![]
@@ -281,7 +281,32 @@ impl<'a, I: Tokens> Parser<I> {
                 continue;
             }
 
-            elems.push(self.parse_class_member()?);
+            let start = cur_pos!();
+            match self.parse_class_member() {
+                Ok(member) => elems.push(member),
+                Err(err) => {
+                    if !self.should_recover() || self.input.cur().is_none() {
+                        return Err(err);
+                    }
+
+                    let span = self.recover(start, err, Boundary::ClassMember);
+                    elems.push(ClassMember::ClassProp(ClassProp {
+                        span,
+                        key: Box::new(Expr::Invalid(Invalid { span })),
+                        value: None,
+                        type_ann: None,
+                        is_static: false,
+                        decorators: vec![],
+                        computed: false,
+                        accessibility: None,
+                        is_abstract: false,
+                        is_optional: false,
+                        readonly: false,
+                        declare: false,
+                        definite: false,
+                    }));
+                }
+            }
         }
         Ok(elems)
     }
@@ -300,7 +325,7 @@ impl<'a, I: Tokens> Parser<I> {
     fn parse_class_member(&mut self) -> PResult<ClassMember> {
         let start = cur_pos!();
         let decorators = self.parse_decorators(false)?;
-        let declare = self.syntax().typescript() && eat!("declare");
+        let declare = self.syntax().typescript_or_flow() && eat!("declare");
         let accessibility = if self.input.syntax().typescript() {
             self.parse_access_modifier()?
         } else {
@@ -442,7 +467,10 @@ impl<'a, I: Tokens> Parser<I> {
             _ => (false, false),
         };
 
-        if self.input.syntax().typescript() && !is_abstract && !is_static && accessibility.is_none()
+        if self.input.syntax().typescript_or_flow()
+            && !is_abstract
+            && !is_static
+            && accessibility.is_none()
         {
             let idx = self.try_parse_ts_index_signature(start, readonly)?;
             if let Some(idx) = idx {
@@ -813,11 +841,13 @@ impl<'a, I: Tokens> Parser<I> {
     }
 
     fn is_class_method(&mut self) -> PResult<bool> {
-        Ok(is!('(') || (self.input.syntax().typescript() && is!('<')))
+        Ok(is!('(') || (self.input.syntax().typescript_or_flow() && is!('<')))
     }
 
     fn is_class_property(&mut self) -> PResult<bool> {
-        Ok((self.input.syntax().typescript() && is_one_of!('!', ':')) || is_one_of!('=', ';', '}'))
+        Ok((self.input.syntax().typescript() && is!('!'))
+            || (self.input.syntax().typescript_or_flow() && is!(':'))
+            || is_one_of!('=', ';', '}'))
     }
 
     fn parse_fn<T>(
@@ -911,7 +941,7 @@ impl<'a, I: Tokens> Parser<I> {
         };
 
         self.with_ctx(ctx).parse_with(|p| {
-            let type_params = if p.syntax().typescript() && is!('<') {
+            let type_params = if p.syntax().typescript_or_flow() && is!('<') {
                 //
                 Some(p.parse_ts_type_params()?)
             } else {
@@ -930,7 +960,7 @@ impl<'a, I: Tokens> Parser<I> {
             expect!(')');
 
             // typescript extension
-            let return_type = if p.syntax().typescript() && is!(':') {
+            let return_type = if p.syntax().typescript_or_flow() && is!(':') {
                 p.parse_ts_type_or_type_predicate_ann(&tok!(':'))
                     .map(Some)?
             } else {
@@ -980,7 +1010,7 @@ impl<'a, I: Tokens> Parser<I> {
     where
         Self: FnBodyParser<T>,
     {
-        if self.ctx().in_declare && self.syntax().typescript() && is!('{') {
+        if self.ctx().in_declare && self.syntax().typescript_or_flow() && is!('{') {
             //            self.emit_err(
             //                self.ctx().span_of_fn_name.expect("we are not in function"),
             //                SyntaxError::TS1183,
@@ -1002,6 +1032,29 @@ impl<'a, I: Tokens> Parser<I> {
         };
         self.with_ctx(ctx).with_state(state).parse_fn_body_inner()
     }
+
+    /// Parses statements of a top-level function body until eof.
+    ///
+    /// The input should not contain braces of the body. This is used to
+    /// reparse a function body incrementally.
+    pub(crate) fn parse_fn_body_stmts(
+        &mut self,
+        is_async: bool,
+        is_generator: bool,
+    ) -> PResult<Vec<Stmt>> {
+        let ctx = Context {
+            module: true,
+            strict: true,
+            include_in_expr: true,
+            in_async: is_async,
+            in_generator: is_generator,
+            in_function: true,
+            ..Default::default()
+        };
+        self.set_ctx(ctx);
+
+        self.parse_block_body(true, false, None)
+    }
 }
 
 impl<'a, I: Tokens> Parser<I> {
@@ -1206,7 +1259,7 @@ impl<I: Tokens> FnBodyParser<BlockStmtOrExpr> for Parser<I> {
 impl<I: Tokens> FnBodyParser<Option<BlockStmt>> for Parser<I> {
     fn parse_fn_body_inner(&mut self) -> PResult<Option<BlockStmt>> {
         // allow omitting body and allow placing `{` on next line
-        if self.input.syntax().typescript() && !is!('{') && eat!(';') {
+        if self.input.syntax().typescript_or_flow() && !is!('{') && eat!(';') {
             return Ok(None);
         }
         self.include_in_expr(true).parse_block(true).map(Some)
@@ -36,7 +36,7 @@ impl<'a, I: Tokens> Parser<I> {
     pub(super) fn parse_assignment_expr(&mut self) -> PResult<Box<Expr>> {
         trace_cur!(parse_assignment_expr);
 
-        if self.input.syntax().typescript() {
+        if self.input.syntax().typescript_or_flow() {
             // Note: When the JSX plugin is on, type assertions (`<T> x`) aren't valid
             // syntax.
 
@@ -67,7 +67,7 @@ impl<'a, I: Tokens> Parser<I> {
             }
         }
 
-        if self.input.syntax().typescript()
+        if self.input.syntax().typescript_or_flow()
             && (is_one_of!('<', JSXTagStart))
             && peeked_is!(IdentName)
         {
@@ -242,7 +242,7 @@ impl<'a, I: Tokens> Parser<I> {
                 return self.parse_async_fn_expr();
             }
 
-            if can_be_arrow && self.input.syntax().typescript() && peeked_is!('<') {
+            if can_be_arrow && self.input.syntax().typescript_or_flow() && peeked_is!('<') {
                 // try parsing `async<T>() => {}`
                 if let Some(res) = self.try_parse_ts(|p| {
                     let start = cur_pos!();
@@ -453,7 +453,7 @@ impl<'a, I: Tokens> Parser<I> {
             let callee = self.parse_member_expr_or_new_expr(is_new_expr)?;
             return_if_arrow!(callee);
 
-            let type_args = if self.input.syntax().typescript() && is!('<') {
+            let type_args = if self.input.syntax().typescript_or_flow() && is!('<') {
                 self.try_parse_ts(|p| {
                     let args = p.parse_ts_type_args()?;
                     if !is!('(') {
@@ -569,6 +569,12 @@ impl<'a, I: Tokens> Parser<I> {
 
         let expr_start = async_span.map(|x| x.lo()).unwrap_or(cur_pos!());
 
+        if self.input.syntax().flow() && async_span.is_none() {
+            if let Some(expr) = self.try_parse_flow_type_cast() {
+                return Ok(expr);
+            }
+        }
+
         // At this point, we can't know if it's parenthesized
         // expression or head of arrow function.
         // But as all patterns of javascript is subset of
@@ -581,7 +587,7 @@ impl<'a, I: Tokens> Parser<I> {
         });
 
         // This is slow path. We handle arrow in conditional expression.
-        if self.syntax().typescript() && self.ctx().in_cond_expr && is!(':') {
+        if self.syntax().typescript_or_flow() && self.ctx().in_cond_expr && is!(':') {
             // TODO: Remove clone
             let items_ref = &paren_items;
             if let Some(expr) = self.try_parse_ts(|p| {
@@ -611,7 +617,7 @@ impl<'a, I: Tokens> Parser<I> {
         }
 
         let return_type = if !self.ctx().in_cond_expr
-            && self.input.syntax().typescript()
+            && self.input.syntax().typescript_or_flow()
             && is!(':')
             && !self.ctx().in_case_cond
         {
@@ -861,8 +867,11 @@ impl<'a, I: Tokens> Parser<I> {
         let _ = cur!(false);
         let start = obj.span().lo();
 
-        if self.input.syntax().typescript() {
-            if !self.input.had_line_break_before_cur() && is!('!') {
+        if self.input.syntax().typescript_or_flow() {
+            if self.input.syntax().typescript()
+                && !self.input.had_line_break_before_cur()
+                && is!('!')
+            {
                 self.input.set_expr_allowed(false);
                 assert_and_bump!('!');
 
@@ -1089,7 +1098,7 @@ impl<'a, I: Tokens> Parser<I> {
         let callee = self.parse_new_expr()?;
         return_if_arrow!(callee);
 
-        let type_args = if self.input.syntax().typescript() && is!('<') {
+        let type_args = if self.input.syntax().typescript_or_flow() && is!('<') {
             self.try_parse_ts(|p| {
                 let type_args = p.parse_ts_type_args()?;
                 if is!('(') {
@@ -1189,7 +1198,7 @@ impl<'a, I: Tokens> Parser<I> {
             let pat_start = cur_pos!();
 
             let mut arg = {
-                if self.input.syntax().typescript()
+                if self.input.syntax().typescript_or_flow()
                     && (is!(IdentRef) || (is!("...") && peeked_is!(IdentRef)))
                 {
                     let spread = if eat!("...") {
@@ -1218,7 +1227,7 @@ impl<'a, I: Tokens> Parser<I> {
                 }
             };
 
-            let optional = if self.input.syntax().typescript() {
+            let optional = if self.input.syntax().typescript_or_flow() {
                 if is!('?') {
                     if peeked_is!(',') || peeked_is!(':') || peeked_is!(')') || peeked_is!('=') {
                         assert_and_bump!('?');
@@ -1274,7 +1283,7 @@ impl<'a, I: Tokens> Parser<I> {
                 false
             };
 
-            if optional || (self.input.syntax().typescript() && is!(':')) {
+            if optional || (self.input.syntax().typescript_or_flow() && is!(':')) {
                 let start = cur_pos!();
 
                 // TODO: `async(...args?: any[]) : any => {}`
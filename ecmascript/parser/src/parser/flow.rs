@@ -0,0 +1,520 @@
+//! Flow extensions.
+//!
+//! Flow types are parsed into the same nodes as typescript types, so this
+//! module only contains the syntax which does not exist in typescript.
+
+use super::*;
+use crate::token::BinOpToken;
+use swc_atoms::js_word;
+
+#[parser]
+impl<I: Tokens> Parser<I> {
+    /// Returns true if the current token is the `|` of `|}`, which closes an
+    /// exact object type.
+    pub(super) fn is_flow_exact_object_end(&mut self) -> PResult<bool> {
+        Ok(self.input.syntax().flow() && is!('|') && peeked_is!('}'))
+    }
+
+    /// Parses a maybe type, like `?T`.
+    ///
+    /// This is represented as `T | null | undefined`.
+    pub(super) fn parse_flow_maybe_type(&mut self) -> PResult<Box<TsType>> {
+        debug_assert!(self.input.syntax().flow());
+
+        let start = cur_pos!();
+        let question_span = self.input.cur_span();
+        assert_and_bump!('?');
+
+        let ty = self.parse_ts_type_operator_or_higher()?;
+        let keyword = |kind: TsKeywordTypeKind| {
+            Box::new(TsType::TsKeywordType(TsKeywordType {
+                span: question_span,
+                kind,
+            }))
+        };
+
+        Ok(Box::new(TsType::TsUnionOrIntersectionType(
+            TsUnionOrIntersectionType::TsUnionType(TsUnionType {
+                span: span!(start),
+                types: vec![
+                    ty,
+                    keyword(TsKeywordTypeKind::TsNullKeyword),
+                    keyword(TsKeywordTypeKind::TsUndefinedKeyword),
+                ],
+            }),
+        )))
+    }
+
+    /// Parses an existential type, `*`.
+    ///
+    /// This is represented as `any`.
+    pub(super) fn parse_flow_existential_type(&mut self) -> PResult<Box<TsType>> {
+        debug_assert!(self.input.syntax().flow());
+
+        let start = cur_pos!();
+        assert_and_bump!('*');
+
+        Ok(Box::new(TsType::TsKeywordType(TsKeywordType {
+            span: span!(start),
+            kind: TsKeywordTypeKind::TsAnyKeyword,
+        })))
+    }
+
+    /// Parses an object type.
+    ///
+    /// Object types of flow may be exact (`{| a: T |}`), explicitly inexact
+    /// (`{ a: T, ... }`), may have a variance sigil on a member (`{ +a: T }`)
+    /// and may spread other object types (`{ ...A, b: T }`). A spread is
+    /// represented as an intersection with the other members.
+    pub(super) fn parse_flow_object_type(&mut self) -> PResult<Box<TsType>> {
+        debug_assert!(self.input.syntax().flow());
+
+        let start = cur_pos!();
+        expect!('{');
+
+        let mut members = vec![];
+        let mut spreads = vec![];
+
+        // `{||}` is tokenized as `{`, `||`, `}`.
+        if self.input.eat(&Token::BinOp(BinOpToken::LogicalOr)) {
+            expect!('}');
+        } else {
+            let exact = eat!('|');
+
+            loop {
+                if exact && self.is_flow_exact_object_end()? {
+                    assert_and_bump!('|');
+                    expect!('}');
+                    break;
+                }
+                if !exact && eat!('}') {
+                    break;
+                }
+
+                if eat!("...") {
+                    // `...` without a type marks an inexact object type.
+                    if !is_one_of!('}', ',', ';') && !self.is_flow_exact_object_end()? {
+                        spreads.push(self.parse_ts_type()?);
+                    }
+                    if !eat!(',') && !self.is_flow_exact_object_end()? {
+                        expect!(';');
+                    }
+                    continue;
+                }
+
+                // Variance sigils are ignored.
+                if is_one_of!('+', '-') {
+                    bump!();
+                }
+                members.push(self.parse_ts_type_member()?);
+            }
+        }
+
+        let lit = Box::new(TsType::TsTypeLit(TsTypeLit {
+            span: span!(start),
+            members,
+        }));
+        if spreads.is_empty() {
+            return Ok(lit);
+        }
+        match *lit {
+            TsType::TsTypeLit(TsTypeLit { ref members, .. }) if members.is_empty() => {}
+            _ => spreads.push(lit),
+        }
+        if spreads.len() == 1 {
+            return Ok(spreads.pop().unwrap());
+        }
+
+        Ok(Box::new(TsType::TsUnionOrIntersectionType(
+            TsUnionOrIntersectionType::TsIntersectionType(TsIntersectionType {
+                span: span!(start),
+                types: spreads,
+            }),
+        )))
+    }
+
+    /// Parses a function type, like `(string, b?: number) => void`.
+    ///
+    /// Unlike typescript, names of parameters are optional.
+    pub(super) fn parse_flow_fn_type(&mut self) -> PResult<TsFnType> {
+        debug_assert!(self.input.syntax().flow());
+
+        let start = cur_pos!();
+        let type_params = self.try_parse_ts_type_params()?;
+
+        expect!('(');
+        let mut params = vec![];
+        while !eof!() && !is!(')') {
+            params.push(self.parse_flow_fn_type_param()?);
+            if !is!(')') {
+                expect!(',');
+            }
+        }
+        expect!(')');
+
+        let type_ann = self.parse_ts_type_or_type_predicate_ann(&tok!("=>"))?;
+
+        Ok(TsFnType {
+            span: span!(start),
+            type_params,
+            params,
+            type_ann,
+        })
+    }
+
+    /// Parses a parameter of a function type.
+    ///
+    /// An unnamed parameter is represented as an identifier with an empty
+    /// name.
+    fn parse_flow_fn_type_param(&mut self) -> PResult<TsFnParam> {
+        let start = cur_pos!();
+        let dot3_token = if eat!("...") {
+            Some(self.input.prev_span())
+        } else {
+            None
+        };
+
+        let param_start = cur_pos!();
+        let ident = if is!(IdentName) && (peeked_is!(':') || peeked_is!('?')) {
+            let mut ident = self.parse_ident_name()?;
+            ident.optional = eat!('?');
+            let type_ann_start = cur_pos!();
+            if !is!(':') {
+                unexpected!()
+            }
+            ident.type_ann = Some(self.parse_ts_type_ann(true, type_ann_start)?);
+            ident.span = span!(param_start);
+            ident
+        } else {
+            let type_ann = self.parse_ts_type()?;
+            let span = span!(param_start);
+
+            Ident {
+                type_ann: Some(TsTypeAnn { span, type_ann }),
+                ..Ident::new(js_word!(""), span)
+            }
+        };
+
+        Ok(match dot3_token {
+            Some(dot3_token) => TsFnParam::Rest(RestPat {
+                span: span!(start),
+                dot3_token,
+                arg: Box::new(Pat::Ident(ident)),
+                type_ann: None,
+            }),
+            None => TsFnParam::Ident(ident),
+        })
+    }
+
+    /// Parses `opaque type T: Super = U`, after `opaque`.
+    ///
+    /// The super type is only used by the type checker, so it's dropped unless
+    /// the alias is declared without a type, as in `declare opaque type T:
+    /// Super;`.
+    pub(super) fn parse_flow_opaque_type_alias(
+        &mut self,
+        start: BytePos,
+    ) -> PResult<TsTypeAliasDecl> {
+        debug_assert!(self.input.syntax().flow());
+
+        expect!("type");
+
+        let id = self.parse_ident_name()?;
+        let type_params = self.try_parse_ts_type_params()?;
+        let super_type = self.try_parse_ts_type()?;
+
+        let type_ann = if self.ctx().in_declare && !is!('=') {
+            match super_type {
+                Some(ty) => ty,
+                None => Box::new(TsType::TsKeywordType(TsKeywordType {
+                    span: id.span,
+                    kind: TsKeywordTypeKind::TsUnknownKeyword,
+                })),
+            }
+        } else {
+            self.expect_then_parse_ts_type(&tok!('='))?
+        };
+        expect!(';');
+
+        Ok(TsTypeAliasDecl {
+            span: span!(start),
+            declare: self.ctx().in_declare,
+            id,
+            type_params,
+            type_ann,
+        })
+    }
+
+    /// Eats `type` or `typeof` of an import specifier like `import { type A }`.
+    ///
+    /// Returns true if the specifier imports a type.
+    pub(super) fn eat_flow_import_specifier_kind(&mut self) -> PResult<bool> {
+        if !self.input.syntax().flow() || !is_one_of!("type", "typeof") {
+            return Ok(false);
+        }
+        // `import { type }` and `import { type as t }` import a value named `type`.
+        if !peeked_is!(IdentName) || peeked_is!("as") {
+            return Ok(false);
+        }
+
+        bump!();
+        Ok(true)
+    }
+
+    /// Tries to parse a type cast, like `(x: T)`.
+    pub(super) fn try_parse_flow_type_cast(&mut self) -> Option<Box<Expr>> {
+        debug_assert!(self.input.syntax().flow());
+
+        let in_cond_expr = self.ctx().in_cond_expr;
+
+        self.try_parse_ts(|p| {
+            let start = cur_pos!();
+            expect!('(');
+
+            let expr = p.include_in_expr(true).parse_assignment_expr()?;
+            if !is!(':') {
+                return Ok(None);
+            }
+            let type_ann_start = cur_pos!();
+            let type_ann = p.parse_ts_type_ann(true, type_ann_start)?;
+            expect!(')');
+
+            // `(x: T) => x` and `(x: T): U => x` are arrow functions, while `((x: T): U)`
+            // is a nested type cast.
+            if is!("=>") {
+                return Ok(None);
+            }
+            if !in_cond_expr && is!(':') {
+                let is_arrow = p
+                    .ts_look_ahead(|p| {
+                        p.parse_ts_type_or_type_predicate_ann(&tok!(':'))?;
+                        Ok(is!("=>"))
+                    })
+                    .unwrap_or(false);
+                if is_arrow {
+                    return Ok(None);
+                }
+            }
+
+            Ok(Some(Box::new(Expr::TsTypeCast(TsTypeCastExpr {
+                span: span!(start),
+                expr,
+                type_ann,
+            }))))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FlowConfig;
+
+    fn module(src: &'static str) -> Module {
+        test_parser(src, Syntax::Flow(FlowConfig::default()), |p| {
+            p.parse_module()
+        })
+    }
+
+    fn type_of_var(src: &'static str) -> TsType {
+        match module(src).body.into_iter().next() {
+            Some(ModuleItem::Stmt(Stmt::Decl(Decl::Var(v)))) => {
+                match v.decls.into_iter().next().unwrap().name {
+                    Pat::Ident(Ident {
+                        type_ann: Some(type_ann),
+                        ..
+                    }) => *type_ann.type_ann,
+                    name => panic!("expected a type annotation: {:?}", name),
+                }
+            }
+            item => panic!("expected a variable declaration: {:?}", item),
+        }
+    }
+
+    #[test]
+    fn maybe_type() {
+        match type_of_var("var a: ?string;") {
+            TsType::TsUnionOrIntersectionType(TsUnionOrIntersectionType::TsUnionType(u)) => {
+                assert_eq!(u.types.len(), 3)
+            }
+            ty => panic!("expected a union type: {:?}", ty),
+        }
+    }
+
+    #[test]
+    fn exact_object_type() {
+        match type_of_var("var a: {| +b: string, c?: ?number |};") {
+            TsType::TsTypeLit(lit) => assert_eq!(lit.members.len(), 2),
+            ty => panic!("expected a type literal: {:?}", ty),
+        }
+
+        match type_of_var("var a: {||};") {
+            TsType::TsTypeLit(lit) => assert_eq!(lit.members.len(), 0),
+            ty => panic!("expected a type literal: {:?}", ty),
+        }
+    }
+
+    #[test]
+    fn object_type_spread() {
+        match type_of_var("var a: { ...B, c: string, ... };") {
+            TsType::TsUnionOrIntersectionType(TsUnionOrIntersectionType::TsIntersectionType(i)) => {
+                assert_eq!(i.types.len(), 2)
+            }
+            ty => panic!("expected an intersection type: {:?}", ty),
+        }
+    }
+
+    #[test]
+    fn unnamed_fn_type_params() {
+        match type_of_var("var a: (string, ?number, ...Array<string>) => void;") {
+            TsType::TsFnOrConstructorType(TsFnOrConstructorType::TsFnType(f)) => {
+                assert_eq!(f.params.len(), 3)
+            }
+            ty => panic!("expected a function type: {:?}", ty),
+        }
+    }
+
+    #[test]
+    fn type_cast() {
+        match module("(a: any);").body[0] {
+            ModuleItem::Stmt(Stmt::Expr(ExprStmt { ref expr, .. })) => match **expr {
+                Expr::TsTypeCast(..) => {}
+                ref expr => panic!("expected a type cast: {:?}", expr),
+            },
+            ref item => panic!("expected an expression statement: {:?}", item),
+        }
+    }
+
+    #[test]
+    fn nested_type_cast() {
+        match module("((a: any): string);").body[0] {
+            ModuleItem::Stmt(Stmt::Expr(ExprStmt { ref expr, .. })) => match **expr {
+                Expr::TsTypeCast(TsTypeCastExpr { ref expr, .. }) => match **expr {
+                    Expr::TsTypeCast(..) => {}
+                    ref expr => panic!("expected a type cast: {:?}", expr),
+                },
+                ref expr => panic!("expected a type cast: {:?}", expr),
+            },
+            ref item => panic!("expected an expression statement: {:?}", item),
+        }
+    }
+
+    #[test]
+    fn arrow_is_not_type_cast() {
+        match module("(a: any) => a;").body[0] {
+            ModuleItem::Stmt(Stmt::Expr(ExprStmt { ref expr, .. })) => match **expr {
+                Expr::Arrow(..) => {}
+                ref expr => panic!("expected an arrow function: {:?}", expr),
+            },
+            ref item => panic!("expected an expression statement: {:?}", item),
+        }
+    }
+
+    #[test]
+    fn opaque_type() {
+        let m = module("opaque type A: string = string;\ndeclare opaque type B;");
+        match m.body[0] {
+            ModuleItem::Stmt(Stmt::Decl(Decl::TsTypeAlias(..))) => {}
+            ref item => panic!("expected a type alias: {:?}", item),
+        }
+        match m.body[1] {
+            ModuleItem::Stmt(Stmt::Decl(Decl::TsTypeAlias(TsTypeAliasDecl {
+                declare: true,
+                ..
+            }))) => {}
+            ref item => panic!("expected a declared type alias: {:?}", item),
+        }
+    }
+
+    #[test]
+    fn import_type() {
+        let m = module(
+            "import type A from 'a';\nimport typeof B from 'b';\nimport { type C, d } from \
+             'c';\nimport { typeof E } from 'e';",
+        );
+        let imports = m
+            .body
+            .into_iter()
+            .map(|item| match item {
+                ModuleItem::ModuleDecl(ModuleDecl::Import(i)) => i,
+                item => panic!("expected an import: {:?}", item),
+            })
+            .collect::<Vec<_>>();
+
+        assert!(imports[0].type_only);
+        assert!(imports[1].type_only);
+        assert!(!imports[2].type_only);
+        assert_eq!(imports[2].specifiers.len(), 1);
+        assert!(imports[3].type_only);
+    }
+
+    #[test]
+    fn generics() {
+        module(
+            "class A<+T: Object = {}> extends B<T> implements C {\n  props: T;\n}\nfunction \
+             f<T>(a: T): Array<T> { return g<T>(a); }",
+        );
+    }
+
+    #[test]
+    fn declare_opaque_type() {
+        let m = module("declare export opaque type A: string;\nexport opaque type B = string;");
+        match m.body[0] {
+            ModuleItem::Stmt(Stmt::Decl(Decl::TsTypeAlias(TsTypeAliasDecl {
+                declare: true,
+                ..
+            }))) => {}
+            ref item => panic!("expected a declared type alias: {:?}", item),
+        }
+        match m.body[1] {
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                decl: Decl::TsTypeAlias(TsTypeAliasDecl { declare: false, .. }),
+                ..
+            })) => {}
+            ref item => panic!("expected a type alias: {:?}", item),
+        }
+    }
+
+    #[test]
+    fn typescript_only_syntax() {
+        fn is_err(src: &'static str) -> bool {
+            crate::with_test_sess(src, |_, input| {
+                let lexer = Lexer::new(
+                    Syntax::Flow(FlowConfig::default()),
+                    JscTarget::Es2019,
+                    input,
+                    None,
+                );
+                let mut p = Parser::new_from(lexer);
+                let res = p.parse_module();
+
+                Ok(res.is_err() || !p.take_errors().is_empty())
+            })
+            .unwrap()
+        }
+
+        for src in &[
+            "enum A { B }",
+            "const enum A { B }",
+            "namespace A {}",
+            "import a = require('a');",
+            "var a = <T>b;",
+            "a!.b;",
+            "a as T;",
+            "class A { private b: string; }",
+            "class A { constructor(public b) {} }",
+            "abstract class A {}",
+        ] {
+            assert!(is_err(src), "`{}` should be rejected", src);
+        }
+    }
+
+    #[test]
+    fn declare() {
+        module(
+            "declare var a: number;\ndeclare function f(a: string): void;\ndeclare class A { b(): \
+             void }\ndeclare export function g(): void;\ndeclare module 'm' { declare var c: \
+             number; }",
+        );
+    }
+}
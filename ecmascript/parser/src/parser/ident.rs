@@ -121,7 +121,8 @@ impl<'a, I: Tokens> Parser<I> {
                 Word::Keyword(Keyword::Await) if p.ctx().module => {
                     syntax_error!(p.input.prev_span(), SyntaxError::ExpectedIdent)
                 }
-                Word::Keyword(Keyword::This) if p.input.syntax().typescript() => {
+                // `this` parameters are shared with flow.
+                Word::Keyword(Keyword::This) if p.input.syntax().typescript_or_flow() => {
                     Ok(js_word!("this"))
                 }
                 Word::Keyword(Keyword::Let) => Ok(js_word!("let")),
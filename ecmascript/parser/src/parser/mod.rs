@@ -23,11 +23,13 @@ use test::Bencher;
 mod macros;
 mod class_and_fn;
 mod expr;
+mod flow;
 mod ident;
 pub mod input;
 mod jsx;
 mod object;
 mod pat;
+mod recovery;
 mod stmt;
 mod typescript;
 mod util;
@@ -40,6 +42,8 @@ pub type PResult<T> = Result<T, Error>;
 pub struct Parser<I: Tokens> {
     /// [false] while backtracking
     emit_err: bool,
+    /// [true] if the parser should recover from errors.
+    recovery: bool,
     state: State,
     input: Buffer<I>,
 }
@@ -62,11 +66,33 @@ impl<I: Tokens> Parser<I> {
     pub fn new_from(input: I) -> Self {
         Parser {
             emit_err: true,
+            recovery: false,
             input: Buffer::new(input),
             state: Default::default(),
         }
     }
 
+    /// Enables resilient parsing.
+    ///
+    /// In recovery mode, the parser does not bail on the first error. Instead,
+    /// it records the error and resynchronizes at the next statement, class
+    /// member or object member. Each node which failed to parse is replaced by
+    /// a placeholder containing [Invalid], so `parse_module` returns a full
+    /// [Module] even for broken input. Errors are available from
+    /// [Parser::take_errors].
+    ///
+    /// Placeholders are
+    ///
+    ///  - an expression statement of [Expr::Invalid] for statements and module
+    ///    items.
+    ///  - a class property with [Expr::Invalid] as key for class members.
+    ///  - a key-value property with [Expr::Invalid] as computed key for object
+    ///    members.
+    pub fn with_recovery(mut self, recovery: bool) -> Self {
+        self.recovery = recovery;
+        self
+    }
+
     pub fn take_errors(&mut self) -> Vec<Error> {
         self.input().take_errors()
     }
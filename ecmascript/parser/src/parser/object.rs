@@ -1,6 +1,6 @@
 //! Parser for object literal.
 
-use super::*;
+use super::{recovery::Boundary, *};
 use crate::parser::class_and_fn::is_not_this;
 use swc_atoms::js_word;
 use swc_common::Spanned;
@@ -19,18 +19,33 @@ impl<'a, I: Tokens> Parser<I> {
 
         let mut first = true;
         while !eat!('}') {
-            // Handle comma
-            if first {
-                first = false;
-            } else {
-                expect!(',');
-                if eat!('}') {
-                    break;
+            let prop_start = cur_pos!();
+            let res = self.parse_with(|p| {
+                // Handle comma
+                if first {
+                    first = false;
+                } else {
+                    expect!(',');
+                    if is!('}') {
+                        return Ok(None);
+                    }
                 }
-            }
 
-            let prop = self.parse_object_prop()?;
-            props.push(prop);
+                p.parse_object_prop().map(Some)
+            });
+
+            match res {
+                Ok(Some(prop)) => props.push(prop),
+                Ok(None) => {}
+                Err(err) => {
+                    if !self.should_recover() || self.input.cur().is_none() {
+                        return Err(err);
+                    }
+
+                    let span = self.recover(prop_start, err, Boundary::ObjectMember);
+                    props.push(self.make_invalid_prop(span));
+                }
+            }
         }
 
         self.make_object(span!(start), props)
@@ -113,6 +128,16 @@ impl<I: Tokens> ParseObject<Box<Expr>> for Parser<I> {
         Ok(Box::new(Expr::Object(ObjectLit { span, props })))
     }
 
+    fn make_invalid_prop(&mut self, span: Span) -> Self::Prop {
+        PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+            key: PropName::Computed(ComputedPropName {
+                span,
+                expr: Box::new(Expr::Invalid(Invalid { span })),
+            }),
+            value: Box::new(Expr::Invalid(Invalid { span })),
+        })))
+    }
+
     /// spec: 'PropertyDefinition'
     fn parse_object_prop(&mut self) -> PResult<Self::Prop> {
         let start = cur_pos!();
@@ -182,7 +207,7 @@ impl<I: Tokens> ParseObject<Box<Expr>> for Parser<I> {
         }
 
         // Handle `a(){}` (and async(){} / get(){} / set(){})
-        if (self.input.syntax().typescript() && is!('<')) || is!('(') {
+        if (self.input.syntax().typescript_or_flow() && is!('<')) || is!('(') {
             return self
                 .parse_fn_args_body(
                     // no decorator in an object literal
@@ -386,6 +411,16 @@ impl<I: Tokens> ParseObject<Pat> for Parser<I> {
         }))
     }
 
+    fn make_invalid_prop(&mut self, span: Span) -> Self::Prop {
+        ObjectPatProp::KeyValue(KeyValuePatProp {
+            key: PropName::Computed(ComputedPropName {
+                span,
+                expr: Box::new(Expr::Invalid(Invalid { span })),
+            }),
+            value: Box::new(Pat::Invalid(Invalid { span })),
+        })
+    }
+
     /// Production 'BindingProperty'
     fn parse_object_prop(&mut self) -> PResult<Self::Prop> {
         let start = cur_pos!();
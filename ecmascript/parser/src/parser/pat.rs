@@ -159,7 +159,7 @@ impl<'a, I: Tokens> Parser<I> {
         let mut pat = self.parse_binding_element()?;
         let mut opt = false;
 
-        if self.input.syntax().typescript() {
+        if self.input.syntax().typescript_or_flow() {
             if eat!('?') {
                 match pat {
                     Pat::Ident(Ident {
@@ -273,7 +273,7 @@ impl<'a, I: Tokens> Parser<I> {
                 let dot3_token = span!(pat_start);
 
                 let pat = self.parse_binding_pat_or_ident()?;
-                let type_ann = if self.input.syntax().typescript() && is!(':') {
+                let type_ann = if self.input.syntax().typescript_or_flow() && is!(':') {
                     let cur_pos = cur_pos!();
                     Some(self.parse_ts_type_ann(/* eat_colon */ true, cur_pos)?)
                 } else {
@@ -386,7 +386,7 @@ impl<'a, I: Tokens> Parser<I> {
                     .into();
                 }
 
-                let type_ann = if self.input.syntax().typescript() && is!(':') {
+                let type_ann = if self.input.syntax().typescript_or_flow() && is!(':') {
                     let cur_pos = cur_pos!();
                     let ty = self.parse_ts_type_ann(/* eat_colon */ true, cur_pos)?;
                     Some(ty)
@@ -0,0 +1,233 @@
+//! Error recovery for resilient parsing.
+//!
+//! See [Parser::with_recovery].
+
+use super::*;
+use crate::token::Keyword;
+use std::cmp::max;
+
+/// Kind of node the parser resynchronizes at after an error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Boundary {
+    Stmt,
+    ClassMember,
+    ObjectMember,
+}
+
+#[parser]
+impl<'a, I: Tokens> Parser<I> {
+    /// Returns true if an error should be recovered from instead of being
+    /// returned.
+    ///
+    /// Errors are never recovered while backtracking, as the caller of a
+    /// speculative parse relies on the error.
+    pub(super) fn should_recover(&self) -> bool {
+        self.recovery && self.emit_err
+    }
+
+    /// Records `err` and skips tokens until the next `boundary`.
+    ///
+    /// Returns the span of the node which should be replaced by an
+    /// [Invalid] node. The span starts at `start`, the start of the node which
+    /// failed to parse.
+    ///
+    /// This always consumes at least one token unless the node starting at
+    /// `start` already consumed one, so a caller looping until a closing token
+    /// can't loop infinitely. The only exception is eof, which callers should
+    /// check for.
+    pub(super) fn recover(&mut self, start: BytePos, err: Error, boundary: Boundary) -> Span {
+        self.input_ref().add_error(err.clone());
+
+        let mut depth = 0usize;
+        let mut progressed = cur_pos!() > start;
+
+        loop {
+            let had_line_break = self.input.had_line_break_before_cur();
+            let token = match self.input.cur() {
+                Some(token) => token.clone(),
+                None => break,
+            };
+
+            if depth == 0 && progressed {
+                match token {
+                    tok!('}') => break,
+                    tok!(',') if boundary == Boundary::ObjectMember => break,
+                    _ if had_line_break && starts_node(&token, boundary) => break,
+                    _ => {}
+                }
+            }
+
+            match token {
+                tok!('{') | tok!('(') | tok!('[') | tok!("${") => depth += 1,
+                tok!('}') | tok!(')') | tok!(']') => depth = depth.saturating_sub(1),
+                // `err` may come from this token if it was not consumed.
+                Token::Error(ref e) if *e != err => self.input_ref().add_error(e.clone()),
+                _ => {}
+            }
+            self.input.bump();
+            progressed = true;
+
+            if depth == 0 && boundary != Boundary::ObjectMember && token == Token::Semi {
+                break;
+            }
+        }
+
+        Span::new(start, max(start, last_pos!()), Default::default())
+    }
+}
+
+/// Returns true if a `token` after a line break is likely to start a new node.
+fn starts_node(token: &Token, boundary: Boundary) -> bool {
+    match boundary {
+        Boundary::Stmt => match *token {
+            Token::Word(Word::Keyword(kwd)) => match kwd {
+                Keyword::Var
+                | Keyword::Let
+                | Keyword::Const
+                | Keyword::Function
+                | Keyword::Class
+                | Keyword::If
+                | Keyword::For
+                | Keyword::While
+                | Keyword::Do
+                | Keyword::Return
+                | Keyword::Break
+                | Keyword::Continue
+                | Keyword::Try
+                | Keyword::Switch
+                | Keyword::Throw
+                | Keyword::Import
+                | Keyword::Export => true,
+                _ => false,
+            },
+            tok!('@') => true,
+            _ => false,
+        },
+        // Class members are almost always placed on their own line.
+        Boundary::ClassMember => true,
+        Boundary::ObjectMember => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::with_test_sess;
+
+    fn parse(src: &'static str) -> (Module, Vec<Error>) {
+        with_test_sess(src, |_, input| {
+            let lexer = Lexer::new(Syntax::default(), JscTarget::Es2019, input, None);
+            let mut p = Parser::new_from(lexer).with_recovery(true);
+            let module = p.parse_module().expect("parser should recover from errors");
+
+            Ok((module, p.take_errors()))
+        })
+        .unwrap_or_else(|output| panic!("failed to parse \n{}\n{}", src, output))
+    }
+
+    fn is_invalid_stmt(item: &ModuleItem) -> bool {
+        match *item {
+            ModuleItem::Stmt(Stmt::Expr(ExprStmt { ref expr, .. })) => match **expr {
+                Expr::Invalid(..) => true,
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    #[test]
+    fn stmt() {
+        let (module, errors) = parse("var a = ;\nvar b = 1;");
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(module.body.len(), 2);
+        assert!(is_invalid_stmt(&module.body[0]));
+        assert!(!is_invalid_stmt(&module.body[1]));
+    }
+
+    #[test]
+    fn stmt_without_semi() {
+        let (module, errors) = parse("foo(\nvar a = 1;\nbar();");
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(module.body.len(), 3);
+        assert!(is_invalid_stmt(&module.body[0]));
+    }
+
+    #[test]
+    fn class_member() {
+        let (module, errors) = parse("class A { foo( ; bar() {} }");
+
+        assert_eq!(errors.len(), 1);
+        match module.body[0] {
+            ModuleItem::Stmt(Stmt::Decl(Decl::Class(ClassDecl { ref class, .. }))) => {
+                assert_eq!(class.body.len(), 2);
+                match class.body[0] {
+                    ClassMember::ClassProp(ClassProp { ref key, .. }) => match **key {
+                        Expr::Invalid(..) => {}
+                        _ => panic!("expected an invalid key"),
+                    },
+                    _ => panic!("expected an invalid class member"),
+                }
+                match class.body[1] {
+                    ClassMember::Method(..) => {}
+                    _ => panic!("expected a class method"),
+                }
+            }
+            _ => panic!("expected a class declaration"),
+        }
+    }
+
+    #[test]
+    fn object_member() {
+        let (module, errors) = parse("var o = { a: , b: 1 };");
+
+        assert_eq!(errors.len(), 1);
+        match module.body[0] {
+            ModuleItem::Stmt(Stmt::Decl(Decl::Var(ref v))) => match v.decls[0].init {
+                Some(ref init) => match **init {
+                    Expr::Object(ref obj) => {
+                        assert_eq!(obj.props.len(), 2);
+                    }
+                    _ => panic!("expected an object literal"),
+                },
+                None => panic!("expected an initializer"),
+            },
+            _ => panic!("expected a variable declaration"),
+        }
+    }
+
+    #[test]
+    fn unterminated_fn() {
+        let (module, errors) = parse("function foo() {\n  a(");
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(module.body.len(), 1);
+        match module.body[0] {
+            ModuleItem::Stmt(Stmt::Decl(Decl::Fn(..))) => {}
+            _ => panic!("expected a function declaration"),
+        }
+    }
+
+    #[test]
+    fn lexer_error() {
+        let (module, errors) = parse("var a = 08;\nvar b = 1;");
+
+        assert_eq!(errors.len(), 1, "{:?}", errors);
+        assert_eq!(module.body.len(), 2);
+        assert!(is_invalid_stmt(&module.body[0]));
+        assert!(!is_invalid_stmt(&module.body[1]));
+    }
+
+    #[test]
+    fn disabled_by_default() {
+        let res = with_test_sess("var a = ;", |_, input| {
+            let lexer = Lexer::new(Syntax::default(), JscTarget::Es2019, input, None);
+            let mut p = Parser::new_from(lexer);
+
+            Ok(p.parse_module().is_err())
+        });
+
+        assert_eq!(res.ok(), Some(true));
+    }
+}
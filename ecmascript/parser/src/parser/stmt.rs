@@ -1,4 +1,4 @@
-use super::{pat::PatType, *};
+use super::{pat::PatType, recovery::Boundary, *};
 use crate::error::SyntaxError;
 use swc_atoms::js_word;
 use swc_common::Spanned;
@@ -23,10 +23,38 @@ impl<'a, I: Tokens> Parser<I> {
 
         let mut stmts = vec![];
         while {
-            let c = cur!(false).ok();
+            let c = if self.should_recover() {
+                // `cur!` returns errors from lexer, which should be recovered from.
+                self.input.cur()
+            } else {
+                cur!(false).ok()
+            };
             c != end
         } {
-            let stmt = self.parse_stmt_like(true, top_level)?;
+            let start = cur_pos!();
+            let stmt = match self.parse_stmt_like(true, top_level) {
+                Ok(stmt) => stmt,
+                Err(err) => {
+                    if !self.should_recover() {
+                        return Err(err);
+                    }
+
+                    let span = self.recover(start, err, Boundary::Stmt);
+                    if span.lo() != span.hi() {
+                        stmts.push(Type::from(Stmt::Expr(ExprStmt {
+                            span,
+                            expr: Box::new(Expr::Invalid(Invalid { span })),
+                        })));
+                    }
+
+                    if self.input.cur().is_none() {
+                        // Error is already recorded, and there's nothing to parse.
+                        self.set_ctx(old_ctx);
+                        return Ok(stmts);
+                    }
+                    continue;
+                }
+            };
             if allow_directives {
                 allow_directives = false;
                 if stmt.is_use_strict() {
@@ -307,7 +335,7 @@ impl<'a, I: Tokens> Parser<I> {
                 }));
             }
 
-            if self.input.syntax().typescript() {
+            if self.input.syntax().typescript_or_flow() {
                 if let Some(decl) = self.parse_ts_expr_stmt(decorators, ident.clone())? {
                     return Ok(Stmt::Decl(decl));
                 }
@@ -579,7 +607,7 @@ impl<'a, I: Tokens> Parser<I> {
 
             let type_ann_start = cur_pos!();
 
-            if self.syntax().typescript() && eat!(':') {
+            if self.syntax().typescript_or_flow() && eat!(':') {
                 let ctx = Context {
                     in_type: true,
                     ..self.ctx()
@@ -725,7 +753,7 @@ impl<'a, I: Tokens> Parser<I> {
         };
 
         // Typescript extension
-        if self.input.syntax().typescript() && is!(':') {
+        if self.input.syntax().typescript_or_flow() && is!(':') {
             let type_annotation = self.try_parse_ts_type_ann()?;
             match name {
                 Pat::Array(ArrayPat {
@@ -60,12 +60,13 @@ impl<'a, I: Tokens> Parser<I> {
             .map(ModuleItem::from);
         }
 
-        let type_only = self.input.syntax().typescript()
-            && is!("type")
+        // `import typeof A from 'a'` of flow imports the type of a value.
+        let mut type_only = self.input.syntax().typescript_or_flow()
+            && (is!("type") || (self.input.syntax().flow() && is!("typeof")))
             && (peeked_is!('{') || !peeked_is!("from") && !peeked_is!(','));
 
         if type_only {
-            assert_and_bump!("type");
+            bump!();
         }
 
         let mut specifiers = vec![];
@@ -93,6 +94,7 @@ impl<'a, I: Tokens> Parser<I> {
                 }));
             } else if eat!('{') {
                 let mut first = true;
+                let mut has_type_specifier = false;
                 while !eof!() && !is!('}') {
                     if first {
                         first = false;
@@ -100,9 +102,21 @@ impl<'a, I: Tokens> Parser<I> {
                         break;
                     }
 
+                    // Types imported by `import { type A }` of flow are dropped, as the ast
+                    // can't mark a specifier as type-only.
+                    if self.eat_flow_import_specifier_kind()? {
+                        has_type_specifier = true;
+                        self.parse_import_specifier()?;
+                        continue;
+                    }
+
                     specifiers.push(self.parse_import_specifier()?);
                 }
                 expect!('}');
+
+                if has_type_specifier && specifiers.is_empty() {
+                    type_only = true;
+                }
             }
         }
 
@@ -185,7 +199,7 @@ impl<'a, I: Tokens> Parser<I> {
             }
         }
 
-        if self.input.syntax().typescript() && is!(IdentName) {
+        if self.input.syntax().typescript_or_flow() && is!(IdentName) {
             let sym = match *cur!(true)? {
                 Token::Word(ref w) => w.clone().into(),
                 _ => unreachable!(),
@@ -236,7 +250,7 @@ impl<'a, I: Tokens> Parser<I> {
         let mut export_ns = None;
         let ns_export_specifier_start = cur_pos!();
 
-        let type_only = self.input.syntax().typescript() && eat!("type");
+        let type_only = self.input.syntax().typescript_or_flow() && eat!("type");
 
         if eat!('*') {
             has_star = true;
@@ -8,7 +8,7 @@ use swc_common::{Spanned, SyntaxContext};
 impl<I: Tokens> Parser<I> {
     /// `tsNextTokenCanFollowModifier`
     fn ts_next_token_can_follow_modifier(&mut self) -> PResult<bool> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         // Note: TypeScript's implementation is much more complicated because
         // more things are considered modifiers there.
@@ -56,7 +56,7 @@ impl<I: Tokens> Parser<I> {
     /// `tsIsListTerminator`
 
     fn is_ts_list_terminator(&mut self, kind: ParsingContext) -> PResult<bool> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         Ok(match kind {
             ParsingContext::EnumMembers | ParsingContext::TypeMembers => is!('}'),
@@ -73,7 +73,7 @@ impl<I: Tokens> Parser<I> {
     where
         F: FnMut(&mut Self) -> PResult<T>,
     {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         let mut buf = vec![];
         while !self.is_ts_list_terminator(kind)? {
@@ -109,7 +109,7 @@ impl<I: Tokens> Parser<I> {
     where
         F: FnMut(&mut Self) -> PResult<(BytePos, T)>,
     {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         let mut buf = vec![];
 
@@ -160,7 +160,7 @@ impl<I: Tokens> Parser<I> {
     where
         F: FnMut(&mut Self) -> PResult<T>,
     {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         if !skip_first_token {
             if bracket {
@@ -183,7 +183,7 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsParseEntityName`
     fn parse_ts_entity_name(&mut self, allow_reserved_words: bool) -> PResult<TsEntityName> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         let init = self.parse_ident_name()?;
         match init {
@@ -226,7 +226,7 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsParseTypeReference`
     fn parse_ts_type_ref(&mut self) -> PResult<TsTypeRef> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         let start = cur_pos!();
 
@@ -257,7 +257,7 @@ impl<I: Tokens> Parser<I> {
         has_asserts_keyword: bool,
         lhs: TsThisType,
     ) -> PResult<TsTypePredicate> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         let param_name = TsThisTypeOrIdent::TsThisType(lhs);
         let type_ann = if eat!("is") {
@@ -280,7 +280,7 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsParseThisTypeNode`
     fn parse_ts_this_type_node(&mut self) -> PResult<TsThisType> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         expect!("this");
 
@@ -333,7 +333,7 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsParseTypeQuery`
     fn parse_ts_type_query(&mut self) -> PResult<TsTypeQuery> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         let start = cur_pos!();
         expect!("typeof");
@@ -355,12 +355,22 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsParseTypeParameter`
     fn parse_ts_type_param(&mut self) -> PResult<TsTypeParam> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         let start = cur_pos!();
 
+        // Variance sigils of flow are ignored.
+        if self.input.syntax().flow() && is_one_of!('+', '-') {
+            bump!();
+        }
+
         let name = self.parse_ident_name()?;
-        let constraint = self.eat_then_parse_ts_type(&tok!("extends"))?;
+        let constraint = if self.input.syntax().flow() {
+            // Flow uses `T: Bound` instead of `T extends Bound`.
+            self.try_parse_ts_type()?
+        } else {
+            self.eat_then_parse_ts_type(&tok!("extends"))?
+        };
         let default = self.eat_then_parse_ts_type(&tok!('='))?;
 
         Ok(TsTypeParam {
@@ -399,7 +409,7 @@ impl<I: Tokens> Parser<I> {
         &mut self,
         return_token: &'static Token,
     ) -> PResult<TsTypeAnn> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         self.in_type().parse_with(|p| {
             let return_token_start = cur_pos!();
@@ -459,7 +469,7 @@ impl<I: Tokens> Parser<I> {
     where
         F: FnOnce(&mut Self) -> PResult<Option<bool>>,
     {
-        if !self.input.syntax().typescript() {
+        if !self.input.syntax().typescript_or_flow() {
             return Ok(false);
         }
         let mut cloned = self.clone();
@@ -481,7 +491,7 @@ impl<I: Tokens> Parser<I> {
     where
         F: FnOnce(&mut Self) -> PResult<Option<T>>,
     {
-        if !self.input.syntax().typescript() {
+        if !self.input.syntax().typescript_or_flow() {
             return None;
         }
         let mut cloned = self.clone();
@@ -503,7 +513,7 @@ impl<I: Tokens> Parser<I> {
         eat_colon: bool,
         start: BytePos,
     ) -> PResult<TsTypeAnn> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         self.in_type().parse_with(|p| {
             if eat_colon {
@@ -534,8 +544,11 @@ impl<I: Tokens> Parser<I> {
     }
 
     /// `tsExpectThenParseType`
-    fn expect_then_parse_ts_type(&mut self, token: &'static Token) -> PResult<Box<TsType>> {
-        debug_assert!(self.input.syntax().typescript());
+    pub(super) fn expect_then_parse_ts_type(
+        &mut self,
+        token: &'static Token,
+    ) -> PResult<Box<TsType>> {
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         self.in_type().parse_with(|p| {
             if !p.input.eat(token) {
@@ -548,7 +561,7 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsNextThenParseType`
     pub(super) fn next_then_parse_ts_type(&mut self) -> PResult<Box<TsType>> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         self.in_type().parse_with(|p| {
             bump!();
@@ -644,7 +657,7 @@ impl<I: Tokens> Parser<I> {
     fn parse_ts_module_block(&mut self) -> PResult<TsModuleBlock> {
         trace_cur!(parse_ts_module_block);
 
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         let start = cur_pos!();
         expect!('{');
@@ -664,7 +677,7 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsParseModuleOrNamespaceDeclaration`
     fn parse_ts_module_or_ns_decl(&mut self, start: BytePos) -> PResult<TsModuleDecl> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         let id = self.parse_ident_name()?;
         let body: TsNamespaceBody = if eat!('.') {
@@ -696,7 +709,7 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsParseAmbientExternalModuleDeclaration`
     fn parse_ts_ambient_external_module_decl(&mut self, start: BytePos) -> PResult<TsModuleDecl> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         let (global, id) = if is!("global") {
             let id = self.parse_ident_name()?;
@@ -731,7 +744,7 @@ impl<I: Tokens> Parser<I> {
     }
 
     pub fn parse_type(&mut self) -> PResult<Box<TsType>> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         self.in_type().parse_ts_type()
     }
@@ -742,7 +755,7 @@ impl<I: Tokens> Parser<I> {
     pub(super) fn parse_ts_type(&mut self) -> PResult<Box<TsType>> {
         trace_cur!(parse_ts_type);
 
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         // Need to set `state.inType` so that we don't parse JSX in a type context.
         debug_assert!(self.ctx().in_type);
@@ -778,8 +791,15 @@ impl<I: Tokens> Parser<I> {
     fn parse_ts_non_conditional_type(&mut self) -> PResult<Box<TsType>> {
         trace_cur!(parse_ts_non_conditional_type);
 
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
+        if self.input.syntax().flow() && is_one_of!('(', '<') {
+            if let Some(ty) = self.try_parse_ts(|p| p.parse_flow_fn_type().map(Some)) {
+                return Ok(Box::new(TsType::TsFnOrConstructorType(
+                    TsFnOrConstructorType::TsFnType(ty),
+                )));
+            }
+        }
         if self.is_ts_start_of_fn_type()? {
             return self
                 .parse_ts_fn_or_constructor_type(true)
@@ -798,7 +818,7 @@ impl<I: Tokens> Parser<I> {
     }
 
     fn is_ts_start_of_fn_type(&mut self) -> PResult<bool> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         if is!('<') {
             return Ok(true);
@@ -826,7 +846,7 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsParseHeritageClause`
     pub(super) fn parse_ts_heritage_clause(&mut self) -> PResult<Vec<TsExprWithTypeArgs>> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         self.parse_ts_delimited_list(ParsingContext::HeritageClauseElement, |p| {
             p.parse_expr_with_type_args()
@@ -835,7 +855,7 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsParseExpressionWithTypeArguments`
     fn parse_expr_with_type_args(&mut self) -> PResult<TsExprWithTypeArgs> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         let start = cur_pos!();
         // Note: TS uses parseLeftHandSideExpressionOrHigher,
@@ -856,7 +876,7 @@ impl<I: Tokens> Parser<I> {
     }
     /// `tsParseInterfaceDeclaration`
     pub(super) fn parse_ts_interface_decl(&mut self, start: BytePos) -> PResult<TsInterfaceDecl> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         let id = self.parse_ident_name()?;
         match id.sym {
@@ -915,7 +935,7 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsParseTypeAliasDeclaration`
     fn parse_ts_type_alias_decl(&mut self, start: BytePos) -> PResult<TsTypeAliasDecl> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         let id = self.parse_ident_name()?;
         let type_params = self.try_parse_ts_type_params()?;
@@ -998,7 +1018,7 @@ impl<I: Tokens> Parser<I> {
     where
         F: FnOnce(&mut Self) -> PResult<T>,
     {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         let mut cloned = self.clone();
         cloned.emit_err = false;
@@ -1008,7 +1028,7 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsIsUnambiguouslyStartOfFunctionType`
     fn is_ts_unambiguously_start_of_fn_type(&mut self) -> PResult<bool> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         assert_and_bump!('(');
         if is_one_of!(')', "...") {
@@ -1034,7 +1054,7 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsSkipParameterStart`
     fn skip_ts_parameter_start(&mut self) -> PResult<bool> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         let _ = self.eat_any_ts_modifier()?;
 
@@ -1078,9 +1098,9 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsParseTypeMemberSemicolon`
     fn parse_ts_type_member_semicolon(&mut self) -> PResult<()> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
-        if !eat!(',') {
+        if !eat!(',') && !self.is_flow_exact_object_end()? {
             expect!(';');
         }
 
@@ -1092,7 +1112,7 @@ impl<I: Tokens> Parser<I> {
         &mut self,
         kind: SignatureParsingMode,
     ) -> PResult<Either<TsCallSignatureDecl, TsConstructSignatureDecl>> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         let start = cur_pos!();
 
@@ -1135,7 +1155,7 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsIsUnambiguouslyIndexSignature`
     fn is_ts_unambiguously_index_signature(&mut self) -> PResult<bool> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         // Note: babel's comment is wrong
         assert_and_bump!('['); // Skip '['
@@ -1191,7 +1211,7 @@ impl<I: Tokens> Parser<I> {
         start: BytePos,
         readonly: bool,
     ) -> PResult<Either<TsPropertySignature, TsMethodSignature>> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         // ----- inlined self.parsePropertyName(node);
         let (computed, key) = if eat!('[') {
@@ -1264,8 +1284,8 @@ impl<I: Tokens> Parser<I> {
     }
 
     /// `tsParseTypeMember`
-    fn parse_ts_type_member(&mut self) -> PResult<TsTypeElement> {
-        debug_assert!(self.input.syntax().typescript());
+    pub(super) fn parse_ts_type_member(&mut self) -> PResult<TsTypeElement> {
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         fn into_type_elem(
             e: Either<TsCallSignatureDecl, TsConstructSignatureDecl>,
@@ -1303,7 +1323,7 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsIsStartOfConstructSignature`
     fn is_ts_start_of_construct_signature(&mut self) -> PResult<bool> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         bump!();
 
@@ -1312,7 +1332,7 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsParseTypeLiteral`
     fn parse_ts_type_lit(&mut self) -> PResult<TsTypeLit> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         let start = cur_pos!();
         let members = self.parse_ts_object_type_members()?;
@@ -1324,7 +1344,7 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsParseObjectTypeMembers`
     fn parse_ts_object_type_members(&mut self) -> PResult<Vec<TsTypeElement>> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         expect!('{');
         let members =
@@ -1335,7 +1355,7 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsIsStartOfMappedType`
     fn is_ts_start_of_mapped_type(&mut self) -> PResult<bool> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         bump!();
         if eat!('+') || eat!('-') {
@@ -1358,7 +1378,7 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsParseMappedTypeParameter`
     fn parse_ts_mapped_type_param(&mut self) -> PResult<TsTypeParam> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         let start = cur_pos!();
         let name = self.parse_ident_name()?;
@@ -1375,7 +1395,7 @@ impl<I: Tokens> Parser<I> {
     /// `tsParseMappedType`
     #[allow(clippy::cognitive_complexity)]
     fn parse_ts_mapped_type(&mut self) -> PResult<TsMappedType> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         let start = cur_pos!();
         expect!('{');
@@ -1424,7 +1444,7 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsParseTupleType`
     fn parse_ts_tuple_type(&mut self) -> PResult<TsTupleType> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         let start = cur_pos!();
         let elem_types = self.parse_ts_bracketed_list(
@@ -1461,7 +1481,7 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsParseTupleElementType`
     fn parse_ts_tuple_element_type(&mut self) -> PResult<TsTupleElement> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         // parses `...TsType[]`
         let start = cur_pos!();
@@ -1509,7 +1529,7 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsParseParenthesizedType`
     fn parse_ts_parenthesized_type(&mut self) -> PResult<TsParenthesizedType> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         let start = cur_pos!();
         expect!('(');
@@ -1528,7 +1548,7 @@ impl<I: Tokens> Parser<I> {
     ) -> PResult<TsFnOrConstructorType> {
         trace_cur!(parse_ts_fn_or_constructor_type);
 
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         let start = cur_pos!();
         if !is_fn_type {
@@ -1561,7 +1581,7 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsParseLiteralTypeNode`
     fn parse_ts_lit_type_node(&mut self) -> PResult<TsLitType> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         let start = cur_pos!();
 
@@ -1590,7 +1610,7 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsParseBindingListForSignature`
     fn parse_ts_binding_list_for_signature(&mut self) -> PResult<Vec<TsFnParam>> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         let params = self.parse_formal_params()?;
         let mut list = vec![];
@@ -1632,7 +1652,7 @@ impl<I: Tokens> Parser<I> {
     }
 
     /// `tsTryParseType`
-    fn try_parse_ts_type(&mut self) -> PResult<Option<Box<TsType>>> {
+    pub(super) fn try_parse_ts_type(&mut self) -> PResult<Option<Box<TsType>>> {
         self.eat_then_parse_ts_type(&tok!(':'))
     }
 
@@ -1647,9 +1667,10 @@ impl<I: Tokens> Parser<I> {
     /// `tsParseNonArrayType`
     #[allow(clippy::cognitive_complexity)]
     fn parse_ts_non_array_type(&mut self) -> PResult<Box<TsType>> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         let start = cur_pos!();
+        let is_flow = self.input.syntax().flow();
 
         match *cur!(true)? {
             Token::Word(Word::Ident(..))
@@ -1760,6 +1781,13 @@ impl<I: Tokens> Parser<I> {
                 return self.parse_ts_type_query().map(TsType::from).map(Box::new);
             }
 
+            tok!('*') if is_flow => {
+                return self.parse_flow_existential_type();
+            }
+
+            tok!('{') if is_flow => {
+                return self.parse_flow_object_type();
+            }
             tok!('{') => {
                 return if self.ts_look_ahead(|p| p.is_ts_start_of_mapped_type())? {
                     self.parse_ts_mapped_type().map(TsType::from).map(Box::new)
@@ -1786,7 +1814,7 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsParseArrayTypeOrHigher`
     fn parse_ts_array_type_or_higher(&mut self, readonly: bool) -> PResult<Box<TsType>> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         let mut ty = self.parse_ts_non_array_type()?;
 
@@ -1813,7 +1841,7 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsParseTypeOperator`
     fn parse_ts_type_operator(&mut self, op: TsTypeOperatorOp) -> PResult<TsTypeOperator> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         let start = cur_pos!();
         match op {
@@ -1832,7 +1860,7 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsParseInferType`
     fn parse_ts_infer_type(&mut self) -> PResult<TsInferType> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         let start = cur_pos!();
         expect!("infer");
@@ -1850,8 +1878,12 @@ impl<I: Tokens> Parser<I> {
     }
 
     /// `tsParseTypeOperatorOrHigher`
-    fn parse_ts_type_operator_or_higher(&mut self) -> PResult<Box<TsType>> {
-        debug_assert!(self.input.syntax().typescript());
+    pub(super) fn parse_ts_type_operator_or_higher(&mut self) -> PResult<Box<TsType>> {
+        debug_assert!(self.input.syntax().typescript_or_flow());
+
+        if self.input.syntax().flow() && is!('?') {
+            return self.parse_flow_maybe_type();
+        }
 
         let operator = if is!("keyof") {
             Some(TsTypeOperatorOp::KeyOf)
@@ -1919,7 +1951,7 @@ impl<I: Tokens> Parser<I> {
                     Ok(None)
                 }
             }
-            "global" => {
+            "global" if self.input.syntax().typescript() => {
                 // `global { }` (with no `declare`) may appear inside an ambient module
                 // declaration.
                 // Would like to use tsParseAmbientExternalModuleDeclaration here, but already
@@ -1960,7 +1992,8 @@ impl<I: Tokens> Parser<I> {
             "try_parse_ts_declare should be called after eating `declare`"
         );
 
-        if self.ctx().in_declare {
+        // Flow allows `declare` in ambient modules.
+        if self.ctx().in_declare && !self.input.syntax().flow() {
             let span_of_declare = span!(start);
             self.emit_err(span_of_declare, SyntaxError::TS1038);
         }
@@ -1971,6 +2004,11 @@ impl<I: Tokens> Parser<I> {
         };
 
         self.with_ctx(ctx).parse_with(|p| {
+            // `declare export function f(): void;` of flow.
+            if p.input.syntax().flow() && eat!("export") {
+                eat!("default");
+            }
+
             if is!("function") {
                 return p
                     .parse_fn_decl(decorators)
@@ -1991,7 +2029,7 @@ impl<I: Tokens> Parser<I> {
                     .map(Some);
             }
 
-            if is!("const") && peeked_is!("enum") {
+            if p.input.syntax().typescript() && is!("const") && peeked_is!("enum") {
                 assert_and_bump!("const");
                 let _ = cur!(true);
                 assert_and_bump!("enum");
@@ -2016,7 +2054,7 @@ impl<I: Tokens> Parser<I> {
                     .map(Some);
             }
 
-            if is!("global") {
+            if p.input.syntax().typescript() && is!("global") {
                 return p
                     .parse_ts_ambient_external_module_decl(start)
                     .map(Decl::from)
@@ -2068,7 +2106,7 @@ impl<I: Tokens> Parser<I> {
         next: bool,
     ) -> PResult<Option<Decl>> {
         match value {
-            js_word!("abstract") => {
+            js_word!("abstract") if self.input.syntax().typescript() => {
                 if next || is!("class") {
                     if next {
                         bump!();
@@ -2089,7 +2127,7 @@ impl<I: Tokens> Parser<I> {
                 }
             }
 
-            js_word!("enum") => {
+            js_word!("enum") if self.input.syntax().typescript() => {
                 if next || is!(IdentRef) {
                     if next {
                         bump!();
@@ -2114,7 +2152,8 @@ impl<I: Tokens> Parser<I> {
                 }
             }
 
-            js_word!("module") => {
+            // Flow has only `declare module`.
+            js_word!("module") if self.input.syntax().typescript() || self.ctx().in_declare => {
                 if next {
                     bump!();
                 }
@@ -2135,7 +2174,7 @@ impl<I: Tokens> Parser<I> {
                 }
             }
 
-            js_word!("namespace") => {
+            js_word!("namespace") if self.input.syntax().typescript() => {
                 if next || is!(IdentRef) {
                     if next {
                         bump!();
@@ -2159,6 +2198,18 @@ impl<I: Tokens> Parser<I> {
                 }
             }
 
+            js_word!("opaque") if self.input.syntax().flow() => {
+                if next || is!("type") {
+                    if next {
+                        bump!();
+                    }
+                    return self
+                        .parse_flow_opaque_type_alias(start)
+                        .map(From::from)
+                        .map(Some);
+                }
+            }
+
             _ => {}
         }
 
@@ -2219,7 +2270,7 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsParseTypeArguments`
     pub fn parse_ts_type_args(&mut self) -> PResult<TsTypeParamInstantiation> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         let start = cur_pos!();
         let params = self.in_type().parse_with(|p| {
@@ -2245,7 +2296,7 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsParseIntersectionTypeOrHigher`
     fn parse_ts_intersection_type_or_higher(&mut self) -> PResult<Box<TsType>> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         self.parse_ts_union_or_intersection_type(
             UnionOrIntersection::Intersection,
@@ -2256,7 +2307,7 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsParseUnionTypeOrHigher`
     fn parse_ts_union_type_or_higher(&mut self) -> PResult<Box<TsType>> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         self.parse_ts_union_or_intersection_type(
             UnionOrIntersection::Union,
@@ -2275,16 +2326,16 @@ impl<I: Tokens> Parser<I> {
     where
         F: FnMut(&mut Self) -> PResult<Box<TsType>>,
     {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         let start = cur_pos!(); // include the leading operator in the start
         self.input.eat(operator);
 
         let ty = parse_constituent_type(self)?;
-        if self.input.is(&operator) {
+        if self.input.is(&operator) && !self.is_flow_exact_object_end()? {
             let mut types = vec![ty];
 
-            while self.input.eat(operator) {
+            while !self.is_flow_exact_object_end()? && self.input.eat(operator) {
                 types.push(parse_constituent_type(self)?);
             }
 
@@ -2312,7 +2363,7 @@ impl<I: Tokens> Parser<I> {
     where
         F: FnOnce(&mut Self) -> PResult<T>,
     {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().typescript_or_flow());
 
         let cloned = self.input.token_context().clone();
         self.input
@@ -142,6 +142,8 @@ pub trait ParseObject<Obj> {
     type Prop;
     fn make_object(&mut self, span: Span, props: Vec<Self::Prop>) -> PResult<Obj>;
     fn parse_object_prop(&mut self) -> PResult<Self::Prop>;
+    /// Creates a placeholder for a property which failed to parse.
+    fn make_invalid_prop(&mut self, span: Span) -> Self::Prop;
 }
 
 pub struct WithState<'w, I: 'w + Tokens> {
@@ -557,6 +557,94 @@ impl Token {
     }
 }
 
+/// Coarse classification of a token, intended for syntax highlighters and
+/// formatters.
+///
+/// Unlike [Token], this does not carry any value and is not affected by
+/// changes to the internal representation of tokens.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenKind {
+    /// Spaces, tabs and other non-line-terminating white spaces.
+    Whitespace,
+    /// A single line terminator. `\r\n` is one newline.
+    Newline,
+    /// `// comment`, without the line terminator.
+    LineComment,
+    /// `/* comment */`
+    BlockComment,
+    /// `#!/usr/bin/env node`
+    Shebang,
+
+    /// Reserved words and contextual keywords like `let` and `await`.
+    Keyword,
+    Ident,
+    /// `null`
+    Null,
+    /// `true` or `false`
+    Bool,
+    Str,
+    Num,
+    BigInt,
+    Regex,
+    /// Raw text of a template literal, excluding `` ` `` and `${`.
+    Template,
+
+    /// Brackets, separators and delimiters like `(`, `;`, `=>` and `${`.
+    Punct,
+    /// Unary, binary, update and assignment operators.
+    Operator,
+
+    JSXName,
+    JSXText,
+
+    /// A token which could not be lexed.
+    Error,
+}
+
+impl TokenKind {
+    /// Returns true for white spaces, newlines and comments.
+    pub fn is_trivia(self) -> bool {
+        match self {
+            TokenKind::Whitespace
+            | TokenKind::Newline
+            | TokenKind::LineComment
+            | TokenKind::BlockComment => true,
+            _ => false,
+        }
+    }
+}
+
+impl Token {
+    pub fn kind(&self) -> TokenKind {
+        match *self {
+            Word(Word::Keyword(..)) => TokenKind::Keyword,
+            Word(Word::Null) => TokenKind::Null,
+            Word(Word::True) | Word(Word::False) => TokenKind::Bool,
+            Word(Word::Ident(..)) => TokenKind::Ident,
+
+            Str { .. } => TokenKind::Str,
+            Num(..) => TokenKind::Num,
+            BigInt(..) => TokenKind::BigInt,
+            Regex(..) => TokenKind::Regex,
+            Template { .. } => TokenKind::Template,
+
+            BinOp(..) | AssignOp(..) | PlusPlus | MinusMinus | Bang | Tilde | QuestionMark => {
+                TokenKind::Operator
+            }
+
+            Arrow | Hash | At | Dot | DotDotDot | LParen | RParen | LBracket | RBracket
+            | LBrace | RBrace | Semi | Comma | BackQuote | Colon | ColonColon | DollarLBrace
+            | JSXTagStart | JSXTagEnd => TokenKind::Punct,
+
+            JSXName { .. } => TokenKind::JSXName,
+            JSXText { .. } => TokenKind::JSXText,
+            Shebang(..) => TokenKind::Shebang,
+            Token::Error(..) => TokenKind::Error,
+        }
+    }
+}
+
 impl Word {
     pub(crate) fn cow(&self) -> Cow<JsWord> {
         match *self {
@@ -23,7 +23,7 @@ fn run(b: &mut Bencher, src: &str, config: Config) {
             e.into_diagnostic(&handler).emit()
         }
 
-        let mut folder = preset_env(Mark::fresh(Mark::root()), config);
+        let mut folder = preset_env(Mark::fresh(Mark::root()), None, config);
 
         b.iter(|| test::black_box(module.clone().fold_with(&mut folder)));
         Ok(())
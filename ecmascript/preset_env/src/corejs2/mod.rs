@@ -4,7 +4,7 @@ use self::{
     data::{BUILTIN_TYPES, INSTANCE_PROPERTIES, STATIC_PROPERTIES},
 };
 use crate::{util::DataMapExt, version::should_enable, Versions};
-use fxhash::FxHashSet;
+use fxhash::FxHashMap;
 use swc_atoms::{js_word, JsWord};
 use swc_common::DUMMY_SP;
 use swc_ecma_ast::*;
@@ -17,7 +17,8 @@ mod entry;
 pub(crate) struct UsageVisitor {
     is_any_target: bool,
     target: Versions,
-    pub required: FxHashSet<&'static str>,
+    /// Required modules, with the usage which requires it.
+    pub required: FxHashMap<&'static str, JsWord>,
 }
 
 impl UsageVisitor {
@@ -53,39 +54,39 @@ impl UsageVisitor {
         v
     }
 
-    /// Add imports
-    fn add(&mut self, features: &'static [&'static str]) {
+    /// Add imports required by `usage`.
+    fn add(&mut self, usage: &str, features: &'static [&'static str]) {
         let UsageVisitor {
             is_any_target,
             target,
             ..
         } = self;
 
-        self.required.extend(features.iter().filter_map(|f| {
+        for f in features {
             if !*is_any_target {
                 if let Some(v) = BUILTINS.get(&**f) {
                     // Skip
                     if !should_enable(*target, *v, true) {
-                        return None;
+                        continue;
                     }
                 }
             }
 
-            Some(f)
-        }));
+            self.required.entry(*f).or_insert_with(|| usage.into());
+        }
     }
 
     fn add_property_deps_inner(&mut self, obj: Option<&JsWord>, prop: &JsWord) {
         if let Some(obj) = obj {
             if let Some(map) = STATIC_PROPERTIES.get_data(&obj) {
                 if let Some(features) = map.get_data(&prop) {
-                    self.add(features);
+                    self.add(&format!("{}.{}", obj, prop), features);
                 }
             }
         }
 
         if let Some(features) = INSTANCE_PROPERTIES.get_data(&prop) {
-            self.add(features);
+            self.add(prop, features);
         }
     }
 
@@ -130,7 +131,7 @@ impl Visit for UsageVisitor {
 
         for (name, builtin) in BUILTIN_TYPES {
             if node.sym == **name {
-                self.add(builtin)
+                self.add(name, builtin)
             }
         }
     }
@@ -245,14 +246,14 @@ impl Visit for UsageVisitor {
                 //
                 for (name, imports) in INSTANCE_PROPERTIES {
                     if i.sym == **name {
-                        self.add(imports)
+                        self.add(name, imports)
                     }
                 }
             }
             Expr::Lit(Lit::Str(Str { ref value, .. })) if node.computed => {
                 for (name, imports) in INSTANCE_PROPERTIES {
                     if *value == **name {
-                        self.add(imports);
+                        self.add(name, imports);
                     }
                 }
             }
@@ -268,7 +269,7 @@ impl Visit for UsageVisitor {
                                 Expr::Lit(Lit::Str(Str { ref value, .. })) if node.computed => {
                                     for (name, imports) in INSTANCE_PROPERTIES {
                                         if *value == **name {
-                                            self.add(imports);
+                                            self.add(name, imports);
                                         }
                                     }
                                 }
@@ -276,7 +277,7 @@ impl Visit for UsageVisitor {
                                 Expr::Ident(ref p) if !node.computed => {
                                     for (prop, imports) in *props {
                                         if p.sym == **prop {
-                                            self.add(imports);
+                                            self.add(&format!("{}.{}", ty, prop), imports);
                                         }
                                     }
                                 }
@@ -308,7 +309,7 @@ impl Visit for UsageVisitor {
             },
             _ => false,
         } {
-            self.add(&["web.dom.iterable"])
+            self.add("Symbol.iterator", &["web.dom.iterable"])
         }
     }
 
@@ -319,7 +320,9 @@ impl Visit for UsageVisitor {
         e.visit_children_with(self);
 
         match e.op {
-            op!("in") if is_symbol_iterator(&e.left) => self.add(&["web.dom.iterable"]),
+            op!("in") if is_symbol_iterator(&e.left) => {
+                self.add("Symbol.iterator", &["web.dom.iterable"])
+            }
             _ => {}
         }
     }
@@ -331,7 +334,7 @@ impl Visit for UsageVisitor {
         println!("Yield");
 
         if e.delegate {
-            self.add(&["web.dom.iterable"])
+            self.add("yield*", &["web.dom.iterable"])
         }
     }
 }
@@ -52,6 +52,13 @@ static TYPED_ARRAY_STATIC_METHODS: FeatureMap = data_map!(Map {
     of: &["es.typed-array.of"],
 });
 
+/// Proposals which are polyfilled if `shippedProposals` is enabled.
+pub static SHIPPED_PROPOSALS: &[&str] = &[
+    "esnext.global-this",
+    "esnext.promise.all-settled",
+    "esnext.string.match-all",
+];
+
 //
 pub static PROMISE_DEPENDENCIES: &[&str] = &["es.promise", "es.object.to-string"];
 
@@ -910,13 +917,13 @@ pub static STATIC_PROPERTIES: DataMap<DataMap<&[&str]>> = data_map!(Map {
     Float64Array: &TYPED_ARRAY_STATIC_METHODS,
 });
 
-//pub static COMMON_INSTANCE_DEPENDENCIES: &[&str] = &[
-//    "es.object.to-string",
-//    "es.object.define-getter",
-//    "es.object.define-setter",
-//    "es.object.lookup-getter",
-//    "es.object.lookup-setter",
-//    "es.regexp.exec",
-//];
+pub static COMMON_INSTANCE_DEPENDENCIES: &[&str] = &[
+    "es.object.to-string",
+    "es.object.define-getter",
+    "es.object.define-setter",
+    "es.object.lookup-getter",
+    "es.object.lookup-setter",
+    "es.regexp.exec",
+];
 
 pub static POSSIBLE_GLOBAL_OBJECTS: &[&str] = &["global", "globalThis", "self", "window"];
@@ -3,29 +3,36 @@ use crate::{
     corejs3::{
         compat::DATA as CORE_JS_COMPAT_DATA,
         data::{
-            COMMON_ITERATORS, INSTANCE_PROPERTIES, POSSIBLE_GLOBAL_OBJECTS, PROMISE_DEPENDENCIES,
-            STATIC_PROPERTIES,
+            COMMON_INSTANCE_DEPENDENCIES, COMMON_ITERATORS, INSTANCE_PROPERTIES,
+            POSSIBLE_GLOBAL_OBJECTS, PROMISE_DEPENDENCIES, SHIPPED_PROPOSALS, STATIC_PROPERTIES,
         },
     },
     util::DataMapExt,
     version::should_enable,
     Versions,
 };
-use fxhash::FxHashSet;
+use fxhash::FxHashMap;
 use swc_atoms::{js_word, JsWord};
-use swc_common::DUMMY_SP;
+use swc_common::{SyntaxContext, DUMMY_SP};
 use swc_ecma_ast::*;
+use swc_ecma_transforms::util::{id, Id};
 use swc_ecma_visit::{noop_visit_type, Node, Visit, VisitWith};
 
 pub(crate) struct UsageVisitor {
     shipped_proposals: bool,
+    proposals: bool,
     is_any_target: bool,
     target: Versions,
-    pub required: FxHashSet<&'static str>,
+    /// Types of variables, inferred from their initializers.
+    ///
+    /// [None] if the type is unknown.
+    types: FxHashMap<Id, Option<&'static str>>,
+    /// Required modules, with the usage which requires it.
+    pub required: FxHashMap<&'static str, JsWord>,
 }
 
 impl UsageVisitor {
-    pub fn new(target: Versions, shipped_proposals: bool) -> Self {
+    pub fn new(target: Versions, shipped_proposals: bool, proposals: bool) -> Self {
         //        let mut v = Self { required: vec![] };
         //
         //
@@ -47,24 +54,30 @@ impl UsageVisitor {
 
         Self {
             shipped_proposals,
+            proposals,
             is_any_target: target.is_any_target(),
             target,
+            types: Default::default(),
             required: Default::default(),
         }
     }
 
-    /// Add imports
-    fn add(&mut self, features: &[&'static str]) {
+    /// Add imports required by `usage`.
+    fn add(&mut self, usage: &str, features: &[&'static str]) {
         let UsageVisitor {
             shipped_proposals,
+            proposals,
             is_any_target,
             target,
             ..
         } = self;
 
-        self.required.extend(features.iter().filter_map(|f| {
-            if !*shipped_proposals && f.starts_with("esnext.") {
-                return None;
+        for f in features {
+            if f.starts_with("esnext.")
+                && !*proposals
+                && !(*shipped_proposals && SHIPPED_PROPOSALS.contains(f))
+            {
+                continue;
             }
 
             let feature = CORE_JS_COMPAT_DATA.get(&**f);
@@ -72,39 +85,40 @@ impl UsageVisitor {
             if !*is_any_target {
                 if let Some(feature) = feature {
                     if !should_enable(*target, *feature, true) {
-                        return None;
+                        continue;
                     }
                 }
             }
 
-            Some(f)
-        }));
+            self.required.entry(*f).or_insert_with(|| usage.into());
+        }
     }
 
     fn add_builtin(&mut self, built_in: &str) {
         if let Some(features) = BUILTINS.get_data(built_in) {
-            self.add(features)
+            self.add(built_in, features)
         }
     }
-    fn add_property_deps(&mut self, obj: &Expr, prop: &Expr) {
-        let prop = match prop {
-            Expr::Ident(i) => &i.sym,
-            Expr::Lit(Lit::Str(s)) => &s.value,
-            _ => return,
-        };
+
+    fn add_property_deps(&mut self, obj: &Expr, prop: &JsWord) {
+        let obj_ty = self.type_of(obj);
 
         let obj = match obj {
-            Expr::Ident(i) => &i.sym,
-            _ => {
-                self.add_property_deps_inner(None, prop);
-                return;
-            }
+            Expr::Ident(i) if is_global(i) => Some(&i.sym),
+            _ => None,
         };
 
-        self.add_property_deps_inner(Some(obj), prop)
+        self.add_property_deps_inner(obj, obj_ty, prop)
     }
 
-    fn add_property_deps_inner(&mut self, obj: Option<&JsWord>, prop: &JsWord) {
+    /// `obj` is the name of the object if it's a global variable, and `obj_ty`
+    /// is the type of the object if it's known.
+    fn add_property_deps_inner(
+        &mut self,
+        obj: Option<&JsWord>,
+        obj_ty: Option<&str>,
+        prop: &JsWord,
+    ) {
         if let Some(obj) = obj {
             if POSSIBLE_GLOBAL_OBJECTS.contains(&&**obj) {
                 self.add_builtin(prop);
@@ -112,19 +126,34 @@ impl UsageVisitor {
 
             if let Some(map) = STATIC_PROPERTIES.get_data(&obj) {
                 if let Some(features) = map.get_data(&prop) {
-                    self.add(features);
+                    self.add(&format!("{}.{}", obj, prop), features);
                 }
             }
         }
 
         if let Some(features) = INSTANCE_PROPERTIES.get_data(&prop) {
-            self.add(features);
+            match obj_ty {
+                // e.g. `''.includes` does not require `es.array.includes`
+                Some(ty) => {
+                    let features = features
+                        .iter()
+                        .copied()
+                        .filter(|f| {
+                            f.split('.').nth(1) == Some(ty)
+                                || COMMON_INSTANCE_DEPENDENCIES.contains(f)
+                        })
+                        .collect::<Vec<_>>();
+                    self.add(prop, &features)
+                }
+                None => self.add(prop, features),
+            }
         }
     }
 
     fn visit_object_pat_props(&mut self, obj: &Expr, props: &[ObjectPatProp]) {
+        let obj_ty = self.type_of(obj);
         let obj = match obj {
-            Expr::Ident(i) => Some(&i.sym),
+            Expr::Ident(i) if is_global(i) => Some(&i.sym),
             _ => None,
         };
 
@@ -133,25 +162,43 @@ impl UsageVisitor {
                 ObjectPatProp::KeyValue(KeyValuePatProp {
                     key: PropName::Ident(i),
                     ..
-                }) => self.add_property_deps_inner(obj, &i.sym),
+                }) => self.add_property_deps_inner(obj, obj_ty, &i.sym),
                 ObjectPatProp::Assign(AssignPatProp { key, .. }) => {
-                    self.add_property_deps_inner(obj, &key.sym)
+                    self.add_property_deps_inner(obj, obj_ty, &key.sym)
                 }
 
                 _ => {}
             }
         }
     }
+
+    /// Returns the type of `e`, in the form used by names of core-js modules.
+    fn type_of(&self, e: &Expr) -> Option<&'static str> {
+        match e {
+            Expr::Ident(i) => self.types.get(&id(i)).copied().flatten(),
+            _ => type_of_value(e),
+        }
+    }
 }
 
 impl Visit for UsageVisitor {
     noop_visit_type!();
 
+    fn visit_module(&mut self, m: &Module, _: &dyn Node) {
+        let mut v = TypeCollector {
+            types: Default::default(),
+        };
+        m.visit_with(&Invalid { span: DUMMY_SP } as _, &mut v);
+        self.types = v.types;
+
+        m.visit_children_with(self);
+    }
+
     /// `[a, b] = c`
     fn visit_array_pat(&mut self, p: &ArrayPat, _: &dyn Node) {
         p.visit_children_with(self);
 
-        self.add(COMMON_ITERATORS)
+        self.add("destructuring", COMMON_ITERATORS)
     }
 
     fn visit_assign_expr(&mut self, e: &AssignExpr, _: &dyn Node) {
@@ -174,7 +221,9 @@ impl Visit for UsageVisitor {
             op!("in") => {
                 // 'entries' in Object
                 // 'entries' in [1, 2, 3]
-                self.add_property_deps(&e.right, &e.left);
+                if let Expr::Lit(Lit::Str(s)) = &*e.left {
+                    self.add_property_deps(&e.right, &s.value);
+                }
             }
             _ => {}
         }
@@ -189,7 +238,7 @@ impl Visit for UsageVisitor {
                 Expr::Ident(Ident {
                     sym: js_word!("import"),
                     ..
-                }) => self.add(PROMISE_DEPENDENCIES),
+                }) => self.add("import()", PROMISE_DEPENDENCIES),
                 _ => {}
             }
         }
@@ -199,7 +248,7 @@ impl Visit for UsageVisitor {
         e.visit_children_with(self);
 
         match e {
-            Expr::Ident(i) => self.add_builtin(&i.sym),
+            Expr::Ident(i) if is_global(i) => self.add_builtin(&i.sym),
             _ => {}
         }
     }
@@ -208,7 +257,7 @@ impl Visit for UsageVisitor {
     fn visit_expr_or_spread(&mut self, e: &ExprOrSpread, _: &dyn Node) {
         e.visit_children_with(self);
         if e.spread.is_some() {
-            self.add(COMMON_ITERATORS)
+            self.add("spread", COMMON_ITERATORS)
         }
     }
 
@@ -216,14 +265,14 @@ impl Visit for UsageVisitor {
     fn visit_for_of_stmt(&mut self, s: &ForOfStmt, _: &dyn Node) {
         s.visit_children_with(self);
 
-        self.add(COMMON_ITERATORS)
+        self.add("for-of", COMMON_ITERATORS)
     }
 
     fn visit_function(&mut self, f: &Function, _: &dyn Node) {
         f.visit_children_with(self);
 
         if f.is_async {
-            self.add(PROMISE_DEPENDENCIES)
+            self.add("async function", PROMISE_DEPENDENCIES)
         }
     }
 
@@ -236,8 +285,14 @@ impl Visit for UsageVisitor {
         // Object.entries
         // [1, 2, 3].entries
 
+        let prop = match &*e.prop {
+            Expr::Ident(i) if !e.computed => &i.sym,
+            Expr::Lit(Lit::Str(s)) if e.computed => &s.value,
+            _ => return,
+        };
+
         match e.obj {
-            ExprOrSuper::Expr(ref obj) => self.add_property_deps(&obj, &e.prop),
+            ExprOrSuper::Expr(ref obj) => self.add_property_deps(&obj, prop),
             _ => {}
         }
     }
@@ -270,7 +325,139 @@ impl Visit for UsageVisitor {
         e.visit_children_with(self);
 
         if e.delegate {
-            self.add(COMMON_ITERATORS)
+            self.add("yield*", COMMON_ITERATORS)
+        }
+    }
+}
+
+/// Returns `true` if `i` is not declared in the module.
+///
+/// Identifiers are resolved by the resolver, so this is always `true` for
+/// identifiers which are not resolved.
+fn is_global(i: &Ident) -> bool {
+    i.span.ctxt == SyntaxContext::empty()
+}
+
+/// Returns the type of a value, in the form used by names of core-js modules.
+fn type_of_value(e: &Expr) -> Option<&'static str> {
+    match e {
+        Expr::Paren(ParenExpr { expr, .. }) => type_of_value(expr),
+        Expr::Array(..) => Some("array"),
+        Expr::Object(..) => Some("object"),
+        Expr::Fn(..) | Expr::Arrow(..) | Expr::Class(..) => Some("function"),
+        Expr::Tpl(..) | Expr::Lit(Lit::Str(..)) => Some("string"),
+        Expr::Lit(Lit::Num(..)) => Some("number"),
+        Expr::Lit(Lit::Bool(..)) => Some("boolean"),
+        Expr::Lit(Lit::Regex(..)) => Some("regexp"),
+        Expr::New(NewExpr { callee, .. }) => match &**callee {
+            Expr::Ident(i) if is_global(i) => match &*i.sym {
+                "Array" => Some("array"),
+                "Date" => Some("date"),
+                "Map" => Some("map"),
+                "Number" => Some("number"),
+                "Promise" => Some("promise"),
+                "RegExp" => Some("regexp"),
+                "Set" => Some("set"),
+                "String" => Some("string"),
+                "WeakMap" => Some("weak-map"),
+                "WeakSet" => Some("weak-set"),
+                _ => None,
+            },
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Collects types of variables which are declared once and never reassigned.
+struct TypeCollector {
+    types: FxHashMap<Id, Option<&'static str>>,
+}
+
+impl TypeCollector {
+    fn declare(&mut self, i: &Ident, ty: Option<&'static str>) {
+        let ty = if self.types.contains_key(&id(i)) {
+            None
+        } else {
+            ty
+        };
+        self.types.insert(id(i), ty);
+    }
+}
+
+impl Visit for TypeCollector {
+    noop_visit_type!();
+
+    fn visit_assign_expr(&mut self, e: &AssignExpr, _: &dyn Node) {
+        e.visit_children_with(self);
+
+        match &e.left {
+            PatOrExpr::Expr(left) => match &**left {
+                Expr::Ident(i) => self.declare(i, None),
+                _ => {}
+            },
+            PatOrExpr::Pat(left) => match &**left {
+                Pat::Expr(left) => match &**left {
+                    Expr::Ident(i) => self.declare(i, None),
+                    _ => {}
+                },
+                _ => {}
+            },
+        }
+    }
+
+    fn visit_class_decl(&mut self, c: &ClassDecl, _: &dyn Node) {
+        c.visit_children_with(self);
+
+        self.declare(&c.ident, Some("function"));
+    }
+
+    fn visit_fn_decl(&mut self, f: &FnDecl, _: &dyn Node) {
+        f.visit_children_with(self);
+
+        self.declare(&f.ident, Some("function"));
+    }
+
+    fn visit_import_default_specifier(&mut self, s: &ImportDefaultSpecifier, _: &dyn Node) {
+        self.declare(&s.local, None);
+    }
+
+    fn visit_import_named_specifier(&mut self, s: &ImportNamedSpecifier, _: &dyn Node) {
+        self.declare(&s.local, None);
+    }
+
+    fn visit_import_star_as_specifier(&mut self, s: &ImportStarAsSpecifier, _: &dyn Node) {
+        self.declare(&s.local, None);
+    }
+
+    /// Bindings other than `var a = b` make the type unknown.
+    fn visit_pat(&mut self, p: &Pat, _: &dyn Node) {
+        p.visit_children_with(self);
+
+        match p {
+            Pat::Ident(i) => self.declare(i, None),
+            _ => {}
+        }
+    }
+
+    fn visit_update_expr(&mut self, e: &UpdateExpr, _: &dyn Node) {
+        e.visit_children_with(self);
+
+        match &*e.arg {
+            Expr::Ident(i) => self.declare(i, None),
+            _ => {}
+        }
+    }
+
+    fn visit_var_declarator(&mut self, d: &VarDeclarator, _: &dyn Node) {
+        match &d.name {
+            Pat::Ident(i) => {
+                d.init.visit_with(d as _, self);
+
+                let ty = d.init.as_ref().and_then(|init| type_of_value(init));
+                self.declare(i, ty);
+            }
+            _ => d.visit_children_with(self),
         }
     }
 }
@@ -9,12 +9,13 @@ use st_map::StaticMap;
 use std::{
     convert::{TryFrom, TryInto},
     process::Command,
+    sync::{Arc, Mutex},
 };
 use swc_atoms::{js_word, JsWord};
-use swc_common::{chain, FromVariant, Mark, DUMMY_SP};
+use swc_common::{chain, comments::Comments, FromVariant, Mark, DUMMY_SP};
 use swc_ecma_ast::*;
 use swc_ecma_transforms::{
-    compat::{es2015, es2016, es2017, es2018, es2020, es3},
+    compat::{es2015, es2016, es2017, es2018, es2020, es3, Assumptions},
     pass::{noop, Optional},
     util::prepend_stmts,
 };
@@ -28,8 +29,31 @@ mod regenerator;
 mod transform_data;
 mod version;
 
-pub fn preset_env(global_mark: Mark, c: Config) -> impl Fold {
-    let loose = c.loose;
+/// If `comments` is given, calls which are free of side effects are annotated
+/// with `/*#__PURE__*/`.
+pub fn preset_env<'a>(
+    global_mark: Mark,
+    comments: Option<&'a dyn Comments>,
+    c: Config,
+) -> impl 'a + Fold {
+    preset_env_with_report(global_mark, comments, c, Default::default())
+}
+
+/// [preset_env], which records injected polyfills to `report`.
+pub fn preset_env_with_report<'a>(
+    global_mark: Mark,
+    comments: Option<&'a dyn Comments>,
+    c: Config,
+    report: PolyfillReport,
+) -> impl 'a + Fold {
+    let assumptions = if c.loose {
+        c.assumptions.loose()
+    } else {
+        c.assumptions
+    };
+    let es2015_config = assumptions.es2015();
+    let es2018_config = assumptions.es2018();
+    let es2020_config = assumptions.es2020();
     let targets: Versions = c.targets.try_into().expect("failed to parse targets");
     let is_any_target = targets.is_any_target();
 
@@ -66,12 +90,28 @@ pub fn preset_env(global_mark: Mark, c: Config) -> impl Fold {
 
     // ES2020
 
-    let pass = add!(pass, NullishCoalescing, es2020::nullish_coalescing());
-    let pass = add!(pass, OptionalChaining, es2020::optional_chaining());
-    let pass = add!(pass, ClassProperties, es2020::class_properties());
+    let pass = add!(
+        pass,
+        NullishCoalescing,
+        es2020::nullish_coalescing(es2020_config.nullish_coalescing)
+    );
+    let pass = add!(
+        pass,
+        OptionalChaining,
+        es2020::optional_chaining(es2020_config.optional_chaining)
+    );
+    let pass = add!(
+        pass,
+        ClassProperties,
+        es2020::class_properties(es2020_config.class_properties)
+    );
 
     // ES2018
-    let pass = add!(pass, ObjectRestSpread, es2018::object_rest_spread());
+    let pass = add!(
+        pass,
+        ObjectRestSpread,
+        es2018::object_rest_spread(es2018_config.object_rest_spread)
+    );
     let pass = add!(pass, OptionalCatchBinding, es2018::optional_catch_binding());
 
     // ES2017
@@ -83,13 +123,12 @@ pub fn preset_env(global_mark: Mark, c: Config) -> impl Fold {
     // ES2015
     let pass = add!(pass, BlockScopedFunctions, es2015::block_scoped_functions());
     let pass = add!(pass, TemplateLiterals, es2015::template_literal(), true);
-    let pass = add!(pass, Classes, es2015::classes());
     let pass = add!(
         pass,
-        Spread,
-        es2015::spread(es2015::spread::Config { loose }),
-        true
+        Classes,
+        es2015::classes(comments, es2015_config.classes)
     );
+    let pass = add!(pass, Spread, es2015::spread(es2015_config.spread), true);
     let pass = add!(pass, FunctionName, es2015::function_name());
     let pass = add!(pass, ArrowFunctions, es2015::arrow());
     let pass = add!(pass, DuplicateKeys, es2015::duplicate_keys());
@@ -97,15 +136,12 @@ pub fn preset_env(global_mark: Mark, c: Config) -> impl Fold {
     // TODO:    InstanceOf,
     let pass = add!(pass, TypeOfSymbol, es2015::typeof_symbol());
     let pass = add!(pass, ShorthandProperties, es2015::shorthand());
-    let pass = add!(pass, Parameters, es2015::parameters());
     let pass = add!(
         pass,
-        ForOf,
-        es2015::for_of(es2015::for_of::Config {
-            assume_array: loose
-        }),
-        true
+        Parameters,
+        es2015::parameters(es2015_config.parameters)
     );
+    let pass = add!(pass, ForOf, es2015::for_of(es2015_config.for_of), true);
     let pass = add!(
         pass,
         ComputedProperties,
@@ -115,7 +151,7 @@ pub fn preset_env(global_mark: Mark, c: Config) -> impl Fold {
     let pass = add!(
         pass,
         Destructuring,
-        es2015::destructuring(es2015::destructuring::Config { loose }),
+        es2015::destructuring(es2015_config.destructuring),
         true
     );
     let pass = add!(pass, Regenerator, es2015::regenerator(global_mark), true);
@@ -156,9 +192,12 @@ pub fn preset_env(global_mark: Mark, c: Config) -> impl Fold {
                 patch: 0
             }),
             shipped_proposals: c.shipped_proposals,
+            proposals: c.proposals,
             targets,
             includes: included_modules,
             excludes: excluded_modules,
+            debug: c.debug,
+            report,
         }
     )
 }
@@ -195,118 +234,184 @@ pub struct BrowserData<T: Default> {
     pub opera_mobile: T,
 }
 
+/// Polyfills injected by [preset_env_with_report].
+///
+/// This is a handle, and clones share the same list. Polyfills of all modules
+/// processed by the pass are recorded.
+#[derive(Debug, Clone, Default)]
+pub struct PolyfillReport {
+    polyfills: Arc<Mutex<Vec<Polyfill>>>,
+}
+
+impl PolyfillReport {
+    /// Returns injected polyfills, in the order of injection.
+    pub fn polyfills(&self) -> Vec<Polyfill> {
+        self.polyfills.lock().unwrap().clone()
+    }
+
+    fn add(&self, polyfill: Polyfill) {
+        self.polyfills.lock().unwrap().push(polyfill);
+    }
+}
+
+/// An import injected by [preset_env].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polyfill {
+    /// e.g. `core-js/modules/es.map`
+    pub src: JsWord,
+    pub reason: PolyfillReason,
+}
+
+/// Why a polyfill is injected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PolyfillReason {
+    /// Required by a built-in, a property or a syntax used in the module.
+    ///
+    /// e.g. `Map`, `includes`, `Array.from`, `for-of`
+    Usage(JsWord),
+    /// Expanded from an entry import like `import 'core-js'`.
+    Entry,
+    /// Requested by `include`.
+    Include,
+    /// `regeneratorRuntime` is used, mostly because of lowered generators or
+    /// async functions.
+    Regenerator,
+}
+
 #[derive(Debug)]
 struct Polyfills {
     mode: Option<Mode>,
     targets: Versions,
     shipped_proposals: bool,
+    proposals: bool,
     corejs: Version,
     regenerator: bool,
     includes: FxHashSet<String>,
     excludes: FxHashSet<String>,
+    debug: bool,
+    report: PolyfillReport,
 }
 
 impl Fold for Polyfills {
     fn fold_module(&mut self, mut m: Module) -> Module {
         let span = m.span;
 
-        let required = match self.mode {
+        let required: Vec<(&str, PolyfillReason)> = match self.mode {
             None => Default::default(),
             Some(Mode::Usage) => {
-                let mut r = match self.corejs {
+                let mut r: Vec<_> = match self.corejs {
                     Version { major: 2, .. } => {
                         let mut v = corejs2::UsageVisitor::new(self.targets);
                         m.visit_with(&Invalid { span: DUMMY_SP } as _, &mut v);
 
                         v.required
+                            .into_iter()
+                            .map(|(s, usage)| (s, PolyfillReason::Usage(usage)))
+                            .collect()
                     }
                     Version { major: 3, .. } => {
-                        let mut v =
-                            corejs3::UsageVisitor::new(self.targets, self.shipped_proposals);
+                        let mut v = corejs3::UsageVisitor::new(
+                            self.targets,
+                            self.shipped_proposals,
+                            self.proposals,
+                        );
                         m.visit_with(&Invalid { span: DUMMY_SP } as _, &mut v);
+
                         v.required
+                            .into_iter()
+                            .map(|(s, usage)| (s, PolyfillReason::Usage(usage)))
+                            .collect()
                     }
 
                     _ => unimplemented!("corejs version other than 2 / 3"),
                 };
 
                 if regenerator::is_required(&m) {
-                    r.insert("regenerator-runtime/runtime".into());
+                    r.push(("regenerator-runtime/runtime", PolyfillReason::Regenerator));
                 }
 
                 r
             }
-            Some(Mode::Entry) => match self.corejs {
-                Version { major: 2, .. } => {
-                    let mut v = corejs2::Entry::new(self.targets, self.regenerator);
-                    m = m.fold_with(&mut v);
-                    v.imports
-                }
+            Some(Mode::Entry) => {
+                let imports = match self.corejs {
+                    Version { major: 2, .. } => {
+                        let mut v = corejs2::Entry::new(self.targets, self.regenerator);
+                        m = m.fold_with(&mut v);
+                        v.imports
+                    }
 
-                Version { major: 3, .. } => {
-                    let mut v = corejs3::Entry::new(self.targets, self.corejs, !self.regenerator);
-                    m = m.fold_with(&mut v);
-                    v.imports
-                }
+                    Version { major: 3, .. } => {
+                        let mut v =
+                            corejs3::Entry::new(self.targets, self.corejs, !self.regenerator);
+                        m = m.fold_with(&mut v);
+                        v.imports
+                    }
+
+                    _ => unimplemented!("corejs version other than 2 / 3"),
+                };
 
-                _ => unimplemented!("corejs version other than 2 / 3"),
-            },
+                imports
+                    .into_iter()
+                    .map(|s| (s, PolyfillReason::Entry))
+                    .collect()
+            }
         };
-        let required = required
+        let mut required = required
             .into_iter()
-            .filter(|s| !self.excludes.contains(&**s))
-            .map(|s| -> JsWord {
-                if s != "regenerator-runtime/runtime" {
+            .filter(|(s, _)| !self.excludes.contains(*s))
+            .chain(
+                self.includes
+                    .iter()
+                    .map(|s| (&**s, PolyfillReason::Include)),
+            )
+            .map(|(s, reason)| Polyfill {
+                src: if s != "regenerator-runtime/runtime" {
                     format!("core-js/modules/{}", s).into()
                 } else {
                     format!("regenerator-runtime/runtime").into()
-                }
+                },
+                reason,
             })
-            .chain(self.includes.iter().map(|s| {
-                if s != "regenerator-runtime/runtime" {
-                    format!("core-js/modules/{}", s).into()
-                } else {
-                    format!("regenerator-runtime/runtime").into()
-                }
-            }))
             .collect::<Vec<_>>();
 
         if cfg!(debug_assertions) {
-            let mut v = required.into_iter().collect::<Vec<_>>();
-            v.sort();
-            prepend_stmts(
-                &mut m.body,
-                v.into_iter().map(|src| {
-                    ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
-                        span,
-                        specifiers: vec![],
-                        src: Str {
-                            span: DUMMY_SP,
-                            value: src,
-                            has_escape: false,
-                        },
-                        type_only: false,
-                    }))
-                }),
-            );
-        } else {
-            prepend_stmts(
-                &mut m.body,
-                required.into_iter().map(|src| {
-                    ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
-                        span,
-                        specifiers: vec![],
-                        src: Str {
-                            span: DUMMY_SP,
-                            value: src,
-                            has_escape: false,
-                        },
-                        type_only: false,
-                    }))
-                }),
-            );
+            required.sort_by(|a, b| a.src.cmp(&b.src));
         }
 
+        // The runtime defines `regeneratorRuntime` as a global variable.
+        if required
+            .iter()
+            .any(|p| &*p.src == "regenerator-runtime/runtime")
+        {
+            regenerator::remove_runtime_require(&mut m.body);
+        }
+
+        if self.debug {
+            println!("Injected polyfills:");
+            for p in &required {
+                println!("  {}: {:?}", p.src, p.reason);
+            }
+        }
+
+        prepend_stmts(
+            &mut m.body,
+            required.into_iter().map(|p| {
+                let src = p.src.clone();
+                self.report.add(p);
+
+                ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+                    span,
+                    specifiers: vec![],
+                    src: Str {
+                        span: DUMMY_SP,
+                        value: src,
+                        has_escape: false,
+                    },
+                    type_only: false,
+                }))
+            }),
+        );
+
         m.body.retain(|item| match item {
             ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
                 src:
@@ -403,6 +508,10 @@ pub struct Config {
     #[serde(default)]
     pub loose: bool,
 
+    /// Assumptions used by compat passes. `loose` enables some of them.
+    #[serde(default)]
+    pub assumptions: Assumptions,
+
     /// Skipped es features.
     ///
     /// e.g.)
@@ -426,6 +535,11 @@ pub struct Config {
     #[serde(default)]
     pub shipped_proposals: bool,
 
+    /// Polyfills all proposals supported by core-js 3, not only the shipped
+    /// ones.
+    #[serde(default)]
+    pub proposals: bool,
+
     #[serde(default)]
     pub force_all_transforms: bool,
 }
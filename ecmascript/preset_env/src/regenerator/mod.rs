@@ -1,7 +1,13 @@
 use swc_common::DUMMY_SP;
-use swc_ecma_ast::Invalid;
-use swc_ecma_visit::{noop_visit_type, Visit, VisitWith};
+use swc_ecma_ast::*;
+use swc_ecma_visit::{noop_visit_type, Node, Visit, VisitWith};
 
+/// Returns `true` if `regeneratorRuntime` is used.
+///
+/// As polyfills are injected after lowering, this is `true` only if a
+/// generator or an async function is lowered by `regenerator` (or the code
+/// references `regeneratorRuntime` by itself). Generators which are supported
+/// by targets do not require the runtime.
 pub(super) fn is_required<T: VisitWith<RegeneratorVisitor>>(node: &T) -> bool {
     let mut v = RegeneratorVisitor { found: false };
     node.visit_with(&Invalid { span: DUMMY_SP } as _, &mut v);
@@ -12,7 +18,44 @@ pub(super) struct RegeneratorVisitor {
     found: bool,
 }
 
-/// TODO
 impl Visit for RegeneratorVisitor {
     noop_visit_type!();
+
+    fn visit_ident(&mut self, i: &Ident, _: &dyn Node) {
+        if &*i.sym == "regeneratorRuntime" {
+            self.found = true;
+        }
+    }
+}
+
+/// Removes `var regeneratorRuntime = require('regenerator-runtime');`, which
+/// is injected by `regenerator`.
+pub(super) fn remove_runtime_require(body: &mut Vec<ModuleItem>) {
+    body.retain(|item| {
+        let var = match item {
+            ModuleItem::Stmt(Stmt::Decl(Decl::Var(var))) if var.decls.len() == 1 => &var.decls[0],
+            _ => return true,
+        };
+        match (&var.name, var.init.as_deref()) {
+            (
+                Pat::Ident(name),
+                Some(Expr::Call(CallExpr {
+                    callee: ExprOrSuper::Expr(callee),
+                    args,
+                    ..
+                })),
+            ) if &*name.sym == "regeneratorRuntime" && args.len() == 1 => {
+                let is_require = match &**callee {
+                    Expr::Ident(callee) => &*callee.sym == "require",
+                    _ => false,
+                };
+                let is_runtime = match &*args[0].expr {
+                    Expr::Lit(Lit::Str(s)) => &*s.value == "regenerator-runtime",
+                    _ => false,
+                };
+                !(is_require && is_runtime)
+            }
+            _ => true,
+        }
+    });
 }
@@ -1,5 +1,3 @@
-import "core-js/modules/es.array.includes";
-import "core-js/modules/es.array.index-of";
 import "core-js/modules/es.array.iterator";
 import "core-js/modules/es.function.bind";
 import "core-js/modules/es.object.define-getter";
@@ -8,8 +6,6 @@ import "core-js/modules/es.regexp.exec";
 import "core-js/modules/es.regexp.flags";
 import "core-js/modules/es.string.includes";
 import "core-js/modules/es.string.replace";
-import "core-js/modules/es.string.trim";
-import "core-js/modules/web.dom-collections.iterator";
 
 var a = [];
 a.values();
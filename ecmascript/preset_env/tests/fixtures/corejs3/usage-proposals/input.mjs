@@ -0,0 +1,2 @@
+compositeKey;
+globalThis;
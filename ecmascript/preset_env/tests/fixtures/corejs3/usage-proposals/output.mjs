@@ -0,0 +1,4 @@
+import "core-js/modules/esnext.composite-key";
+import "core-js/modules/esnext.global-this";
+compositeKey;
+globalThis;
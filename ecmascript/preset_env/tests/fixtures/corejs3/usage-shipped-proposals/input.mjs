@@ -0,0 +1,5 @@
+Map;
+globalThis;
+Promise.allSettled([]);
+'a'.matchAll('a');
+compositeKey;
@@ -0,0 +1,14 @@
+import "core-js/modules/es.array.iterator";
+import "core-js/modules/es.map";
+import "core-js/modules/es.object.to-string";
+import "core-js/modules/es.promise";
+import "core-js/modules/es.string.iterator";
+import "core-js/modules/esnext.global-this";
+import "core-js/modules/esnext.promise.all-settled";
+import "core-js/modules/esnext.string.match-all";
+import "core-js/modules/web.dom-collections.iterator";
+Map;
+globalThis;
+Promise.allSettled([]);
+'a'.matchAll('a');
+compositeKey;
@@ -0,0 +1,118 @@
+use std::path::Path;
+use swc_common::{input::StringInput, Mark};
+use swc_ecma_parser::{EsConfig, Parser, Syntax};
+use swc_ecma_preset_env::{
+    preset_env_with_report, Config, Polyfill, PolyfillReason, PolyfillReport,
+};
+use swc_ecma_visit::FoldWith;
+use testing::Tester;
+
+/// Runs `preset_env` on `input.mjs` of a fixture, and returns the report.
+fn report(fixture: &str, config: &str) -> Vec<Polyfill> {
+    let config: Config = serde_json::from_str(config).expect("failed to parse config");
+    let report = PolyfillReport::default();
+
+    Tester::new()
+        .print_errors(|cm, handler| {
+            let fm = cm
+                .load_file(
+                    &Path::new(env!("CARGO_MANIFEST_DIR"))
+                        .join("tests")
+                        .join("fixtures")
+                        .join(fixture)
+                        .join("input.mjs"),
+                )
+                .expect("failed to load file");
+            let mut p = Parser::new(
+                Syntax::Es(EsConfig {
+                    dynamic_import: true,
+                    ..Default::default()
+                }),
+                StringInput::from(&*fm),
+                None,
+            );
+            let module = p
+                .parse_module()
+                .map_err(|e| e.into_diagnostic(&handler).emit())?;
+
+            module.fold_with(&mut preset_env_with_report(
+                Mark::fresh(Mark::root()),
+                None,
+                config,
+                report.clone(),
+            ));
+            Ok(())
+        })
+        .unwrap();
+
+    let mut polyfills = report.polyfills();
+    polyfills.sort_by(|a, b| a.src.cmp(&b.src));
+    polyfills
+}
+
+fn srcs(polyfills: &[Polyfill]) -> Vec<&str> {
+    polyfills.iter().map(|p| &*p.src).collect()
+}
+
+#[test]
+fn usage() {
+    let polyfills = report(
+        "corejs3/usage-promise-all",
+        r#"{ "mode": "usage", "coreJs": 3, "targets": { "browsers": ["ie > 10"] } }"#,
+    );
+
+    assert_eq!(
+        srcs(&polyfills),
+        vec![
+            "core-js/modules/es.array.iterator",
+            "core-js/modules/es.object.to-string",
+            "core-js/modules/es.promise",
+            "core-js/modules/es.string.iterator",
+            "core-js/modules/web.dom-collections.iterator",
+        ]
+    );
+    for p in &polyfills {
+        match p.reason {
+            PolyfillReason::Usage(ref usage) => {
+                assert!(usage.starts_with("Promise"), "{:?}", p);
+            }
+            _ => panic!("should be required by usage: {:?}", p),
+        }
+    }
+}
+
+#[test]
+fn entry() {
+    let polyfills = report(
+        "corejs3/entry-chrome-71",
+        r#"{ "mode": "entry", "coreJs": 3, "targets": { "chrome": "71" } }"#,
+    );
+
+    assert_eq!(
+        srcs(&polyfills),
+        vec![
+            "core-js/modules/es.array.unscopables.flat",
+            "core-js/modules/es.array.unscopables.flat-map",
+            "core-js/modules/es.math.hypot",
+            "core-js/modules/es.object.from-entries",
+            "core-js/modules/web.immediate",
+        ]
+    );
+    assert!(polyfills.iter().all(|p| p.reason == PolyfillReason::Entry));
+}
+
+#[test]
+fn regenerator() {
+    let polyfills = report(
+        "corejs3/usage-regenerator-used-generator",
+        r#"{ "mode": "usage", "coreJs": 3 }"#,
+    );
+
+    assert_eq!(
+        polyfills,
+        vec![Polyfill {
+            src: "regenerator-runtime/runtime".into(),
+            reason: PolyfillReason::Regenerator,
+        }]
+    );
+}
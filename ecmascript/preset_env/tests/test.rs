@@ -175,6 +175,7 @@ fn exec(c: PresetConfig, dir: PathBuf) -> Result<(), Error> {
         .print_errors(|cm, handler| {
             let mut pass = preset_env(
                 Mark::fresh(Mark::root()),
+                None,
                 Config {
                     debug: c.debug,
                     mode: match c.use_built_ins {
@@ -192,10 +193,23 @@ fn exec(c: PresetConfig, dir: PathBuf) -> Result<(), Error> {
                     exclude: c.exclude,
                     core_js: match c.corejs {
                         CoreJs::Ver(v) => Some(v),
-                        ref s => unimplemented!("Unknown core js version: {:?}", s),
+                        CoreJs::Val(ref v) => match v.get("version") {
+                            Some(version) => Some(
+                                serde_json::from_value(version.clone())
+                                    .expect("failed to parse version of core js"),
+                            ),
+                            None => unimplemented!("Unknown core js version: {:?}", v),
+                        },
                     },
                     force_all_transforms: c.force_all_transforms,
                     shipped_proposals: c.shipped_proposals,
+                    proposals: match c.corejs {
+                        CoreJs::Val(ref v) => v
+                            .get("proposals")
+                            .and_then(|v| v.as_bool())
+                            .unwrap_or(false),
+                        _ => false,
+                    },
                     targets: c.targets,
                 },
             );
@@ -204,7 +218,10 @@ fn exec(c: PresetConfig, dir: PathBuf) -> Result<(), Error> {
                 let mut buf = vec![];
                 {
                     let mut emitter = Emitter {
-                        cfg: swc_ecma_codegen::Config { minify: false },
+                        cfg: swc_ecma_codegen::Config {
+                            minify: false,
+                            ..Default::default()
+                        },
                         comments: None,
                         cm: cm.clone(),
                         wr: Box::new(swc_ecma_codegen::text_writer::JsWriter::new(
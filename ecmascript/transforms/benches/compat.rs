@@ -116,19 +116,19 @@ fn all(b: &mut Bencher) {
     tr!(b, || chain!(
         compat::es2017(),
         compat::es2016(),
-        compat::es2015(Mark::fresh(Mark::root()), Default::default()),
+        compat::es2015(Mark::fresh(Mark::root()), None, Default::default()),
         compat::es3(Default::default()),
     ));
 }
 
 #[bench]
 fn es2018(b: &mut Bencher) {
-    tr!(b, || compat::es2018());
+    tr!(b, || compat::es2018(Default::default()));
 }
 
 #[bench]
 fn es2018_object_rest_spread(b: &mut Bencher) {
-    tr!(b, || compat::es2018::object_rest_spread());
+    tr!(b, || compat::es2018::object_rest_spread(Default::default()));
 }
 
 #[bench]
@@ -155,6 +155,7 @@ fn es2016_exponentation(b: &mut Bencher) {
 fn es2015(b: &mut Bencher) {
     tr!(b, || compat::es2015(
         Mark::fresh(Mark::root()),
+        None,
         Default::default()
     ));
 }
@@ -176,7 +177,7 @@ fn es2015_block_scoping(b: &mut Bencher) {
 
 #[bench]
 fn es2015_classes(b: &mut Bencher) {
-    tr!(b, || compat::es2015::classes());
+    tr!(b, || compat::es2015::classes(None, Default::default()));
 }
 
 #[bench]
@@ -196,7 +197,7 @@ fn es2015_duplicate_keys(b: &mut Bencher) {
 
 #[bench]
 fn es2015_parameters(b: &mut Bencher) {
-    tr!(b, || compat::es2015::parameters());
+    tr!(b, || compat::es2015::parameters(Default::default()));
 }
 
 #[bench]
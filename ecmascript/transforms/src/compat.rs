@@ -1,7 +1,11 @@
 //! New-generation javascript to old-javascript compiler.
 
-pub use self::{es2015::es2015, es2016::es2016, es2017::es2017, es2018::es2018, es3::es3};
+pub use self::{
+    assumptions::Assumptions, es2015::es2015, es2016::es2016, es2017::es2017, es2018::es2018,
+    es2020::es2020, es3::es3,
+};
 
+mod assumptions;
 pub mod es2015;
 pub mod es2016;
 pub mod es2017;
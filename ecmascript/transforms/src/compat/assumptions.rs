@@ -0,0 +1,103 @@
+use super::{
+    es2015::{self, classes, destructuring, for_of, parameters, spread},
+    es2018::{self, object_rest_spread},
+    es2020::{self, class_properties, nullish_coalescing, opt_chaining},
+};
+use serde::{Deserialize, Serialize};
+
+/// Assumptions about the input code, which allow compat passes to emit
+/// smaller code by ignoring some details of the specification.
+///
+/// See https://babeljs.io/docs/en/assumptions
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct Assumptions {
+    /// The super class of a class is never changed.
+    #[serde(default)]
+    pub constant_super: bool,
+
+    /// `length` of functions is not used.
+    #[serde(default)]
+    pub ignore_function_length: bool,
+
+    /// Iterated values are arrays.
+    #[serde(default)]
+    pub iterable_is_array: bool,
+
+    /// Classes are never called as a function.
+    #[serde(default)]
+    pub no_class_calls: bool,
+
+    /// `document.all` is never used.
+    #[serde(default)]
+    pub no_document_all: bool,
+
+    /// Private fields are not accessed by reflection.
+    #[serde(default)]
+    pub private_fields_as_properties: bool,
+
+    /// Getters have no side effects.
+    #[serde(default)]
+    pub pure_getters: bool,
+
+    /// Class fields don't shadow setters of super classes.
+    #[serde(default)]
+    pub set_public_class_fields: bool,
+
+    /// Properties of spread objects can be set instead of defined.
+    #[serde(default)]
+    pub set_spread_properties: bool,
+}
+
+impl Assumptions {
+    /// Enables assumptions implied by `loose: true`.
+    pub fn loose(mut self) -> Self {
+        self.iterable_is_array = true;
+        self
+    }
+
+    pub fn es2015(&self) -> es2015::Config {
+        es2015::Config {
+            classes: classes::Config {
+                no_class_calls: self.no_class_calls,
+                constant_super: self.constant_super,
+            },
+            for_of: for_of::Config {
+                assume_array: self.iterable_is_array,
+            },
+            destructuring: destructuring::Config {
+                loose: self.iterable_is_array,
+            },
+            parameters: parameters::Config {
+                ignore_function_length: self.ignore_function_length,
+            },
+            spread: spread::Config {
+                loose: self.iterable_is_array,
+            },
+        }
+    }
+
+    pub fn es2018(&self) -> es2018::Config {
+        es2018::Config {
+            object_rest_spread: object_rest_spread::Config {
+                set_spread_properties: self.set_spread_properties,
+            },
+        }
+    }
+
+    pub fn es2020(&self) -> es2020::Config {
+        es2020::Config {
+            nullish_coalescing: nullish_coalescing::Config {
+                no_document_all: self.no_document_all,
+            },
+            optional_chaining: opt_chaining::Config {
+                no_document_all: self.no_document_all,
+                pure_getters: self.pure_getters,
+            },
+            class_properties: class_properties::Config {
+                set_public_class_fields: self.set_public_class_fields,
+                private_fields_as_properties: self.private_fields_as_properties,
+            },
+        }
+    }
+}
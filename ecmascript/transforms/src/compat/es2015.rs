@@ -7,7 +7,7 @@ pub use self::{
     template_literal::template_literal, typeof_symbol::typeof_symbol,
 };
 use serde::Deserialize;
-use swc_common::{chain, Mark};
+use swc_common::{chain, comments::Comments, Mark};
 use swc_ecma_visit::Fold;
 
 mod arrow;
@@ -20,7 +20,7 @@ mod duplicate_keys;
 pub mod for_of;
 mod function_name;
 mod instanceof;
-mod parameters;
+pub mod parameters;
 mod regenerator;
 mod shorthand_property;
 pub mod spread;
@@ -40,15 +40,22 @@ fn exprs() -> impl Fold {
 }
 
 /// Compiles es2015 to es5.
-pub fn es2015(global_mark: Mark, c: Config) -> impl Fold {
+///
+/// If `comments` is given, calls which are free of side effects are
+/// annotated with `/*#__PURE__*/`.
+pub fn es2015<'a>(
+    global_mark: Mark,
+    comments: Option<&'a dyn Comments>,
+    c: Config,
+) -> impl 'a + Fold {
     chain!(
         block_scoped_functions(),
         template_literal(),
-        classes(),
+        classes(comments, c.classes),
         spread(c.spread),
         function_name(),
         exprs(),
-        parameters(),
+        parameters(c.parameters),
         for_of(c.for_of),
         computed_properties(),
         destructuring(c.destructuring),
@@ -59,12 +66,18 @@ pub fn es2015(global_mark: Mark, c: Config) -> impl Fold {
 
 #[derive(Debug, Default, Deserialize)]
 pub struct Config {
+    #[serde(flatten)]
+    pub classes: classes::Config,
+
     #[serde(flatten)]
     pub for_of: for_of::Config,
 
     #[serde(flatten)]
     pub destructuring: destructuring::Config,
 
+    #[serde(flatten)]
+    pub parameters: parameters::Config,
+
     #[serde(flatten)]
     pub spread: spread::Config,
 }
@@ -76,7 +89,7 @@ mod tests {
 
     test!(
         ::swc_ecma_parser::Syntax::default(),
-        |_| es2015(Mark::fresh(Mark::root()), Default::default()),
+        |_| es2015(Mark::fresh(Mark::root()), None, Default::default()),
         issue_169,
         r#"
 export class Foo {
@@ -108,7 +121,7 @@ export var Foo = function() {
 
     test!(
         ::swc_ecma_parser::Syntax::default(),
-        |_| es2015(Mark::fresh(Mark::root()), Default::default()),
+        |_| es2015(Mark::fresh(Mark::root()), None, Default::default()),
         issue_189,
         r#"
 class HomePage extends React.Component {}
@@ -128,7 +141,7 @@ var HomePage = function(_Component) {
 
     test!(
         ::swc_ecma_parser::Syntax::default(),
-        |_| es2015(Mark::fresh(Mark::root()), Default::default()),
+        |_| es2015(Mark::fresh(Mark::root()), None, Default::default()),
         issue_227,
         "export default function fn1(...args) {
   fn2(...args);
@@ -195,7 +208,7 @@ function foo(scope) {
 
     test!(
         ::swc_ecma_parser::Syntax::default(),
-        |_| es2015(Mark::fresh(Mark::root()), Default::default()),
+        |_| es2015(Mark::fresh(Mark::root()), None, Default::default()),
         issue_413,
         r#"
 export const getBadgeBorderRadius = (text, color) => {
@@ -211,7 +224,7 @@ export var getBadgeBorderRadius = function(text, color) {
 
     test!(
         ::swc_ecma_parser::Syntax::default(),
-        |_| es2015(Mark::fresh(Mark::root()), Default::default()),
+        |_| es2015(Mark::fresh(Mark::root()), None, Default::default()),
         issue_400_1,
         "class A {
     constructor() {
@@ -272,7 +285,7 @@ var B = function(A) {
 
     test_exec!(
         ::swc_ecma_parser::Syntax::default(),
-        |_| es2015(Mark::fresh(Mark::root()), Default::default()),
+        |_| es2015(Mark::fresh(Mark::root()), None, Default::default()),
         issue_400_2,
         "class A {
     constructor() {
@@ -1012,6 +1012,7 @@ expect(foo()).toBe(false);
             let mark = Mark::fresh(Mark::root());
             es2015::es2015(
                 mark,
+                None,
                 es2015::Config {
                     ..Default::default()
                 },
@@ -12,8 +12,9 @@ use crate::util::{
     ModuleItemLike, StmtLike,
 };
 use fxhash::FxBuildHasher;
+use serde::Deserialize;
 use std::iter;
-use swc_common::{Mark, Spanned, DUMMY_SP};
+use swc_common::{comments::Comments, Mark, Span, Spanned, DUMMY_SP};
 use swc_ecma_ast::*;
 use swc_ecma_visit::{noop_fold_type, Fold, FoldWith, Node, Visit, VisitWith};
 
@@ -24,8 +25,28 @@ pub(crate) mod native;
 mod prop_name;
 mod super_field;
 
-pub fn classes() -> impl Fold {
-    Classes::default()
+/// If `comments` is given, the class IIFE and `_createClass` calls are
+/// annotated with `/*#__PURE__*/`.
+pub fn classes<'a>(comments: Option<&'a dyn Comments>, c: Config) -> impl 'a + Fold {
+    Classes {
+        comments,
+        c,
+        in_strict: false,
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Config {
+    /// Assume that classes are never called as a function, and omit
+    /// `_classCallCheck`.
+    #[serde(default)]
+    pub no_class_calls: bool,
+
+    /// Assume that the super class is never changed, and compile
+    /// `super.foo` to `Parent.prototype.foo` instead of using `_get`.
+    #[serde(default)]
+    pub constant_super: bool,
 }
 
 type IndexMap<K, V> = indexmap::IndexMap<K, V, FxBuildHasher>;
@@ -61,8 +82,10 @@ type IndexMap<K, V> = indexmap::IndexMap<K, V, FxBuildHasher>;
 ///   return Test;
 /// }();
 /// ```
-#[derive(Default, Clone, Copy)]
-struct Classes {
+#[derive(Clone, Copy)]
+struct Classes<'a> {
+    comments: Option<&'a dyn Comments>,
+    c: Config,
     in_strict: bool,
 }
 
@@ -73,7 +96,19 @@ struct Data {
     get: Option<Box<Expr>>,
 }
 
-impl Classes {
+impl Classes<'_> {
+    /// Returns a span for a call which is annotated with `/*#__PURE__*/`.
+    fn pure_span(&self) -> Span {
+        match self.comments {
+            Some(comments) => {
+                let span = Span::dummy_with_cmt();
+                comments.add_pure_comment(span.lo());
+                span
+            }
+            None => DUMMY_SP,
+        }
+    }
+
     fn fold_stmt_like<T>(&mut self, stmts: Vec<T>) -> Vec<T>
     where
         T: StmtLike + ModuleItemLike + FoldWith<Self>,
@@ -168,7 +203,7 @@ impl Classes {
     }
 }
 
-impl Fold for Classes {
+impl Fold for Classes<'_> {
     noop_fold_type!();
 
     fn fold_module_items(&mut self, items: Vec<ModuleItem>) -> Vec<ModuleItem> {
@@ -219,7 +254,7 @@ impl Fold for Classes {
     }
 }
 
-impl Classes {
+impl Classes<'_> {
     fn fold_class_as_var_decl(&mut self, ident: Ident, class: Class) -> VarDecl {
         let span = class.span;
         let rhs = self.fold_class(Some(ident.clone()), class);
@@ -343,7 +378,7 @@ impl Classes {
         };
 
         Expr::Call(CallExpr {
-            span: DUMMY_SP,
+            span: self.pure_span(),
             callee: Expr::Fn(FnExpr {
                 ident: None,
                 function: Function {
@@ -540,6 +575,7 @@ impl Classes {
             // Handle `super.XX`
             body = self.handle_super_access(
                 &class_name,
+                super_class_ident.as_ref(),
                 body,
                 if is_this_declared {
                     Some(this_mark)
@@ -548,8 +584,10 @@ impl Classes {
                 },
             );
 
-            // inject _classCallCheck(this, Bar);
-            inject_class_call_check(&mut body, class_name.clone());
+            if !self.c.no_class_calls {
+                // inject _classCallCheck(this, Bar);
+                inject_class_call_check(&mut body, class_name.clone());
+            }
 
             stmts.push(Stmt::Decl(Decl::Fn(FnDecl {
                 ident: class_name.clone(),
@@ -566,7 +604,11 @@ impl Classes {
 
         // convert class methods
         // stmts.extend(self.fold_class_methods(class_name.clone(), priv_methods));
-        stmts.extend(self.fold_class_methods(class_name.clone(), methods));
+        stmts.extend(self.fold_class_methods(
+            class_name.clone(),
+            super_class_ident.as_ref(),
+            methods,
+        ));
 
         if stmts.first().map(|v| !v.is_use_strict()).unwrap_or(false) && !self.in_strict {
             prepend(
@@ -615,12 +657,18 @@ impl Classes {
     fn handle_super_access(
         &mut self,
         class_name: &Ident,
+        super_class_ident: Option<&Ident>,
         body: Vec<Stmt>,
         this_mark: Option<Mark>,
     ) -> Vec<Stmt> {
         let mut vars = vec![];
         let mut folder = SuperFieldAccessFolder {
             class_name,
+            constant_super_class: if self.c.constant_super {
+                super_class_ident
+            } else {
+                None
+            },
             vars: &mut vars,
             constructor_this_mark: this_mark,
             // constructor cannot be static
@@ -665,7 +713,12 @@ impl Classes {
         body
     }
 
-    fn fold_class_methods(&mut self, class_name: Ident, methods: Vec<ClassMethod>) -> Vec<Stmt> {
+    fn fold_class_methods(
+        &mut self,
+        class_name: Ident,
+        super_class_ident: Option<&Ident>,
+        methods: Vec<ClassMethod>,
+    ) -> Vec<Stmt> {
         if methods.is_empty() {
             return vec![];
         }
@@ -727,12 +780,13 @@ impl Classes {
 
         /// _createClass(Foo, [{}], [{}]);
         fn mk_create_class_call(
+            span: Span,
             class_name: Ident,
             methods: ExprOrSpread,
             static_methods: Option<ExprOrSpread>,
         ) -> Stmt {
             CallExpr {
-                span: DUMMY_SP,
+                span,
                 callee: helper!(create_class, "createClass"),
                 args: iter::once(class_name.as_arg())
                     .chain(iter::once(methods))
@@ -763,6 +817,11 @@ impl Classes {
             let mut vars = vec![];
             let mut folder = SuperFieldAccessFolder {
                 class_name: &class_name,
+                constant_super_class: if self.c.constant_super {
+                    super_class_ident
+                } else {
+                    None
+                },
                 vars: &mut vars,
                 constructor_this_mark: None,
                 is_static: m.is_static,
@@ -834,6 +893,7 @@ impl Classes {
             return vec![];
         }
         vec![mk_create_class_call(
+            self.pure_span(),
             class_name,
             mk_arg_obj_for_create_class(props),
             if static_props.is_empty() {
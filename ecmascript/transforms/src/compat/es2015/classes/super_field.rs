@@ -22,6 +22,8 @@ use swc_ecma_visit::{noop_fold_type, Fold, FoldWith};
 /// 'foo', this).call(this, a);
 pub(crate) struct SuperFieldAccessFolder<'a> {
     pub class_name: &'a Ident,
+    /// Ident of the super class, if it's assumed to be constant.
+    pub constant_super_class: Option<&'a Ident>,
 
     pub vars: &'a mut Vec<VarDeclarator>,
     /// Mark for the `_this`. Used only when folding constructor.
@@ -43,6 +45,7 @@ pub(crate) struct SuperFieldAccessFolder<'a> {
 struct SuperCalleeFolder<'a> {
     vars: &'a mut Vec<VarDeclarator>,
     class_name: &'a Ident,
+    constant_super_class: Option<&'a Ident>,
     /// True if we should inject get and
     inject_get: bool,
     inject_set: bool,
@@ -209,6 +212,22 @@ impl<'a> SuperCalleeFolder<'a> {
     fn super_to_get_call(&mut self, super_token: Span, prop: Box<Expr>, computed: bool) -> Expr {
         self.inject_get = true;
 
+        if let Some(super_class) = self.constant_super_class {
+            // Parent.prototype.foo
+            let obj = if self.is_static {
+                Expr::Ident(super_class.clone())
+            } else {
+                super_class.clone().make_member(quote_ident!("prototype"))
+            };
+
+            return Expr::Member(MemberExpr {
+                span: super_token,
+                obj: obj.as_obj(),
+                prop,
+                computed,
+            });
+        }
+
         let proto_arg = get_prototype_of(if self.is_static {
             // Foo
             Expr::Ident(self.class_name.clone())
@@ -440,6 +459,7 @@ impl<'a> Fold for SuperFieldAccessFolder<'a> {
 
         let mut callee_folder = SuperCalleeFolder {
             class_name: self.class_name,
+            constant_super_class: self.constant_super_class,
             inject_get: false,
             inject_set: false,
             vars: self.vars,
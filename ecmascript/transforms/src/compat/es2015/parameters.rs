@@ -1,15 +1,29 @@
-use crate::util::{prepend_stmts, ExprFactory};
-use arrayvec::ArrayVec;
+use crate::util::{prepend_stmts, undefined, ExprFactory};
+use serde::Deserialize;
 use swc_common::{Mark, Spanned, DUMMY_SP};
 use swc_ecma_ast::*;
 use swc_ecma_visit::{noop_fold_type, Fold, FoldWith};
 
-pub fn parameters() -> impl 'static + Fold {
-    Params
+pub fn parameters(c: Config) -> impl 'static + Fold {
+    Params { c }
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Config {
+    /// Assume that `length` of functions is not used, and assign default
+    /// values to parameters in place.
+    ///
+    /// `function f(a = 1) {}` becomes `function f(a) { if (a === void 0) a =
+    /// 1; }`.
+    #[serde(default)]
+    pub ignore_function_length: bool,
 }
 
 #[derive(Clone, Copy)]
-struct Params;
+struct Params {
+    c: Config,
+}
 // prevent_recurse!(Params, Pat);
 
 impl Params {
@@ -17,6 +31,7 @@ impl Params {
         let body = validate!(body);
 
         let mut params = vec![];
+        let mut defaults = vec![];
         let mut decls = vec![];
         let mut unpack_rest = None;
         let mut decls_after_unpack = vec![];
@@ -40,6 +55,46 @@ impl Params {
                         definite: false,
                     })
                 }
+                Pat::Assign(AssignPat { left, right, .. })
+                    if self.c.ignore_function_length
+                        && decls.is_empty()
+                        && match *left {
+                            Pat::Ident(..) => true,
+                            _ => false,
+                        } =>
+                {
+                    let ident = match *left {
+                        Pat::Ident(ident) => ident,
+                        _ => unreachable!(),
+                    };
+
+                    // if (a === void 0) a = 1;
+                    defaults.push(Stmt::If(IfStmt {
+                        span,
+                        test: Box::new(Expr::Bin(BinExpr {
+                            span: DUMMY_SP,
+                            left: Box::new(Expr::Ident(ident.clone())),
+                            op: op!("==="),
+                            right: undefined(DUMMY_SP),
+                        })),
+                        cons: Box::new(
+                            AssignExpr {
+                                span,
+                                left: PatOrExpr::Pat(Box::new(Pat::Ident(ident.clone()))),
+                                op: op!("="),
+                                right,
+                            }
+                            .into_stmt(),
+                        ),
+                        alt: None,
+                    }));
+
+                    params.push(Param {
+                        span: DUMMY_SP,
+                        decorators: Default::default(),
+                        pat: Pat::Ident(ident),
+                    });
+                }
                 Pat::Assign(..) => {
                     let binding = private_ident!(span, "param");
 
@@ -215,7 +270,8 @@ impl Params {
         }
 
         let mut stmts = body.stmts;
-        let mut iter: ArrayVec<[_; 3]> = Default::default();
+        // Defaults assigned in place are evaluated before other parameters.
+        let mut iter = defaults;
 
         if !decls.is_empty() {
             iter.push(Stmt::Decl(Decl::Var(VarDecl {
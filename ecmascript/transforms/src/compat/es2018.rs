@@ -1,12 +1,22 @@
 pub use self::{
     object_rest_spread::object_rest_spread, optional_catch_binding::optional_catch_binding,
 };
+use serde::Deserialize;
 use swc_common::chain;
 use swc_ecma_visit::Fold;
 
-mod object_rest_spread;
+pub mod object_rest_spread;
 mod optional_catch_binding;
 
-pub fn es2018() -> impl Fold {
-    chain!(object_rest_spread(), optional_catch_binding())
+pub fn es2018(c: Config) -> impl Fold {
+    chain!(
+        object_rest_spread(c.object_rest_spread),
+        optional_catch_binding()
+    )
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct Config {
+    #[serde(flatten)]
+    pub object_rest_spread: object_rest_spread::Config,
 }
@@ -1,14 +1,24 @@
 use crate::util::{
     alias_ident_for, alias_if_required, is_literal, var::VarCollector, ExprFactory, StmtLike,
 };
+use serde::Deserialize;
 use std::{iter, mem};
 use swc_common::{chain, util::move_map::MoveMap, Mark, Spanned, DUMMY_SP};
 use swc_ecma_ast::*;
 use swc_ecma_visit::{noop_fold_type, Fold, FoldWith, Node, Visit, VisitWith};
 
 /// `@babel/plugin-proposal-object-rest-spread`
-pub fn object_rest_spread() -> impl Fold {
-    chain!(ObjectRest, ObjectSpread)
+pub fn object_rest_spread(c: Config) -> impl Fold {
+    chain!(ObjectRest, ObjectSpread { c })
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Config {
+    /// Assume that properties of spread objects can be set instead of
+    /// defined, and use `_extends` instead of `_objectSpread`.
+    #[serde(default)]
+    pub set_spread_properties: bool,
 }
 
 struct ObjectRest;
@@ -1056,7 +1066,9 @@ fn simplify_pat(pat: Pat) -> Pat {
     pat.fold_with(&mut PatSimplifier)
 }
 
-struct ObjectSpread;
+struct ObjectSpread {
+    c: Config,
+}
 
 impl Fold for ObjectSpread {
     noop_fold_type!();
@@ -1119,7 +1131,11 @@ impl Fold for ObjectSpread {
 
                 Expr::Call(CallExpr {
                     span,
-                    callee: helper!(object_spread, "objectSpread"),
+                    callee: if self.c.set_spread_properties {
+                        helper!(extends, "extends")
+                    } else {
+                        helper!(object_spread, "objectSpread")
+                    },
                     args,
                     type_args: Default::default(),
                 })
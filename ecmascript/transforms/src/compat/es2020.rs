@@ -3,7 +3,30 @@ pub use self::{
     nullish_coalescing::nullish_coalescing,
     opt_chaining::optional_chaining,
 };
+use serde::Deserialize;
+use swc_common::chain;
+use swc_ecma_visit::Fold;
 
-mod class_properties;
-mod nullish_coalescing;
-mod opt_chaining;
+pub mod class_properties;
+pub mod nullish_coalescing;
+pub mod opt_chaining;
+
+pub fn es2020(c: Config) -> impl Fold {
+    chain!(
+        nullish_coalescing(c.nullish_coalescing),
+        optional_chaining(c.optional_chaining),
+        class_properties(c.class_properties),
+    )
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct Config {
+    #[serde(flatten)]
+    pub nullish_coalescing: nullish_coalescing::Config,
+
+    #[serde(flatten)]
+    pub optional_chaining: opt_chaining::Config,
+
+    #[serde(flatten)]
+    pub class_properties: class_properties::Config,
+}
@@ -11,6 +11,7 @@ use crate::{
         undefined, ExprFactory, ModuleItemLike, StmtLike,
     },
 };
+use serde::Deserialize;
 use std::{collections::HashSet, mem::take};
 use swc_atoms::JsWord;
 use swc_common::{util::move_map::MoveMap, Mark, Spanned, DUMMY_SP};
@@ -29,24 +30,42 @@ mod used_name;
 /// # Impl note
 ///
 /// We use custom helper to handle export defaul class
-pub fn class_properties() -> impl Fold {
+pub fn class_properties(c: Config) -> impl Fold {
     ClassProperties {
         typescript: false,
+        c,
         mark: Mark::root(),
     }
 }
 
 /// Class properties pass for the typescript.
-pub fn typescript_class_properties() -> impl Fold {
+pub fn typescript_class_properties(c: Config) -> impl Fold {
     ClassProperties {
         typescript: true,
+        c,
         mark: Mark::root(),
     }
 }
 
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Config {
+    /// Assume that class fields don't shadow setters of super classes, and
+    /// initialize them with assignments instead of `_defineProperty`.
+    #[serde(default)]
+    pub set_public_class_fields: bool,
+
+    /// Assume that private fields are not accessed by `Object` reflection
+    /// APIs, and store them as non-enumerable properties with unique keys
+    /// instead of `WeakMap`s.
+    #[serde(default)]
+    pub private_fields_as_properties: bool,
+}
+
 #[derive(Clone)]
 struct ClassProperties {
     typescript: bool,
+    c: Config,
     mark: Mark,
 }
 
@@ -364,6 +383,12 @@ impl ClassProperties {
                         );
                     }
 
+                    // `b` in `this.b = 'foo'`
+                    let ident_key = match *prop.key {
+                        Expr::Ident(ref i) if !prop.computed => Some(i.clone()),
+                        _ => None,
+                    };
+
                     let key = if self.typescript {
                         // `b` in
                         //
@@ -410,12 +435,14 @@ impl ClassProperties {
                     };
 
                     let assigned_value = prop.value.is_some();
+                    let prop_is_static = prop.is_static;
 
                     let value = prop.value.unwrap_or_else(|| undefined(prop_span));
                     let value = if prop.is_static {
                         value
                             .fold_with(&mut SuperFieldAccessFolder {
                                 class_name: &ident,
+                                constant_super_class: None,
                                 vars: &mut vars,
                                 constructor_this_mark: None,
                                 is_static: true,
@@ -466,6 +493,38 @@ impl ClassProperties {
                                 right: value,
                             })));
                         }
+                    } else if self.c.set_public_class_fields {
+                        let (member_prop, computed) = match ident_key {
+                            Some(i) => (Box::new(Expr::Ident(i)), false),
+                            None => (key, true),
+                        };
+                        let obj = if prop_is_static {
+                            ident.clone().as_obj()
+                        } else {
+                            ThisExpr { span: DUMMY_SP }.as_obj()
+                        };
+
+                        // this.b = 'foo'
+                        let expr = Box::new(Expr::Assign(AssignExpr {
+                            span: DUMMY_SP,
+                            left: PatOrExpr::Expr(Box::new(Expr::Member(MemberExpr {
+                                span: DUMMY_SP,
+                                obj,
+                                computed,
+                                prop: member_prop,
+                            }))),
+                            op: op!("="),
+                            right: value,
+                        }));
+
+                        if prop_is_static {
+                            extra_stmts.push(Stmt::Expr(ExprStmt {
+                                span: DUMMY_SP,
+                                expr,
+                            }));
+                        } else {
+                            constructor_exprs.push(expr);
+                        }
                     } else {
                         let callee = helper!(define_property, "defineProperty");
 
@@ -503,6 +562,7 @@ impl ClassProperties {
                         statics.insert(prop.key.id.sym.clone());
                     }
 
+                    let class_ident = &ident;
                     let ident = Ident::new(
                         format!("_{}", prop.key.id.sym).into(),
                         // We use `self.mark` for private variables.
@@ -516,6 +576,76 @@ impl ClassProperties {
                     );
                     let value = prop.value.unwrap_or_else(|| undefined(prop_span));
 
+                    if self.c.private_fields_as_properties {
+                        // var _foo = _classPrivateFieldLooseKey("foo");
+                        //
+                        // Declared before the class, so that initializers of static fields can use
+                        // keys of private fields declared later.
+                        vars.push(VarDeclarator {
+                            span: DUMMY_SP,
+                            definite: false,
+                            name: Pat::Ident(ident.clone()),
+                            init: Some(Box::new(Expr::Call(CallExpr {
+                                span: DUMMY_SP,
+                                callee: helper!(
+                                    class_private_field_loose_key,
+                                    "classPrivateFieldLooseKey"
+                                ),
+                                args: vec![quote_str!(prop.key.id.sym.clone()).as_arg()],
+                                type_args: Default::default(),
+                            }))),
+                        });
+
+                        // Object.defineProperty(this, _foo, { writable: true, value: value })
+                        let define = Box::new(Expr::Call(CallExpr {
+                            span: DUMMY_SP,
+                            callee: ExprOrSuper::Expr(member_expr!(
+                                DUMMY_SP,
+                                Object.defineProperty
+                            )),
+                            args: vec![
+                                if prop.is_static {
+                                    class_ident.clone().as_arg()
+                                } else {
+                                    ThisExpr { span: DUMMY_SP }.as_arg()
+                                },
+                                ident.as_arg(),
+                                ObjectLit {
+                                    span: DUMMY_SP,
+                                    props: vec![
+                                        PropOrSpread::Prop(Box::new(Prop::KeyValue(
+                                            KeyValueProp {
+                                                key: PropName::Ident(quote_ident!("writable")),
+                                                value: Box::new(Expr::Lit(Lit::Bool(Bool {
+                                                    span: DUMMY_SP,
+                                                    value: true,
+                                                }))),
+                                            },
+                                        ))),
+                                        PropOrSpread::Prop(Box::new(Prop::KeyValue(
+                                            KeyValueProp {
+                                                key: PropName::Ident(quote_ident!("value")),
+                                                value,
+                                            },
+                                        ))),
+                                    ],
+                                }
+                                .as_arg(),
+                            ],
+                            type_args: Default::default(),
+                        }));
+
+                        if prop.is_static {
+                            extra_stmts.push(Stmt::Expr(ExprStmt {
+                                span: DUMMY_SP,
+                                expr: define,
+                            }));
+                        } else {
+                            constructor_exprs.push(define);
+                        }
+                        continue;
+                    }
+
                     let extra_init = if prop.is_static {
                         Box::new(Expr::Object(ObjectLit {
                             span: DUMMY_SP,
@@ -665,6 +795,7 @@ impl ClassProperties {
             vars: vec![],
             class_name: &ident,
             in_assign_pat: false,
+            private_fields_as_properties: self.c.private_fields_as_properties,
         });
 
         (
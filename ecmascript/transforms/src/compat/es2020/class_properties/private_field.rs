@@ -14,6 +14,9 @@ pub(super) struct FieldAccessFolder<'a> {
     pub vars: Vec<VarDeclarator>,
     pub statics: &'a HashSet<JsWord>,
     pub in_assign_pat: bool,
+    /// Private fields are stored as properties, and `this.#foo` is
+    /// `_classPrivateFieldLooseBase(this, _foo)[_foo]`.
+    pub private_fields_as_properties: bool,
 }
 
 macro_rules! take_vars {
@@ -51,6 +54,42 @@ impl<'a> Fold for FieldAccessFolder<'a> {
     take_vars!(fold_constructor, Constructor);
 
     fn fold_expr(&mut self, e: Expr) -> Expr {
+        if self.private_fields_as_properties {
+            // As the result is a plain member expression, assignments and
+            // calls don't need special handling.
+            return match e.fold_children_with(self) {
+                Expr::Member(MemberExpr {
+                    span,
+                    obj: ExprOrSuper::Expr(obj),
+                    prop,
+                    ..
+                }) if prop.is_private_name() => {
+                    let n = prop.private_name().unwrap();
+                    let ident = Ident::new(
+                        format!("_{}", n.id.sym).into(),
+                        n.id.span.apply_mark(self.mark),
+                    );
+
+                    Expr::Member(MemberExpr {
+                        span,
+                        obj: CallExpr {
+                            span: DUMMY_SP,
+                            callee: helper!(
+                                class_private_field_loose_base,
+                                "classPrivateFieldLooseBase"
+                            ),
+                            args: vec![obj.as_arg(), ident.clone().as_arg()],
+                            type_args: Default::default(),
+                        }
+                        .as_obj(),
+                        prop: Box::new(Expr::Ident(ident)),
+                        computed: true,
+                    })
+                }
+                e => e,
+            };
+        }
+
         match e {
             Expr::Update(UpdateExpr {
                 span,
@@ -1,4 +1,5 @@
 use crate::util::{alias_if_required, undefined, StmtLike};
+use serde::Deserialize;
 use std::mem::replace;
 use swc_common::DUMMY_SP;
 use swc_ecma_ast::*;
@@ -7,12 +8,24 @@ use swc_ecma_visit::{noop_fold_type, Fold, FoldWith};
 #[cfg(test)]
 mod tests;
 
-pub fn nullish_coalescing() -> impl Fold + 'static {
-    NullishCoalescing::default()
+pub fn nullish_coalescing(c: Config) -> impl Fold + 'static {
+    NullishCoalescing {
+        c,
+        ..Default::default()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Config {
+    /// Assume that `document.all` is not used, and compare with `!= null`.
+    #[serde(default)]
+    pub no_document_all: bool,
 }
 
 #[derive(Debug, Default)]
 struct NullishCoalescing {
+    c: Config,
     vars: Vec<VarDeclarator>,
 }
 
@@ -86,6 +99,20 @@ impl Fold for NullishCoalescing {
                     Expr::Ident(l.clone())
                 };
 
+                if self.c.no_document_all {
+                    return Expr::Cond(CondExpr {
+                        span,
+                        test: Box::new(Expr::Bin(BinExpr {
+                            span: DUMMY_SP,
+                            left: Box::new(var_expr),
+                            op: op!("!="),
+                            right: Box::new(Expr::Lit(Lit::Null(Null { span: DUMMY_SP }))),
+                        })),
+                        cons: Box::new(Expr::Ident(l)),
+                        alt: right,
+                    });
+                }
+
                 return Expr::Cond(CondExpr {
                     span,
                     test: Box::new(Expr::Bin(BinExpr {
@@ -2,7 +2,7 @@ use super::*;
 use swc_ecma_parser::{EsConfig, Syntax};
 
 fn tr(_: ()) -> impl Fold {
-    nullish_coalescing()
+    nullish_coalescing(Default::default())
 }
 
 fn syntax() -> Syntax {
@@ -106,3 +106,25 @@ function foo() {
 
 "#
 );
+
+test!(
+    syntax(),
+    |_| nullish_coalescing(Config {
+        no_document_all: true
+    }),
+    no_document_all,
+    r#"
+function foo(opts) {
+  var foo = opts.foo ?? "default";
+  var bar = opts ?? {};
+}
+"#,
+    r#"
+function foo(opts) {
+  var _foo;
+
+  var foo = (_foo = opts.foo) != null ? _foo : "default";
+  var bar = opts != null ? opts : {};
+}
+"#
+);
@@ -1,15 +1,33 @@
 use crate::util::{prepend, undefined, ExprFactory, StmtLike};
+use serde::Deserialize;
 use std::{fmt::Debug, iter::once, mem};
-use swc_common::{Spanned, DUMMY_SP};
+use swc_common::{Span, Spanned, DUMMY_SP};
 use swc_ecma_ast::*;
 use swc_ecma_visit::{noop_fold_type, Fold, FoldWith};
 
-pub fn optional_chaining() -> impl Fold {
-    OptChaining::default()
+pub fn optional_chaining(c: Config) -> impl Fold {
+    OptChaining {
+        c,
+        ..Default::default()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Config {
+    /// Assume that `document.all` is not used, and compare with `== null`.
+    #[serde(default)]
+    pub no_document_all: bool,
+
+    /// Assume that getters have no side effects, and access member
+    /// expressions twice instead of storing them in a temporary variable.
+    #[serde(default)]
+    pub pure_getters: bool,
 }
 
 #[derive(Debug, Default)]
 struct OptChaining {
+    c: Config,
     vars: Vec<VarDeclarator>,
 }
 
@@ -265,6 +283,7 @@ impl OptChaining {
 
                 let (left, right, alt) = match obj {
                     Expr::Ident(..) => (Box::new(obj.clone()), Box::new(obj), e.expr),
+                    _ if self.is_pure(&obj) => (Box::new(obj.clone()), Box::new(obj), e.expr),
                     _ => {
                         let i = private_ident!(obj_span, "ref");
                         self.vars.push(VarDeclarator {
@@ -292,24 +311,7 @@ impl OptChaining {
                     }
                 };
 
-                let right = Box::new(Expr::Bin(BinExpr {
-                    span: DUMMY_SP,
-                    left: right,
-                    op: op!("==="),
-                    right: undefined(span),
-                }));
-
-                let test = Box::new(Expr::Bin(BinExpr {
-                    span,
-                    left: Box::new(Expr::Bin(BinExpr {
-                        span: obj_span,
-                        left,
-                        op: op!("==="),
-                        right: Box::new(Expr::Lit(Lit::Null(Null { span: DUMMY_SP }))),
-                    })),
-                    op: op!("||"),
-                    right,
-                }));
+                let test = self.test_nullish(span, obj_span, left, right);
 
                 validate!(CondExpr {
                     span,
@@ -337,6 +339,9 @@ impl OptChaining {
 
                 let (left, right, alt) = match obj {
                     Expr::Ident(..) => (Box::new(obj.clone()), Box::new(obj), e.expr),
+                    _ if !is_super_access && self.is_pure(&obj) => {
+                        (Box::new(obj.clone()), Box::new(obj), e.expr)
+                    }
                     _ => {
                         let i = private_ident!(obj_span, "ref");
                         self.vars.push(VarDeclarator {
@@ -376,22 +381,7 @@ impl OptChaining {
                     }
                 };
 
-                let test = Box::new(Expr::Bin(BinExpr {
-                    span,
-                    left: Box::new(Expr::Bin(BinExpr {
-                        span: DUMMY_SP,
-                        left,
-                        op: op!("==="),
-                        right: Box::new(Expr::Lit(Lit::Null(Null { span: DUMMY_SP }))),
-                    })),
-                    op: op!("||"),
-                    right: Box::new(Expr::Bin(BinExpr {
-                        span: DUMMY_SP,
-                        left: right,
-                        op: op!("==="),
-                        right: undefined(span),
-                    })),
-                }));
+                let test = self.test_nullish(span, DUMMY_SP, left, right);
 
                 validate!(CondExpr {
                     span: DUMMY_SP,
@@ -403,4 +393,65 @@ impl OptChaining {
             _ => unreachable!("TsOptChain.expr = {:?}", e.expr),
         }
     }
+
+    /// Creates `left === null || right === void 0`, or `left == null` if
+    /// `document.all` is not used.
+    fn test_nullish(
+        &self,
+        span: Span,
+        left_span: Span,
+        left: Box<Expr>,
+        right: Box<Expr>,
+    ) -> Box<Expr> {
+        if self.c.no_document_all {
+            return Box::new(Expr::Bin(BinExpr {
+                span,
+                left,
+                op: op!("=="),
+                right: Box::new(Expr::Lit(Lit::Null(Null { span: DUMMY_SP }))),
+            }));
+        }
+
+        Box::new(Expr::Bin(BinExpr {
+            span,
+            left: Box::new(Expr::Bin(BinExpr {
+                span: left_span,
+                left,
+                op: op!("==="),
+                right: Box::new(Expr::Lit(Lit::Null(Null { span: DUMMY_SP }))),
+            })),
+            op: op!("||"),
+            right: Box::new(Expr::Bin(BinExpr {
+                span: DUMMY_SP,
+                left: right,
+                op: op!("==="),
+                right: undefined(span),
+            })),
+        }))
+    }
+
+    /// Returns `true` if `e` can be evaluated twice because getters are
+    /// assumed to be pure.
+    fn is_pure(&self, e: &Expr) -> bool {
+        if !self.c.pure_getters {
+            return false;
+        }
+
+        match e {
+            Expr::Ident(..) | Expr::This(..) => true,
+            Expr::Member(MemberExpr {
+                obj: ExprOrSuper::Expr(obj),
+                prop,
+                computed,
+                ..
+            }) => {
+                self.is_pure(obj)
+                    && match **prop {
+                        Expr::Lit(..) => true,
+                        _ => !computed,
+                    }
+            }
+            _ => false,
+        }
+    }
 }
@@ -0,0 +1,21 @@
+/// Returns a long-form explanation of a diagnostic code emitted by a
+/// transform.
+///
+/// Codes of transforms start with `E1`. Codes emitted by the parser are
+/// explained by `swc_ecma_parser::error::explain`.
+pub fn explain(code: &str) -> Option<&'static str> {
+    Some(match code {
+        "E1001" => {
+            r#"A namespaced JSX element name was used while `throwIfNamespace` is enabled.
+
+Erroneous code example:
+
+    var a = <svg:rect />;
+
+React does not support namespaced element names. Use a plain element name, or
+set `jsc.transform.react.throwIfNamespace` to `false` to emit the name as a
+string literal, like `React.createElement("svg:rect")`."#
+        }
+        _ => return None,
+    })
+}
@@ -0,0 +1,167 @@
+use swc_atoms::js_word;
+use swc_common::util::move_map::MoveMap;
+use swc_ecma_ast::*;
+use swc_ecma_visit::{Fold, FoldWith};
+
+/// Strips flow type annotations out.
+///
+/// Unlike `typescript::strip`, imports are not dropped unless they are marked
+/// as type-only, because flow requires types to be imported explicitly.
+pub fn strip() -> impl Fold {
+    Strip
+}
+
+struct Strip;
+
+/// Returns true if `decl` exists only for the type checker.
+fn is_type_only(decl: &Decl) -> bool {
+    match *decl {
+        Decl::TsInterface(..)
+        | Decl::TsTypeAlias(..)
+        | Decl::TsModule(..)
+        | Decl::Var(VarDecl { declare: true, .. })
+        | Decl::Class(ClassDecl { declare: true, .. })
+        | Decl::Fn(FnDecl { declare: true, .. })
+        | Decl::Fn(FnDecl {
+            function: Function { body: None, .. },
+            ..
+        }) => true,
+        _ => false,
+    }
+}
+
+macro_rules! type_to_none {
+    ($name:ident, $T:ty) => {
+        fn $name(&mut self, _: Option<$T>) -> Option<$T> {
+            None
+        }
+    };
+}
+
+impl Fold for Strip {
+    fn fold_array_pat(&mut self, mut pat: ArrayPat) -> ArrayPat {
+        pat = pat.fold_children_with(self);
+
+        pat.optional = false;
+
+        pat
+    }
+
+    fn fold_class(&mut self, node: Class) -> Class {
+        Class {
+            span: node.span,
+            is_abstract: false,
+            type_params: None,
+            super_type_params: None,
+            implements: vec![],
+
+            decorators: node.decorators.fold_with(self),
+            body: node.body.fold_with(self),
+            super_class: node.super_class.fold_with(self),
+        }
+    }
+
+    /// Removes declared fields, like `foo: string;`, as babel does.
+    fn fold_class_members(&mut self, members: Vec<ClassMember>) -> Vec<ClassMember> {
+        members.move_flat_map(|member| match member {
+            ClassMember::TsIndexSignature(..) => None,
+            ClassMember::Method(ClassMethod {
+                function: Function { body: None, .. },
+                ..
+            }) => None,
+            ClassMember::ClassProp(ClassProp {
+                value: None,
+                type_ann: Some(..),
+                ref decorators,
+                ..
+            })
+            | ClassMember::PrivateProp(PrivateProp {
+                value: None,
+                type_ann: Some(..),
+                ref decorators,
+                ..
+            }) if decorators.is_empty() => None,
+
+            _ => Some(member.fold_with(self)),
+        })
+    }
+
+    fn fold_expr(&mut self, expr: Expr) -> Expr {
+        match expr {
+            Expr::TsTypeCast(TsTypeCastExpr { expr, .. }) | Expr::TsAs(TsAsExpr { expr, .. }) => {
+                (*expr).fold_with(self)
+            }
+            _ => expr.fold_children_with(self),
+        }
+    }
+
+    fn fold_ident(&mut self, i: Ident) -> Ident {
+        Ident {
+            optional: false,
+            ..i.fold_children_with(self)
+        }
+    }
+
+    fn fold_module_items(&mut self, items: Vec<ModuleItem>) -> Vec<ModuleItem> {
+        items.move_flat_map(|item| match item {
+            ModuleItem::Stmt(Stmt::Decl(ref decl))
+            | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { ref decl, .. }))
+                if is_type_only(decl) =>
+            {
+                None
+            }
+
+            ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+                type_only: true, ..
+            }))
+            | ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(NamedExport {
+                type_only: true,
+                ..
+            }))
+            | ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(ExportDefaultDecl {
+                decl: DefaultDecl::TsInterfaceDecl(..),
+                ..
+            })) => None,
+
+            _ => Some(item.fold_with(self)),
+        })
+    }
+
+    fn fold_object_pat(&mut self, mut pat: ObjectPat) -> ObjectPat {
+        pat = pat.fold_children_with(self);
+
+        pat.optional = false;
+
+        pat
+    }
+
+    type_to_none!(fold_opt_ts_type, Box<TsType>);
+    type_to_none!(fold_opt_ts_type_ann, TsTypeAnn);
+    type_to_none!(fold_opt_ts_type_param_decl, TsTypeParamDecl);
+    type_to_none!(
+        fold_opt_ts_type_param_instantiation,
+        TsTypeParamInstantiation
+    );
+
+    /// Removes `this` from parameter list
+    fn fold_params(&mut self, params: Vec<Param>) -> Vec<Param> {
+        let mut params = params.fold_children_with(self);
+
+        params.retain(|param| match param.pat {
+            Pat::Ident(Ident {
+                sym: js_word!("this"),
+                ..
+            }) => false,
+            _ => true,
+        });
+
+        params
+    }
+
+    fn fold_stmts(&mut self, stmts: Vec<Stmt>) -> Vec<Stmt> {
+        stmts.move_flat_map(|stmt| match stmt {
+            Stmt::Decl(ref decl) if is_type_only(decl) => None,
+            _ => Some(stmt.fold_with(self)),
+        })
+    }
+}
@@ -1,10 +1,12 @@
+use inflector::Inflector;
 use once_cell::sync::Lazy;
 use scoped_tls::scoped_thread_local;
+use serde::{Deserialize, Serialize};
 use std::sync::atomic::{AtomicBool, Ordering};
 use swc_common::{FileName, FilePathMapping, Mark, SourceMap, DUMMY_SP};
 use swc_ecma_ast::*;
 use swc_ecma_parser::{lexer::Lexer, Parser, StringInput};
-use swc_ecma_utils::{prepend_stmts, quote_ident, quote_str, DropSpan};
+use swc_ecma_utils::{prepend_stmts, quote_ident, quote_str, DropSpan, ExprFactory};
 use swc_ecma_visit::{as_folder, noop_visit_mut_type, Fold, VisitMut, VisitMutWith};
 
 #[macro_export]
@@ -62,28 +64,205 @@ macro_rules! add_to {
 
 scoped_thread_local!(pub static HELPERS: Helpers);
 
+/// Configures how external helpers are imported.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct Config {
+    /// The module which provides helpers.
+    #[serde(default = "default_module")]
+    pub module: String,
+
+    #[serde(default)]
+    pub import_style: ImportStyle,
+
+    /// Uses `require` instead of `import`.
+    #[serde(default)]
+    pub require: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            module: default_module(),
+            import_style: Default::default(),
+            require: false,
+        }
+    }
+}
+
+fn default_module() -> String {
+    "@swc/helpers".into()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ImportStyle {
+    /// `import * as swcHelpers from '@swc/helpers'`
+    Namespace,
+    /// `import { classCallCheck as _classCallCheck } from '@swc/helpers'`
+    Named,
+    /// `import _classCallCheck from '@swc/helpers/_class_call_check'`
+    ///
+    /// The name of a file is same as the name of the helper file of swc.
+    PerFile,
+}
+
+impl Default for ImportStyle {
+    fn default() -> Self {
+        ImportStyle::Namespace
+    }
+}
+
 /// Tracks used helper methods. (e.g. __extends)
 #[derive(Default)]
 pub struct Helpers {
     external: bool,
+    config: Config,
     mark: HelperMark,
     inner: Inner,
 }
 
 impl Helpers {
     pub fn new(external: bool) -> Self {
+        Helpers::with_config(external, Default::default())
+    }
+
+    /// `config` is used only if `external` is true.
+    pub fn with_config(external: bool, config: Config) -> Self {
         Helpers {
             external,
+            config,
             mark: Default::default(),
             inner: Default::default(),
         }
     }
+
     pub(crate) const fn mark(&self) -> Mark {
         self.mark.0
     }
     pub(crate) const fn external(&self) -> bool {
         self.external
     }
+
+    /// Returns `true` if helpers are accessed as properties of `swcHelpers`.
+    pub(crate) fn is_namespace(&self) -> bool {
+        self.external && self.config.import_style == ImportStyle::Namespace
+    }
+
+    fn imports(&self) -> Vec<ModuleItem> {
+        let mark = self.mark();
+        let Config {
+            ref module,
+            import_style,
+            require,
+        } = self.config;
+
+        let import = |local: Ident, imported: Option<Ident>, src: String| {
+            if require {
+                // var _foo = require('foo').foo;
+                let call = Expr::Call(CallExpr {
+                    span: DUMMY_SP,
+                    callee: quote_ident!("require").as_callee(),
+                    args: vec![quote_str!(src).as_arg()],
+                    type_args: Default::default(),
+                });
+                let init = match imported {
+                    Some(imported) => call.make_member(imported),
+                    None => call,
+                };
+
+                ModuleItem::Stmt(Stmt::Decl(Decl::Var(VarDecl {
+                    span: DUMMY_SP,
+                    kind: VarDeclKind::Var,
+                    declare: false,
+                    decls: vec![VarDeclarator {
+                        span: DUMMY_SP,
+                        name: Pat::Ident(local),
+                        init: Some(Box::new(init)),
+                        definite: false,
+                    }],
+                })))
+            } else {
+                let specifier = match (import_style, imported) {
+                    (ImportStyle::Namespace, _) => {
+                        ImportSpecifier::Namespace(ImportStarAsSpecifier {
+                            span: DUMMY_SP,
+                            local,
+                        })
+                    }
+                    (_, Some(imported)) => ImportSpecifier::Named(ImportNamedSpecifier {
+                        span: DUMMY_SP,
+                        local,
+                        imported: Some(imported),
+                    }),
+                    (_, None) => ImportSpecifier::Default(ImportDefaultSpecifier {
+                        span: DUMMY_SP,
+                        local,
+                    }),
+                };
+
+                ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+                    span: DUMMY_SP,
+                    specifiers: vec![specifier],
+                    src: quote_str!(src),
+                    type_only: false,
+                }))
+            }
+        };
+
+        match import_style {
+            ImportStyle::Namespace => vec![import(
+                quote_ident!(DUMMY_SP.apply_mark(mark), "swcHelpers"),
+                None,
+                module.clone(),
+            )],
+            ImportStyle::Named => self
+                .used()
+                .into_iter()
+                .map(|name| {
+                    let name = helper_name(name);
+                    import(
+                        quote_ident!(DUMMY_SP.apply_mark(mark), format!("_{}", name)),
+                        Some(quote_ident!(external_name(&name))),
+                        module.clone(),
+                    )
+                })
+                .collect(),
+            ImportStyle::PerFile => self
+                .used()
+                .into_iter()
+                .map(|name| {
+                    import(
+                        quote_ident!(DUMMY_SP.apply_mark(mark), format!("_{}", helper_name(name))),
+                        None,
+                        format!("{}/_{}", module, name),
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Returns the name used in code, like `classCallCheck` for
+/// `class_call_check`.
+///
+/// Helpers are declared and imported as this name prefixed with `_`.
+pub fn helper_name(name: &str) -> String {
+    match name {
+        "class_name_tdz_error" => "classNameTDZError".into(),
+        "type_of" => "typeof".into(),
+        _ => name.to_camel_case(),
+    }
+}
+
+/// Returns the name exported by external helper modules.
+///
+/// This should be kept in sync with `external_name!`.
+fn external_name(name: &str) -> String {
+    match name {
+        "typeof" | "instanceof" | "throw" => format!("_{}", name),
+        _ => name.into(),
+    }
 }
 
 #[derive(Clone, Copy)]
@@ -115,32 +294,60 @@ macro_rules! define_helpers {
                     )*
                 }
             )*
-        }
-
-        impl InjectHelpers {
-            fn is_helper_used(&self) -> bool{
-                let mut value = false;
 
-                HELPERS.with(|helpers|{
+            /// Enables a helper by the name of its file, without the `_`
+            /// prefix and the extension. (e.g. `class_call_check`)
+            ///
+            /// Returns `false` if there's no such helper.
+            pub fn enable(&self, name: &str) -> bool {
+                match name {
                     $(
-                        value |= helpers.inner.$name.load(Ordering::Relaxed);
+                        stringify!($name) => {
+                            self.$name();
+                            true
+                        }
                     )*
-                });
+                    _ => false,
+                }
+            }
 
-                value
+            /// Returns definitions of enabled helpers, with identifiers
+            /// marked with `mark`.
+            pub fn definitions(&self, mark: Mark) -> Vec<ModuleItem> {
+                let mut buf = vec![];
+
+                $(
+                    add_to!(buf, $name, self.inner.$name, mark);
+                )*
+
+                buf
             }
 
-            fn build_helpers(&self) -> Vec<ModuleItem> {
+            /// Names of enabled helpers.
+            fn used(&self) -> Vec<&'static str> {
                 let mut buf = vec![];
 
+                $(
+                    if self.inner.$name.load(Ordering::Relaxed) {
+                        buf.push(stringify!($name));
+                    }
+                )*
+
+                buf
+            }
+        }
+
+        impl InjectHelpers {
+            fn is_helper_used(&self) -> bool{
+                let mut value = false;
+
                 HELPERS.with(|helpers|{
-                    debug_assert!(!helpers.external);
                     $(
-                            add_to!(buf, $name, helpers.inner.$name, helpers.mark.0);
+                        value |= helpers.inner.$name.load(Ordering::Relaxed);
                     )*
                 });
 
-                buf
+                value
             }
         }
     };
@@ -161,7 +368,7 @@ define_helpers!(Helpers {
     class_name_tdz_error: (),
     class_private_field_get: (),
     class_private_field_loose_base: (),
-    // class_private_field_loose_key: (),
+    class_private_field_loose_key: (),
     class_private_field_set: (),
     class_private_method_get: (),
     class_private_method_set: (),
@@ -235,24 +442,17 @@ struct InjectHelpers;
 
 impl InjectHelpers {
     fn mk_helpers(&self) -> Vec<ModuleItem> {
-        let (mark, external) = HELPERS.with(|helper| (helper.mark(), helper.external()));
-        if external {
-            if self.is_helper_used() {
-                vec![ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
-                    span: DUMMY_SP,
-                    specifiers: vec![ImportSpecifier::Namespace(ImportStarAsSpecifier {
-                        span: DUMMY_SP,
-                        local: quote_ident!(DUMMY_SP.apply_mark(mark), "swcHelpers"),
-                    })],
-                    src: quote_str!("@swc/helpers"),
-                    type_only: false,
-                }))]
+        if !self.is_helper_used() {
+            return vec![];
+        }
+
+        HELPERS.with(|helpers| {
+            if helpers.external() {
+                helpers.imports()
             } else {
-                vec![]
+                helpers.definitions(helpers.mark())
             }
-        } else {
-            self.build_helpers()
-        }
+        })
     }
 }
 
@@ -282,23 +482,18 @@ mod tests {
     use crate::pass::noop;
     use swc_ecma_visit::{as_folder, FoldWith};
 
-    #[test]
-    fn external_helper() {
-        let input = "_throw()
-swcHelpers._throw()";
+    fn test_external(config: Config, enable: impl FnOnce(), input: &str, expected: &str) {
         crate::tests::Tester::run(|tester| {
-            HELPERS.set(&Helpers::new(true), || {
+            HELPERS.set(&Helpers::with_config(true, config), || {
                 let expected = tester.apply_transform(
                     as_folder(DropSpan {
                         preserve_ctxt: false,
                     }),
                     "output.js",
                     Default::default(),
-                    "import * as swcHelpers1 from '@swc/helpers';
-_throw();
-swcHelpers._throw();",
+                    expected,
                 )?;
-                enable_helper!(throw);
+                enable();
 
                 eprintln!("----- Actual -----");
 
@@ -329,6 +524,90 @@ swcHelpers._throw();",
         });
     }
 
+    #[test]
+    fn external_helper() {
+        test_external(
+            Default::default(),
+            || {
+                enable_helper!(throw);
+            },
+            "_throw()
+swcHelpers._throw()",
+            "import * as swcHelpers1 from '@swc/helpers';
+_throw();
+swcHelpers._throw();",
+        );
+    }
+
+    #[test]
+    fn external_helper_named() {
+        test_external(
+            Config {
+                module: "@babel/runtime/helpers".into(),
+                import_style: ImportStyle::Named,
+                require: false,
+            },
+            || {
+                enable_helper!(inherits);
+            },
+            "foo()",
+            "import { inherits as _inherits } from '@babel/runtime/helpers';
+import { setPrototypeOf as _setPrototypeOf } from '@babel/runtime/helpers';
+foo();",
+        );
+    }
+
+    #[test]
+    fn external_helper_per_file() {
+        test_external(
+            Config {
+                import_style: ImportStyle::PerFile,
+                ..Default::default()
+            },
+            || {
+                enable_helper!(class_call_check);
+                enable_helper!(type_of);
+            },
+            "foo()",
+            "import _classCallCheck from '@swc/helpers/_class_call_check';
+import _typeof from '@swc/helpers/_type_of';
+foo();",
+        );
+    }
+
+    #[test]
+    fn external_helper_require() {
+        test_external(
+            Config {
+                require: true,
+                ..Default::default()
+            },
+            || {
+                enable_helper!(class_call_check);
+            },
+            "foo()",
+            "var swcHelpers = require('@swc/helpers');
+foo();",
+        );
+    }
+
+    #[test]
+    fn external_helper_require_named() {
+        test_external(
+            Config {
+                import_style: ImportStyle::Named,
+                require: true,
+                ..Default::default()
+            },
+            || {
+                enable_helper!(type_of);
+            },
+            "foo()",
+            "var _typeof = require('@swc/helpers')._typeof;
+foo();",
+        );
+    }
+
     #[test]
     fn use_strict_before_helper() {
         crate::tests::test_transform(
@@ -8,6 +8,7 @@ extern crate swc_ecma_utils;
 #[cfg(feature = "const-modules")]
 pub use self::const_modules::const_modules;
 pub use self::{
+    explain::explain,
     fixer::fixer,
     hygiene::hygiene,
     resolver::{resolver, resolver_with_mark},
@@ -28,7 +29,9 @@ mod hygiene;
 pub mod compat;
 mod const_modules;
 pub mod debug;
+mod explain;
 mod fixer;
+pub mod flow;
 pub mod modules;
 pub mod optimization;
 pub mod pass;
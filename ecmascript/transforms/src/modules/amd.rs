@@ -1,6 +1,6 @@
 use super::util::{
-    self, define_es_module, define_property, has_use_strict, initialize_to_undefined,
-    local_name_for_src, make_descriptor, use_strict, Exports, ModulePass, Scope,
+    self, define_es_module, define_property, has_use_strict, initialize_to_undefined, interop_call,
+    local_name_for_src, make_descriptor, use_strict, Exports, ImportInterop, ModulePass, Scope,
 };
 use crate::util::{prepend_stmts, var::VarCollector, DestructuringFinder, ExprFactory};
 use fxhash::FxHashSet;
@@ -75,7 +75,9 @@ impl Fold for Amd {
             };
 
             match decl {
-                ModuleDecl::Import(import) => self.scope.insert_import(import),
+                ModuleDecl::Import(import) => self
+                    .scope
+                    .insert_import(import, self.config.config.import_interop()),
 
                 ModuleDecl::ExportAll(..)
                 | ModuleDecl::ExportDecl(..)
@@ -314,6 +316,9 @@ impl Fold for Amd {
                                 })
                             {
                                 let is_import_default = orig.sym == js_word!("default");
+                                // Node uses `module.exports` as the default export.
+                                let is_node_default = is_import_default
+                                    && self.config.config.import_interop() == ImportInterop::Node;
 
                                 let key = (orig.sym.clone(), orig.span.ctxt());
                                 if self.scope.declared_vars.contains(&key) {
@@ -332,7 +337,7 @@ impl Fold for Amd {
                                 }
 
                                 if let Some(ref src) = export.src {
-                                    if is_import_default {
+                                    if is_import_default && !is_node_default {
                                         self.scope
                                             .import_types
                                             .entry(src.value.clone())
@@ -341,6 +346,9 @@ impl Fold for Amd {
                                 }
 
                                 let value = match imported {
+                                    Some(ref imported) if is_node_default => {
+                                        Box::new(Expr::Ident(imported.clone().unwrap()))
+                                    }
                                     Some(ref imported) => Box::new(
                                         imported.clone().unwrap().make_member(orig.clone()),
                                     ),
@@ -504,18 +512,10 @@ impl Fold for Amd {
                 let ty = self.scope.import_types.get(&src);
 
                 if let Some(&wildcard) = ty {
-                    if !self.config.config.no_interop {
+                    let interop = self.config.config.import_interop();
+                    if interop.needs_helper(wildcard) {
                         let imported = ident.clone();
-                        let right = Box::new(Expr::Call(CallExpr {
-                            span: DUMMY_SP,
-                            callee: if wildcard {
-                                helper!(interop_require_wildcard, "interopRequireWildcard")
-                            } else {
-                                helper!(interop_require_default, "interopRequireDefault")
-                            },
-                            args: vec![imported.as_arg()],
-                            type_args: Default::default(),
-                        }));
+                        let right = Box::new(interop_call(imported.into(), wildcard, interop));
                         import_stmts.push(
                             AssignExpr {
                                 span: DUMMY_SP,
@@ -1,11 +1,12 @@
 pub use super::util::Config;
 use super::util::{
-    define_es_module, define_property, has_use_strict, initialize_to_undefined, make_descriptor,
-    make_require_call, use_strict, ModulePass, Scope,
+    define_es_module, define_property, has_use_strict, initialize_to_undefined, interop_call,
+    is_dynamic_import, make_descriptor, make_require_call, use_strict, ImportInterop, ModulePass,
+    Scope,
 };
-use crate::util::{var::VarCollector, DestructuringFinder, ExprFactory};
+use crate::util::{find_ids, var::VarCollector, DestructuringFinder, ExprFactory, Id};
 use fxhash::FxHashSet;
-use swc_atoms::js_word;
+use swc_atoms::{js_word, JsWord};
 use swc_common::{Mark, DUMMY_SP};
 use swc_ecma_ast::*;
 use swc_ecma_visit::{noop_fold_type, Fold, FoldWith, VisitWith};
@@ -38,6 +39,12 @@ impl Fold for CommonJs {
             stmts.push(ModuleItem::Stmt(use_strict()));
         }
 
+        let annotation = if self.config.export_interop_annotation {
+            self.export_annotation(&items)
+        } else {
+            vec![]
+        };
+
         let mut exports = vec![];
         let mut initialized = FxHashSet::default();
         let mut export_alls = vec![];
@@ -46,9 +53,9 @@ impl Fold for CommonJs {
             self.in_top_level = true;
 
             match item {
-                ModuleItem::ModuleDecl(ModuleDecl::Import(import)) => {
-                    self.scope.insert_import(import)
-                }
+                ModuleItem::ModuleDecl(ModuleDecl::Import(import)) => self
+                    .scope
+                    .insert_import(import, self.config.import_interop()),
                 ModuleItem::ModuleDecl(ModuleDecl::ExportAll(..))
                 | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(..))
                 | ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(..))
@@ -303,6 +310,9 @@ impl Fold for CommonJs {
                                 })
                             {
                                 let is_import_default = orig.sym == js_word!("default");
+                                // Node uses `module.exports` as the default export.
+                                let is_node_default = is_import_default
+                                    && self.config.import_interop() == ImportInterop::Node;
 
                                 let key = (orig.sym.clone(), orig.span.ctxt());
                                 if self.scope.declared_vars.contains(&key) {
@@ -321,7 +331,7 @@ impl Fold for CommonJs {
                                 }
 
                                 if let Some(ref src) = export.src {
-                                    if is_import_default {
+                                    if is_import_default && !is_node_default {
                                         self.scope
                                             .import_types
                                             .entry(src.value.clone())
@@ -365,6 +375,9 @@ impl Fold for CommonJs {
                                 self.in_top_level = is_top_level;
 
                                 let value = match imported {
+                                    Some(ref imported) if is_node_default => {
+                                        Box::new(Expr::Ident(imported.clone().unwrap()))
+                                    }
                                     Some(ref imported) => Box::new(
                                         imported.clone().unwrap().make_member(orig.clone()),
                                     ),
@@ -504,19 +517,12 @@ impl Fold for CommonJs {
                     let ty = self.scope.import_types.get(&src);
 
                     let rhs = match ty {
-                        Some(true) if !self.config.no_interop => Box::new(Expr::Call(CallExpr {
-                            span: DUMMY_SP,
-                            callee: helper!(interop_require_wildcard, "interopRequireWildcard"),
-                            args: vec![require.as_arg()],
-                            type_args: Default::default(),
-                        })),
-                        Some(false) if !self.config.no_interop => Box::new(Expr::Call(CallExpr {
-                            span: DUMMY_SP,
-                            callee: helper!(interop_require_default, "interopRequireDefault"),
-                            args: vec![require.as_arg()],
-                            type_args: Default::default(),
-                        })),
-                        _ => Box::new(require),
+                        Some(&wildcard) => Box::new(interop_call(
+                            require,
+                            wildcard,
+                            self.config.import_interop(),
+                        )),
+                        None => Box::new(require),
                     };
 
                     let ident = Ident::new(import.0, import.1);
@@ -607,12 +613,44 @@ impl Fold for CommonJs {
 
         stmts.append(&mut extra_stmts);
 
+        if !annotation.is_empty() {
+            // 0 && (module.exports = { foo: foo, bar: bar });
+            stmts.push(
+                Expr::Bin(BinExpr {
+                    span: DUMMY_SP,
+                    left: Box::new(Expr::Lit(Lit::Num(Number {
+                        span: DUMMY_SP,
+                        value: 0.0,
+                    }))),
+                    op: op!("&&"),
+                    right: Box::new(Expr::Assign(AssignExpr {
+                        span: DUMMY_SP,
+                        left: PatOrExpr::Expr(member_expr!(DUMMY_SP, module.exports)),
+                        op: op!("="),
+                        right: Box::new(Expr::Object(ObjectLit {
+                            span: DUMMY_SP,
+                            props: annotation,
+                        })),
+                    })),
+                })
+                .into_stmt()
+                .into(),
+            );
+        }
+
         stmts
     }
 
     fn fold_expr(&mut self, expr: Expr) -> Expr {
         let top_level = self.in_top_level;
-        Scope::fold_expr(self, quote_ident!("exports"), top_level, expr)
+        let expr = Scope::fold_expr(self, quote_ident!("exports"), top_level, expr);
+
+        match expr {
+            Expr::Call(call) if !self.config.ignore_dynamic && is_dynamic_import(&call) => {
+                self.dynamic_import(call)
+            }
+            _ => expr,
+        }
     }
 
     fn fold_prop(&mut self, p: Prop) -> Prop {
@@ -655,6 +693,159 @@ impl Fold for CommonJs {
     mark_as_nested!();
 }
 
+impl CommonJs {
+    /// Converts `import('foo')` to
+    ///
+    /// ```js
+    /// Promise.resolve().then(function () {
+    ///     return _interopRequireWildcard(require('foo'));
+    /// })
+    /// ```
+    ///
+    /// A non-literal specifier is passed through `Promise.resolve` so that it
+    /// is evaluated eagerly, like `import()` does.
+    ///
+    /// The specifier keeps its span, so comments like
+    /// `/* webpackChunkName: "foo" */` stay in front of it.
+    fn dynamic_import(&self, mut call: CallExpr) -> Expr {
+        if call.args.len() != 1 {
+            return Expr::Call(call);
+        }
+        let arg = call.args.pop().unwrap();
+
+        let require = |arg: ExprOrSpread| {
+            Expr::Call(CallExpr {
+                span: DUMMY_SP,
+                callee: quote_ident!(DUMMY_SP.apply_mark(self.root_mark), "require").as_callee(),
+                args: vec![arg],
+                type_args: Default::default(),
+            })
+        };
+        let (resolve_args, params, require) = match *arg.expr {
+            Expr::Lit(Lit::Str(..)) => (vec![], vec![], require(arg)),
+            _ => {
+                let s = private_ident!("s");
+                let param = Param {
+                    span: DUMMY_SP,
+                    decorators: Default::default(),
+                    pat: Pat::Ident(s.clone()),
+                };
+
+                (vec![arg], vec![param], require(s.as_arg()))
+            }
+        };
+
+        let then = FnExpr {
+            ident: None,
+            function: Function {
+                span: DUMMY_SP,
+                is_async: false,
+                is_generator: false,
+                decorators: Default::default(),
+                body: Some(BlockStmt {
+                    span: DUMMY_SP,
+                    stmts: vec![Stmt::Return(ReturnStmt {
+                        span: DUMMY_SP,
+                        arg: Some(Box::new(interop_call(
+                            require,
+                            true,
+                            self.config.import_interop(),
+                        ))),
+                    })],
+                }),
+                params,
+                type_params: Default::default(),
+                return_type: Default::default(),
+            },
+        };
+
+        Expr::Call(CallExpr {
+            span: call.span,
+            callee: Expr::Call(CallExpr {
+                span: DUMMY_SP,
+                callee: member_expr!(DUMMY_SP, Promise.resolve).as_callee(),
+                args: resolve_args,
+                type_args: Default::default(),
+            })
+            .make_member(quote_ident!("then"))
+            .as_callee(),
+            args: vec![then.as_arg()],
+            type_args: Default::default(),
+        })
+    }
+
+    /// Creates properties of `module.exports = { foo: foo, bar: bar }`, which
+    /// is used by node to detect named exports of a commonjs module.
+    ///
+    /// The default export is not included, as node provides it anyway.
+    /// `export *` is not included either, because cjs-module-lexer detects the
+    /// code we emit for it.
+    fn export_annotation(&self, items: &[ModuleItem]) -> Vec<PropOrSpread> {
+        let imported: FxHashSet<Id> = items
+            .iter()
+            .filter_map(|item| match item {
+                ModuleItem::ModuleDecl(ModuleDecl::Import(import)) => Some(&import.specifiers),
+                _ => None,
+            })
+            .flatten()
+            .map(|s| {
+                let local = match s {
+                    ImportSpecifier::Named(s) => &s.local,
+                    ImportSpecifier::Default(s) => &s.local,
+                    ImportSpecifier::Namespace(s) => &s.local,
+                };
+                (local.sym.clone(), local.span.ctxt())
+            })
+            .collect();
+
+        // (exported, local)
+        let mut names: Vec<(JsWord, Ident)> = vec![];
+
+        for item in items {
+            match item {
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => match export.decl {
+                    Decl::Class(ref c) => names.push((c.ident.sym.clone(), c.ident.clone())),
+                    Decl::Fn(ref f) => names.push((f.ident.sym.clone(), f.ident.clone())),
+                    Decl::Var(ref v) => {
+                        let ids: Vec<Ident> = find_ids(&v.decls);
+                        names.extend(ids.into_iter().map(|id| (id.sym.clone(), id)));
+                    }
+                    _ => {}
+                },
+                ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(export)) => {
+                    for s in &export.specifiers {
+                        if let ExportSpecifier::Named(s) = s {
+                            let exported = s.exported.as_ref().unwrap_or(&s.orig).sym.clone();
+                            // Imports are replaced, so we can't refer to them.
+                            let is_local = export.src.is_none()
+                                && !imported.contains(&(s.orig.sym.clone(), s.orig.span.ctxt()));
+                            let local = if is_local {
+                                s.orig.clone()
+                            } else {
+                                Ident::new(exported.clone(), DUMMY_SP)
+                            };
+
+                            names.push((exported, local));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        names
+            .into_iter()
+            .filter(|(exported, _)| *exported != js_word!("default"))
+            .map(|(exported, local)| {
+                PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                    key: PropName::Ident(Ident::new(exported, DUMMY_SP)),
+                    value: Box::new(Expr::Ident(local)),
+                })))
+            })
+            .collect()
+    }
+}
+
 impl ModulePass for CommonJs {
     fn config(&self) -> &Config {
         &self.config
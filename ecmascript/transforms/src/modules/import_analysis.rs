@@ -1,18 +1,26 @@
-use super::util::Scope;
+use super::util::{is_dynamic_import, ImportInterop, Scope};
 use swc_atoms::js_word;
 use swc_common::DUMMY_SP;
 use swc_ecma_ast::*;
-use swc_ecma_visit::{noop_fold_type, Fold, Node, Visit};
+use swc_ecma_visit::{noop_fold_type, Fold, Node, Visit, VisitWith};
 
-pub fn import_analyzer() -> impl Fold {
+/// `ignore_dynamic` should be `true` if the module pass does not convert
+/// `import()`.
+pub fn import_analyzer(import_interop: ImportInterop, ignore_dynamic: bool) -> impl Fold {
     ImportAnalyzer {
+        import_interop,
+        ignore_dynamic,
         scope: Default::default(),
+        has_dynamic_import: false,
     }
 }
 
 /// Inject required helpers methods **for** module transform passes.
 struct ImportAnalyzer {
+    import_interop: ImportInterop,
+    ignore_dynamic: bool,
     scope: Scope,
+    has_dynamic_import: bool,
 }
 
 impl Fold for ImportAnalyzer {
@@ -21,7 +29,14 @@ impl Fold for ImportAnalyzer {
     fn fold_module(&mut self, module: Module) -> Module {
         self.visit_module(&module, &Invalid { span: DUMMY_SP } as _);
 
+        if self.has_dynamic_import && self.import_interop.needs_helper(true) {
+            enable_helper!(interop_require_wildcard);
+        }
+
         for (_, ty) in self.scope.import_types.drain() {
+            if !self.import_interop.needs_helper(ty) {
+                continue;
+            }
             if ty {
                 enable_helper!(interop_require_wildcard);
             } else {
@@ -36,6 +51,14 @@ impl Fold for ImportAnalyzer {
 impl Visit for ImportAnalyzer {
     noop_visit_type!();
 
+    fn visit_call_expr(&mut self, call: &CallExpr, _parent: &dyn Node) {
+        call.visit_children_with(self);
+
+        if !self.ignore_dynamic && is_dynamic_import(call) {
+            self.has_dynamic_import = true;
+        }
+    }
+
     fn visit_export_all(&mut self, export: &ExportAll, _parent: &dyn Node) {
         *self
             .scope
@@ -65,6 +88,8 @@ impl Visit for ImportAnalyzer {
                     ImportSpecifier::Namespace(..) => unreachable!(
                         "import * as foo cannot be used with other type of import specifiers"
                     ),
+                    // Node uses `module.exports` as the default export.
+                    ImportSpecifier::Default(_) if self.import_interop == ImportInterop::Node => {}
                     ImportSpecifier::Default(_) => {
                         self.scope
                             .import_types
@@ -84,10 +109,12 @@ impl Visit for ImportAnalyzer {
                         let is_default = name == js_word!("default");
 
                         if is_default {
-                            self.scope
-                                .import_types
-                                .entry(import.src.value.clone())
-                                .or_insert(false);
+                            if self.import_interop != ImportInterop::Node {
+                                self.scope
+                                    .import_types
+                                    .entry(import.src.value.clone())
+                                    .or_insert(false);
+                            }
                         } else {
                             self.scope
                                 .import_types
@@ -109,10 +136,12 @@ impl Visit for ImportAnalyzer {
 
             if let Some(ref src) = export.src {
                 if is_import_default {
-                    self.scope
-                        .import_types
-                        .entry(src.value.clone())
-                        .or_insert(false);
+                    if self.import_interop != ImportInterop::Node {
+                        self.scope
+                            .import_types
+                            .entry(src.value.clone())
+                            .or_insert(false);
+                    }
                 } else {
                     self.scope
                         .import_types
@@ -0,0 +1,318 @@
+//! Rewriting of module specifiers.
+use std::{
+    path::{Component, Path, PathBuf},
+    sync::Arc,
+};
+use swc_atoms::{js_word, JsWord};
+use swc_common::{FileName, Mark, SyntaxContext};
+use swc_ecma_ast::*;
+use swc_ecma_visit::{noop_fold_type, Fold, FoldWith};
+
+/// Decides which specifier is emitted for a module specifier.
+pub trait ImportResolver {
+    /// Returns the specifier to emit for `module_specifier`, which is imported
+    /// from `base`.
+    ///
+    /// Returns [None] to keep the specifier as is.
+    fn resolve_import(&self, base: &FileName, module_specifier: &str) -> Option<JsWord>;
+}
+
+impl<T: ?Sized + ImportResolver> ImportResolver for &'_ T {
+    fn resolve_import(&self, base: &FileName, module_specifier: &str) -> Option<JsWord> {
+        (**self).resolve_import(base, module_specifier)
+    }
+}
+
+impl<T: ?Sized + ImportResolver> ImportResolver for Box<T> {
+    fn resolve_import(&self, base: &FileName, module_specifier: &str) -> Option<JsWord> {
+        (**self).resolve_import(base, module_specifier)
+    }
+}
+
+impl<T: ?Sized + ImportResolver> ImportResolver for Arc<T> {
+    fn resolve_import(&self, base: &FileName, module_specifier: &str) -> Option<JsWord> {
+        (**self).resolve_import(base, module_specifier)
+    }
+}
+
+/// Rewrites specifiers of
+///
+///  - `import 'foo'`
+///  - `export * from 'foo'`
+///  - `export { foo } from 'foo'`
+///  - `import('foo')`
+///  - `require('foo')`
+///
+/// using `resolver`. `base` is the file being transformed.
+///
+/// This should be applied after the resolver and before module transforms.
+/// `require` is treated as a call to the global function only if it's not
+/// declared in a nested scope, so `top_level_mark` should be the mark passed
+/// to the resolver.
+pub fn import_rewriter<R: ImportResolver>(
+    base: FileName,
+    resolver: R,
+    top_level_mark: Mark,
+) -> impl Fold {
+    ImportRewriter {
+        base,
+        resolver,
+        top_level_ctxt: SyntaxContext::empty().apply_mark(top_level_mark),
+    }
+}
+
+struct ImportRewriter<R: ImportResolver> {
+    base: FileName,
+    resolver: R,
+    top_level_ctxt: SyntaxContext,
+}
+
+impl<R: ImportResolver> ImportRewriter<R> {
+    fn rewrite(&self, src: Str) -> Str {
+        match self.resolver.resolve_import(&self.base, &src.value) {
+            Some(value) => Str {
+                value,
+                has_escape: false,
+                ..src
+            },
+            None => src,
+        }
+    }
+}
+
+impl<R: ImportResolver> Fold for ImportRewriter<R> {
+    noop_fold_type!();
+
+    fn fold_import_decl(&mut self, i: ImportDecl) -> ImportDecl {
+        ImportDecl {
+            src: self.rewrite(i.src),
+            ..i
+        }
+    }
+
+    fn fold_export_all(&mut self, e: ExportAll) -> ExportAll {
+        ExportAll {
+            src: self.rewrite(e.src),
+            ..e
+        }
+    }
+
+    fn fold_named_export(&mut self, e: NamedExport) -> NamedExport {
+        NamedExport {
+            src: e.src.map(|src| self.rewrite(src)),
+            ..e
+        }
+    }
+
+    fn fold_call_expr(&mut self, e: CallExpr) -> CallExpr {
+        let mut e = e.fold_children_with(self);
+
+        let is_import_or_require = match e.callee {
+            ExprOrSuper::Expr(ref callee) => match **callee {
+                Expr::Ident(Ident {
+                    sym: js_word!("import"),
+                    ..
+                }) => true,
+                // Unresolved references keep the empty context.
+                Expr::Ident(Ident {
+                    sym: js_word!("require"),
+                    span,
+                    ..
+                }) => span.ctxt == SyntaxContext::empty() || span.ctxt == self.top_level_ctxt,
+                _ => false,
+            },
+            _ => false,
+        };
+        if !is_import_or_require || e.args.len() != 1 {
+            return e;
+        }
+
+        if let Expr::Lit(Lit::Str(ref mut src)) = *e.args[0].expr {
+            let s = src.clone();
+            *src = self.rewrite(s);
+        }
+
+        e
+    }
+}
+
+/// An [ImportResolver] which handles `paths` and `baseUrl` of tsconfig.
+///
+/// Paths are resolved from `Real` files, and rewritten into relative
+/// specifiers.
+#[derive(Debug, Clone, Default)]
+pub struct TsConfigResolver {
+    /// `compilerOptions.baseUrl`. Should be an absolute path.
+    pub base_url: Option<PathBuf>,
+    /// `compilerOptions.paths`.
+    ///
+    /// Each pattern may contain one `*`. Targets are relative to `base_url`.
+    pub paths: Vec<(String, Vec<String>)>,
+    /// Rewrites `.ts`, `.tsx`, `.mts` and `.cts` extensions of relative
+    /// specifiers to `.js`, `.js`, `.mjs` and `.cjs`.
+    pub rewrite_extensions: bool,
+}
+
+impl TsConfigResolver {
+    /// Returns the path of the target of the best matching pattern.
+    fn resolve_paths(&self, base_url: &Path, specifier: &str) -> Option<PathBuf> {
+        // An exact match wins, and otherwise the longest prefix wins.
+        let mut best: Option<(usize, &str, &[String])> = None;
+
+        for (pattern, targets) in &self.paths {
+            let (matched, captured) = match pattern.find('*') {
+                None if pattern == specifier => (usize::max_value(), ""),
+                None => continue,
+                Some(idx) => {
+                    let (prefix, suffix) = (&pattern[..idx], &pattern[idx + 1..]);
+                    if specifier.len() < prefix.len() + suffix.len()
+                        || !specifier.starts_with(prefix)
+                        || !specifier.ends_with(suffix)
+                    {
+                        continue;
+                    }
+                    (
+                        prefix.len(),
+                        &specifier[prefix.len()..specifier.len() - suffix.len()],
+                    )
+                }
+            };
+
+            if best.map(|(len, ..)| matched > len).unwrap_or(true) {
+                best = Some((matched, captured, targets.as_slice()));
+            }
+        }
+
+        let (_, captured, targets) = best?;
+        let candidates: Vec<PathBuf> = targets
+            .iter()
+            .map(|target| base_url.join(target.replacen('*', captured, 1)))
+            .collect();
+
+        // Like tsc, we prefer the first target which exists.
+        candidates
+            .iter()
+            .find(|path| exists(path))
+            .or_else(|| candidates.first())
+            .cloned()
+    }
+
+    fn rewrite_extension(&self, specifier: String) -> String {
+        if !self.rewrite_extensions || !is_relative(&specifier) || specifier.ends_with(".d.ts") {
+            return specifier;
+        }
+
+        for &(from, to) in &[
+            (".ts", ".js"),
+            (".tsx", ".js"),
+            (".mts", ".mjs"),
+            (".cts", ".cjs"),
+        ] {
+            if specifier.ends_with(from) {
+                return format!("{}{}", &specifier[..specifier.len() - from.len()], to);
+            }
+        }
+
+        specifier
+    }
+}
+
+impl ImportResolver for TsConfigResolver {
+    fn resolve_import(&self, base: &FileName, module_specifier: &str) -> Option<JsWord> {
+        let resolved = match (base, &self.base_url) {
+            (FileName::Real(base), Some(base_url)) if !is_relative(module_specifier) => {
+                let target = self.resolve_paths(base_url, module_specifier).or_else(|| {
+                    // `baseUrl` is used only if the file exists, because
+                    // otherwise it's a package.
+                    Some(base_url.join(module_specifier)).filter(|path| exists(path))
+                });
+
+                target.map(|target| relative_specifier(base.parent()?, &target))
+            }
+            _ => None,
+        };
+
+        match resolved {
+            Some(Some(specifier)) => Some(self.rewrite_extension(specifier).into()),
+            _ => {
+                let specifier = self.rewrite_extension(module_specifier.to_string());
+                if specifier == module_specifier {
+                    None
+                } else {
+                    Some(specifier.into())
+                }
+            }
+        }
+    }
+}
+
+fn is_relative(specifier: &str) -> bool {
+    specifier == "."
+        || specifier == ".."
+        || specifier.starts_with("./")
+        || specifier.starts_with("../")
+}
+
+/// Returns `true` if `path` is a file, or a file with a known extension, or a
+/// directory.
+fn exists(path: &Path) -> bool {
+    if path.exists() {
+        return true;
+    }
+
+    let file_name = match path.file_name() {
+        Some(v) => v.to_string_lossy(),
+        None => return false,
+    };
+    ["ts", "tsx", "d.ts", "js", "jsx", "json"]
+        .iter()
+        .any(|ext| {
+            path.with_file_name(format!("{}.{}", file_name, ext))
+                .exists()
+        })
+}
+
+/// Creates a specifier for `to` which can be used from `from_dir`.
+fn relative_specifier(from_dir: &Path, to: &Path) -> Option<String> {
+    let from: Vec<_> = normalize(from_dir);
+    let to: Vec<_> = normalize(to);
+
+    let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+    // Paths on different drives.
+    if common == 0 && (!from.is_empty() || !to.is_empty()) {
+        return None;
+    }
+
+    let mut parts = vec![];
+    for _ in common..from.len() {
+        parts.push(String::from(".."));
+    }
+    for c in &to[common..] {
+        parts.push(c.as_os_str().to_string_lossy().into_owned());
+    }
+
+    let specifier = parts.join("/");
+    if specifier.starts_with("../") || specifier == ".." {
+        Some(specifier)
+    } else {
+        Some(format!("./{}", specifier))
+    }
+}
+
+fn normalize(path: &Path) -> Vec<Component> {
+    let mut components = vec![];
+    for c in path.components() {
+        match c {
+            Component::CurDir => {}
+            Component::ParentDir => match components.last() {
+                Some(Component::Normal(..)) => {
+                    components.pop();
+                }
+                _ => components.push(c),
+            },
+            _ => components.push(c),
+        }
+    }
+
+    components
+}
@@ -0,0 +1,884 @@
+use super::util::{default_strict_mode, has_use_strict, local_name_for_src, use_strict};
+use crate::util::{find_ids, id, ExprFactory, Id};
+use fxhash::{FxHashMap, FxHashSet};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::iter;
+use swc_atoms::{js_word, JsWord};
+use swc_common::DUMMY_SP;
+use swc_ecma_ast::*;
+use swc_ecma_visit::{noop_fold_type, noop_visit_type, Fold, FoldWith, Node, Visit, VisitWith};
+
+/// Converts es modules to the `System.register` format.
+///
+/// ```js
+/// System.register(['foo'], function (_export, _context) {
+///     'use strict';
+///     var foo, bar;
+///     return {
+///         setters: [function (_foo) {
+///             foo = _foo.default;
+///         }],
+///         execute: function () {
+///             _export('bar', bar = foo());
+///         }
+///     };
+/// });
+/// ```
+///
+/// Top-level declarations are hoisted to the factory function so that
+/// setters and hoisted functions can see them, and every assignment to an
+/// exported binding is wrapped with `_export` to keep the binding live.
+pub fn system_js(config: Config) -> impl Fold {
+    SystemJs { config }
+}
+
+struct SystemJs {
+    config: Config,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct Config {
+    #[serde(default)]
+    pub module_id: Option<String>,
+    #[serde(default = "default_strict_mode")]
+    pub strict_mode: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            module_id: None,
+            strict_mode: default_strict_mode(),
+        }
+    }
+}
+
+/// Bindings a module takes from one of its dependencies.
+#[derive(Default)]
+struct Dep {
+    /// `(local, imported)`, where `imported` is [None] for
+    /// `import * as foo from 'foo'`.
+    imports: Vec<(Ident, Option<JsWord>)>,
+    /// `(exported, orig)`, where `orig` is [None] for
+    /// `export * as foo from 'foo'`.
+    reexports: Vec<(JsWord, Option<JsWord>)>,
+    /// `export * from 'foo'`
+    export_all: bool,
+}
+
+impl Fold for SystemJs {
+    noop_fold_type!();
+
+    fn fold_module(&mut self, module: Module) -> Module {
+        let export_ident = private_ident!("_export");
+        let context_ident = private_ident!("_context");
+
+        let mut items = module.body;
+        let strict_mode = self.config.strict_mode || has_use_strict(&items);
+        if has_use_strict(&items) {
+            items.remove(0);
+        }
+
+        let mut deps: IndexMap<JsWord, Dep> = Default::default();
+        // Map from a local binding to its exported names.
+        let mut exports: FxHashMap<Id, Vec<JsWord>> = Default::default();
+        let mut vars = vec![];
+        let mut fns = vec![];
+        let mut execute = vec![];
+
+        macro_rules! export {
+            ($local:expr, $exported:expr) => {
+                exports.entry(id(&$local)).or_default().push($exported)
+            };
+        }
+
+        for item in items {
+            let decl = match item {
+                ModuleItem::Stmt(Stmt::Decl(decl)) => {
+                    hoist_decl(decl, &mut vars, &mut fns, &mut execute);
+                    continue;
+                }
+                ModuleItem::Stmt(stmt) => {
+                    execute.push(stmt);
+                    continue;
+                }
+                ModuleItem::ModuleDecl(decl) => decl,
+            };
+
+            match decl {
+                ModuleDecl::Import(import) => {
+                    let dep = deps.entry(import.src.value).or_default();
+
+                    for specifier in import.specifiers {
+                        let (local, imported) = match specifier {
+                            ImportSpecifier::Default(s) => (s.local, Some(js_word!("default"))),
+                            ImportSpecifier::Named(s) => {
+                                let imported = s.imported.as_ref().unwrap_or(&s.local).sym.clone();
+                                (s.local, Some(imported))
+                            }
+                            ImportSpecifier::Namespace(s) => (s.local, None),
+                        };
+
+                        vars.push(local.clone());
+                        dep.imports.push((local, imported));
+                    }
+                }
+
+                ModuleDecl::ExportAll(export) => {
+                    deps.entry(export.src.value).or_default().export_all = true;
+                }
+
+                ModuleDecl::ExportNamed(NamedExport {
+                    src: Some(src),
+                    specifiers,
+                    ..
+                }) => {
+                    let dep = deps.entry(src.value).or_default();
+
+                    for specifier in specifiers {
+                        dep.reexports.push(match specifier {
+                            ExportSpecifier::Namespace(s) => (s.name.sym, None),
+                            ExportSpecifier::Default(s) => {
+                                (s.exported.sym, Some(js_word!("default")))
+                            }
+                            ExportSpecifier::Named(s) => (
+                                s.exported.unwrap_or_else(|| s.orig.clone()).sym,
+                                Some(s.orig.sym),
+                            ),
+                        });
+                    }
+                }
+
+                ModuleDecl::ExportNamed(NamedExport {
+                    src: None,
+                    specifiers,
+                    ..
+                }) => {
+                    for specifier in specifiers {
+                        match specifier {
+                            ExportSpecifier::Named(ExportNamedSpecifier {
+                                orig, exported, ..
+                            }) => {
+                                let exported = exported.as_ref().unwrap_or(&orig).sym.clone();
+                                export!(orig, exported);
+                            }
+                            _ => unreachable!(
+                                "export {{ foo }} should not have a namespace or default specifier"
+                            ),
+                        }
+                    }
+                }
+
+                ModuleDecl::ExportDecl(ExportDecl { decl, .. }) => {
+                    match decl {
+                        Decl::Fn(FnDecl { ref ident, .. })
+                        | Decl::Class(ClassDecl { ref ident, .. }) => {
+                            export!(ident, ident.sym.clone())
+                        }
+                        Decl::Var(ref var) => {
+                            for ident in find_ids::<_, Ident>(&var.decls) {
+                                export!(ident, ident.sym.clone())
+                            }
+                        }
+                        _ => {}
+                    }
+
+                    hoist_decl(decl, &mut vars, &mut fns, &mut execute);
+                }
+
+                ModuleDecl::ExportDefaultDecl(ExportDefaultDecl { decl, .. }) => match decl {
+                    DefaultDecl::Fn(FnExpr { ident, function }) => {
+                        let ident = ident.unwrap_or_else(|| private_ident!("_default"));
+                        export!(ident, js_word!("default"));
+
+                        fns.push(FnDecl {
+                            ident,
+                            function,
+                            declare: false,
+                        });
+                    }
+                    DefaultDecl::Class(ClassExpr { ident, class }) => {
+                        let ident = ident.unwrap_or_else(|| private_ident!("_default"));
+                        export!(ident, js_word!("default"));
+
+                        hoist_decl(
+                            Decl::Class(ClassDecl {
+                                ident,
+                                class,
+                                declare: false,
+                            }),
+                            &mut vars,
+                            &mut fns,
+                            &mut execute,
+                        );
+                    }
+                    DefaultDecl::TsInterfaceDecl(..) => {}
+                },
+
+                ModuleDecl::ExportDefaultExpr(ExportDefaultExpr { expr, .. }) => {
+                    execute.push(export_call(&export_ident, js_word!("default"), expr).into_stmt());
+                }
+
+                ModuleDecl::TsImportEquals(..)
+                | ModuleDecl::TsExportAssignment(..)
+                | ModuleDecl::TsNamespaceExport(..) => {}
+            }
+        }
+
+        // ====================
+        //  Rewrite bindings
+        // ====================
+
+        let execute = execute.fold_with(&mut VarHoister { vars: &mut vars });
+
+        let mut rewriter = Rewriter {
+            export: &export_ident,
+            context: &context_ident,
+            exports: &exports,
+            tmp: None,
+        };
+        let execute = execute.fold_with(&mut rewriter);
+        let fns: Vec<_> = fns
+            .into_iter()
+            .map(|f| f.fold_with(&mut rewriter))
+            .collect();
+        vars.extend(rewriter.tmp);
+
+        // ====================
+        //  Setters
+        // ====================
+
+        let mut dep_srcs = vec![];
+        let mut setters = vec![];
+
+        for (src, dep) in deps {
+            let param = private_ident!(local_name_for_src(&src));
+            let mut stmts = vec![];
+
+            for (local, imported) in dep.imports {
+                stmts.push(
+                    AssignExpr {
+                        span: DUMMY_SP,
+                        left: PatOrExpr::Pat(Box::new(Pat::Ident(local.clone()))),
+                        op: op!("="),
+                        right: Box::new(member_or_self(&param, imported)),
+                    }
+                    .into_stmt(),
+                );
+
+                // import { foo } from 'foo';
+                // export { foo };
+                if let Some(names) = exports.get(&id(&local)) {
+                    for name in names {
+                        stmts.push(
+                            export_call(
+                                &export_ident,
+                                name.clone(),
+                                Box::new(local.clone().into()),
+                            )
+                            .into_stmt(),
+                        );
+                    }
+                }
+            }
+
+            for (exported, orig) in dep.reexports {
+                stmts.push(
+                    export_call(
+                        &export_ident,
+                        exported,
+                        Box::new(member_or_self(&param, orig)),
+                    )
+                    .into_stmt(),
+                );
+            }
+
+            if dep.export_all {
+                stmts.extend(export_all(&export_ident, &param));
+            }
+
+            dep_srcs.push(Some(Lit::Str(quote_str!(src)).as_arg()));
+            setters.push(Some(
+                FnExpr {
+                    ident: None,
+                    function: Function {
+                        span: DUMMY_SP,
+                        is_async: false,
+                        is_generator: false,
+                        decorators: Default::default(),
+                        params: if stmts.is_empty() {
+                            vec![]
+                        } else {
+                            vec![Param {
+                                span: DUMMY_SP,
+                                decorators: Default::default(),
+                                pat: Pat::Ident(param),
+                            }]
+                        },
+                        body: Some(BlockStmt {
+                            span: DUMMY_SP,
+                            stmts,
+                        }),
+                        type_params: Default::default(),
+                        return_type: Default::default(),
+                    },
+                }
+                .as_arg(),
+            ));
+        }
+
+        // ====================
+        //  Emit
+        // ====================
+
+        let mut stmts = vec![];
+        if strict_mode {
+            stmts.push(use_strict());
+        }
+
+        let mut declared = FxHashSet::default();
+        let decls: Vec<_> = vars
+            .into_iter()
+            .filter(|i| declared.insert(id(i)))
+            .map(|i| VarDeclarator {
+                span: DUMMY_SP,
+                name: Pat::Ident(i),
+                init: None,
+                definite: false,
+            })
+            .collect();
+        if !decls.is_empty() {
+            stmts.push(Stmt::Decl(Decl::Var(VarDecl {
+                span: DUMMY_SP,
+                kind: VarDeclKind::Var,
+                decls,
+                declare: false,
+            })));
+        }
+
+        // Functions are hoisted, so their exports are available before
+        // `execute` is called, even if there's a circular dependency.
+        let mut fn_exports = vec![];
+        for f in &fns {
+            if let Some(names) = exports.get(&id(&f.ident)) {
+                for name in names {
+                    fn_exports.push(
+                        export_call(
+                            &export_ident,
+                            name.clone(),
+                            Box::new(f.ident.clone().into()),
+                        )
+                        .into_stmt(),
+                    );
+                }
+            }
+        }
+        stmts.extend(fns.into_iter().map(|f| Stmt::Decl(Decl::Fn(f))));
+        stmts.extend(fn_exports);
+
+        let is_async = {
+            let mut v = TopLevelAwaitFinder { found: false };
+            execute.visit_with(&Invalid { span: DUMMY_SP } as _, &mut v);
+            v.found
+        };
+
+        stmts.push(Stmt::Return(ReturnStmt {
+            span: DUMMY_SP,
+            arg: Some(Box::new(Expr::Object(ObjectLit {
+                span: DUMMY_SP,
+                props: vec![
+                    PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                        key: PropName::Ident(quote_ident!("setters")),
+                        value: Box::new(Expr::Array(ArrayLit {
+                            span: DUMMY_SP,
+                            elems: setters,
+                        })),
+                    }))),
+                    PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                        key: PropName::Ident(quote_ident!("execute")),
+                        value: Box::new(
+                            FnExpr {
+                                ident: None,
+                                function: Function {
+                                    span: DUMMY_SP,
+                                    is_async,
+                                    is_generator: false,
+                                    decorators: Default::default(),
+                                    params: vec![],
+                                    body: Some(BlockStmt {
+                                        span: DUMMY_SP,
+                                        stmts: execute,
+                                    }),
+                                    type_params: Default::default(),
+                                    return_type: Default::default(),
+                                },
+                            }
+                            .into(),
+                        ),
+                    }))),
+                ],
+            }))),
+        }));
+
+        let factory = FnExpr {
+            ident: None,
+            function: Function {
+                span: DUMMY_SP,
+                is_async: false,
+                is_generator: false,
+                decorators: Default::default(),
+                params: vec![export_ident, context_ident]
+                    .into_iter()
+                    .map(|i| Param {
+                        span: DUMMY_SP,
+                        decorators: Default::default(),
+                        pat: Pat::Ident(i),
+                    })
+                    .collect(),
+                body: Some(BlockStmt {
+                    span: DUMMY_SP,
+                    stmts,
+                }),
+                type_params: Default::default(),
+                return_type: Default::default(),
+            },
+        };
+
+        Module {
+            body: vec![CallExpr {
+                span: DUMMY_SP,
+                callee: member_expr!(DUMMY_SP, System.register).as_callee(),
+                args: self
+                    .config
+                    .module_id
+                    .clone()
+                    .map(|s| quote_str!(s).as_arg())
+                    .into_iter()
+                    .chain(iter::once(
+                        ArrayLit {
+                            span: DUMMY_SP,
+                            elems: dep_srcs,
+                        }
+                        .as_arg(),
+                    ))
+                    .chain(iter::once(factory.as_arg()))
+                    .collect(),
+                type_args: Default::default(),
+            }
+            .into_stmt()
+            .into()],
+            ..module
+        }
+    }
+}
+
+/// Moves a top-level declaration out of `execute`.
+///
+/// Functions are moved as-is, while classes and variables are declared with
+/// `var` and initialized in `execute`.
+fn hoist_decl(decl: Decl, vars: &mut Vec<Ident>, fns: &mut Vec<FnDecl>, execute: &mut Vec<Stmt>) {
+    match decl {
+        Decl::Fn(f) => fns.push(f),
+        Decl::Class(ClassDecl { ident, class, .. }) => {
+            vars.push(ident.clone());
+            execute.push(
+                AssignExpr {
+                    span: DUMMY_SP,
+                    left: PatOrExpr::Pat(Box::new(Pat::Ident(ident.clone()))),
+                    op: op!("="),
+                    right: Box::new(Expr::Class(ClassExpr {
+                        ident: Some(ident),
+                        class,
+                    })),
+                }
+                .into_stmt(),
+            );
+        }
+        Decl::Var(var) => {
+            if let Some(expr) = hoist_var_decl(var, vars) {
+                execute.push(Stmt::Expr(ExprStmt {
+                    span: DUMMY_SP,
+                    expr,
+                }));
+            }
+        }
+        Decl::TsInterface(..) | Decl::TsTypeAlias(..) | Decl::TsEnum(..) | Decl::TsModule(..) => {}
+    }
+}
+
+/// Converts `var a = 1, b;` to `a = 1` and stores declared names to `vars`.
+fn hoist_var_decl(var: VarDecl, vars: &mut Vec<Ident>) -> Option<Box<Expr>> {
+    let span = var.span;
+    let mut exprs = vec![];
+
+    for decl in var.decls {
+        vars.extend(find_ids::<_, Ident>(&decl.name));
+
+        if let Some(init) = decl.init {
+            exprs.push(Box::new(Expr::Assign(AssignExpr {
+                span: decl.span,
+                left: PatOrExpr::Pat(Box::new(decl.name)),
+                op: op!("="),
+                right: init,
+            })));
+        }
+    }
+
+    match exprs.len() {
+        0 => None,
+        1 => exprs.pop(),
+        _ => Some(Box::new(Expr::Seq(SeqExpr { span, exprs }))),
+    }
+}
+
+/// Creates `_export('name', value)`.
+fn export_call(export: &Ident, name: JsWord, value: Box<Expr>) -> Expr {
+    Expr::Call(CallExpr {
+        span: DUMMY_SP,
+        callee: export.clone().as_callee(),
+        args: vec![
+            Lit::Str(quote_str!(name)).as_arg(),
+            ExprOrSpread {
+                spread: None,
+                expr: value,
+            },
+        ],
+        type_args: Default::default(),
+    })
+}
+
+/// Creates `_foo.name`, or `_foo` if `name` is [None].
+fn member_or_self(obj: &Ident, name: Option<JsWord>) -> Expr {
+    match name {
+        Some(name) => obj.clone().make_member(Ident::new(name, DUMMY_SP)),
+        None => obj.clone().into(),
+    }
+}
+
+/// Creates
+///
+/// ```js
+/// var _exportObj = {};
+/// for (var _key in _foo)
+///     if (_key !== 'default' && _key !== '__esModule') _exportObj[_key] = _foo[_key];
+/// _export(_exportObj);
+/// ```
+fn export_all(export: &Ident, imported: &Ident) -> Vec<Stmt> {
+    let obj = private_ident!("_exportObj");
+    let key = private_ident!("_key");
+
+    let is_not = |s: &str| key.clone().make_bin(op!("!=="), Lit::Str(quote_str!(s)));
+
+    vec![
+        Stmt::Decl(Decl::Var(VarDecl {
+            span: DUMMY_SP,
+            kind: VarDeclKind::Var,
+            decls: vec![VarDeclarator {
+                span: DUMMY_SP,
+                name: Pat::Ident(obj.clone()),
+                init: Some(Box::new(Expr::Object(ObjectLit {
+                    span: DUMMY_SP,
+                    props: vec![],
+                }))),
+                definite: false,
+            }],
+            declare: false,
+        })),
+        Stmt::ForIn(ForInStmt {
+            span: DUMMY_SP,
+            left: VarDeclOrPat::VarDecl(VarDecl {
+                span: DUMMY_SP,
+                kind: VarDeclKind::Var,
+                decls: vec![VarDeclarator {
+                    span: DUMMY_SP,
+                    name: Pat::Ident(key.clone()),
+                    init: None,
+                    definite: false,
+                }],
+                declare: false,
+            }),
+            right: Box::new(imported.clone().into()),
+            body: Box::new(Stmt::If(IfStmt {
+                span: DUMMY_SP,
+                test: Box::new(is_not("default").make_bin(op!("&&"), is_not("__esModule"))),
+                cons: Box::new(
+                    AssignExpr {
+                        span: DUMMY_SP,
+                        left: PatOrExpr::Expr(Box::new(obj.clone().computed_member(key.clone()))),
+                        op: op!("="),
+                        right: Box::new(imported.clone().computed_member(key.clone())),
+                    }
+                    .into_stmt(),
+                ),
+                alt: None,
+            })),
+        }),
+        CallExpr {
+            span: DUMMY_SP,
+            callee: export.clone().as_callee(),
+            args: vec![obj.as_arg()],
+            type_args: Default::default(),
+        }
+        .into_stmt(),
+    ]
+}
+
+/// Hoists `var` declarations in `execute` to the factory function.
+struct VarHoister<'a> {
+    vars: &'a mut Vec<Ident>,
+}
+
+impl Fold for VarHoister<'_> {
+    noop_fold_type!();
+
+    /// Noop
+    fn fold_arrow_expr(&mut self, f: ArrowExpr) -> ArrowExpr {
+        f
+    }
+
+    /// Noop
+    fn fold_constructor(&mut self, c: Constructor) -> Constructor {
+        c
+    }
+
+    fn fold_for_in_stmt(&mut self, s: ForInStmt) -> ForInStmt {
+        ForInStmt {
+            left: self.fold_loop_left(s.left),
+            body: s.body.fold_with(self),
+            ..s
+        }
+    }
+
+    fn fold_for_of_stmt(&mut self, s: ForOfStmt) -> ForOfStmt {
+        ForOfStmt {
+            left: self.fold_loop_left(s.left),
+            body: s.body.fold_with(self),
+            ..s
+        }
+    }
+
+    fn fold_for_stmt(&mut self, s: ForStmt) -> ForStmt {
+        let init = match s.init {
+            Some(VarDeclOrExpr::VarDecl(var)) if var.kind == VarDeclKind::Var => {
+                hoist_var_decl(var, self.vars).map(VarDeclOrExpr::Expr)
+            }
+            init => init,
+        };
+
+        ForStmt {
+            init,
+            body: s.body.fold_with(self),
+            ..s
+        }
+    }
+
+    /// Noop
+    fn fold_function(&mut self, f: Function) -> Function {
+        f
+    }
+
+    /// Noop
+    fn fold_getter_prop(&mut self, p: GetterProp) -> GetterProp {
+        p
+    }
+
+    /// Noop
+    fn fold_setter_prop(&mut self, p: SetterProp) -> SetterProp {
+        p
+    }
+
+    fn fold_stmt(&mut self, stmt: Stmt) -> Stmt {
+        match stmt {
+            Stmt::Decl(Decl::Var(var)) if var.kind == VarDeclKind::Var => {
+                let span = var.span;
+                match hoist_var_decl(var, self.vars) {
+                    Some(expr) => Stmt::Expr(ExprStmt { span, expr }),
+                    None => Stmt::Empty(EmptyStmt { span }),
+                }
+            }
+            _ => stmt.fold_children_with(self),
+        }
+    }
+}
+
+impl VarHoister<'_> {
+    fn fold_loop_left(&mut self, left: VarDeclOrPat) -> VarDeclOrPat {
+        match left {
+            VarDeclOrPat::VarDecl(var) if var.kind == VarDeclKind::Var => {
+                let decl = var.decls.into_iter().next().unwrap();
+                self.vars.extend(find_ids::<_, Ident>(&decl.name));
+
+                VarDeclOrPat::Pat(decl.name)
+            }
+            _ => left,
+        }
+    }
+}
+
+/// Wraps assignments to exported bindings with `_export`, and replaces
+/// `import.meta` and `import()`.
+struct Rewriter<'a> {
+    export: &'a Ident,
+    context: &'a Ident,
+    exports: &'a FxHashMap<Id, Vec<JsWord>>,
+    /// Stores the value of assignments to patterns and postfix updates.
+    tmp: Option<Ident>,
+}
+
+impl Rewriter<'_> {
+    fn is_exported(&self, i: &Ident) -> bool {
+        self.exports.contains_key(&id(i))
+    }
+
+    /// `a = 1` => `_export('a', a = 1)`
+    fn wrap(&self, i: &Ident, mut value: Expr) -> Expr {
+        if let Some(names) = self.exports.get(&id(i)) {
+            for name in names {
+                value = export_call(self.export, name.clone(), Box::new(value));
+            }
+        }
+
+        value
+    }
+
+    /// `[a, b] = c` => `(_tmp = [a, b] = c, _export('a', a), _tmp)`
+    fn preserve_value(&mut self, ids: Vec<Ident>, value: Expr) -> Expr {
+        let tmp = self
+            .tmp
+            .get_or_insert_with(|| private_ident!("_tmp"))
+            .clone();
+
+        let mut exprs = vec![Box::new(Expr::Assign(AssignExpr {
+            span: DUMMY_SP,
+            left: PatOrExpr::Pat(Box::new(Pat::Ident(tmp.clone()))),
+            op: op!("="),
+            right: Box::new(value),
+        }))];
+        for i in ids {
+            exprs.push(Box::new(self.wrap(&i, i.clone().into())));
+        }
+        exprs.push(Box::new(tmp.into()));
+
+        Expr::Seq(SeqExpr {
+            span: DUMMY_SP,
+            exprs,
+        })
+    }
+}
+
+impl Fold for Rewriter<'_> {
+    noop_fold_type!();
+
+    fn fold_expr(&mut self, expr: Expr) -> Expr {
+        let expr = expr.fold_children_with(self);
+
+        match expr {
+            Expr::Assign(assign) => {
+                let simple = match assign.left {
+                    PatOrExpr::Expr(ref e) => match **e {
+                        Expr::Ident(ref i) => Some(i.clone()),
+                        _ => None,
+                    },
+                    PatOrExpr::Pat(ref p) => match **p {
+                        Pat::Ident(ref i) => Some(i.clone()),
+                        _ => None,
+                    },
+                };
+                if let Some(i) = simple {
+                    return self.wrap(&i, Expr::Assign(assign));
+                }
+
+                let ids: Vec<_> = match assign.left {
+                    PatOrExpr::Pat(ref p) => find_ids::<_, Ident>(&**p),
+                    PatOrExpr::Expr(..) => vec![],
+                }
+                .into_iter()
+                .filter(|i| self.is_exported(i))
+                .collect();
+
+                if ids.is_empty() {
+                    Expr::Assign(assign)
+                } else {
+                    self.preserve_value(ids, Expr::Assign(assign))
+                }
+            }
+
+            Expr::Update(update) => {
+                let i = match *update.arg {
+                    Expr::Ident(ref i) if self.is_exported(i) => i.clone(),
+                    _ => return Expr::Update(update),
+                };
+
+                if update.prefix {
+                    self.wrap(&i, Expr::Update(update))
+                } else {
+                    self.preserve_value(vec![i], Expr::Update(update))
+                }
+            }
+
+            Expr::MetaProp(MetaPropExpr { meta, prop })
+                if meta.sym == js_word!("import") && &*prop.sym == "meta" =>
+            {
+                self.context.clone().make_member(quote_ident!("meta"))
+            }
+
+            Expr::Call(mut call) => {
+                let is_dynamic_import = match call.callee {
+                    ExprOrSuper::Expr(ref callee) => match **callee {
+                        Expr::Ident(Ident {
+                            sym: js_word!("import"),
+                            ..
+                        }) => true,
+                        _ => false,
+                    },
+                    _ => false,
+                };
+
+                if is_dynamic_import {
+                    call.callee = self
+                        .context
+                        .clone()
+                        .make_member(quote_ident!("import"))
+                        .as_callee();
+                }
+
+                Expr::Call(call)
+            }
+
+            _ => expr,
+        }
+    }
+}
+
+/// Finds `await` which is not in a function.
+struct TopLevelAwaitFinder {
+    found: bool,
+}
+
+impl Visit for TopLevelAwaitFinder {
+    noop_visit_type!();
+
+    fn visit_arrow_expr(&mut self, _: &ArrowExpr, _: &dyn Node) {}
+
+    fn visit_await_expr(&mut self, _: &AwaitExpr, _: &dyn Node) {
+        self.found = true;
+    }
+
+    fn visit_constructor(&mut self, _: &Constructor, _: &dyn Node) {}
+
+    fn visit_for_of_stmt(&mut self, s: &ForOfStmt, _: &dyn Node) {
+        if s.await_token.is_some() {
+            self.found = true;
+        }
+
+        s.visit_children_with(self);
+    }
+
+    fn visit_function(&mut self, _: &Function, _: &dyn Node) {}
+
+    fn visit_getter_prop(&mut self, _: &GetterProp, _: &dyn Node) {}
+
+    fn visit_setter_prop(&mut self, _: &SetterProp, _: &dyn Node) {}
+}
@@ -1,8 +1,9 @@
 use self::config::BuiltConfig;
 pub use self::config::Config;
 use super::util::{
-    self, define_es_module, define_property, has_use_strict, initialize_to_undefined,
-    local_name_for_src, make_descriptor, make_require_call, use_strict, Exports, ModulePass, Scope,
+    self, define_es_module, define_property, has_use_strict, initialize_to_undefined, interop_call,
+    local_name_for_src, make_descriptor, make_require_call, use_strict, Exports, ImportInterop,
+    ModulePass, Scope,
 };
 use crate::util::{prepend_stmts, var::VarCollector, DestructuringFinder, ExprFactory};
 use fxhash::FxHashSet;
@@ -76,7 +77,9 @@ impl Fold for Umd {
             };
 
             match decl {
-                ModuleDecl::Import(import) => self.scope.insert_import(import),
+                ModuleDecl::Import(import) => self
+                    .scope
+                    .insert_import(import, self.config.config.import_interop()),
 
                 ModuleDecl::ExportAll(..)
                 | ModuleDecl::ExportDecl(..)
@@ -309,6 +312,9 @@ impl Fold for Umd {
                                 })
                             {
                                 let is_import_default = orig.sym == js_word!("default");
+                                // Node uses `module.exports` as the default export.
+                                let is_node_default = is_import_default
+                                    && self.config.config.import_interop() == ImportInterop::Node;
 
                                 let key = (orig.sym.clone(), orig.span.ctxt());
                                 if self.scope.declared_vars.contains(&key) {
@@ -327,7 +333,7 @@ impl Fold for Umd {
                                 }
 
                                 if let Some(ref src) = export.src {
-                                    if is_import_default {
+                                    if is_import_default && !is_node_default {
                                         self.scope
                                             .import_types
                                             .entry(src.value.clone())
@@ -336,6 +342,9 @@ impl Fold for Umd {
                                 }
 
                                 let value = match imported {
+                                    Some(ref imported) if is_node_default => {
+                                        Box::new(Expr::Ident(imported.clone().unwrap()))
+                                    }
                                     Some(ref imported) => Box::new(
                                         imported.clone().unwrap().make_member(orig.clone()),
                                     ),
@@ -508,18 +517,10 @@ impl Fold for Umd {
                 match ty {
                     Some(&wildcard) => {
                         let imported = ident.clone();
+                        let interop = self.config.config.import_interop();
 
-                        if !self.config.config.no_interop {
-                            let right = Box::new(Expr::Call(CallExpr {
-                                span: DUMMY_SP,
-                                callee: if wildcard {
-                                    helper!(interop_require_wildcard, "interopRequireWildcard")
-                                } else {
-                                    helper!(interop_require_default, "interopRequireDefault")
-                                },
-                                args: vec![imported.as_arg()],
-                                type_args: Default::default(),
-                            }));
+                        if interop.needs_helper(wildcard) {
+                            let right = Box::new(interop_call(imported.into(), wildcard, interop));
 
                             import_stmts.push(
                                 AssignExpr {
@@ -29,6 +29,19 @@ pub struct Config {
     pub lazy: Lazy,
     #[serde(default)]
     pub no_interop: bool,
+    #[serde(default)]
+    pub import_interop: ImportInterop,
+    /// Keeps `import()` as is instead of converting it to `require()`.
+    ///
+    /// Defaults to `true`. Only commonjs converts `import()`, and only if this
+    /// is set to `false`.
+    #[serde(default = "default_ignore_dynamic")]
+    pub ignore_dynamic: bool,
+    /// Emits `0 && (module.exports = { foo, bar })` so that
+    /// [cjs-module-lexer](https://github.com/guybedford/cjs-module-lexer) can
+    /// detect named exports when the module is imported from node esm.
+    #[serde(default)]
+    pub export_interop_annotation: bool,
 }
 
 impl Default for Config {
@@ -38,11 +51,60 @@ impl Default for Config {
             strict_mode: default_strict_mode(),
             lazy: Lazy::default(),
             no_interop: false,
+            import_interop: Default::default(),
+            ignore_dynamic: default_ignore_dynamic(),
+            export_interop_annotation: false,
+        }
+    }
+}
+
+impl Config {
+    /// `no_interop` overrides `import_interop`.
+    pub fn import_interop(&self) -> ImportInterop {
+        if self.no_interop {
+            ImportInterop::None
+        } else {
+            self.import_interop
         }
     }
 }
 
-const fn default_strict_mode() -> bool {
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ImportInterop {
+    /// The default export is `exports.default` if the module has
+    /// `__esModule`, and `module.exports` otherwise.
+    Babel,
+    /// The default export is always `module.exports`, like node does for
+    /// commonjs modules.
+    Node,
+    /// The default export is always `exports.default`.
+    None,
+}
+
+impl Default for ImportInterop {
+    fn default() -> Self {
+        ImportInterop::Babel
+    }
+}
+
+impl ImportInterop {
+    /// Returns `true` if `require('foo')` should be wrapped with an interop
+    /// helper.
+    pub(super) fn needs_helper(self, wildcard: bool) -> bool {
+        match self {
+            ImportInterop::Babel => true,
+            ImportInterop::Node => wildcard,
+            ImportInterop::None => false,
+        }
+    }
+}
+
+pub(super) const fn default_strict_mode() -> bool {
+    true
+}
+
+const fn default_ignore_dynamic() -> bool {
     true
 }
 
@@ -273,7 +335,7 @@ impl Scope {
         }
     }
 
-    pub fn insert_import(&mut self, mut import: ImportDecl) {
+    pub fn insert_import(&mut self, mut import: ImportDecl, interop: ImportInterop) {
         if import.specifiers.is_empty() {
             // import 'foo';
             //   -> require('foo');
@@ -322,6 +384,13 @@ impl Scope {
                     ImportSpecifier::Namespace(..) => unreachable!(
                         "import * as foo cannot be used with other type of import specifiers"
                     ),
+                    ImportSpecifier::Default(i) if interop == ImportInterop::Node => {
+                        // Node uses `module.exports` as the default export.
+                        self.idents.insert(
+                            (i.local.sym.clone(), i.local.span.ctxt()),
+                            (import.src.value.clone(), "".into()),
+                        );
+                    }
                     ImportSpecifier::Default(i) => {
                         self.idents.insert(
                             (i.local.sym.clone(), i.local.span.ctxt()),
@@ -338,6 +407,14 @@ impl Scope {
                         let name = imported.map(|i| i.sym).unwrap_or_else(|| local.sym.clone());
                         let is_default = name == js_word!("default");
 
+                        if is_default && interop == ImportInterop::Node {
+                            self.idents.insert(
+                                (local.sym.clone(), local.span.ctxt()),
+                                (import.src.value.clone(), "".into()),
+                            );
+                            continue;
+                        }
+
                         self.idents.insert(
                             (local.sym.clone(), local.span.ctxt()),
                             (import.src.value.clone(), name),
@@ -678,6 +755,50 @@ pub(super) fn make_require_call(mark: Mark, src: JsWord) -> Expr {
     })
 }
 
+/// Wraps `obj` with `_interopRequireWildcard` or `_interopRequireDefault`, if
+/// `interop` requires it.
+pub(super) fn interop_call(obj: Expr, wildcard: bool, interop: ImportInterop) -> Expr {
+    if !interop.needs_helper(wildcard) {
+        return obj;
+    }
+
+    let mut args = vec![obj.as_arg()];
+    if interop == ImportInterop::Node {
+        args.push(
+            Lit::Bool(Bool {
+                span: DUMMY_SP,
+                value: true,
+            })
+            .as_arg(),
+        );
+    }
+
+    Expr::Call(CallExpr {
+        span: DUMMY_SP,
+        callee: if wildcard {
+            helper!(interop_require_wildcard, "interopRequireWildcard")
+        } else {
+            helper!(interop_require_default, "interopRequireDefault")
+        },
+        args,
+        type_args: Default::default(),
+    })
+}
+
+/// Returns `true` for `import('foo')`.
+pub(super) fn is_dynamic_import(call: &CallExpr) -> bool {
+    match call.callee {
+        ExprOrSuper::Expr(ref callee) => match **callee {
+            Expr::Ident(Ident {
+                sym: js_word!("import"),
+                ..
+            }) => true,
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
 pub(super) fn local_name_for_src(src: &JsWord) -> JsWord {
     if !src.contains('/') {
         return format!("_{}", src.to_camel_case()).into();
@@ -1,10 +1,12 @@
+pub use self::side_effect::module_has_side_effects;
 use self::side_effect::{ImportDetector, SideEffectVisitor};
 use crate::pass::RepeatedJsPass;
 use fxhash::FxHashSet;
-use std::{any::type_name, borrow::Cow};
+use std::{any::type_name, borrow::Cow, fmt};
 use swc_atoms::JsWord;
 use swc_common::{
     chain,
+    comments::Comments,
     pass::{CompilerPass, Repeated},
     util::move_map::MoveMap,
     Mark, Span, Spanned,
@@ -26,7 +28,6 @@ macro_rules! preserve {
 
 mod side_effect;
 
-#[derive(Debug)]
 pub struct Config<'a> {
     /// If this is [None], all exports are treated as used.
     pub used: Option<Cow<'a, [Id]>>,
@@ -35,6 +36,10 @@ pub struct Config<'a> {
     ///
     /// Should not be `Mark::root()`. Used to reduce allocation of [Mark].
     pub used_mark: Mark,
+
+    /// Calls annotated with `/*#__PURE__*/` are treated as free of side
+    /// effects if this is set.
+    pub comments: Option<&'a dyn Comments>,
 }
 
 impl Default for Config<'_> {
@@ -42,10 +47,21 @@ impl Default for Config<'_> {
         Self {
             used: None,
             used_mark: Mark::fresh(Mark::root()),
+            comments: None,
         }
     }
 }
 
+impl fmt::Debug for Config<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Config")
+            .field("used", &self.used)
+            .field("used_mark", &self.used_mark)
+            .field("comments", &self.comments.is_some())
+            .finish()
+    }
+}
+
 pub fn dce<'a>(config: Config<'a>) -> impl RepeatedJsPass + 'a {
     assert_ne!(
         config.used_mark,
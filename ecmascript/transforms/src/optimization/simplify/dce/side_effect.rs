@@ -1,7 +1,7 @@
 use super::Dce;
 use fxhash::FxHashSet;
 use swc_atoms::JsWord;
-use swc_common::DUMMY_SP;
+use swc_common::{comments::Comments, Span, DUMMY_SP};
 use swc_ecma_ast::*;
 use swc_ecma_utils::{ident::IdentLike, ExprExt, Id};
 use swc_ecma_visit::{Node, Visit, VisitWith};
@@ -37,6 +37,8 @@ impl Dce<'_> {
         let mut v = SideEffectVisitor {
             included: &mut self.included,
             exports: self.config.used.as_ref().map(|v| &**v),
+            comments: self.config.comments,
+            skip_fn_bodies: false,
             found: false,
         };
 
@@ -61,6 +63,10 @@ impl SideEffectVisitor<'_> {
 pub(super) struct SideEffectVisitor<'a> {
     included: &'a mut FxHashSet<Id>,
     exports: Option<&'a [Id]>,
+    comments: Option<&'a dyn Comments>,
+    /// If true, bodies of functions are not visited as they are not evaluated
+    /// when the function is created.
+    skip_fn_bodies: bool,
     found: bool,
 }
 
@@ -79,6 +85,15 @@ impl SideEffectVisitor<'_> {
 
         false
     }
+
+    /// Returns `true` if the call at `span` is annotated with
+    /// `/*#__PURE__*/`.
+    fn is_pure_annotated(&self, span: Span) -> bool {
+        match self.comments {
+            Some(comments) => comments.has_flag(span.lo(), "PURE"),
+            None => false,
+        }
+    }
 }
 
 impl Visit for SideEffectVisitor<'_> {
@@ -100,6 +115,14 @@ impl Visit for SideEffectVisitor<'_> {
         node.visit_children_with(self)
     }
 
+    fn visit_arrow_expr(&mut self, node: &ArrowExpr, _: &dyn Node) {
+        if self.found || self.skip_fn_bodies {
+            return;
+        }
+
+        node.visit_children_with(self)
+    }
+
     fn visit_assign_expr(&mut self, node: &AssignExpr, _: &dyn Node) {
         if self.found {
             return;
@@ -129,6 +152,12 @@ impl Visit for SideEffectVisitor<'_> {
             _ => {}
         }
 
+        if self.is_pure_annotated(node.span) {
+            // Only the call itself is pure.
+            node.args.visit_with(node as _, self);
+            return;
+        }
+
         self.found = true;
     }
 
@@ -182,6 +211,14 @@ impl Visit for SideEffectVisitor<'_> {
         self.found = true;
     }
 
+    fn visit_function(&mut self, node: &Function, _: &dyn Node) {
+        if self.found || self.skip_fn_bodies {
+            return;
+        }
+
+        node.visit_children_with(self)
+    }
+
     fn visit_ident(&mut self, node: &Ident, _: &dyn Node) {
         if self.found {
             return;
@@ -220,11 +257,16 @@ impl Visit for SideEffectVisitor<'_> {
         self.found = true
     }
 
-    fn visit_new_expr(&mut self, _: &NewExpr, _: &dyn Node) {
+    fn visit_new_expr(&mut self, node: &NewExpr, _: &dyn Node) {
         if self.found {
             return;
         }
 
+        if self.is_pure_annotated(node.span) {
+            node.args.visit_with(node as _, self);
+            return;
+        }
+
         self.found = true;
     }
 
@@ -262,3 +304,96 @@ impl Visit for SideEffectVisitor<'_> {
         self.found = true;
     }
 }
+
+/// Returns true if evaluating the top-level statements of `module` may have
+/// side effects.
+///
+/// Imports are not checked, as side effects of dependencies should be checked
+/// by the caller.
+pub fn module_has_side_effects(module: &Module, comments: Option<&dyn Comments>) -> bool {
+    module.body.iter().any(|item| match item {
+        ModuleItem::ModuleDecl(decl) => match decl {
+            ModuleDecl::Import(..) | ModuleDecl::ExportNamed(..) | ModuleDecl::ExportAll(..) => {
+                false
+            }
+            ModuleDecl::ExportDecl(export) => decl_has_side_effects(&export.decl, comments),
+            ModuleDecl::ExportDefaultDecl(export) => match export.decl {
+                DefaultDecl::Class(ref c) => class_has_side_effects(&c.class, comments),
+                _ => false,
+            },
+            ModuleDecl::ExportDefaultExpr(export) => expr_has_side_effects(&export.expr, comments),
+            _ => true,
+        },
+        ModuleItem::Stmt(stmt) => match stmt {
+            Stmt::Empty(..) => false,
+            Stmt::Decl(decl) => decl_has_side_effects(decl, comments),
+            Stmt::Expr(e) => expr_has_side_effects(&e.expr, comments),
+            _ => true,
+        },
+    })
+}
+
+fn decl_has_side_effects(decl: &Decl, comments: Option<&dyn Comments>) -> bool {
+    match decl {
+        Decl::Fn(..) | Decl::TsInterface(..) | Decl::TsTypeAlias(..) => false,
+        Decl::Class(c) => class_has_side_effects(&c.class, comments),
+        Decl::Var(var) => var.decls.iter().any(|d| match d.name {
+            Pat::Ident(..) => d
+                .init
+                .as_ref()
+                .map_or(false, |e| expr_has_side_effects(e, comments)),
+            // Destructuring may invoke getters.
+            _ => true,
+        }),
+        _ => true,
+    }
+}
+
+fn class_has_side_effects(c: &Class, comments: Option<&dyn Comments>) -> bool {
+    if !c.decorators.is_empty() {
+        return true;
+    }
+
+    match c.super_class.as_deref() {
+        None | Some(Expr::Ident(..)) | Some(Expr::Member(..)) => {}
+        Some(e) => {
+            if expr_has_side_effects(e, comments) {
+                return true;
+            }
+        }
+    }
+
+    c.body.iter().any(|member| match member {
+        ClassMember::ClassProp(p) => {
+            p.computed
+                || !p.decorators.is_empty()
+                || (p.is_static
+                    && p.value
+                        .as_ref()
+                        .map_or(false, |e| expr_has_side_effects(e, comments)))
+        }
+        ClassMember::Method(m) => match m.key {
+            PropName::Computed(..) => true,
+            _ => !m.function.decorators.is_empty(),
+        },
+        _ => false,
+    })
+}
+
+fn expr_has_side_effects(e: &Expr, comments: Option<&dyn Comments>) -> bool {
+    match e {
+        Expr::Lit(..) | Expr::Fn(..) | Expr::Arrow(..) => return false,
+        _ => {}
+    }
+
+    let mut included = FxHashSet::default();
+    let mut v = SideEffectVisitor {
+        included: &mut included,
+        exports: None,
+        comments,
+        skip_fn_bodies: true,
+        found: false,
+    };
+    e.visit_with(&Invalid { span: DUMMY_SP } as _, &mut v);
+    v.found
+}
@@ -25,9 +25,9 @@ macro_rules! helper_expr {
         );
         let mark = enable_helper!($field_name);
         let span = $span.apply_mark(mark);
-        let external = crate::helpers::HELPERS.with(|helper| helper.external());
+        let is_namespace = crate::helpers::HELPERS.with(|helper| helper.is_namespace());
 
-        if external {
+        if is_namespace {
             quote_ident!(span, "swcHelpers").make_member(quote_ident!($span, external_name!($s)))
         } else {
             Expr::from(quote_ident!(span, concat!('_', $s)))
@@ -6,7 +6,7 @@ pub use self::{
     jsx_self::jsx_self,
     jsx_src::jsx_src,
 };
-use swc_common::{chain, sync::Lrc, SourceMap};
+use swc_common::{chain, comments::Comments, sync::Lrc, SourceMap};
 use swc_ecma_visit::Fold;
 
 mod display_name;
@@ -17,11 +17,15 @@ mod jsx_src;
 /// `@babel/preset-react`
 ///
 /// Preset for all React plugins.
-pub fn react(cm: Lrc<SourceMap>, options: Options) -> impl Fold {
+pub fn react<'a>(
+    cm: Lrc<SourceMap>,
+    comments: Option<&'a dyn Comments>,
+    options: Options,
+) -> impl 'a + Fold {
     let Options { development, .. } = options;
 
     chain!(
-        jsx(cm.clone(), options),
+        jsx(cm.clone(), comments, options),
         display_name(),
         jsx_src(development, cm),
         jsx_self(development)
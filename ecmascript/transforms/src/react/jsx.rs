@@ -5,7 +5,10 @@ use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::{iter, mem};
 use swc_atoms::{js_word, JsWord};
-use swc_common::{iter::IdentifyLast, sync::Lrc, FileName, SourceMap, Spanned, DUMMY_SP};
+use swc_common::{
+    comments::Comments, errors::DiagnosticId, iter::IdentifyLast, sync::Lrc, FileName, SourceMap,
+    Span, Spanned, DUMMY_SP,
+};
 use swc_ecma_ast::*;
 use swc_ecma_parser::{Parser, StringInput, Syntax};
 use swc_ecma_visit::{noop_fold_type, Fold, FoldWith};
@@ -86,8 +89,15 @@ fn parse_option(cm: &SourceMap, name: &str, src: String) -> Box<Expr> {
 /// `@babel/plugin-transform-react-jsx`
 ///
 /// Turn JSX into React function calls
-pub fn jsx(cm: Lrc<SourceMap>, options: Options) -> impl Fold {
+///
+/// If `comments` is given, the calls are annotated with `/*#__PURE__*/`.
+pub fn jsx<'a>(
+    cm: Lrc<SourceMap>,
+    comments: Option<&'a dyn Comments>,
+    options: Options,
+) -> impl 'a + Fold {
     Jsx {
+        comments,
         pragma: ExprOrSuper::Expr(parse_option(&cm, "pragma", options.pragma)),
         pragma_frag: ExprOrSpread {
             spread: None,
@@ -98,16 +108,33 @@ pub fn jsx(cm: Lrc<SourceMap>, options: Options) -> impl Fold {
     }
 }
 
-struct Jsx {
+struct Jsx<'a> {
+    comments: Option<&'a dyn Comments>,
     pragma: ExprOrSuper,
     pragma_frag: ExprOrSpread,
     use_builtins: bool,
     throw_if_namespace: bool,
 }
 
-impl Jsx {
+impl Jsx<'_> {
+    /// Annotates the call at `span` with `/*#__PURE__*/`.
+    fn pure_span(&self, span: Span) -> Span {
+        let comments = match self.comments {
+            Some(comments) => comments,
+            None => return span,
+        };
+
+        let span = if span.is_dummy() {
+            Span::dummy_with_cmt()
+        } else {
+            span
+        };
+        comments.add_pure_comment(span.lo());
+        span
+    }
+
     fn jsx_frag_to_expr(&mut self, el: JSXFragment) -> Expr {
-        let span = el.span();
+        let span = self.pure_span(el.span());
 
         Expr::Call(CallExpr {
             span,
@@ -127,7 +154,7 @@ impl Jsx {
     }
 
     fn jsx_elem_to_expr(&mut self, el: JSXElement) -> Expr {
-        let span = el.span();
+        let span = self.pure_span(el.span());
 
         let name = self.jsx_name(el.opening.name);
 
@@ -252,7 +279,7 @@ impl Jsx {
     }
 }
 
-impl Fold for Jsx {
+impl Fold for Jsx<'_> {
     noop_fold_type!();
 
     fn fold_expr(&mut self, expr: Expr) -> Expr {
@@ -290,7 +317,7 @@ impl Fold for Jsx {
     }
 }
 
-impl Jsx {
+impl Jsx<'_> {
     fn jsx_name(&self, name: JSXElementName) -> Box<Expr> {
         let span = name.span();
         match name {
@@ -316,12 +343,13 @@ impl Jsx {
                 if self.throw_if_namespace {
                     HANDLER.with(|handler| {
                         handler
-                            .struct_span_err(
+                            .struct_span_err_with_code(
                                 span,
                                 "JSX Namespace is disabled by default because react does not \
                                  support it yet. You can specify \
                                  jsc.transform.react.throwIfNamespace to false to override \
                                  default behavior",
+                                DiagnosticId::Error("E1001".into()),
                             )
                             .emit()
                     });
@@ -12,9 +12,9 @@ use swc_common::{chain, Mark};
 
 fn tr(t: &mut Tester, options: Options) -> impl Fold {
     chain!(
-        jsx(t.cm.clone(), options),
+        jsx(t.cm.clone(), None, options),
         display_name(),
-        classes(),
+        classes(None, Default::default()),
         arrow(),
     )
 }
@@ -1175,3 +1175,22 @@ test!(
     "let page = React.createElement('p', null, 'Click ', React.createElement('em', null, 'New \
      melody'), ' listen to a randomly generated melody');"
 );
+
+#[test]
+fn pure_annotation() {
+    crate::tests::test_transform_with_comments(
+        ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+            jsx: true,
+            ..Default::default()
+        }),
+        |t, m| {
+            m.fold_with(&mut jsx(
+                t.cm.clone(),
+                Some(&t.comments),
+                Default::default(),
+            ))
+        },
+        "<div><span /></div>;",
+        r#"/*#__PURE__*/ React.createElement("div", null, /*#__PURE__*/ React.createElement("span", null));"#,
+    );
+}
@@ -985,7 +985,7 @@ test!(
     syntax(),
     |_| chain!(
         tr(),
-        classes(),
+        classes(None, Default::default()),
         destructuring(Default::default()),
         common_js(Mark::fresh(Mark::root()), Default::default())
     ),
@@ -1140,7 +1140,7 @@ identical!(
 
 test!(
     ts(),
-    |_| chain!(resolver(), class_properties()),
+    |_| chain!(resolver(), class_properties(Default::default())),
     issue_890_1,
     "const DURATION = 1000
 
@@ -8,7 +8,10 @@ use std::{
     sync::{Arc, RwLock},
 };
 use swc_common::{
-    comments::SingleThreadedComments, errors::Handler, sync::Lrc, FileName, SourceMap,
+    comments::{Comments, SingleThreadedComments},
+    errors::Handler,
+    sync::Lrc,
+    FileName, SourceMap,
 };
 use swc_ecma_ast::{Pat, *};
 use swc_ecma_codegen::Emitter;
@@ -124,6 +127,16 @@ impl<'a> Tester<'a> {
     }
 
     pub fn print(&mut self, module: &Module) -> String {
+        self.print_with(module, None)
+    }
+
+    /// Prints `module` with the comments collected while parsing.
+    pub fn print_with_comments(&mut self, module: &Module) -> String {
+        let comments = self.comments.clone();
+        self.print_with(module, Some(&comments))
+    }
+
+    fn print_with(&mut self, module: &Module, comments: Option<&dyn Comments>) -> String {
         let mut wr = Buf(Arc::new(RwLock::new(vec![])));
         {
             let mut emitter = Emitter {
@@ -135,7 +148,7 @@ impl<'a> Tester<'a> {
                     &mut wr,
                     None,
                 )),
-                comments: None,
+                comments,
             };
 
             // println!("Emitting: {:?}", module);
@@ -237,6 +250,38 @@ pub(crate) fn test_transform<F, P>(
     });
 }
 
+/// Like [test_transform], but comments are kept, so that comments added by a
+/// pass can be tested.
+///
+/// `expected` is parsed and printed with its comments as well, so only the
+/// positions of comments are compared, not formatting.
+pub(crate) fn test_transform_with_comments<F>(syntax: Syntax, tr: F, input: &str, expected: &str)
+where
+    F: FnOnce(&Tester<'_>, Module) -> Module,
+{
+    Tester::run(|tester| {
+        let expected = tester.with_parser("output.js", syntax, expected, |p| p.parse_module())?;
+        let actual = tester.with_parser("input.js", syntax, input, |p| p.parse_module())?;
+        let actual = tr(tester, actual);
+
+        let comments = tester.comments.clone();
+        let expected = expected.fold_with(&mut crate::fixer::fixer(Some(&comments)));
+        let actual = actual.fold_with(&mut crate::fixer::fixer(Some(&comments)));
+
+        let (actual_src, expected_src) = (
+            tester.print_with_comments(&actual),
+            tester.print_with_comments(&expected),
+        );
+
+        assert_eq!(
+            DebugUsingDisplay(actual_src.trim()),
+            DebugUsingDisplay(expected_src.trim())
+        );
+
+        Ok(())
+    });
+}
+
 #[derive(PartialEq, Eq)]
 pub(crate) struct DebugUsingDisplay<'a>(pub &'a str);
 impl<'a> fmt::Debug for DebugUsingDisplay<'a> {
@@ -7,7 +7,7 @@ use swc_ecma_transforms::{
     react::jsx,
     resolver,
 };
-use swc_ecma_visit::Fold;
+use swc_ecma_visit::{Fold, FoldWith};
 
 #[macro_use]
 mod common;
@@ -17,13 +17,13 @@ fn syntax() -> Syntax {
 }
 
 fn tr() -> impl Fold {
-    classes()
+    classes(None, Default::default())
 }
 
 fn spec_tr() -> impl Fold {
     chain!(
         resolver(),
-        classes(),
+        classes(None, Default::default()),
         spread(spread::Config {
             ..Default::default()
         }),
@@ -2195,7 +2195,7 @@ test!(
         jsx: true,
         ..Default::default()
     }),
-    |t| chain!(tr(), jsx(t.cm.clone(), Default::default())),
+    |t| chain!(tr(), jsx(t.cm.clone(), None, Default::default())),
     regression_2775,
     r#"
 import React, {Component} from 'react';
@@ -5177,7 +5177,7 @@ function (Array) {
 // extend_builtins_imported_babel_plugin_transform_builtin_classes
 test_exec!(
     syntax(),
-    |_| chain!(classes(), block_scoping()),
+    |_| chain!(classes(None, Default::default()), block_scoping()),
     extend_builtins_imported_babel_plugin_transform_builtin_classes_exec,
     r#"
 // Imported from
@@ -5478,7 +5478,7 @@ expect(obj.test).toBe(3);
 // extend_builtins_spec
 test_exec!(
     syntax(),
-    |_| chain!(classes(), block_scoping()),
+    |_| chain!(classes(None, Default::default()), block_scoping()),
     extend_builtins_spec_exec,
     r#"
 class List extends Array {}
@@ -6015,7 +6015,7 @@ expect(obj.test).toBe(3);
 // extend_builtins_builtin_objects_throw_when_wrapped
 test_exec!(
     syntax(),
-    |_| chain!(classes(), block_scoping()),
+    |_| chain!(classes(None, Default::default()), block_scoping()),
     extend_builtins_builtin_objects_throw_when_wrapped_exec,
     r#"
 // JSON is wrapped because it starts with an uppercase letter, but it
@@ -6064,7 +6064,7 @@ test_exec!(
     // Just don't do this.
     ignore,
     syntax(),
-    |_| chain!(classes(), block_scoping()),
+    |_| chain!(classes(None, Default::default()), block_scoping()),
     extend_builtins_overwritten_null_exec,
     r#"
 var env = {
@@ -6085,7 +6085,7 @@ test_exec!(
     // Just don't do this. With is evil.
     ignore,
     syntax(),
-    |_| chain!(classes(), block_scoping()),
+    |_| chain!(classes(None, Default::default()), block_scoping()),
     extend_builtins_super_called_exec,
     r#"
 var called = false;
@@ -6109,7 +6109,7 @@ with (env) {
 
 test_exec!(
     syntax(),
-    |_| classes(),
+    |_| classes(None, Default::default()),
     issue_846,
     r#"
 class SomeClass {
@@ -6129,3 +6129,75 @@ const obj = new OtherClass();
 expect(obj.anotherMethod()).toBe(2);
 "#
 );
+
+test!(
+    syntax(),
+    |_| classes(
+        None,
+        classes::Config {
+            no_class_calls: true,
+            constant_super: true,
+        }
+    ),
+    assumptions,
+    r#"
+class Test extends Foo {
+  foo() {
+    return super.foo();
+  }
+
+  static bar() {
+    return super.bar;
+  }
+}
+"#,
+    r#"
+let Test =
+/*#__PURE__*/
+function (Foo) {
+  'use strict';
+  _inherits(Test, Foo);
+
+  function Test() {
+    return _possibleConstructorReturn(this, _getPrototypeOf(Test).apply(this, arguments));
+  }
+
+  _createClass(Test, [{
+    key: "foo",
+    value: function foo() {
+      return Foo.prototype.foo.call(this);
+    }
+  }], [{
+    key: "bar",
+    value: function bar() {
+      return Foo.bar;
+    }
+  }]);
+
+  return Test;
+}(Foo);
+"#
+);
+
+#[test]
+fn pure_annotation() {
+    common::test_transform_with_comments(
+        syntax(),
+        |t, m| m.fold_with(&mut classes(Some(&t.comments), Default::default())),
+        r#""use strict";
+class Foo {
+    bar() {}
+}"#,
+        r#""use strict";
+let Foo = /*#__PURE__*/ function() {
+    function Foo() {
+        _classCallCheck(this, Foo);
+    }
+    /*#__PURE__*/ _createClass(Foo, [{
+        key: "bar",
+        value: function bar() {}
+    }]);
+    return Foo;
+}();"#,
+    );
+}
@@ -518,7 +518,7 @@ test!(
     syntax(),
     |_| chain!(
         resolver(),
-        es2015(Mark::fresh(Mark::root()), Default::default()),
+        es2015(Mark::fresh(Mark::root()), None, Default::default()),
     ),
     issue_404_2,
     "function foo(bar) {
@@ -551,7 +551,7 @@ function foo(bar) {
 // destructuring_function_key_with_object_rest_spread
 test_exec!(
     syntax(),
-    |_| chain!(object_rest_spread(), destructuring(Default::default())),
+    |_| chain!(object_rest_spread(Default::default()), destructuring(Default::default())),
     destructuring_function_key_with_object_rest_spread_exec,
     r#"
 const { [(() => 1)()]: a, ...rest } = { 1: "a" };
@@ -592,10 +592,10 @@ test!(
         spread(spread::Config {
             ..Default::default()
         }),
-        parameters(),
+        parameters(Default::default()),
         destructuring(Default::default()),
         block_scoping(),
-        object_rest_spread(),
+        object_rest_spread(Default::default()),
     ),
     destructuring_for_of,
     r#"
@@ -627,10 +627,10 @@ test!(
         spread(spread::Config {
             ..Default::default()
         }),
-        parameters(),
+        parameters(Default::default()),
         destructuring(Default::default()),
         block_scoping(),
-        object_rest_spread(),
+        object_rest_spread(Default::default()),
     ),
     destructuring_object_basic,
     r#"
@@ -653,10 +653,10 @@ test!(
         spread(spread::Config {
             ..Default::default()
         }),
-        parameters(),
+        parameters(Default::default()),
         destructuring(Default::default()),
         block_scoping(),
-        object_rest_spread(),
+        object_rest_spread(Default::default()),
     ),
     destructuring_assignment_arrow_function_block,
     r#"
@@ -678,10 +678,10 @@ test_exec!(
         spread(spread::Config {
             ..Default::default()
         }),
-        parameters(),
+        parameters(Default::default()),
         destructuring(Default::default()),
         block_scoping(),
-        object_rest_spread(),
+        object_rest_spread(Default::default()),
     ),
     destructuring_non_iterable_exec,
     r#"
@@ -705,10 +705,10 @@ test_exec!(
         spread(spread::Config {
             ..Default::default()
         }),
-        parameters(),
+        parameters(Default::default()),
         destructuring(Default::default()),
         block_scoping(),
-        object_rest_spread(),
+        object_rest_spread(Default::default()),
     ),
     destructuring_empty_object_pattern_exec,
     r#"
@@ -726,10 +726,10 @@ test_exec!(
         spread(spread::Config {
             ..Default::default()
         }),
-        parameters(),
+        parameters(Default::default()),
         destructuring(Default::default()),
         block_scoping(),
-        object_rest_spread(),
+        object_rest_spread(Default::default()),
     ),
     destructuring_chained_exec,
     r#"
@@ -747,11 +747,11 @@ expect(d).toBe(4);
 test_exec!(
     syntax(),
     |_| chain!(
-        object_rest_spread(),
+        object_rest_spread(Default::default()),
         spread(spread::Config {
             ..Default::default()
         }),
-        parameters(),
+        parameters(Default::default()),
         destructuring(Default::default()),
         block_scoping(),
     ),
@@ -802,10 +802,10 @@ test_exec!(
         spread(spread::Config {
             ..Default::default()
         }),
-        parameters(),
+        parameters(Default::default()),
         destructuring(Default::default()),
         block_scoping(),
-        object_rest_spread(),
+        object_rest_spread(Default::default()),
     ),
     destructuring_issue_5090_exec,
     r#"
@@ -829,10 +829,10 @@ test_exec!(
         spread(spread::Config {
             ..Default::default()
         }),
-        parameters(),
+        parameters(Default::default()),
         destructuring(Default::default()),
         block_scoping(),
-        object_rest_spread(),
+        object_rest_spread(Default::default()),
     ),
     destructuring_default_precedence_exec,
     r#"
@@ -914,10 +914,10 @@ test!(
         spread(spread::Config {
             ..Default::default()
         }),
-        parameters(),
+        parameters(Default::default()),
         destructuring(Default::default()),
         block_scoping(),
-        object_rest_spread(),
+        object_rest_spread(Default::default()),
     ),
     destructuring_parameters,
     r#"
@@ -981,10 +981,10 @@ test!(
         spread(spread::Config {
             ..Default::default()
         }),
-        parameters(),
+        parameters(Default::default()),
         destructuring(Default::default()),
         block_scoping(),
-        object_rest_spread(),
+        object_rest_spread(Default::default()),
     ),
     destructuring_array_unpack_optimisation,
     r#"
@@ -1062,10 +1062,10 @@ test!(
         spread(spread::Config {
             ..Default::default()
         }),
-        parameters(),
+        parameters(Default::default()),
         destructuring(Default::default()),
         block_scoping(),
-        object_rest_spread(),
+        object_rest_spread(Default::default()),
     ),
     destructuring_known_array,
     r#"
@@ -1085,14 +1085,14 @@ var x = z[0],
 test!(
     syntax(),
     |_| chain!(
-        object_rest_spread(),
+        object_rest_spread(Default::default()),
         spread(spread::Config {
             ..Default::default()
         }),
-        parameters(),
+        parameters(Default::default()),
         destructuring(Default::default()),
         block_scoping(),
-        object_rest_spread(),
+        object_rest_spread(Default::default()),
     ),
     destructuring_es7_object_rest,
     r#"
@@ -1146,11 +1146,11 @@ expect(oldCourses).toEqual([]);
 test!(
     syntax(),
     |_| chain!(
-        object_rest_spread(),
+        object_rest_spread(Default::default()),
         spread(spread::Config {
             ..Default::default()
         }),
-        parameters(),
+        parameters(Default::default()),
         destructuring(Default::default()),
         block_scoping(),
     ),
@@ -1170,11 +1170,11 @@ var tmp = z.x, ref = tmp === void 0 ? {} : tmp, y = ref.y;
 test!(
     syntax(),
     |_| chain!(
-        object_rest_spread(),
+        object_rest_spread(Default::default()),
         spread(spread::Config {
             ..Default::default()
         }),
-        parameters(),
+        parameters(Default::default()),
         destructuring(Default::default()),
         block_scoping(),
     ),
@@ -1204,11 +1204,11 @@ var ref = [0, 1, 2, 3, 4, 5, 6],
 test!(
     syntax(),
     |_| chain!(
-        object_rest_spread(),
+        object_rest_spread(Default::default()),
         spread(spread::Config {
             ..Default::default()
         }),
-        parameters(),
+        parameters(Default::default()),
         destructuring(Default::default()),
         block_scoping(),
     ),
@@ -1240,11 +1240,11 @@ function isSorted(param) {
 test!(
     syntax(),
     |_| chain!(
-        object_rest_spread(),
+        object_rest_spread(Default::default()),
         spread(spread::Config {
             ..Default::default()
         }),
-        parameters(),
+        parameters(Default::default()),
         destructuring(Default::default()),
         block_scoping(),
     ),
@@ -1276,7 +1276,7 @@ test!(
             ..Default::default()
         }),
         block_scoping(),
-        object_rest_spread()
+        object_rest_spread(Default::default())
     ),
     destructuring_assignment_statement,
     r#"
@@ -1295,10 +1295,10 @@ test!(
         spread(spread::Config {
             ..Default::default()
         }),
-        parameters(),
+        parameters(Default::default()),
         destructuring(Default::default()),
         block_scoping(),
-        object_rest_spread(),
+        object_rest_spread(Default::default()),
     ),
     destructuring_array,
     r#"
@@ -1320,10 +1320,10 @@ test!(
         spread(spread::Config {
             ..Default::default()
         }),
-        parameters(),
+        parameters(Default::default()),
         destructuring(Default::default()),
         block_scoping(),
-        object_rest_spread(),
+        object_rest_spread(Default::default()),
     ),
     destructuring_assignment_arrow_function_no_block,
     r#"
@@ -1340,14 +1340,14 @@ var ref;
 test!(
     syntax(),
     |_| chain!(
-        object_rest_spread(),
+        object_rest_spread(Default::default()),
         spread(spread::Config {
             ..Default::default()
         }),
-        parameters(),
+        parameters(Default::default()),
         destructuring(Default::default()),
         block_scoping(),
-        object_rest_spread(),
+        object_rest_spread(Default::default()),
     ),
     destructuring_issue_9834,
     r#"
@@ -1382,7 +1382,7 @@ var key = prefix + 'state',
 // destructuring_number_key_with_object_rest_spread
 test_exec!(
     syntax(),
-    |_| chain!(object_rest_spread(), destructuring(Default::default())),
+    |_| chain!(object_rest_spread(Default::default()), destructuring(Default::default())),
     destructuring_number_key_with_object_rest_spread_exec,
     r#"
 const foo = {
@@ -1406,10 +1406,10 @@ test!(
         spread(spread::Config {
             ..Default::default()
         }),
-        parameters(),
+        parameters(Default::default()),
         destructuring(Default::default()),
         block_scoping(),
-        object_rest_spread(),
+        object_rest_spread(Default::default()),
     ),
     destructuring_for_in,
     r#"
@@ -1441,10 +1441,10 @@ test!(
         spread(spread::Config {
             ..Default::default()
         }),
-        parameters(),
+        parameters(Default::default()),
         destructuring(Default::default()),
         block_scoping(),
-        object_rest_spread(),
+        object_rest_spread(Default::default()),
     ),
     destructuring_issue_5744,
     r#"
@@ -1465,10 +1465,10 @@ test_exec!(
         spread(spread::Config {
             ..Default::default()
         }),
-        parameters(),
+        parameters(Default::default()),
         destructuring(Default::default()),
         block_scoping(),
-        object_rest_spread(),
+        object_rest_spread(Default::default()),
     ),
     destructuring_spread_generator_exec,
     r#"
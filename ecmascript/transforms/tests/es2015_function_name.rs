@@ -158,7 +158,7 @@ test!(
             legacy: true,
             ..Default::default()
         }),
-        classes(),
+        classes(None, Default::default()),
         function_name(),
     ),
     function_name_function_collision,
@@ -282,7 +282,7 @@ test!(
             legacy: true,
             ..Default::default()
         }),
-        classes(),
+        classes(None, Default::default()),
         function_name(),
     ),
     function_name_collisions,
@@ -343,7 +343,7 @@ test!(
             legacy: true,
             ..Default::default()
         }),
-        classes(),
+        classes(None, Default::default()),
         function_name(),
         common_js(Mark::fresh(Mark::root()), Default::default())
     ),
@@ -469,7 +469,7 @@ test!(
             legacy: true,
             ..Default::default()
         }),
-        classes(),
+        classes(None, Default::default()),
         function_name(),
     ),
     function_name_await,
@@ -498,7 +498,7 @@ test!(
             legacy: true,
             ..Default::default()
         }),
-        classes(),
+        classes(None, Default::default()),
         function_name(),
     ),
     function_name_function_assignment,
@@ -559,7 +559,7 @@ test!(
             legacy: true,
             ..Default::default()
         }),
-        classes(),
+        classes(None, Default::default()),
         function_name(),
     ),
     function_name_shorthand_property,
@@ -660,7 +660,7 @@ test!(
     |_| chain!(
         resolver(),
         function_name(),
-        classes(),
+        classes(None, Default::default()),
         decorators(decorators::Config {
             legacy: true,
             ..Default::default()
@@ -721,7 +721,7 @@ test!(
     |_| chain!(
         resolver(),
         function_name(),
-        classes(),
+        classes(None, Default::default()),
         decorators(decorators::Config {
             legacy: true,
             ..Default::default()
@@ -789,7 +789,7 @@ test!(
             ..Default::default()
         }),
         function_name(),
-        classes(),
+        classes(None, Default::default()),
     ),
     function_name_global,
     r#"
@@ -859,7 +859,7 @@ test!(
             legacy: true,
             ..Default::default()
         }),
-        classes(),
+        classes(None, Default::default()),
         function_name(),
         common_js(Mark::fresh(Mark::root()), Default::default()),
     ),
@@ -909,7 +909,7 @@ test!(
     |_| chain!(
         resolver(),
         function_name(),
-        classes(),
+        classes(None, Default::default()),
         decorators(decorators::Config {
             legacy: true,
             ..Default::default()
@@ -940,7 +940,7 @@ test!(
     |_| chain!(
         resolver(),
         function_name(),
-        classes(),
+        classes(None, Default::default()),
         decorators(decorators::Config {
             legacy: true,
             ..Default::default()
@@ -1002,7 +1002,7 @@ test!(
             legacy: true,
             ..Default::default()
         }),
-        classes(),
+        classes(None, Default::default()),
         function_name(),
     ),
     function_name_basic,
@@ -1165,7 +1165,7 @@ test!(
             ..Default::default()
         }),
         function_name(),
-        classes()
+        classes(None, Default::default())
     ),
     function_name_self_reference,
     r#"
@@ -1232,7 +1232,7 @@ test!(
             legacy: true,
             ..Default::default()
         }),
-        classes(),
+        classes(None, Default::default()),
         function_name(),
     ),
     function_name_method_definition,
@@ -1260,7 +1260,7 @@ test!(
             legacy: true,
             ..Default::default()
         }),
-        classes(),
+        classes(None, Default::default()),
         function_name(),
     ),
     function_name_own_bindings,
@@ -21,7 +21,7 @@ fn syntax() -> Syntax {
 fn tr() -> impl Fold {
     chain!(
         resolver(),
-        parameters(),
+        parameters(Default::default()),
         swc_ecma_transforms::compat::es2015::destructuring(destructuring::Config { loose: false }),
         swc_ecma_transforms::compat::es2015::block_scoping(),
     )
@@ -182,7 +182,7 @@ foo(1, 2, 3);"#
 
 test!(
     syntax(),
-    |_| chain!(classes(), tr()),
+    |_| chain!(classes(None, Default::default()), tr()),
     default_iife_4253,
     r#"class Ref {
   constructor(id = ++Ref.nextID) {
@@ -218,7 +218,7 @@ expect(new Ref().id).toBe(2);"#
 
 test!(
     syntax(),
-    |_| chain!(classes(), tr()),
+    |_| chain!(classes(None, Default::default()), tr()),
     default_iife_self,
     r#"class Ref {
   constructor(ref = Ref) {
@@ -1215,7 +1215,7 @@ test!(
     syntax(),
     |_| chain!(
         tr(),
-        classes(),
+        classes(None, Default::default()),
         swc_ecma_transforms::compat::es2015::spread(Default::default())
     ),
     rest_nested_iife,
@@ -1516,8 +1516,8 @@ function foo() {
 test!(
     syntax(),
     |_| chain!(
-        classes(),
-        parameters(),
+        classes(None, Default::default()),
+        parameters(Default::default()),
         destructuring(Default::default()),
         block_scoping(),
         common_js(Mark::fresh(Mark::root()), Default::default()),
@@ -1572,7 +1572,11 @@ test!(
     // See https://github.com/swc-project/swc/issues/490
     ignore,
     syntax(),
-    |_| chain!(async_to_generator(), arrow(), parameters(),),
+    |_| chain!(
+        async_to_generator(),
+        arrow(),
+        parameters(Default::default()),
+    ),
     parameters_rest_async_arrow_functions_1,
     r#"
 var concat = async (...arrs) => {
@@ -1599,7 +1603,11 @@ test!(
     // See https://github.com/swc-project/swc/issues/490
     ignore,
     syntax(),
-    |_| chain!(async_to_generator(), arrow(), parameters(),),
+    |_| chain!(
+        async_to_generator(),
+        arrow(),
+        parameters(Default::default()),
+    ),
     parameters_rest_async_arrow_functions_2,
     r#"
 var x = async (...rest) => {
@@ -1631,7 +1639,7 @@ var x = function () {
 // regression_6057_simple
 test!(
     syntax(),
-    |_| parameters(),
+    |_| parameters(Default::default()),
     regression_6057_simple,
     r#"
 const a = 'bar';
@@ -1658,7 +1666,7 @@ function foo() {
 // parameters_regression_4333
 test!(
     syntax(),
-    |_| chain!(parameters(), block_scoping(),),
+    |_| chain!(parameters(Default::default()), block_scoping(),),
     parameters_regression_4333,
     r#"
 const args = 'bar';
@@ -1683,7 +1691,10 @@ function foo() {
 
 test!(
     syntax(),
-    |_| chain!(parameters(), destructuring(Default::default())),
+    |_| chain!(
+        parameters(Default::default()),
+        destructuring(Default::default())
+    ),
     issue_760,
     "const initialState = 'foo'
 export default function reducer(state = initialState, action = {}) {
@@ -1694,3 +1705,19 @@ export default function reducer(state = initialState, action = {}) {
       } : param1;
   }"
 );
+
+test!(
+    syntax(),
+    |_| parameters(parameters::Config {
+        ignore_function_length: true,
+    }),
+    ignore_function_length,
+    "function foo(a = 1, b = a) {
+    return a + b;
+}",
+    "function foo(a, b) {
+    if (a === void 0) a = 1;
+    if (b === void 0) b = a;
+    return a + b;
+}"
+);
@@ -15,7 +15,7 @@ fn syntax() -> ::swc_ecma_parser::Syntax {
 
 fn tr() -> impl Fold {
     chain!(
-        swc_ecma_transforms::compat::es2015::parameters(),
+        swc_ecma_transforms::compat::es2015::parameters(Default::default()),
         spread(Config {
             ..Default::default()
         })
@@ -41,7 +41,7 @@ fn tr() -> impl Fold {
     chain!(
         ParenRemover,
         validating!(arrow()),
-        validating!(parameters()),
+        validating!(parameters(Default::default())),
         validating!(destructuring(destructuring::Config { loose: false })),
         validating!(function_name()),
         async_to_generator(),
@@ -666,7 +666,7 @@ test!(
 
 test_exec!(
     syntax(),
-    |_| chain!(tr(), es2015(Mark::fresh(Mark::root()), Default::default())),
+    |_| chain!(tr(), es2015(Mark::fresh(Mark::root()), None, Default::default())),
     issue_400_1,
     "class A {
     constructor() {
@@ -727,7 +727,7 @@ test_exec!(
     syntax(),
     |_| chain!(
         async_to_generator(),
-        es2015(Mark::fresh(Mark::root()), Default::default())
+        es2015(Mark::fresh(Mark::root()), None, Default::default())
     ),
     issue_400_3,
     "class A {
@@ -1434,7 +1434,7 @@ test!(
     syntax(),
     |_| chain!(
         async_to_generator(),
-        parameters(),
+        parameters(Default::default()),
         destructuring(destructuring::Config { loose: false }),
     ),
     regression_4943,
@@ -19,7 +19,7 @@ fn syntax() -> Syntax {
 }
 
 fn tr() -> impl Fold {
-    object_rest_spread()
+    object_rest_spread(Default::default())
 }
 
 test!(
@@ -2790,3 +2790,25 @@ const _ref2 = foo((_param)=>{
 
 "#
 );
+
+test!(
+    syntax(),
+    |_| object_rest_spread(object_rest_spread::Config {
+        set_spread_properties: true,
+    }),
+    set_spread_properties,
+    r#"
+z = { x, ...y };
+
+z = { x, w: { ...y } };
+"#,
+    r#"
+z = _extends({
+  x
+}, y);
+z = {
+  x,
+  w: _extends({}, y)
+};
+"#
+);
@@ -35,8 +35,8 @@ fn tr() -> impl Fold {
     chain!(
         resolver(),
         function_name(),
-        class_properties(),
-        classes(),
+        class_properties(Default::default()),
+        classes(None, Default::default()),
         block_scoping(),
         reserved_words(false),
     )
@@ -2819,7 +2819,7 @@ var _x = {
 
 test!(
     syntax(),
-    |_| chain!(resolver(), class_properties()),
+    |_| chain!(resolver(), class_properties(Default::default())),
     issue_308,
     "function bar(props) {}
 class Foo {
@@ -2847,7 +2847,11 @@ class Foo{
 
 test!(
     syntax(),
-    |_| chain!(resolver(), class_properties(), classes()),
+    |_| chain!(
+        resolver(),
+        class_properties(Default::default()),
+        classes(None, Default::default())
+    ),
     issue_342,
     "class Foo {
   constructor(bar) {
@@ -2872,7 +2876,11 @@ let Foo = function Foo(bar) {
 
 test!(
     syntax(),
-    |_| chain!(resolver(), class_properties(), block_scoping()),
+    |_| chain!(
+        resolver(),
+        class_properties(Default::default()),
+        block_scoping()
+    ),
     issue_443,
     "
 const MODE = 1;
@@ -2897,7 +2905,7 @@ _defineProperty(foo, 'MODE', MODE);"
 // public_regression_t7364
 test!(
     syntax(),
-    |_| chain!(class_properties(), async_to_generator()),
+    |_| chain!(class_properties(Default::default()), async_to_generator()),
     public_regression_t7364,
     r#"
 class MyClass {
@@ -2971,7 +2979,7 @@ export { MyClass3 as default }
 // private_regression_t6719
 test!(
     syntax(),
-    |_| chain!(class_properties(), block_scoping()),
+    |_| chain!(class_properties(Default::default()), block_scoping()),
     private_regression_t6719,
     r#"
 function withContext(ComposedComponent) {
@@ -3057,7 +3065,7 @@ function withContext(ComposedComponent) {
 // private_reevaluated
 test!(
     syntax(),
-    |_| chain!(class_properties(), block_scoping()),
+    |_| chain!(class_properties(Default::default()), block_scoping()),
     private_reevaluated,
     r#"
 function classFactory() {
@@ -3121,7 +3129,7 @@ function classFactory() {
 // private_static
 test!(
     syntax(),
-    |_| chain!(class_properties(), block_scoping()),
+    |_| chain!(class_properties(Default::default()), block_scoping()),
     private_static,
     r#"
 class Foo {
@@ -3167,7 +3175,11 @@ expect(Foo.test()).toBe("foo");
 // private_destructuring_object_pattern_1
 test!(
     syntax(),
-    |_| chain!(class_properties(), classes(), block_scoping()),
+    |_| chain!(
+        class_properties(Default::default()),
+        classes(None, Default::default()),
+        block_scoping()
+    ),
     private_destructuring_object_pattern_1,
     r#"
 class Foo {
@@ -3206,7 +3218,7 @@ var _client = new WeakMap();
 // private_static_inherited
 test!(
     syntax(),
-    |_| chain!(class_properties(), block_scoping()),
+    |_| chain!(class_properties(Default::default()), block_scoping()),
     private_static_inherited,
     r#"
 class Base {
@@ -3285,7 +3297,7 @@ class Sub2 extends Base {}
 // private_destructuring_object_pattern_1_exec
 test_exec!(
     syntax(),
-    |_| class_properties(),
+    |_| class_properties(Default::default()),
     private_destructuring_object_pattern_1_exec,
     r#"
 class Foo {
@@ -3312,7 +3324,7 @@ expect(foo.z).toBe('bar');
 // private_static_undefined
 test!(
     syntax(),
-    |_| chain!(class_properties(), block_scoping()),
+    |_| chain!(class_properties(Default::default()), block_scoping()),
     private_static_undefined,
     r#"
 class Foo {
@@ -3351,7 +3363,11 @@ var _bar = {
 // private_destructuring_array_pattern
 test!(
     syntax(),
-    |_| chain!(class_properties(), classes(), block_scoping()),
+    |_| chain!(
+        class_properties(Default::default()),
+        classes(None, Default::default()),
+        block_scoping()
+    ),
     private_destructuring_array_pattern,
     r#"
 class Foo {
@@ -3385,7 +3401,7 @@ var _client = new WeakMap();
 // private_regression_t2983
 test!(
     syntax(),
-    |_| chain!(class_properties(), block_scoping()),
+    |_| chain!(class_properties(Default::default()), block_scoping()),
     private_regression_t2983,
     r#"
 call(class {
@@ -3422,7 +3438,11 @@ export { _class as default }
 // private_regression_t7364
 test!(
     syntax(),
-    |_| chain!(class_properties(), async_to_generator(), block_scoping()),
+    |_| chain!(
+        class_properties(Default::default()),
+        async_to_generator(),
+        block_scoping()
+    ),
     private_regression_t7364,
     r#"
 class MyClass {
@@ -3508,7 +3528,11 @@ export { MyClass3 as default }
 // private_destructuring_array_pattern_1
 test!(
     syntax(),
-    |_| chain!(class_properties(), classes(), block_scoping()),
+    |_| chain!(
+        class_properties(Default::default()),
+        classes(None, Default::default()),
+        block_scoping()
+    ),
     private_destructuring_array_pattern_1,
     r#"
 class Foo {
@@ -3550,8 +3574,8 @@ test!(
             legacy: true,
             ..Default::default()
         }),
-        class_properties(),
-        classes(),
+        class_properties(Default::default()),
+        classes(None, Default::default()),
     ),
     decorators_legacy_interop_strict,
     r#"
@@ -3601,7 +3625,7 @@ let A = (_class = (_temp = function A() {
 // regression_8882_exec
 test_exec!(
     syntax(),
-    |_| class_properties(),
+    |_| class_properties(Default::default()),
     regression_8882_exec,
     r#"
 const classes = [];
@@ -3739,7 +3763,7 @@ for(let i=0; i<= 10; ++i) {
 // private_static_export
 test!(
     syntax(),
-    |_| chain!(class_properties(), block_scoping()),
+    |_| chain!(class_properties(Default::default()), block_scoping()),
     private_static_export,
     r#"
 export class MyClass {
@@ -3770,7 +3794,10 @@ export { MyClass2 as default }
 // static_property_tdz_edgest_case
 test!(
     syntax(),
-    |_| chain!(class_properties(), classes()),
+    |_| chain!(
+        class_properties(Default::default()),
+        classes(None, Default::default())
+    ),
     static_property_tdz_edgest_case,
     r#"
 class A {
@@ -3797,7 +3824,7 @@ _defineProperty(A, _x, void 0);
 // regression_6153
 test!(
     syntax(),
-    |_| chain!(class_properties(), arrow()),
+    |_| chain!(class_properties(Default::default()), arrow()),
     regression_6153,
     r#"
 () => {
@@ -3898,7 +3925,7 @@ var qux = (function () {
 // regression_7371
 test!(
     syntax(),
-    |_| chain!(class_properties(), arrow()),
+    |_| chain!(class_properties(Default::default()), arrow()),
     regression_7371,
     r#"
 "use strict";
@@ -4108,7 +4135,11 @@ new ComputedField();
 // private_canonical
 test!(
     syntax(),
-    |_| chain!(class_properties(), classes(), block_scoping()),
+    |_| chain!(
+        class_properties(Default::default()),
+        classes(None, Default::default()),
+        block_scoping()
+    ),
     private_canonical,
     r#"
 class Point {
@@ -4196,7 +4227,7 @@ var _y = new WeakMap();
 // regression_8882
 test!(
     syntax(),
-    |_| class_properties(),
+    |_| class_properties(Default::default()),
     regression_8882,
     r#"
 const classes = [];
@@ -4248,7 +4279,7 @@ for(let i = 0; i <= 10; ++i){
 // compile_to_class_constructor_collision_ignores_types
 test!(
     ts(),
-    |_| chain!(typescript::strip(), class_properties()),
+    |_| chain!(typescript::strip(), class_properties(Default::default())),
     compile_to_class_constructor_collision_ignores_types,
     r#"
 class C {
@@ -4274,7 +4305,11 @@ class C {
 // private_destructuring_array_pattern_3
 test!(
     syntax(),
-    |_| chain!(class_properties(), classes(), block_scoping()),
+    |_| chain!(
+        class_properties(Default::default()),
+        classes(None, Default::default()),
+        block_scoping()
+    ),
     private_destructuring_array_pattern_3,
     r#"
 class Foo {
@@ -4307,7 +4342,7 @@ var _client = new WeakMap();
 // public_static_super_exec
 test_exec!(
     syntax(),
-    |_| class_properties(),
+    |_| class_properties(Default::default()),
     public_static_super_exec,
     r#"
 class A {
@@ -4332,7 +4367,11 @@ expect(getPropA()).toBe(1);
 // private_destructuring_array_pattern_2
 test!(
     syntax(),
-    |_| chain!(class_properties(), classes(), block_scoping()),
+    |_| chain!(
+        class_properties(Default::default()),
+        classes(None, Default::default()),
+        block_scoping()
+    ),
     private_destructuring_array_pattern_2,
     r#"
 class Foo {
@@ -4365,7 +4404,7 @@ var _client = new WeakMap();
 // private_non_block_arrow_func
 test!(
     syntax(),
-    |_| chain!(class_properties(), block_scoping()),
+    |_| chain!(class_properties(Default::default()), block_scoping()),
     private_non_block_arrow_func,
     r#"
 export default param =>
@@ -4405,7 +4444,7 @@ export default ((param)=>{
 // regression_8110
 test!(
     syntax(),
-    |_| class_properties(),
+    |_| class_properties(Default::default()),
     regression_8110,
     r#"
 const field = Symbol('field');
@@ -4438,8 +4477,8 @@ test!(
             legacy: true,
             ..Default::default()
         }),
-        class_properties(),
-        classes()
+        class_properties(Default::default()),
+        classes(None, Default::default())
     ),
     decorators_legacy_interop_local_define_property,
     r#"
@@ -4495,7 +4534,7 @@ let A = (_class = (_temp = function A() {
 // public_computed_without_block_exec
 test_exec!(
     syntax(),
-    |_| class_properties(),
+    |_| class_properties(Default::default()),
     public_computed_without_block_exec,
     r#"
 const createClass = (k) => class { [k()] = 2 };
@@ -4510,9 +4549,9 @@ expect(instance.foo).toBe(2);
 test!(
     syntax(),
     |_| chain!(
-        class_properties(),
+        class_properties(Default::default()),
         exponentation(),
-        classes(),
+        classes(None, Default::default()),
         block_scoping(),
     ),
     private_instance,
@@ -4542,7 +4581,10 @@ var _bar = new WeakMap();
 // static_property_tdz_general
 test!(
     syntax(),
-    |_| chain!(class_properties(), classes()),
+    |_| chain!(
+        class_properties(Default::default()),
+        classes(None, Default::default())
+    ),
     static_property_tdz_general,
     r#"
 class C {
@@ -4567,7 +4609,7 @@ _defineProperty(C, _ref, 3);
 // public_native_classes
 test!(
     syntax(),
-    |_| chain!(class_properties(), block_scoping()),
+    |_| chain!(class_properties(Default::default()), block_scoping()),
     public_native_classes,
     r#"
 class Foo {
@@ -4619,7 +4661,7 @@ test!(
     // Seems useless, while being hard to implement.
     ignore,
     syntax(),
-    |_| chain!(class_properties(), block_scoping()),
+    |_| chain!(class_properties(Default::default()), block_scoping()),
     private_static_infer_name,
     r#"
 var Foo = class {
@@ -4641,7 +4683,7 @@ var Foo = (_temp = _class = class Foo {}, _num = {
 // regression_7951
 test!(
     syntax(),
-    |_| chain!(resolver(), class_properties()),
+    |_| chain!(resolver(), class_properties(Default::default())),
     regression_7951,
     r#"
 export class Foo extends Bar {
@@ -4667,7 +4709,7 @@ _defineProperty(Foo, "foo", {});
 // private_native_classes
 test!(
     syntax(),
-    |_| chain!(class_properties(), block_scoping()),
+    |_| chain!(class_properties(Default::default()), block_scoping()),
     private_native_classes,
     r#"
 class Foo {
@@ -4715,7 +4757,11 @@ var _bar = new WeakMap();
 // public_computed_without_block
 test!(
     syntax(),
-    |_| chain!(class_properties(), classes(), block_scoping()),
+    |_| chain!(
+        class_properties(Default::default()),
+        classes(None, Default::default()),
+        block_scoping()
+    ),
     public_computed_without_block,
     r#"
 const createClass = (k) => class { [k()] = 2 };
@@ -4738,7 +4784,7 @@ var createClass = (k)=>{
 // private_destructuring_array_pattern_2_exec
 test_exec!(
     syntax(),
-    |_| class_properties(),
+    |_| class_properties(Default::default()),
     private_destructuring_array_pattern_2_exec,
     r#"
 class Foo {
@@ -4763,7 +4809,11 @@ expect(foo.getClient()).toEqual(['bar', 'baz', 'quu']);
 // public_static_super
 test!(
     syntax(),
-    |_| chain!(class_properties(), classes(), block_scoping()),
+    |_| chain!(
+        class_properties(Default::default()),
+        classes(None, Default::default()),
+        block_scoping()
+    ),
     public_static_super,
     r#"
 class A {
@@ -4811,7 +4861,7 @@ _defineProperty(B, "getPropA", () => _get(_getPrototypeOf(B), "prop", B));
 // private_destructuring_array_pattern_exec
 test_exec!(
     syntax(),
-    |_| class_properties(),
+    |_| class_properties(Default::default()),
     private_destructuring_array_pattern_exec,
     r#"
 class Foo {
@@ -4835,7 +4885,7 @@ expect(foo.getClient()).toBe('bar');
 // private_destructuring_array_pattern_1_exec
 test_exec!(
     syntax(),
-    |_| class_properties(),
+    |_| class_properties(Default::default()),
     private_destructuring_array_pattern_1_exec,
     r#"
 class Foo {
@@ -4858,3 +4908,38 @@ expect(foo.y).toBe('bar');
 
 "#
 );
+
+test!(
+    syntax(),
+    |_| chain!(
+        resolver(),
+        class_properties(class_properties::Config {
+            set_public_class_fields: true,
+            private_fields_as_properties: true,
+        })
+    ),
+    assumptions,
+    "class Foo {
+  bar = 1;
+  static baz = 2;
+  #x = 3;
+
+  getX() {
+    return this.#x;
+  }
+}",
+    "var _x = _classPrivateFieldLooseKey('x');
+class Foo {
+    getX() {
+        return _classPrivateFieldLooseBase(this, _x)[_x];
+    }
+    constructor(){
+        this.bar = 1;
+        Object.defineProperty(this, _x, {
+            writable: true,
+            value: 3
+        });
+    }
+}
+Foo.baz = 2;"
+);
@@ -1,13 +1,13 @@
 #![feature(test)]
 use swc_ecma_parser::{Syntax, TsConfig};
-use swc_ecma_transforms::compat::es2020::optional_chaining;
+use swc_ecma_transforms::compat::es2020::{opt_chaining, optional_chaining};
 use swc_ecma_visit::Fold;
 
 #[macro_use]
 mod common;
 
 fn tr(_: ()) -> impl Fold {
-    optional_chaining()
+    optional_chaining(Default::default())
 }
 
 fn syntax() -> Syntax {
@@ -568,3 +568,19 @@ test!(
     "var ref;
 (ref = test.a) === null || ref === void 0 ? void 0 : ref.b.c.d.e.f.g.h.i"
 );
+
+test!(
+    syntax(),
+    |_| optional_chaining(opt_chaining::Config {
+        no_document_all: true,
+        pure_getters: true,
+    }),
+    assumptions,
+    "a.b?.c;
+a.b?.();
+foo()?.bar;",
+    "var ref;
+a.b == null ? void 0 : a.b.c;
+a.b == null ? void 0 : a.b();
+(ref = foo()) == null ? void 0 : ref.bar;"
+);
@@ -0,0 +1,106 @@
+#![feature(test)]
+use swc_ecma_parser::{FlowConfig, Syntax};
+use swc_ecma_transforms::flow::strip;
+use swc_ecma_visit::Fold;
+
+#[macro_use]
+mod common;
+
+fn tr() -> impl Fold {
+    strip()
+}
+
+macro_rules! to {
+    ($name:ident, $from:expr, $to:expr) => {
+        test!(
+            Syntax::Flow(FlowConfig::default()),
+            |_| tr(),
+            $name,
+            $from,
+            $to,
+            ok_if_code_eq
+        );
+    };
+}
+
+to!(
+    annotations,
+    "function foo(a: ?string, b?: number, c: * = 1, ...d: Array<string>): void {
+    var e: { +f: string, ...G } = a;
+}",
+    "function foo(a, b, c = 1, ...d) {
+    var e = a;
+}"
+);
+
+to!(
+    type_alias,
+    "type A = {| b: string |};
+opaque type C: string = string;
+export type D = A;
+export opaque type E = A;
+var f = 1;",
+    "var f = 1;"
+);
+
+to!(
+    interface,
+    "interface A { b: string }
+export interface C { d(): void }",
+    ""
+);
+
+to!(
+    declare,
+    "declare var a: number;
+declare function b(): void;
+declare class C { d(): void }
+declare module 'e' { declare var f: number; }
+declare export function g(): void;",
+    ""
+);
+
+to!(
+    import_type,
+    "import type A from 'a';
+import typeof B from 'b';
+import { type C, typeof D } from 'c';
+import E, { type F, g } from 'e';",
+    "import E, { g } from 'e';"
+);
+
+to!(export_type, "export type { A } from 'a';", "");
+
+to!(type_cast, "var a = ((b: any): string);", "var a = b;");
+
+to!(
+    generics,
+    "class A<+T: Object = {}> extends B<T> implements C {
+    d: T;
+    e: T = f<T>();
+    static g: string;
+}",
+    "class A extends B {
+    e = f();
+}"
+);
+
+to!(
+    this_param,
+    "function a(this: B, c: D) {}",
+    "function a(c) {}"
+);
+
+to!(
+    arrow,
+    "var a = <T>(b: T, c: (string, number) => void): T => b;",
+    "var a = (b, c) => b;"
+);
+
+to!(
+    value_import_is_kept,
+    "import A from 'a';
+var b: A = 1;",
+    "import A from 'a';
+var b = 1;"
+);
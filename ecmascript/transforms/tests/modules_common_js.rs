@@ -1,6 +1,6 @@
 #![feature(test)]
 use swc_common::{chain, Mark};
-use swc_ecma_parser::{Syntax, TsConfig};
+use swc_ecma_parser::{EsConfig, Syntax, TsConfig};
 use swc_ecma_transforms::{
     compat,
     compat::es2020::class_properties,
@@ -10,13 +10,13 @@ use swc_ecma_transforms::{
     modules::{
         common_js::{common_js, Config},
         import_analysis::import_analyzer,
-        util::Lazy,
+        util::{ImportInterop, Lazy},
     },
     optimization::simplifier,
     proposals::{decorators, export},
     resolver_with_mark, typescript,
 };
-use swc_ecma_visit::Fold;
+use swc_ecma_visit::{Fold, FoldWith};
 
 #[macro_use]
 mod common;
@@ -24,6 +24,12 @@ mod common;
 fn syntax() -> Syntax {
     Default::default()
 }
+fn dynamic_import_syntax() -> Syntax {
+    Syntax::Es(EsConfig {
+        dynamic_import: true,
+        ..Default::default()
+    })
+}
 fn ts_syntax() -> Syntax {
     Syntax::Typescript(TsConfig {
         ..Default::default()
@@ -92,7 +98,7 @@ test!(
         chain!(
             resolver_with_mark(mark),
             // Optional::new(typescript::strip(), syntax.typescript()),
-            import_analyzer(),
+            import_analyzer(Default::default(), false),
             inject_helpers(),
             common_js(mark, Default::default()),
             hygiene(),
@@ -121,15 +127,15 @@ test!(
     |_| chain!(
         typescript::strip(),
         decorators(Default::default()),
-        class_properties(),
+        class_properties(Default::default()),
         export(),
         simplifier(Default::default()),
-        compat::es2018(),
+        compat::es2018(Default::default()),
         compat::es2017(),
         compat::es2016(),
-        compat::es2015(Mark::fresh(Mark::root()), Default::default()),
+        compat::es2015(Mark::fresh(Mark::root()), None, Default::default()),
         compat::es3(true),
-        import_analyzer(),
+        import_analyzer(Default::default(), false),
         inject_helpers(),
         common_js(Mark::fresh(Mark::root()), Default::default()),
     ),
@@ -4226,3 +4232,144 @@ test!(
     exports.default = _default;
 "#
 );
+
+test!(
+    dynamic_import_syntax(),
+    |_| tr(Config {
+        ignore_dynamic: false,
+        ..Default::default()
+    }),
+    dynamic_import,
+    "const foo = import('foo');
+const bar = import(name);",
+    "'use strict';
+const foo = Promise.resolve().then(function() {
+    return _interopRequireWildcard(require('foo'));
+});
+const bar = Promise.resolve(name).then(function(s) {
+    return _interopRequireWildcard(require(s));
+});"
+);
+
+test!(
+    dynamic_import_syntax(),
+    |_| tr(Config {
+        no_interop: true,
+        ignore_dynamic: false,
+        ..Default::default()
+    }),
+    dynamic_import_no_interop,
+    "import('foo');",
+    "'use strict';
+Promise.resolve().then(function() {
+    return require('foo');
+});"
+);
+
+test!(
+    dynamic_import_syntax(),
+    |_| tr(Config {
+        ignore_dynamic: true,
+        ..Default::default()
+    }),
+    ignore_dynamic,
+    "import foo from 'foo';
+import('bar').then(foo);",
+    "'use strict';
+var _foo = _interopRequireDefault(require('foo'));
+import('bar').then(_foo.default);"
+);
+
+test!(
+    dynamic_import_syntax(),
+    |_| tr(Default::default()),
+    dynamic_import_is_kept_by_default,
+    "import('foo');",
+    "'use strict';
+import('foo');"
+);
+
+test!(
+    syntax(),
+    |_| tr(Config {
+        import_interop: ImportInterop::Node,
+        ..Default::default()
+    }),
+    import_interop_node,
+    "import foo, { bar } from 'foo';
+import * as ns from 'ns';
+export { default as baz } from 'baz';
+console.log(foo, bar, ns);",
+    "'use strict';
+Object.defineProperty(exports, '__esModule', {
+    value: true
+});
+exports.baz = void 0;
+var _foo = require('foo');
+var ns = _interopRequireWildcard(require('ns'), true);
+var _baz = require('baz');
+exports.baz = _baz;
+console.log(_foo, _foo.bar, ns);"
+);
+
+test!(
+    syntax(),
+    |_| tr(Config {
+        export_interop_annotation: true,
+        ..Default::default()
+    }),
+    export_interop_annotation,
+    "import { d } from 'd';
+export function f() {}
+function g() {}
+export { g as h, d as e };
+export { c } from 'c';",
+    "'use strict';
+Object.defineProperty(exports, '__esModule', {
+    value: true
+});
+exports.f = f;
+Object.defineProperty(exports, 'e', {
+    enumerable: true,
+    get: function() {
+        return _d.d;
+    }
+});
+Object.defineProperty(exports, 'c', {
+    enumerable: true,
+    get: function() {
+        return _c.c;
+    }
+});
+exports.h = void 0;
+var _d = require('d');
+var _c = require('c');
+function f() {}
+function g() {}
+exports.h = g;
+0 && (module.exports = {
+    f: f,
+    h: g,
+    e: e,
+    c: c
+});"
+);
+
+#[test]
+fn dynamic_import_magic_comment() {
+    common::test_transform_with_comments(
+        dynamic_import_syntax(),
+        |_, m| {
+            m.fold_with(&mut tr(Config {
+                no_interop: true,
+                ignore_dynamic: false,
+                ..Default::default()
+            }))
+        },
+        r#"import(/* webpackChunkName: "x" */ './x');"#,
+        r#"'use strict';
+Promise.resolve().then(function() {
+    return require(/* webpackChunkName: "x" */ './x');
+});"#,
+    );
+}
@@ -0,0 +1,161 @@
+#![feature(test)]
+use swc_atoms::JsWord;
+use swc_common::{chain, FileName, Mark};
+use swc_ecma_parser::{EsConfig, Syntax};
+use swc_ecma_transforms::{
+    modules::{
+        common_js::common_js,
+        path::{import_rewriter, ImportResolver, TsConfigResolver},
+    },
+    resolver_with_mark,
+};
+use swc_ecma_visit::Fold;
+
+#[macro_use]
+mod common;
+
+fn syntax() -> Syntax {
+    Syntax::Es(EsConfig {
+        dynamic_import: true,
+        ..Default::default()
+    })
+}
+
+fn base() -> FileName {
+    FileName::Real("/project/src/pages/index.ts".into())
+}
+
+fn ts_config() -> TsConfigResolver {
+    TsConfigResolver {
+        base_url: Some("/project".into()),
+        paths: vec![
+            ("@app/*".into(), vec!["src/app/*".into()]),
+            ("@app/config".into(), vec!["config/index.ts".into()]),
+            ("@lib/*".into(), vec!["lib/*".into()]),
+            ("@lib/utils/*".into(), vec!["src/utils/*".into()]),
+        ],
+        rewrite_extensions: true,
+    }
+}
+
+test!(
+    syntax(),
+    |_| import_rewriter(base(), ts_config(), Mark::fresh(Mark::root())),
+    paths,
+    "import a from '@app/a';
+import config from '@app/config';
+export * from '@lib/utils/b';
+export { c } from '@lib/c';
+import d from 'react';",
+    "import a from '../app/a';
+import config from '../../config/index.js';
+export * from '../utils/b';
+export { c } from '../../lib/c';
+import d from 'react';"
+);
+
+test!(
+    syntax(),
+    |_| import_rewriter(base(), ts_config(), Mark::fresh(Mark::root())),
+    extensions,
+    "import a from './a.ts';
+import b from '../b.tsx';
+import c from './c.mts';
+const d = require('./d.cts');
+const e = import('./e.ts');
+import f from './f.d.ts';
+import g from 'g.ts';",
+    "import a from './a.js';
+import b from '../b.js';
+import c from './c.mjs';
+const d = require('./d.cjs');
+const e = import('./e.js');
+import f from './f.d.ts';
+import g from 'g.ts';"
+);
+
+struct Prefix;
+
+impl ImportResolver for Prefix {
+    fn resolve_import(&self, _: &FileName, module_specifier: &str) -> Option<JsWord> {
+        if module_specifier.starts_with('.') {
+            None
+        } else {
+            Some(format!("/vendor/{}", module_specifier).into())
+        }
+    }
+}
+
+fn tr() -> impl Fold {
+    let mark = Mark::fresh(Mark::root());
+
+    chain!(
+        resolver_with_mark(mark),
+        import_rewriter(base(), Prefix, mark),
+        common_js(mark, Default::default())
+    )
+}
+
+test!(
+    syntax(),
+    |_| tr(),
+    custom_resolver_common_js,
+    "import './a';
+import 'b';
+export * from 'c';",
+    "'use strict';
+Object.defineProperty(exports, '__esModule', {
+    value: true
+});
+require('./a');
+require('/vendor/b');
+var _c = require('/vendor/c');
+Object.keys(_c).forEach(function(key) {
+    if (key === 'default' || key === '__esModule') return;
+    Object.defineProperty(exports, key, {
+        enumerable: true,
+        get: function() {
+            return _c[key];
+        }
+    });
+});"
+);
+
+test!(
+    syntax(),
+    |_| {
+        let mark = Mark::fresh(Mark::root());
+        chain!(
+            resolver_with_mark(mark),
+            import_rewriter(base(), Prefix, mark)
+        )
+    },
+    custom_resolver_shadowed_require,
+    "const a = require('a');
+function load(require) {
+    return require('b');
+}",
+    "const a = require('/vendor/a');
+function load(require) {
+    return require('b');
+}"
+);
+
+test!(
+    syntax(),
+    |_| import_rewriter(
+        base(),
+        TsConfigResolver {
+            base_url: Some("/project".into()),
+            paths: vec![
+                ("@x/*".into(), vec!["first/*".into()]),
+                ("@x/*.js".into(), vec!["second/*.js".into()]),
+            ],
+            rewrite_extensions: false,
+        },
+        Mark::fresh(Mark::root())
+    ),
+    paths_first_pattern_wins,
+    "import a from '@x/a.js';",
+    "import a from '../../first/a.js';"
+);
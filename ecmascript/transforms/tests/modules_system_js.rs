@@ -0,0 +1,187 @@
+#![feature(test)]
+use swc_common::chain;
+use swc_ecma_parser::{EsConfig, Syntax};
+use swc_ecma_transforms::{
+    modules::system_js::{system_js, Config},
+    resolver,
+};
+use swc_ecma_visit::Fold;
+
+#[macro_use]
+mod common;
+
+fn syntax() -> Syntax {
+    Syntax::Es(EsConfig {
+        dynamic_import: true,
+        import_meta: true,
+        top_level_await: true,
+        ..Default::default()
+    })
+}
+
+fn tr(config: Config) -> impl Fold {
+    chain!(resolver(), system_js(config))
+}
+
+test!(
+    syntax(),
+    |_| tr(Default::default()),
+    imports,
+    "import foo, { bar as baz } from 'foo';
+import * as ns from 'ns';
+import 'side-effect';
+
+export var a = 1;
+export function f() {
+    return a;
+}
+a = 2;
+export default foo(baz, ns);",
+    "System.register(['foo', 'ns', 'side-effect'], function(_export, _context) {
+    'use strict';
+    var foo, baz, ns, a;
+    function f() {
+        return a;
+    }
+    _export('f', f);
+    return {
+        setters: [function(_foo) {
+            foo = _foo.default;
+            baz = _foo.bar;
+        }, function(_ns) {
+            ns = _ns;
+        }, function() {}],
+        execute: function() {
+            _export('a', a = 1);
+            _export('a', a = 2);
+            _export('default', foo(baz, ns));
+        }
+    };
+});"
+);
+
+test!(
+    syntax(),
+    |_| tr(Default::default()),
+    reexports,
+    "export * from 'foo';
+export { a as b, default as c } from 'bar';
+import { d } from 'baz';
+export { d as e };",
+    "System.register(['foo', 'bar', 'baz'], function(_export, _context) {
+    'use strict';
+    var d;
+    return {
+        setters: [function(_foo) {
+            var _exportObj = {};
+            for (var _key in _foo)
+                if (_key !== 'default' && _key !== '__esModule') _exportObj[_key] = _foo[_key];
+            _export(_exportObj);
+        }, function(_bar) {
+            _export('b', _bar.a);
+            _export('c', _bar.default);
+        }, function(_baz) {
+            d = _baz.d;
+            _export('e', d);
+        }],
+        execute: function() {}
+    };
+});"
+);
+
+test!(
+    syntax(),
+    |_| tr(Default::default()),
+    live_binding,
+    "export let count = 0;
+export function inc() {
+    count++;
+    ++count;
+    count += 2;
+    [count] = [1];
+}
+export { count as total };",
+    "System.register([], function(_export, _context) {
+    'use strict';
+    var count, _tmp;
+    function inc() {
+        _tmp = count++, _export('total', _export('count', count)), _tmp;
+        _export('total', _export('count', ++count));
+        _export('total', _export('count', count += 2));
+        _tmp = [count] = [1], _export('total', _export('count', count)), _tmp;
+    }
+    _export('inc', inc);
+    return {
+        setters: [],
+        execute: function() {
+            _export('total', _export('count', count = 0));
+        }
+    };
+});"
+);
+
+test!(
+    syntax(),
+    |_| tr(Default::default()),
+    hoisting,
+    "class A {}
+export default class {}
+for (var i = 0; i < 1; i++) {
+    var j = i;
+}
+const { k } = A;",
+    "System.register([], function(_export, _context) {
+    'use strict';
+    var A, _default, k, i, j;
+    return {
+        setters: [],
+        execute: function() {
+            A = class A {
+            };
+            _export('default', _default = class _default {
+            });
+            for(i = 0; i < 1; i++){
+                j = i;
+            }
+            ({ k  } = A);
+        }
+    };
+});"
+);
+
+test!(
+    syntax(),
+    |_| tr(Default::default()),
+    context,
+    "const url = import.meta.url;
+await import('./foo');",
+    "System.register([], function(_export, _context) {
+    'use strict';
+    var url;
+    return {
+        setters: [],
+        execute: async function() {
+            url = _context.meta.url;
+            await _context.import('./foo');
+        }
+    };
+});"
+);
+
+test!(
+    syntax(),
+    |_| tr(Config {
+        module_id: Some(String::from("mod")),
+        strict_mode: false,
+    }),
+    module_id,
+    "export default 1;",
+    "System.register('mod', [], function(_export, _context) {
+    return {
+        setters: [],
+        execute: function() {
+            _export('default', 1);
+        }
+    };
+});"
+);
@@ -4,6 +4,7 @@ use swc_ecma_transforms::{
     optimization::simplify::dce::{self, dce},
     resolver,
 };
+use swc_ecma_visit::FoldWith;
 
 #[macro_use]
 mod common;
@@ -455,3 +456,23 @@ class A {}
 console.log(new A());
 "
 );
+
+#[test]
+fn pure_annotation() {
+    common::test_transform_with_comments(
+        Default::default(),
+        |t, m| {
+            m.fold_with(&mut chain!(
+                resolver(),
+                dce(dce::Config {
+                    comments: Some(&t.comments),
+                    ..Default::default()
+                })
+            ))
+        },
+        "/*#__PURE__*/ foo();
+var a = /*#__PURE__*/ bar();
+/*#__PURE__*/ baz(qux());",
+        "/*#__PURE__*/ baz(qux());",
+    );
+}
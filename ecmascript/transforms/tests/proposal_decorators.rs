@@ -30,7 +30,7 @@ fn syntax(decorators_before_export: bool) -> Syntax {
 }
 
 fn tr() -> impl Fold {
-    chain!(decorators(Default::default()), class_properties(),)
+    chain!(decorators(Default::default()), class_properties(Default::default()),)
 }
 
 fn ts_transform() -> impl Fold {
@@ -40,13 +40,13 @@ fn ts_transform() -> impl Fold {
             legacy: true,
             ..Default::default()
         }),
-        class_properties(),
+        class_properties(Default::default()),
     )
 }
 
 /// Folder for `transformation_*` tests
 fn transformation() -> impl Fold {
-    chain!(strip(), decorators(Default::default()), class_properties(),)
+    chain!(strip(), decorators(Default::default()), class_properties(Default::default()),)
 }
 
 // transformation_declaration
@@ -2053,7 +2053,7 @@ test_exec!(
             legacy: true,
             ..Default::default()
         }),
-        class_properties(),
+        class_properties(Default::default()),
     ),
     legacy_class_constructors_return_new_constructor_exec,
     r#"
@@ -2513,7 +2513,7 @@ test_exec!(
             legacy: true,
             ..Default::default()
         }),
-        class_properties(),
+        class_properties(Default::default()),
     ),
     legacy_class_prototype_methods_numeric_props_exec,
     r#"
@@ -2539,7 +2539,7 @@ test_exec!(
             legacy: true,
             ..Default::default()
         }),
-        class_properties(),
+        class_properties(Default::default()),
     ),
     legacy_class_static_properties_mutate_descriptor_exec,
     r#"
@@ -2654,7 +2654,7 @@ test_exec!(
             legacy: true,
             ..Default::default()
         }),
-        class_properties(),
+        class_properties(Default::default()),
     ),
     legacy_class_static_methods_string_props_exec,
     r#"
@@ -2680,7 +2680,7 @@ test_exec!(
             legacy: true,
             ..Default::default()
         }),
-        class_properties(),
+        class_properties(Default::default()),
     ),
     legacy_class_prototype_properties_string_literal_properties_exec,
     r#"
@@ -2725,7 +2725,7 @@ test_exec!(
             legacy: true,
             ..Default::default()
         }),
-        class_properties(),
+        class_properties(Default::default()),
     ),
     legacy_class_prototype_methods_mutate_descriptor_exec,
     r#"
@@ -2858,7 +2858,7 @@ test_exec!(
             legacy: true,
             ..Default::default()
         }),
-        class_properties(),
+        class_properties(Default::default()),
     ),
     legacy_object_properties_numeric_props_exec,
     r#"
@@ -2915,7 +2915,7 @@ test_exec!(
             legacy: true,
             ..Default::default()
         }),
-        class_properties(),
+        class_properties(Default::default()),
     ),
     legacy_class_prototype_properties_return_descriptor_exec,
     r#"
@@ -3032,7 +3032,7 @@ test_exec!(
             legacy: true,
             ..Default::default()
         }),
-        class_properties(),
+        class_properties(Default::default()),
     ),
     legacy_object_properties_string_props_exec,
     r#"
@@ -3060,7 +3060,7 @@ test_exec!(
             legacy: true,
             ..Default::default()
         }),
-        class_properties(),
+        class_properties(Default::default()),
     ),
     legacy_object_properties_return_descriptor_exec,
     r#"
@@ -3173,7 +3173,7 @@ test_exec!(
             legacy: true,
             ..Default::default()
         }),
-        class_properties(),
+        class_properties(Default::default()),
     ),
     legacy_class_prototype_methods_string_props_exec,
     r#"
@@ -3199,7 +3199,7 @@ test!(
             legacy: true,
             ..Default::default()
         }),
-        class_properties(),
+        class_properties(Default::default()),
     ),
     legacy_regression_8041,
     r#"
@@ -3230,7 +3230,7 @@ test_exec!(
             legacy: true,
             ..Default::default()
         }),
-        class_properties(),
+        class_properties(Default::default()),
     ),
     legacy_class_prototype_methods_return_descriptor_exec,
     r#"
@@ -3365,7 +3365,7 @@ test_exec!(
             legacy: true,
             ..Default::default()
         }),
-        class_properties(),
+        class_properties(Default::default()),
     ),
     legacy_object_ordering_reverse_order_exec,
     r#"
@@ -3409,7 +3409,7 @@ test_exec!(
             legacy: true,
             ..Default::default()
         }),
-        class_properties(),
+        class_properties(Default::default()),
     ),
     legacy_object_methods_numeric_props_exec,
     r#"
@@ -3436,7 +3436,7 @@ test_exec!(
             legacy: true,
             ..Default::default()
         }),
-        class_properties(),
+        class_properties(Default::default()),
     ),
     legacy_class_static_properties_return_descriptor_exec,
     r#"
@@ -3556,7 +3556,7 @@ test_exec!(
             legacy: true,
             ..Default::default()
         }),
-        class_properties(),
+        class_properties(Default::default()),
     ),
     legacy_class_export_default_exec,
     r#"
@@ -3586,7 +3586,7 @@ test_exec!(
             legacy: true,
             ..Default::default()
         }),
-        class_properties(),
+        class_properties(Default::default()),
     ),
     legacy_class_ordering_reverse_order_exec,
     r#"
@@ -3633,7 +3633,7 @@ test_exec!(
             legacy: true,
             ..Default::default()
         }),
-        class_properties(),
+        class_properties(Default::default()),
     ),
     legacy_object_methods_mutate_descriptor_exec,
     r#"
@@ -3762,7 +3762,7 @@ test_exec!(
             legacy: true,
             ..Default::default()
         }),
-        class_properties(),
+        class_properties(Default::default()),
     ),
     legacy_class_static_methods_return_descriptor_exec,
     r#"
@@ -3894,7 +3894,7 @@ test_exec!(
             legacy: true,
             ..Default::default()
         }),
-        class_properties(),
+        class_properties(Default::default()),
     ),
     legacy_object_methods_return_descriptor_exec,
     r#"
@@ -4025,7 +4025,7 @@ test_exec!(
             legacy: true,
             ..Default::default()
         }),
-        class_properties(),
+        class_properties(Default::default()),
     ),
     legacy_object_methods_string_props_exec,
     r#"
@@ -4053,7 +4053,7 @@ test_exec!(
             legacy: true,
             ..Default::default()
         }),
-        class_properties(),
+        class_properties(Default::default()),
     ),
     legacy_class_prototype_properties_child_classes_properties_exec,
     r#"
@@ -4096,7 +4096,7 @@ test_exec!(
             legacy: true,
             ..Default::default()
         }),
-        class_properties(),
+        class_properties(Default::default()),
     ),
     legacy_class_static_methods_mutate_descriptor_exec,
     r#"
@@ -4362,8 +4362,8 @@ test!(
             legacy: true,
             ..Default::default()
         }),
-        class_properties(),
-        // classes(),
+        class_properties(Default::default()),
+        // classes(None, Default::default()),
     ),
     issue_823_2,
     "import {Debounce} from 'lodash-decorators';
@@ -4408,8 +4408,8 @@ test!(
             legacy: true,
             ..Default::default()
         }),
-        class_properties(),
-        classes(),
+        class_properties(Default::default()),
+        classes(None, Default::default()),
     ),
     issue_823_3,
     "import {Debounce} from 'lodash-decorators';
@@ -4671,7 +4671,7 @@ test!(
             legacy: true,
             ..Default::default()
         }),
-        class_properties(),
+        class_properties(Default::default()),
     ),
     issue_879_1,
     "export default class X {
@@ -709,7 +709,7 @@ to!(
 
 test!(
     ::swc_ecma_parser::Syntax::Typescript(Default::default()),
-    |_| chain!(typescript_class_properties(), tr()),
+    |_| chain!(typescript_class_properties(Default::default()), tr()),
     issue_930_instance,
     "class A {
         b = this.a;
@@ -726,7 +726,7 @@ test!(
 
 test!(
     ::swc_ecma_parser::Syntax::Typescript(Default::default()),
-    |_| chain!(typescript_class_properties(), tr()),
+    |_| chain!(typescript_class_properties(Default::default()), tr()),
     issue_930_static,
     "class A {
         static b = 'foo';
@@ -742,7 +742,7 @@ test!(
 
 test!(
     ::swc_ecma_parser::Syntax::Typescript(Default::default()),
-    |_| chain!(typescript_class_properties(), tr()),
+    |_| chain!(typescript_class_properties(Default::default()), tr()),
     typescript_001,
     "class A {
         foo = new Subject()
@@ -761,7 +761,7 @@ test!(
 
 test!(
     ::swc_ecma_parser::Syntax::Typescript(Default::default()),
-    |_| chain!(typescript_class_properties(), tr()),
+    |_| chain!(typescript_class_properties(Default::default()), tr()),
     typescript_002,
     "class A extends B {
             foo = 'foo'
@@ -790,7 +790,7 @@ test!(
 
 test!(
     ::swc_ecma_parser::Syntax::Typescript(Default::default()),
-    |_| chain!(typescript_class_properties(), tr()),
+    |_| chain!(typescript_class_properties(Default::default()), tr()),
     issue_958,
     "export class Test {
         constructor(readonly test?: string) {}
@@ -1,18 +1,22 @@
 use crate::JsCompiler;
-use anyhow::{bail, Error};
+use anyhow::{bail, Context, Error};
 use fxhash::FxHashMap;
 use neon::prelude::*;
 use serde::Deserialize;
-use spack::resolvers::NodeResolver;
+use spack::{config::Mode, loaders::rule::RuleLoader, resolvers::NodeResolver};
 use std::{
+    env, fs,
     panic::{catch_unwind, AssertUnwindSafe},
     sync::Arc,
 };
-use swc::{config::SourceMapsConfig, Compiler, TransformOutput};
-use swc_bundler::{BundleKind, Bundler, Load, Resolve};
+use swc::{
+    config::{JscTarget, SourceMapsConfig},
+    Compiler, TransformOutput,
+};
+use swc_bundler::{BundleKind, Bundler, HmrConfig, Load, Resolve};
 
 struct ConfigItem {
-    loader: Box<dyn Load>,
+    loader: RuleLoader<Box<dyn Load>>,
     resolver: Box<dyn Resolve>,
     static_items: StaticConfigItem,
 }
@@ -38,6 +42,43 @@ impl Task for BundleTask {
 
     fn perform(&self) -> Result<Self::Output, Self::Error> {
         let res = catch_unwind(AssertUnwindSafe(|| {
+            let output = self.config.static_items.config.output.as_ref();
+            let format = output
+                .map(|output| output.bundler_format())
+                .transpose()?
+                .unwrap_or_default();
+            let external_globals = output
+                .map(|output| {
+                    output
+                        .globals
+                        .iter()
+                        .map(|(k, v)| (k.as_str().into(), v.as_str().into()))
+                        .collect()
+                })
+                .unwrap_or_default();
+            let hmr = match self.config.static_items.config.mode {
+                Mode::Debug => {
+                    // Modules changed since the previous build are emitted as an update chunk.
+                    let previous = output
+                        .map(|output| output.path.join(&output.hmr_manifest))
+                        .filter(|path| path.exists())
+                        .map(|path| -> Result<_, Error> {
+                            let manifest = fs::read(&path)
+                                .with_context(|| format!("failed to read `{}`", path.display()))?;
+                            Ok(serde_json::from_slice(&manifest)?)
+                        })
+                        .transpose()?;
+                    let root = &self.config.static_items.config.working_dir;
+                    let root = if root.as_os_str().is_empty() {
+                        env::current_dir()?
+                    } else {
+                        root.clone()
+                    };
+                    Some(HmrConfig { previous, root })
+                }
+                _ => None,
+            };
+
             let bundler = Bundler::new(
                 self.swc.globals(),
                 self.swc.cm.clone(),
@@ -83,10 +124,47 @@ impl Task for BundleTask {
                     .into_iter()
                     .map(From::from)
                     .collect(),
+                    format,
+                    external_globals,
+                    federation: self
+                        .config
+                        .static_items
+                        .config
+                        .federation
+                        .as_ref()
+                        .map(|federation| federation.bundler_config()),
+                    hmr,
+                    ..Default::default()
                 },
             );
 
-            let result = bundler.bundle(self.config.static_items.config.entry.clone().into())?;
+            // Diagnostics like circular dependencies are reported to the handler.
+            let result = self.swc.run_transform(false, || {
+                bundler.bundle(self.config.static_items.config.entry.clone().into())
+            })?;
+
+            if let Some(output) = &self.config.static_items.config.output {
+                for (name, content) in self.config.loader.assets().emitted_assets() {
+                    let path = output.path.join(&name);
+                    fs::write(&path, content)
+                        .with_context(|| format!("failed to emit `{}`", path.display()))?;
+                }
+
+                if let Some(manifest) = bundler.hmr_manifest() {
+                    let path = output.path.join(&output.hmr_manifest);
+                    fs::write(&path, serde_json::to_vec(&manifest)?).with_context(|| {
+                        format!("failed to write hmr manifest to `{}`", path.display())
+                    })?;
+                }
+
+                if let Some(name) = &output.stats {
+                    let path = output.path.join(name);
+                    let stats = serde_json::to_vec_pretty(&bundler.stats(&result))?;
+                    fs::write(&path, stats).with_context(|| {
+                        format!("failed to write stats to `{}`", path.display())
+                    })?;
+                }
+            }
 
             let result = result
                 .into_iter()
@@ -114,9 +192,13 @@ impl Task for BundleTask {
                             })
                             .unwrap_or(false);
 
-                        let output =
-                            self.swc
-                                .print(&m, SourceMapsConfig::Bool(true), None, minify)?;
+                        let output = self.swc.print(
+                            &m,
+                            SourceMapsConfig::Bool(true),
+                            None,
+                            minify,
+                            JscTarget::Es2020,
+                        )?;
 
                         Ok((k, output))
                     })
@@ -191,6 +273,18 @@ pub(crate) fn bundle(mut cx: MethodContext<JsCompiler>) -> JsResult<JsValue> {
             ))
         });
 
+    let loader = RuleLoader::new(
+        c.clone(),
+        static_items.config.module.clone(),
+        static_items
+            .config
+            .output
+            .as_ref()
+            .map(|output| output.public_path.clone())
+            .unwrap_or_default(),
+        loader,
+    );
+
     BundleTask {
         swc: c.clone(),
         config: ConfigItem {
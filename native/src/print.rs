@@ -3,7 +3,7 @@ use anyhow::Error;
 use neon::prelude::*;
 use std::sync::Arc;
 use swc::{
-    config::{Options, SourceMapsConfig},
+    config::{JscTarget, Options, SourceMapsConfig},
     Compiler, TransformOutput,
 };
 use swc_ecma_ast::Program;
@@ -35,6 +35,7 @@ impl Task for PrintTask {
                     .unwrap_or_default()
                     .minify
                     .unwrap_or(false),
+                JscTarget::Es2020,
             )
         })
     }
@@ -99,6 +100,7 @@ pub fn print_sync(mut cx: MethodContext<JsCompiler>) -> JsResult<JsValue> {
                     .unwrap_or(SourceMapsConfig::Bool(false)),
                 None,
                 options.config.unwrap_or_default().minify.unwrap_or(false),
+                JscTarget::Es2020,
             )
         };
         complete_output(cx, result)
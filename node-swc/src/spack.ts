@@ -68,6 +68,11 @@ export interface SpackConfig {
     module: ModuleConfig
 
     options?: Options
+
+    /**
+     * Requires `esm` output.
+     */
+    federation?: FederationConfig
 }
 
 export interface OutputConfig {
@@ -80,6 +85,38 @@ export interface ModuleConfig {
 
 }
 
+export interface FederationConfig {
+    name: string
+
+    /**
+     * @default 'remoteEntry.js'
+     */
+    filename?: string
+
+    /**
+     * Public names like `./Button`, mapped to paths.
+     */
+    exposes?: { [name: string]: string }
+
+    shared?: { [name: string]: SharedConfig }
+
+    /**
+     * @default 'default'
+     */
+    shareScope?: string
+}
+
+export interface SharedConfig {
+    /**
+     * Version provided by this build. If omitted, the package is not bundled.
+     */
+    version?: string
+
+    requiredVersion?: string
+
+    singleton?: boolean
+}
+
 export type Mode = 'production' | 'development' | 'none';
 
 export type EntryConfig = string | string[] | {
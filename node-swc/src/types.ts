@@ -219,6 +219,11 @@ export interface EnvConfig {
 
   loose?: boolean;
 
+  /**
+   * Assumptions about the input code. `loose` enables some of them.
+   */
+  assumptions?: Assumptions;
+
   /// Skipped es features.
   ///
   /// e.g.)
@@ -239,15 +244,63 @@ export interface EnvConfig {
 
   shippedProposals?: boolean;
 
+  /**
+   * Polyfill all proposals supported by core-js 3, not only the shipped ones.
+   */
+  proposals?: boolean;
+
   /**
    * Enable all trnasforms
    */
   forceAllTransforms?: boolean;
 }
 
+/**
+ * Assumptions which allow smaller output.
+ *
+ * See https://babeljs.io/docs/en/assumptions
+ */
+export interface Assumptions {
+  constantSuper?: boolean;
+  ignoreFunctionLength?: boolean;
+  iterableIsArray?: boolean;
+  noClassCalls?: boolean;
+  noDocumentAll?: boolean;
+  privateFieldsAsProperties?: boolean;
+  pureGetters?: boolean;
+  setPublicClassFields?: boolean;
+  setSpreadProperties?: boolean;
+}
+
+export interface HelpersConfig {
+  /**
+   * Defaults to `@swc/helpers`.
+   */
+  module?: string;
+
+  /**
+   * - `namespace`: `import * as swcHelpers from '@swc/helpers'`
+   * - `named`: `import { extends as _extends } from '@swc/helpers'`
+   * - `perFile`: `import _extends from '@swc/helpers/_extends'`
+   *
+   * Defaults to `namespace`.
+   */
+  importStyle?: "namespace" | "named" | "perFile";
+
+  /**
+   * Use `require` instead of imports.
+   */
+  require?: boolean;
+}
+
 export interface JscConfig {
   loose?: boolean;
 
+  /**
+   * Assumptions about the input code. `loose` enables some of them.
+   */
+  assumptions?: Assumptions;
+
   /**
    * Defaults to EsParserConfig
    */
@@ -258,10 +311,38 @@ export interface JscConfig {
    */
   externalHelpers?: boolean;
 
+  /**
+   * Configures how external helpers are imported. Helpers are external if
+   * this is set.
+   */
+  helpers?: HelpersConfig;
+
   /**
    * Defaults to `es3` (which enableds **all** pass).
+   *
+   * If set, syntax which is not supported by the target is an error while
+   * printing.
    */
   target?: JscTarget;
+
+  /**
+   * Like `compilerOptions.baseUrl` of tsconfig. Relative to `cwd`.
+   */
+  baseUrl?: string;
+
+  /**
+   * Like `compilerOptions.paths` of tsconfig. Module specifiers matching a pattern are
+   * rewritten to relative specifiers. Targets are relative to `baseUrl`, which is required.
+   */
+  paths?: { [pattern: string]: string[] };
+
+  /**
+   * Rewrites `.ts`, `.tsx`, `.mts` and `.cts` extensions of relative module specifiers
+   * to `.js`, `.js`, `.mjs` and `.cjs`.
+   *
+   * Defaults to `false`.
+   */
+  rewriteRelativeImportExtensions?: boolean;
 }
 
 export type JscTarget =
@@ -273,7 +354,7 @@ export type JscTarget =
   | "es2018"
   | "es2019";
 
-export type ParserConfig = TsParserConfig | EsParserConfig;
+export type ParserConfig = TsParserConfig | EsParserConfig | FlowParserConfig;
 export interface TsParserConfig {
   syntax: "typescript";
   /**
@@ -290,6 +371,22 @@ export interface TsParserConfig {
   dynamicImport?: boolean;
 }
 
+export interface FlowParserConfig {
+  syntax: "flow";
+  /**
+   * Defaults to `false`.
+   */
+  jsx?: boolean;
+  /**
+   * Defaults to `false`.
+   */
+  decorators?: boolean;
+  /**
+   * Defaults to `false`
+   */
+  dynamicImport?: boolean;
+}
+
 export interface EsParserConfig {
   syntax: "ecmascript";
   /**
@@ -423,7 +520,11 @@ export interface GlobalPassOption {
   envs?: string[];
 }
 
-export type ModuleConfig = CommonJsConfig | UmdConfig | AmdConfig;
+export type ModuleConfig =
+  | CommonJsConfig
+  | UmdConfig
+  | AmdConfig
+  | SystemJsConfig;
 
 export interface BaseModuleConfig {
   /**
@@ -491,6 +592,30 @@ export interface BaseModuleConfig {
    * Defaults to `false`.
    */
   noInterop?: boolean;
+  /**
+   * How the default export of an imported commonjs module is resolved.
+   *
+   *  - `"babel"`: `exports.default` if the module has `__esModule`, and `module.exports` otherwise.
+   *  - `"node"`: always `module.exports`, like node does when importing commonjs from esm.
+   *  - `"none"`: always `exports.default`. Same as `noInterop`.
+   *
+   * Defaults to `"babel"`.
+   */
+  importInterop?: "babel" | "node" | "none";
+  /**
+   * Keeps `import()` as is instead of converting it to `require()`.
+   * Only the commonjs module converts `import()`, and only if this is `false`.
+   *
+   * Defaults to `true`.
+   */
+  ignoreDynamic?: boolean;
+  /**
+   * Emits `0 && (module.exports = { foo: foo })` so that node can detect named exports
+   * when the module is imported from esm.
+   *
+   * Defaults to `false`.
+   */
+  exportInteropAnnotation?: boolean;
 }
 
 export interface CommonJsConfig extends BaseModuleConfig {
@@ -507,6 +632,20 @@ export interface AmdConfig extends BaseModuleConfig {
   moduleId: string;
 }
 
+export interface SystemJsConfig {
+  type: "systemjs";
+  /**
+   * Name passed to `System.register`.
+   */
+  moduleId?: string;
+  /**
+   * Emits 'use strict' directive.
+   *
+   * Defaults to `true`.
+   */
+  strictMode?: boolean;
+}
+
 export interface Output {
   /**
    * Transformed code
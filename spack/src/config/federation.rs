@@ -0,0 +1,77 @@
+use serde::Deserialize;
+use std::{collections::HashMap, path::PathBuf};
+use swc_atoms::JsWord;
+use swc_common::FileName;
+
+/// https://webpack.js.org/concepts/module-federation/
+#[derive(Debug, Deserialize)]
+#[serde(rename = "Federation", rename_all = "camelCase")]
+pub struct FederationConfig {
+    pub name: JsWord,
+
+    /// Name of the container entry.
+    #[serde(default = "default_filename")]
+    pub filename: String,
+
+    /// Public names of modules, like `./Button`, mapped to their paths.
+    #[serde(default)]
+    pub exposes: HashMap<JsWord, PathBuf>,
+
+    #[serde(default)]
+    pub shared: HashMap<JsWord, SharedConfig>,
+
+    #[serde(default = "default_share_scope")]
+    pub share_scope: JsWord,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename = "Federation.Shared", rename_all = "camelCase")]
+pub struct SharedConfig {
+    /// Version of the package provided by this build. If omitted, the package
+    /// is not bundled.
+    #[serde(default)]
+    pub version: Option<JsWord>,
+
+    #[serde(default)]
+    pub required_version: Option<JsWord>,
+
+    #[serde(default)]
+    pub singleton: bool,
+}
+
+fn default_filename() -> String {
+    "remoteEntry.js".into()
+}
+
+fn default_share_scope() -> JsWord {
+    "default".into()
+}
+
+impl FederationConfig {
+    pub fn bundler_config(&self) -> swc_bundler::FederationConfig {
+        swc_bundler::FederationConfig {
+            name: self.name.clone(),
+            filename: self.filename.clone(),
+            exposes: self
+                .exposes
+                .iter()
+                .map(|(name, path)| (name.clone(), FileName::Real(path.clone())))
+                .collect(),
+            shared: self
+                .shared
+                .iter()
+                .map(|(src, shared)| {
+                    (
+                        src.clone(),
+                        swc_bundler::SharedConfig {
+                            version: shared.version.clone(),
+                            required_version: shared.required_version.clone(),
+                            singleton: shared.singleton,
+                        },
+                    )
+                })
+                .collect(),
+            share_scope: self.share_scope.clone(),
+        }
+    }
+}
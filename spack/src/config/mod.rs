@@ -1,7 +1,8 @@
 pub use self::{
-    module::ModuleConfig,
+    federation::{FederationConfig, SharedConfig},
+    module::{LoaderKind, ModuleConfig, RuleConfig},
     optimization::OptimizationConfig,
-    output::OutputConfig,
+    output::{OutputConfig, OutputFormat},
     resolve::{AliasConfig, ResolveConfig},
 };
 use serde::Deserialize;
@@ -9,6 +10,7 @@ use std::{collections::HashMap, fmt, marker::PhantomData, path::PathBuf};
 use string_enum::StringEnum;
 use swc_common::FileName;
 
+mod federation;
 mod module;
 mod optimization;
 mod output;
@@ -38,6 +40,10 @@ pub struct Config {
 
     #[serde(default)]
     pub options: Option<swc::config::Options>,
+
+    /// Module federation. Requires the `esm` output format.
+    #[serde(default)]
+    pub federation: Option<FederationConfig>,
 }
 
 #[derive(StringEnum)]
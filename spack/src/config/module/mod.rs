@@ -1,5 +1,79 @@
+use anyhow::Error;
 use serde::Deserialize;
+use std::path::Path;
+use string_enum::StringEnum;
+use swc::config::FileMatcher;
 
-#[derive(Debug, Default, Deserialize)]
+/// https://webpack.js.org/configuration/module/
+#[derive(Debug, Default, Clone, Deserialize)]
 #[serde(rename = "Module", rename_all = "camelCase")]
-pub struct ModuleConfig {}
+pub struct ModuleConfig {
+    /// Rules are checked in order and the first matching rule wins.
+    #[serde(default)]
+    pub rules: Vec<RuleConfig>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename = "Module.Rule", rename_all = "camelCase")]
+pub struct RuleConfig {
+    #[serde(default)]
+    pub test: Option<FileMatcher>,
+
+    /// Extensions including the leading dot, like `.txt`.
+    #[serde(default)]
+    pub extensions: Vec<String>,
+
+    #[serde(rename = "type")]
+    pub kind: LoaderKind,
+}
+
+#[derive(StringEnum, Clone, Copy, Eq, PartialEq)]
+pub enum LoaderKind {
+    /// `javascript`
+    JavaScript,
+    /// `json`
+    Json,
+    /// `text`
+    Text,
+    /// `asset`
+    Asset,
+}
+
+impl RuleConfig {
+    pub fn matches(&self, path: &Path) -> Result<bool, Error> {
+        if let Some(ext) = path.extension() {
+            let ext = ext.to_string_lossy();
+            if self
+                .extensions
+                .iter()
+                .any(|e| e.trim_start_matches('.') == &*ext)
+            {
+                return Ok(true);
+            }
+        }
+
+        match self.test {
+            Some(ref test) => test.matches(path),
+            None => Ok(false),
+        }
+    }
+}
+
+impl ModuleConfig {
+    /// Selects a loader for `path`.
+    ///
+    /// Files not matched by any rule are loaded as json if the extension is
+    /// `.json`, and as javascript otherwise.
+    pub fn loader_for(&self, path: &Path) -> Result<LoaderKind, Error> {
+        for rule in &self.rules {
+            if rule.matches(path)? {
+                return Ok(rule.kind);
+            }
+        }
+
+        match path.extension() {
+            Some(ext) if ext == "json" => Ok(LoaderKind::Json),
+            _ => Ok(LoaderKind::JavaScript),
+        }
+    }
+}
@@ -1,11 +1,81 @@
+use anyhow::{bail, Error};
 use serde::Deserialize;
-use std::path::PathBuf;
+use std::{collections::HashMap, path::PathBuf};
+use string_enum::StringEnum;
 
 #[derive(Debug, Deserialize)]
-#[serde(rename = "Output")]
+#[serde(rename = "Output", rename_all = "camelCase")]
 pub struct OutputConfig {
     pub path: PathBuf,
 
     #[serde(default)]
     pub name: String,
+
+    /// Prefix of urls exported by asset modules.
+    #[serde(default)]
+    pub public_path: String,
+
+    #[serde(default)]
+    pub format: OutputFormat,
+
+    /// Name of the global variable for exports of the entry.
+    ///
+    /// Required for `umd`, and optional for `iife`.
+    #[serde(default)]
+    pub global_name: Option<String>,
+
+    /// Global variables for external modules, used by `iife` and `umd`.
+    #[serde(default)]
+    pub globals: HashMap<String, String>,
+
+    /// If set, statistics of bundles are written to a json file with this
+    /// name. The format is compatible with `webpack --json`.
+    #[serde(default)]
+    pub stats: Option<String>,
+
+    /// Name of the json file storing hashes of modules in `debug` mode. The
+    /// next build reads it to emit an update chunk for hot module
+    /// replacement.
+    #[serde(default = "default_hmr_manifest")]
+    pub hmr_manifest: String,
+}
+
+fn default_hmr_manifest() -> String {
+    "hmr-manifest.json".into()
+}
+
+#[derive(StringEnum, Clone, Copy, Eq, PartialEq)]
+pub enum OutputFormat {
+    /// `esm`
+    Esm,
+    /// `cjs`
+    CommonJs,
+    /// `iife`
+    Iife,
+    /// `umd`
+    Umd,
+}
+
+impl Default for OutputFormat {
+    fn default() -> Self {
+        OutputFormat::Esm
+    }
+}
+
+impl OutputConfig {
+    pub fn bundler_format(&self) -> Result<swc_bundler::OutputFormat, Error> {
+        Ok(match self.format {
+            OutputFormat::Esm => swc_bundler::OutputFormat::Es,
+            OutputFormat::CommonJs => swc_bundler::OutputFormat::CommonJs,
+            OutputFormat::Iife => swc_bundler::OutputFormat::Iife {
+                global_name: self.global_name.as_deref().map(From::from),
+            },
+            OutputFormat::Umd => match &self.global_name {
+                Some(name) => swc_bundler::OutputFormat::Umd {
+                    global_name: name.as_str().into(),
+                },
+                None => bail!("`output.globalName` is required for `umd`"),
+            },
+        })
+    }
 }
@@ -0,0 +1,82 @@
+use super::text::export_default_str;
+use anyhow::{bail, Context, Error};
+use dashmap::DashMap;
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    fs,
+    hash::{Hash, Hasher},
+    sync::Arc,
+};
+use swc_bundler::Load;
+use swc_common::{FileName, SourceFile};
+use swc_ecma_ast::Module;
+
+/// Loads a binary file as an es module which exports the url of the file.
+///
+/// The file itself is not written by the loader. Use
+/// [AssetLoader::emitted_assets] to get files which should be written to the
+/// output directory.
+pub struct AssetLoader {
+    compiler: Arc<swc::Compiler>,
+    public_path: String,
+    /// Output file name to content.
+    emitted: DashMap<String, Vec<u8>>,
+}
+
+impl AssetLoader {
+    pub fn new(compiler: Arc<swc::Compiler>, public_path: String) -> Self {
+        AssetLoader {
+            compiler,
+            public_path,
+            emitted: Default::default(),
+        }
+    }
+
+    /// Returns files emitted so far, keyed by file name relative to the output
+    /// directory.
+    pub fn emitted_assets(&self) -> HashMap<String, Vec<u8>> {
+        self.emitted
+            .iter()
+            .map(|v| (v.key().clone(), v.value().clone()))
+            .collect()
+    }
+}
+
+impl Load for AssetLoader {
+    fn load(&self, name: &FileName) -> Result<(Arc<SourceFile>, Module), Error> {
+        log::debug!("AssetLoader.load({})", name);
+
+        let path = match name {
+            FileName::Real(v) => v,
+            _ => bail!("asset-loader only accepts path. Got `{}`", name),
+        };
+
+        let content = fs::read(path).with_context(|| format!("failed to load file `{}`", name))?;
+
+        let hash = {
+            let mut hasher = DefaultHasher::new();
+            content.hash(&mut hasher);
+            hasher.finish()
+        };
+
+        let stem = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let file_name = match path.extension() {
+            Some(ext) => format!("{}.{:016x}.{}", stem, hash, ext.to_string_lossy()),
+            None => format!("{}.{:016x}", stem, hash),
+        };
+
+        let url = format!("{}{}", self.public_path, file_name);
+        self.emitted.insert(file_name, content);
+
+        // Binary files cannot be stored in the source map.
+        let fm = self
+            .compiler
+            .cm
+            .new_source_file(name.clone(), String::new());
+
+        Ok((fm, export_default_str(url.into())))
+    }
+}
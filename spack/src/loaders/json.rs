@@ -0,0 +1,175 @@
+use anyhow::{bail, Context, Error};
+use serde_json::Value;
+use std::sync::Arc;
+use swc_atoms::JsWord;
+use swc_bundler::Load;
+use swc_common::{FileName, SourceFile, DUMMY_SP};
+use swc_ecma_ast::*;
+use swc_ecma_utils::is_valid_ident;
+
+/// Loads a json file as an es module.
+///
+/// Top-level keys which are valid identifiers are exported as named exports,
+/// and the whole object is exported as the default export. If a key is
+/// duplicated, the last value is used for both of them.
+pub struct JsonLoader {
+    compiler: Arc<swc::Compiler>,
+}
+
+impl JsonLoader {
+    pub fn new(compiler: Arc<swc::Compiler>) -> Self {
+        JsonLoader { compiler }
+    }
+}
+
+impl Load for JsonLoader {
+    fn load(&self, name: &FileName) -> Result<(Arc<SourceFile>, Module), Error> {
+        log::debug!("JsonLoader.load({})", name);
+
+        let fm = self
+            .compiler
+            .cm
+            .load_file(match name {
+                FileName::Real(v) => &v,
+                _ => bail!("json-loader only accepts path. Got `{}`", name),
+            })
+            .with_context(|| format!("failed to load file `{}`", name))?;
+
+        let value: Value = serde_json::from_str(&fm.src)
+            .with_context(|| format!("failed to parse json file `{}`", name))?;
+
+        Ok((fm, json_to_module(value)))
+    }
+}
+
+fn json_to_module(value: Value) -> Module {
+    let mut body = vec![];
+
+    let default = match value {
+        Value::Object(obj) => {
+            let props = obj
+                .into_iter()
+                .map(|(key, value)| {
+                    let value = json_to_expr(value);
+                    let sym = JsWord::from(key);
+
+                    if !can_export(&sym) {
+                        return PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                            key: PropName::Str(str_lit(sym)),
+                            value,
+                        })));
+                    }
+
+                    let id = Ident::new(sym, DUMMY_SP);
+                    body.push(ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                        span: DUMMY_SP,
+                        decl: Decl::Var(VarDecl {
+                            span: DUMMY_SP,
+                            kind: VarDeclKind::Const,
+                            declare: false,
+                            decls: vec![VarDeclarator {
+                                span: DUMMY_SP,
+                                name: Pat::Ident(id.clone()),
+                                init: Some(value),
+                                definite: false,
+                            }],
+                        }),
+                    })));
+
+                    PropOrSpread::Prop(Box::new(Prop::Shorthand(id)))
+                })
+                .collect();
+
+            Box::new(Expr::Object(ObjectLit {
+                span: DUMMY_SP,
+                props,
+            }))
+        }
+        value => json_to_expr(value),
+    };
+
+    body.push(ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(
+        ExportDefaultExpr {
+            span: DUMMY_SP,
+            expr: default,
+        },
+    )));
+
+    Module {
+        span: DUMMY_SP,
+        body,
+        shebang: None,
+    }
+}
+
+fn json_to_expr(value: Value) -> Box<Expr> {
+    Box::new(match value {
+        Value::Null => Expr::Lit(Lit::Null(Null { span: DUMMY_SP })),
+        Value::Bool(value) => Expr::Lit(Lit::Bool(Bool {
+            span: DUMMY_SP,
+            value,
+        })),
+        Value::Number(n) => {
+            let value = n.as_f64().unwrap_or_default();
+            let lit = Expr::Lit(Lit::Num(Number {
+                span: DUMMY_SP,
+                value: value.abs(),
+            }));
+
+            if value.is_sign_negative() {
+                Expr::Unary(UnaryExpr {
+                    span: DUMMY_SP,
+                    op: op!(unary, "-"),
+                    arg: Box::new(lit),
+                })
+            } else {
+                lit
+            }
+        }
+        Value::String(s) => Expr::Lit(Lit::Str(str_lit(s.into()))),
+        Value::Array(elems) => Expr::Array(ArrayLit {
+            span: DUMMY_SP,
+            elems: elems
+                .into_iter()
+                .map(|elem| {
+                    Some(ExprOrSpread {
+                        spread: None,
+                        expr: json_to_expr(elem),
+                    })
+                })
+                .collect(),
+        }),
+        Value::Object(obj) => Expr::Object(ObjectLit {
+            span: DUMMY_SP,
+            props: obj
+                .into_iter()
+                .map(|(key, value)| {
+                    PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                        key: PropName::Str(str_lit(key.into())),
+                        value: json_to_expr(value),
+                    })))
+                })
+                .collect(),
+        }),
+    })
+}
+
+fn str_lit(value: JsWord) -> Str {
+    Str {
+        span: DUMMY_SP,
+        value,
+        has_escape: false,
+    }
+}
+
+/// Returns true if `sym` can be used as a name of a exported variable.
+fn can_export(sym: &JsWord) -> bool {
+    if !is_valid_ident(sym) || sym.is_reserved_for_es3() {
+        return false;
+    }
+
+    match &**sym {
+        "let" | "yield" | "await" | "arguments" | "eval" => false,
+        _ => true,
+    }
+}
@@ -1,2 +1,6 @@
+pub mod asset;
+pub mod json;
 pub mod neon;
+pub mod rule;
 pub mod swc;
+pub mod text;
@@ -2,7 +2,7 @@ use anyhow::{Context as _, Error};
 use neon::prelude::*;
 use std::sync::{mpsc::channel, Arc};
 use swc_bundler::Load;
-use swc_common::{FileName, SourceFile};
+use swc_common::{comments::Comments, FileName, SourceFile};
 use swc_ecma_ast::{Module, Program};
 
 /// Loader provided by user.
@@ -72,4 +72,8 @@ impl Load for NeonLoader {
         };
         Ok((fm, module))
     }
+
+    fn comments(&self) -> Option<&dyn Comments> {
+        Some(self.swc.comments())
+    }
 }
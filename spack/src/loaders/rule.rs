@@ -0,0 +1,68 @@
+use super::{asset::AssetLoader, json::JsonLoader, text::TextLoader};
+use crate::config::{LoaderKind, ModuleConfig};
+use anyhow::Error;
+use std::sync::Arc;
+use swc_bundler::Load;
+use swc_common::{comments::Comments, FileName, SourceFile};
+use swc_ecma_ast::Module;
+
+/// Selects a loader for each file using [ModuleConfig].
+///
+/// javascript files are loaded using `js`.
+pub struct RuleLoader<L>
+where
+    L: Load,
+{
+    config: ModuleConfig,
+    js: L,
+    json: JsonLoader,
+    text: TextLoader,
+    asset: AssetLoader,
+}
+
+impl<L> RuleLoader<L>
+where
+    L: Load,
+{
+    pub fn new(
+        compiler: Arc<swc::Compiler>,
+        config: ModuleConfig,
+        public_path: String,
+        js: L,
+    ) -> Self {
+        RuleLoader {
+            config,
+            js,
+            json: JsonLoader::new(compiler.clone()),
+            text: TextLoader::new(compiler.clone()),
+            asset: AssetLoader::new(compiler, public_path),
+        }
+    }
+
+    pub fn assets(&self) -> &AssetLoader {
+        &self.asset
+    }
+}
+
+impl<L> Load for RuleLoader<L>
+where
+    L: Load,
+{
+    fn load(&self, name: &FileName) -> Result<(Arc<SourceFile>, Module), Error> {
+        let kind = match name {
+            FileName::Real(path) => self.config.loader_for(path)?,
+            _ => LoaderKind::JavaScript,
+        };
+
+        match kind {
+            LoaderKind::JavaScript => self.js.load(name),
+            LoaderKind::Json => self.json.load(name),
+            LoaderKind::Text => self.text.load(name),
+            LoaderKind::Asset => self.asset.load(name),
+        }
+    }
+
+    fn comments(&self) -> Option<&dyn Comments> {
+        self.js.comments()
+    }
+}
@@ -1,10 +1,10 @@
 use anyhow::{bail, Context, Error};
-use helpers::Helpers;
+use helpers::{Helpers, ImportStyle};
 use std::{collections::HashMap, env, sync::Arc};
 use swc::config::{InputSourceMap, JscConfig, TransformConfig};
 use swc_atoms::JsWord;
 use swc_bundler::Load;
-use swc_common::{FileName, SourceFile, DUMMY_SP};
+use swc_common::{comments::Comments, FileName, SourceFile, DUMMY_SP};
 use swc_ecma_ast::{Expr, Lit, Module, Program, Str};
 use swc_ecma_parser::JscTarget;
 use swc_ecma_transforms::{
@@ -55,7 +55,7 @@ impl Load for SwcLoader {
                 true,
                 true,
             )?;
-            let program = helpers::HELPERS.set(&Helpers::new(true), || {
+            let program = helpers::HELPERS.set(&bundler_helpers(), || {
                 swc_ecma_utils::HANDLER.set(&self.compiler.handler, || {
                     let program =
                         program.fold_with(&mut inline_globals(env_map(), Default::default()));
@@ -88,7 +88,7 @@ impl Load for SwcLoader {
                                         }
                                     },
                                     external_helpers: true,
-                                    ..c.jsc
+                                    ..c.jsc.clone()
                                 },
                                 module: None,
                                 minify: Some(false),
@@ -134,7 +134,7 @@ impl Load for SwcLoader {
             log::trace!("JsLoader.load: parsed");
 
             // Fold module
-            let program = helpers::HELPERS.set(&Helpers::new(true), || {
+            let program = helpers::HELPERS.set(&bundler_helpers(), || {
                 swc_ecma_utils::HANDLER.set(&self.compiler.handler, || {
                     let program =
                         program.fold_with(&mut inline_globals(env_map(), Default::default()));
@@ -157,6 +157,10 @@ impl Load for SwcLoader {
             _ => unreachable!(),
         }
     }
+
+    fn comments(&self) -> Option<&dyn Comments> {
+        Some(self.compiler.comments())
+    }
 }
 
 fn env_map() -> HashMap<JsWord, Expr> {
@@ -179,3 +183,15 @@ fn env_map() -> HashMap<JsWord, Expr> {
 
     m
 }
+
+/// Helpers are imported per file, so that the bundler can inline each helper
+/// only once.
+fn bundler_helpers() -> Helpers {
+    Helpers::with_config(
+        true,
+        helpers::Config {
+            import_style: ImportStyle::PerFile,
+            ..Default::default()
+        },
+    )
+}
@@ -0,0 +1,55 @@
+use anyhow::{bail, Context, Error};
+use std::sync::Arc;
+use swc_atoms::JsWord;
+use swc_bundler::Load;
+use swc_common::{FileName, SourceFile, DUMMY_SP};
+use swc_ecma_ast::*;
+
+/// Loads a file as an es module which exports the content of the file as a
+/// string.
+pub struct TextLoader {
+    compiler: Arc<swc::Compiler>,
+}
+
+impl TextLoader {
+    pub fn new(compiler: Arc<swc::Compiler>) -> Self {
+        TextLoader { compiler }
+    }
+}
+
+impl Load for TextLoader {
+    fn load(&self, name: &FileName) -> Result<(Arc<SourceFile>, Module), Error> {
+        log::debug!("TextLoader.load({})", name);
+
+        let fm = self
+            .compiler
+            .cm
+            .load_file(match name {
+                FileName::Real(v) => &v,
+                _ => bail!("text-loader only accepts path. Got `{}`", name),
+            })
+            .with_context(|| format!("failed to load file `{}`", name))?;
+
+        let module = export_default_str((&**fm.src).into());
+
+        Ok((fm, module))
+    }
+}
+
+/// Creates `export default 'value';`
+pub(super) fn export_default_str(value: JsWord) -> Module {
+    Module {
+        span: DUMMY_SP,
+        body: vec![ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(
+            ExportDefaultExpr {
+                span: DUMMY_SP,
+                expr: Box::new(Expr::Lit(Lit::Str(Str {
+                    span: DUMMY_SP,
+                    value,
+                    has_escape: false,
+                }))),
+            },
+        ))],
+        shebang: None,
+    }
+}
@@ -3,11 +3,14 @@
 //! See: https://github.com/goto-bus-stop/node-resolve
 
 use anyhow::{bail, Context, Error};
+use dashmap::DashMap;
+use regex::Regex;
 use serde::Deserialize;
 use std::{
     fs::File,
     io::BufReader,
     path::{Path, PathBuf},
+    sync::Arc,
 };
 use swc_bundler::Resolve;
 use swc_common::FileName;
@@ -20,15 +23,42 @@ struct PackageJson {
     esnext: Option<String>,
     #[serde(default)]
     main: Option<String>,
+    #[serde(rename = "sideEffects", default)]
+    side_effects: Option<SideEffects>,
 }
 
-pub struct NodeResolver;
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SideEffects {
+    Bool(bool),
+    Globs(Vec<String>),
+}
+
+/// `sideEffects` of a package.json, with globs compiled.
+enum PackageSideEffects {
+    /// The field is missing or `true`.
+    Unknown,
+    /// `false`
+    None,
+    /// Files matching one of the globs have side effects.
+    Globs(Vec<Regex>),
+    /// One of the globs is invalid, so all files are assumed to have side
+    /// effects.
+    All,
+}
+
+pub struct NodeResolver {
+    /// Package directory to its `sideEffects`.
+    side_effects: DashMap<PathBuf, Arc<PackageSideEffects>>,
+}
 
 static EXTENSIONS: &[&str] = &["ts", "tsx", "js", "jsx", "json", "node"];
 
 impl NodeResolver {
     pub fn new() -> Self {
-        Self
+        NodeResolver {
+            side_effects: Default::default(),
+        }
     }
 
     fn wrap(&self, path: PathBuf) -> Result<FileName, Error> {
@@ -76,10 +106,7 @@ impl NodeResolver {
         // TODO how to not always initialise this here?
         let root = PathBuf::from("/");
         let pkg_dir = pkg_path.parent().unwrap_or(&root);
-        let file = File::open(pkg_path)?;
-        let reader = BufReader::new(file);
-        let pkg: PackageJson =
-            serde_json::from_reader(reader).context("failed to deserialize package.json")?;
+        let pkg = self.read_package_json(pkg_path)?;
 
         for main in &[&pkg.swc_main, &pkg.esnext, &pkg.main] {
             if let Some(target) = main {
@@ -93,6 +120,48 @@ impl NodeResolver {
         bail!("package.json does not contain a \"main\" string")
     }
 
+    fn read_package_json(&self, pkg_path: &Path) -> Result<PackageJson, Error> {
+        let file = File::open(pkg_path)?;
+        let reader = BufReader::new(file);
+        serde_json::from_reader(reader).context("failed to deserialize package.json")
+    }
+
+    /// Reads `sideEffects` of the package.json in `pkg_dir`, or returns the
+    /// cached one.
+    fn package_side_effects(&self, pkg_dir: &Path) -> Arc<PackageSideEffects> {
+        if let Some(v) = self.side_effects.get(pkg_dir) {
+            return v.value().clone();
+        }
+
+        let side_effects = match self
+            .read_package_json(&pkg_dir.join("package.json"))
+            .ok()
+            .and_then(|pkg| pkg.side_effects)
+        {
+            None | Some(SideEffects::Bool(true)) => PackageSideEffects::Unknown,
+            Some(SideEffects::Bool(false)) => PackageSideEffects::None,
+            Some(SideEffects::Globs(globs)) => {
+                match globs.iter().map(|glob| glob_to_regex(glob)).collect() {
+                    Ok(globs) => PackageSideEffects::Globs(globs),
+                    Err(err) => {
+                        log::warn!(
+                            "invalid glob in sideEffects of {}: {}",
+                            pkg_dir.display(),
+                            err
+                        );
+                        // Be conservative
+                        PackageSideEffects::All
+                    }
+                }
+            }
+        };
+        let side_effects = Arc::new(side_effects);
+
+        self.side_effects
+            .insert(pkg_dir.to_path_buf(), side_effects.clone());
+        side_effects
+    }
+
     /// Resolve a directory to its index.EXT.
     fn resolve_index(&self, path: &PathBuf) -> Result<PathBuf, Error> {
         // 1. If X/index.js is a file, load X/index.js as JavaScript text.
@@ -160,4 +229,73 @@ impl Resolve for NodeResolver {
         self.resolve_node_modules(base_dir, target)
             .and_then(|p| self.wrap(p))
     }
+
+    /// Uses `sideEffects` of the nearest package.json.
+    fn side_effects(&self, path: &FileName) -> Option<bool> {
+        let path = match path {
+            FileName::Real(v) => v,
+            _ => return None,
+        };
+
+        let pkg_dir = path
+            .ancestors()
+            .skip(1)
+            .find(|dir| dir.join("package.json").is_file())?;
+
+        match *self.package_side_effects(pkg_dir) {
+            PackageSideEffects::Unknown => None,
+            PackageSideEffects::None => Some(false),
+            PackageSideEffects::All => Some(true),
+            PackageSideEffects::Globs(ref globs) => {
+                let rel = path.strip_prefix(pkg_dir).ok()?;
+                let rel = rel.to_string_lossy().replace('\\', "/");
+
+                Some(globs.iter().any(|re| re.is_match(&rel)))
+            }
+        }
+    }
+}
+
+/// Converts a glob in `sideEffects` to a regex.
+///
+/// Like webpack, globs without a slash match files in any directory.
+/// Alternatives like `{a,b}` are supported, and may be nested.
+fn glob_to_regex(glob: &str) -> Result<Regex, regex::Error> {
+    let glob = glob.trim_start_matches("./");
+    let mut re = String::from("^");
+    if !glob.contains('/') {
+        re.push_str("(?:.*/)?");
+    }
+
+    // Depth of `{}`
+    let mut depth = 0usize;
+    let mut chars = glob.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    re.push_str("(?:.*/)?");
+                } else {
+                    re.push_str(".*");
+                }
+            }
+            '*' => re.push_str("[^/]*"),
+            '?' => re.push_str("[^/]"),
+            '{' => {
+                depth += 1;
+                re.push_str("(?:");
+            }
+            ',' if depth > 0 => re.push('|'),
+            '}' if depth > 0 => {
+                depth -= 1;
+                re.push(')');
+            }
+            _ => re.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    re.push('$');
+
+    Regex::new(&re)
 }
@@ -2,7 +2,11 @@
 
 extern crate test;
 
-use spack::{loaders::swc::SwcLoader, resolvers::NodeResolver};
+use spack::{
+    config::{LoaderKind, ModuleConfig, RuleConfig},
+    loaders::{rule::RuleLoader, swc::SwcLoader},
+    resolvers::NodeResolver,
+};
 use std::{
     collections::HashMap,
     env,
@@ -11,7 +15,7 @@ use std::{
     path::{Path, PathBuf},
     sync::Arc,
 };
-use swc::config::SourceMapsConfig;
+use swc::config::{JscTarget, SourceMapsConfig};
 use swc_bundler::{BundleKind, Bundler, Config};
 use swc_common::{FileName, GLOBALS};
 use swc_ecma_transforms::fixer;
@@ -122,12 +126,23 @@ fn reference_tests(tests: &mut Vec<TestDescAndFn>, errors: bool) -> Result<(), i
                 let compiler = Arc::new(swc::Compiler::new(cm.clone(), Arc::new(handler)));
 
                 GLOBALS.set(compiler.globals(), || {
-                    let loader = SwcLoader::new(
+                    let loader = RuleLoader::new(
                         compiler.clone(),
-                        swc::config::Options {
-                            swcrc: true,
-                            ..Default::default()
+                        ModuleConfig {
+                            rules: vec![RuleConfig {
+                                test: None,
+                                extensions: vec![".txt".into()],
+                                kind: LoaderKind::Text,
+                            }],
                         },
+                        String::new(),
+                        SwcLoader::new(
+                            compiler.clone(),
+                            swc::config::Options {
+                                swcrc: true,
+                                ..Default::default()
+                            },
+                        ),
                     );
                     let bundler = Bundler::new(
                         compiler.globals(),
@@ -174,6 +189,7 @@ fn reference_tests(tests: &mut Vec<TestDescAndFn>, errors: bool) -> Result<(), i
                             .into_iter()
                             .map(From::from)
                             .collect(),
+                            ..Default::default()
                         },
                     );
 
@@ -191,6 +207,7 @@ fn reference_tests(tests: &mut Vec<TestDescAndFn>, errors: bool) -> Result<(), i
                                 SourceMapsConfig::Bool(false),
                                 None,
                                 false,
+                                JscTarget::Es2020,
                             )
                             .expect("failed to print?")
                             .code;
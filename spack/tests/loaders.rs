@@ -0,0 +1,136 @@
+use spack::loaders::{asset::AssetLoader, json::JsonLoader};
+use std::{fs, sync::Arc};
+use swc_bundler::Load;
+use swc_common::FileName;
+use swc_ecma_ast::*;
+
+fn run<F>(op: F)
+where
+    F: FnOnce(Arc<swc::Compiler>),
+{
+    testing::run_test2(false, |cm, handler| {
+        op(Arc::new(swc::Compiler::new(cm, Arc::new(handler))));
+        Ok(())
+    })
+    .unwrap();
+}
+
+fn default_export(module: &Module) -> &Expr {
+    module
+        .body
+        .iter()
+        .find_map(|item| match item {
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(export)) => Some(&*export.expr),
+            _ => None,
+        })
+        .expect("should have a default export")
+}
+
+fn str_value(expr: &Expr) -> &str {
+    match expr {
+        Expr::Lit(Lit::Str(s)) => &s.value,
+        _ => panic!("not a string literal: {:?}", expr),
+    }
+}
+
+#[test]
+fn json_duplicate_keys() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("data.json");
+    fs::write(
+        &path,
+        r#"{ "name": "swc", "version": 1, "name": "spack", "not-ident": [true, null] }"#,
+    )
+    .unwrap();
+
+    run(|compiler| {
+        let (_, module) = JsonLoader::new(compiler)
+            .load(&FileName::Real(path))
+            .unwrap();
+
+        let named = module
+            .body
+            .iter()
+            .filter_map(|item| match item {
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                    decl: Decl::Var(var),
+                    ..
+                })) => Some(&var.decls[0]),
+                _ => None,
+            })
+            .map(|decl| match &decl.name {
+                Pat::Ident(i) => (&*i.sym, &**decl.init.as_ref().unwrap()),
+                _ => unreachable!(),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(named.len(), 2);
+        assert_eq!(named[0].0, "name");
+        assert_eq!(str_value(named[0].1), "spack");
+        assert_eq!(named[1].0, "version");
+
+        // The default export refers to the exported values, and keeps other
+        // keys as-is.
+        let props = match default_export(&module) {
+            Expr::Object(obj) => &obj.props,
+            expr => panic!("default export should be an object: {:?}", expr),
+        };
+        assert_eq!(props.len(), 3);
+        match &props[0] {
+            PropOrSpread::Prop(prop) => match &**prop {
+                Prop::Shorthand(i) => assert_eq!(&*i.sym, "name"),
+                prop => panic!("should be a shorthand: {:?}", prop),
+            },
+            _ => unreachable!(),
+        }
+        match &props[2] {
+            PropOrSpread::Prop(prop) => match &**prop {
+                Prop::KeyValue(KeyValueProp {
+                    key: PropName::Str(key),
+                    value,
+                }) => {
+                    assert_eq!(&*key.value, "not-ident");
+                    assert!(matches!(**value, Expr::Array(..)));
+                }
+                prop => panic!("should be a key-value property: {:?}", prop),
+            },
+            _ => unreachable!(),
+        }
+    });
+}
+
+#[test]
+fn json_invalid() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("data.json");
+    fs::write(&path, "{ name: 'spack' }").unwrap();
+
+    run(|compiler| {
+        let err = JsonLoader::new(compiler)
+            .load(&FileName::Real(path))
+            .expect_err("json5 syntax should be rejected");
+        assert!(err.to_string().contains("failed to parse json file"));
+    });
+}
+
+#[test]
+fn asset_is_emitted() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("logo.png");
+    fs::write(&path, &[0x89, b'P', b'N', b'G']).unwrap();
+
+    run(|compiler| {
+        let loader = AssetLoader::new(compiler, "/static/".into());
+        let (_, module) = loader.load(&FileName::Real(path)).unwrap();
+
+        let url = str_value(default_export(&module));
+        assert!(url.starts_with("/static/logo."), "{}", url);
+        assert!(url.ends_with(".png"), "{}", url);
+
+        let emitted = loader.emitted_assets();
+        assert_eq!(emitted.len(), 1);
+        assert_eq!(
+            emitted.get(&url["/static/".len()..]).map(|v| &**v),
+            Some(&[0x89, b'P', b'N', b'G'][..])
+        );
+    });
+}
@@ -0,0 +1,9 @@
+import { B } from './b'
+import './c';
+
+export class A {
+    method() {
+        return new B();
+    }
+}
+
@@ -0,0 +1,6 @@
+import { A } from "./a";
+import './c';
+
+export class B extends A {
+
+}
\ No newline at end of file
@@ -0,0 +1,9 @@
+console.log('c');
+class A {
+    method() {
+        return new B();
+    }
+}
+class B extends A {
+}
+console.log(A, B);
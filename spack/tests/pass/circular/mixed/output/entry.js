@@ -1,9 +1,9 @@
+console.log('c');
+class B extends A {
+}
 class A {
     method() {
         return new B();
     }
 }
-class B extends A {
-}
-console.log('c');
 console.log(A, B);
@@ -0,0 +1,8 @@
+import { B } from './b'
+
+export class A {
+    method() {
+        return new B();
+    }
+}
+
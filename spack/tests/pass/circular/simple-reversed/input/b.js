@@ -0,0 +1,5 @@
+import { A } from "./a";
+
+export class B extends A {
+
+}
\ No newline at end of file
@@ -0,0 +1,4 @@
+import { B } from './b';
+import { A } from './a';
+
+console.log(A, B);
\ No newline at end of file
@@ -0,0 +1,8 @@
+class A {
+    method() {
+        return new B();
+    }
+}
+class B extends A {
+}
+console.log(A, B);
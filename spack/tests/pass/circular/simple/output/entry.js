@@ -1,8 +1,8 @@
+class B extends A {
+}
 class A {
     method() {
         return new B();
     }
 }
-class B extends A {
-}
 console.log(A, B);
@@ -1,4 +1,4 @@
-console.log('a');
-console.log('b');
 console.log('c');
+console.log('b');
+console.log('a');
 console.log('entry');
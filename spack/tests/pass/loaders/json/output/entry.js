@@ -0,0 +1,5 @@
+const name = "spack";
+const data = {
+    name
+};
+console.log(data.name);
@@ -0,0 +1,3 @@
+import text from './a.txt';
+
+console.log(text);
@@ -0,0 +1,2 @@
+const text = 'hello\n';
+console.log(text);
@@ -0,0 +1,5 @@
+import './b';
+import './polyfill';
+import './setup';
+
+console.log('entry');
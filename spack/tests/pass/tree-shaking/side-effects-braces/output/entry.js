@@ -0,0 +1,3 @@
+console.log('polyfill');
+console.log('setup');
+console.log('entry');
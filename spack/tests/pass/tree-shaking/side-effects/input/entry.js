@@ -0,0 +1,4 @@
+import './b';
+import './polyfill';
+
+console.log('entry');
@@ -0,0 +1,2 @@
+console.log('polyfill');
+console.log('entry');
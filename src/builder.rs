@@ -5,7 +5,11 @@ use swc_atoms::JsWord;
 use swc_common::{chain, comments::Comments, errors::Handler, Mark, SourceMap};
 use swc_ecma_parser::Syntax;
 use swc_ecma_transforms::{
-    compat, const_modules, fixer, helpers, hygiene, modules, pass::Optional, typescript,
+    compat::{self, Assumptions},
+    const_modules, fixer, helpers, hygiene,
+    modules::{self, util::ImportInterop},
+    pass::Optional,
+    typescript,
 };
 
 /// Builder is used to create a high performance `Compiler`.
@@ -16,7 +20,7 @@ pub struct PassBuilder<'a, 'b, P: swc_ecma_visit::Fold> {
     pass: P,
     global_mark: Mark,
     target: JscTarget,
-    loose: bool,
+    assumptions: Assumptions,
     hygiene: bool,
     fixer: bool,
 }
@@ -25,7 +29,7 @@ impl<'a, 'b, P: swc_ecma_visit::Fold> PassBuilder<'a, 'b, P> {
     pub fn new(
         cm: &'a Arc<SourceMap>,
         handler: &'b Handler,
-        loose: bool,
+        assumptions: Assumptions,
         global_mark: Mark,
         pass: P,
     ) -> Self {
@@ -35,7 +39,7 @@ impl<'a, 'b, P: swc_ecma_visit::Fold> PassBuilder<'a, 'b, P> {
             pass,
             target: JscTarget::Es5,
             global_mark,
-            loose,
+            assumptions,
             hygiene: true,
             env: None,
             fixer: true,
@@ -52,7 +56,7 @@ impl<'a, 'b, P: swc_ecma_visit::Fold> PassBuilder<'a, 'b, P> {
             handler: self.handler,
             pass,
             target: self.target,
-            loose: self.loose,
+            assumptions: self.assumptions,
             hygiene: self.hygiene,
             env: self.env,
             global_mark: self.global_mark,
@@ -120,50 +124,47 @@ impl<'a, 'b, P: swc_ecma_visit::Fold> PassBuilder<'a, 'b, P> {
     where
         P: 'cmt,
     {
-        let need_interop_analysis = match module {
-            Some(ModuleConfig::CommonJs(ref c)) => !c.no_interop,
-            Some(ModuleConfig::Amd(ref c)) => !c.config.no_interop,
-            Some(ModuleConfig::Umd(ref c)) => !c.config.no_interop,
-            Some(ModuleConfig::Es6) | None => false,
+        // Only commonjs converts `import()`.
+        let (import_interop, ignore_dynamic) = match module {
+            Some(ModuleConfig::CommonJs(ref c)) => (c.import_interop(), c.ignore_dynamic),
+            Some(ModuleConfig::Amd(ref c)) => (c.config.import_interop(), true),
+            Some(ModuleConfig::Umd(ref c)) => (c.config.import_interop(), true),
+            Some(ModuleConfig::SystemJs(..)) | Some(ModuleConfig::Es6) | None => {
+                (ImportInterop::None, true)
+            }
         };
+        let need_interop_analysis = import_interop != ImportInterop::None;
 
         // compat
         let compat_pass = if let Some(env) = self.env {
             Either::Left(chain!(
                 Optional::new(typescript::strip(), syntax.typescript()),
-                swc_ecma_preset_env::preset_env(self.global_mark, env)
+                swc_ecma_preset_env::preset_env(self.global_mark, comments, env)
             ))
         } else {
+            let es2020 = self.assumptions.es2020();
             Either::Right(chain!(
                 Optional::new(
-                    compat::es2020::nullish_coalescing(),
+                    compat::es2020::nullish_coalescing(es2020.nullish_coalescing),
                     self.target < JscTarget::Es2020
                 ),
                 Optional::new(
-                    compat::es2020::optional_chaining(),
+                    compat::es2020::optional_chaining(es2020.optional_chaining),
                     self.target < JscTarget::Es2020
                 ),
                 Optional::new(
-                    compat::es2020::class_properties(),
+                    compat::es2020::class_properties(es2020.class_properties),
                     self.target < JscTarget::Es2020,
                 ),
                 Optional::new(typescript::strip(), syntax.typescript()),
-                Optional::new(compat::es2018(), self.target <= JscTarget::Es2018),
+                Optional::new(
+                    compat::es2018(self.assumptions.es2018()),
+                    self.target <= JscTarget::Es2018
+                ),
                 Optional::new(compat::es2017(), self.target <= JscTarget::Es2017),
                 Optional::new(compat::es2016(), self.target <= JscTarget::Es2016),
                 Optional::new(
-                    compat::es2015(
-                        self.global_mark,
-                        compat::es2015::Config {
-                            for_of: compat::es2015::for_of::Config {
-                                assume_array: self.loose
-                            },
-                            spread: compat::es2015::spread::Config { loose: self.loose },
-                            destructuring: compat::es2015::destructuring::Config {
-                                loose: self.loose
-                            },
-                        }
-                    ),
+                    compat::es2015(self.global_mark, comments, self.assumptions.es2015()),
                     self.target <= JscTarget::Es2015
                 ),
                 Optional::new(
@@ -179,7 +180,7 @@ impl<'a, 'b, P: swc_ecma_visit::Fold> PassBuilder<'a, 'b, P> {
             compat::reserved_words::reserved_words(),
             // module / helper
             Optional::new(
-                modules::import_analysis::import_analyzer(),
+                modules::import_analysis::import_analyzer(import_interop, ignore_dynamic),
                 need_interop_analysis
             ),
             helpers::inject_helpers(),
@@ -2,6 +2,7 @@ use crate::builder::PassBuilder;
 use anyhow::{bail, Context, Error};
 use dashmap::DashMap;
 use either::Either;
+use indexmap::IndexMap;
 use once_cell::sync::Lazy;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
@@ -19,9 +20,12 @@ use swc_ecma_ast::{Expr, ExprStmt, ModuleItem, Stmt};
 pub use swc_ecma_parser::JscTarget;
 use swc_ecma_parser::{lexer::Lexer, Parser, StringInput, Syntax, TsConfig};
 use swc_ecma_transforms::{
-    compat::es2020::typescript_class_properties,
-    const_modules, modules,
-    optimization::{inline_globals, json_parse, simplifier},
+    compat::{es2020::typescript_class_properties, Assumptions},
+    const_modules, flow, helpers, modules,
+    optimization::{
+        inline_globals, json_parse, simplifier,
+        simplify::{self, dce},
+    },
     pass::{noop, Optional},
     proposals::{decorators, export},
     react, resolver_with_mark, typescript,
@@ -177,10 +181,22 @@ impl Options {
             transform,
             syntax,
             external_helpers,
+            helpers,
             target,
+            target_is_set,
             loose,
+            assumptions,
+            base_url,
+            paths,
+            rewrite_relative_import_extensions,
         } = config.jsc;
 
+        let assumptions = if loose {
+            assumptions.loose()
+        } else {
+            assumptions
+        };
+
         let syntax = syntax.unwrap_or_default();
         let mut transform = transform.unwrap_or_default();
 
@@ -222,9 +238,33 @@ impl Options {
             .global_mark
             .unwrap_or_else(|| Mark::fresh(Mark::root()));
 
+        let import_rewriter = {
+            let enabled =
+                base_url.is_some() || !paths.is_empty() || rewrite_relative_import_extensions;
+
+            let base = if self.filename.is_empty() {
+                FileName::Anon
+            } else {
+                FileName::Real(self.resolve_path(Path::new(&self.filename)))
+            };
+            let resolver = modules::path::TsConfigResolver {
+                base_url: base_url.map(|base_url| self.resolve_path(&base_url)),
+                paths: paths.into_iter().collect(),
+                rewrite_extensions: rewrite_relative_import_extensions,
+            };
+
+            Optional::new(
+                modules::path::import_rewriter(base, resolver, root_mark),
+                enabled,
+            )
+        };
+
         let pass = chain!(
             // handle jsx
-            Optional::new(react::react(cm.clone(), transform.react), syntax.jsx()),
+            Optional::new(
+                react::react(cm.clone(), comments, transform.react),
+                syntax.jsx()
+            ),
             // Decorators may use type information
             Optional::new(
                 decorators(decorators::Config {
@@ -233,20 +273,42 @@ impl Options {
                 }),
                 syntax.decorators()
             ),
-            Optional::new(typescript_class_properties(), syntax.typescript()),
+            Optional::new(
+                typescript_class_properties(assumptions.es2020().class_properties),
+                syntax.typescript()
+            ),
             Optional::new(typescript::strip(), syntax.typescript()),
+            Optional::new(flow::strip(), syntax.flow()),
             resolver_with_mark(root_mark),
+            import_rewriter,
             const_modules,
             optimization,
             Optional::new(
                 export(),
                 syntax.export_default_from() || syntax.export_namespace_from()
             ),
-            Optional::new(simplifier(Default::default()), enable_optimizer),
+            Optional::new(
+                simplifier(simplify::Config {
+                    dce: dce::Config {
+                        comments,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }),
+                enable_optimizer
+            ),
             json_parse_pass
         );
 
-        let pass = PassBuilder::new(&cm, &handler, loose, root_mark, pass)
+        // Syntax supported by the targets of `env` is not lowered.
+        let (codegen_target, check_target) = if config.env.is_some() {
+            (JscTarget::Es2020, false)
+        } else {
+            // Only syntax of an explicit target is checked while printing.
+            (target, target_is_set)
+        };
+
+        let pass = PassBuilder::new(&cm, &handler, assumptions, root_mark, pass)
             .target(target)
             .hygiene(!self.disable_hygiene)
             .fixer(!self.disable_fixer)
@@ -256,9 +318,12 @@ impl Options {
         BuiltConfig {
             minify: config.minify.unwrap_or(false),
             pass,
-            external_helpers,
+            external_helpers: external_helpers || helpers.is_some(),
+            helpers: helpers.unwrap_or_default(),
             syntax,
             target,
+            codegen_target,
+            check_target,
             is_module,
             source_maps: self
                 .source_maps
@@ -267,6 +332,16 @@ impl Options {
             input_source_map: self.input_source_map.clone(),
         }
     }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn resolve_path(&self, path: &Path) -> PathBuf {
+        self.cwd.join(path)
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn resolve_path(&self, path: &Path) -> PathBuf {
+        path.to_path_buf()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -333,6 +408,7 @@ impl Default for Rc {
                     external_helpers: false,
                     target: Default::default(),
                     loose: false,
+                    ..Default::default()
                 },
                 module: None,
                 minify: None,
@@ -350,6 +426,7 @@ impl Default for Rc {
                     external_helpers: false,
                     target: Default::default(),
                     loose: false,
+                    ..Default::default()
                 },
                 module: None,
                 minify: None,
@@ -367,6 +444,7 @@ impl Default for Rc {
                     external_helpers: false,
                     target: Default::default(),
                     loose: false,
+                    ..Default::default()
                 },
                 module: None,
                 minify: None,
@@ -501,30 +579,114 @@ pub struct BuiltConfig<P: swc_ecma_visit::Fold> {
     pub pass: P,
     pub syntax: Syntax,
     pub target: JscTarget,
+    /// The target of printed literals.
+    pub codegen_target: JscTarget,
+    /// Syntax which is not supported by `codegen_target` is an error while
+    /// printing. Enabled if `jsc.target` is set explicitly.
+    pub check_target: bool,
     pub minify: bool,
     pub external_helpers: bool,
+    /// Used if `external_helpers` is true.
+    pub helpers: helpers::Config,
     pub source_maps: SourceMapsConfig,
     pub input_source_map: InputSourceMap,
     pub is_module: bool,
 }
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
-#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", from = "JscConfigRepr")]
 pub struct JscConfig {
-    #[serde(rename = "parser", default)]
+    #[serde(rename = "parser")]
     pub syntax: Option<Syntax>,
 
-    #[serde(default)]
     pub transform: Option<TransformConfig>,
 
-    #[serde(default)]
     pub external_helpers: bool,
 
-    #[serde(default)]
+    /// Configures how external helpers are imported. Helpers are external if
+    /// this is set.
+    pub helpers: Option<helpers::Config>,
+
+    /// Defaults to es5.
     pub target: JscTarget,
 
-    #[serde(default)]
+    /// [true] if `target` is set by the config instead of being the default.
+    ///
+    /// Syntax which is not supported by the target is an error only if this
+    /// is set.
+    #[serde(skip)]
+    pub target_is_set: bool,
+
     pub loose: bool,
+
+    /// Assumptions about the input code. `loose` enables some of them.
+    pub assumptions: Assumptions,
+
+    /// Like `compilerOptions.baseUrl` of tsconfig. Relative to `cwd`.
+    pub base_url: Option<PathBuf>,
+
+    /// Like `compilerOptions.paths` of tsconfig. Targets are relative to
+    /// `baseUrl`, so this is ignored if `baseUrl` is not set.
+    ///
+    /// The order of declaration is preserved, as the first pattern wins if
+    /// patterns match equally.
+    pub paths: IndexMap<String, Vec<String>>,
+
+    /// Rewrites `.ts` extensions of relative module specifiers to `.js`.
+    pub rewrite_relative_import_extensions: bool,
+}
+
+/// [JscConfig] as written in a config, which knows if `target` is set.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+struct JscConfigRepr {
+    #[serde(rename = "parser", default)]
+    syntax: Option<Syntax>,
+
+    #[serde(default)]
+    transform: Option<TransformConfig>,
+
+    #[serde(default)]
+    external_helpers: bool,
+
+    #[serde(default)]
+    helpers: Option<helpers::Config>,
+
+    #[serde(default)]
+    target: Option<JscTarget>,
+
+    #[serde(default)]
+    loose: bool,
+
+    #[serde(default)]
+    assumptions: Assumptions,
+
+    #[serde(default)]
+    base_url: Option<PathBuf>,
+
+    #[serde(default)]
+    paths: IndexMap<String, Vec<String>>,
+
+    #[serde(default)]
+    rewrite_relative_import_extensions: bool,
+}
+
+impl From<JscConfigRepr> for JscConfig {
+    fn from(c: JscConfigRepr) -> Self {
+        JscConfig {
+            syntax: c.syntax,
+            transform: c.transform,
+            external_helpers: c.external_helpers,
+            helpers: c.helpers,
+            target: c.target.unwrap_or_default(),
+            target_is_set: c.target.is_some(),
+            loose: c.loose,
+            assumptions: c.assumptions,
+            base_url: c.base_url,
+            paths: c.paths,
+            rewrite_relative_import_extensions: c.rewrite_relative_import_extensions,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -537,6 +699,8 @@ pub enum ModuleConfig {
     Umd(modules::umd::Config),
     #[serde(rename = "amd")]
     Amd(modules::amd::Config),
+    #[serde(rename = "systemjs")]
+    SystemJs(modules::system_js::Config),
     #[serde(rename = "es6")]
     Es6,
 }
@@ -554,6 +718,7 @@ impl ModuleConfig {
             }
             Some(ModuleConfig::Umd(config)) => Box::new(modules::umd::umd(cm, root_mark, config)),
             Some(ModuleConfig::Amd(config)) => Box::new(modules::amd::amd(config)),
+            Some(ModuleConfig::SystemJs(config)) => Box::new(modules::system_js::system_js(config)),
         }
     }
 }
@@ -743,7 +908,20 @@ impl Merge for JscConfig {
         self.syntax.merge(&from.syntax);
         self.transform.merge(&from.transform);
         self.target.merge(&from.target);
+        self.target_is_set |= from.target_is_set;
         self.external_helpers.merge(&from.external_helpers);
+        self.assumptions.merge(&from.assumptions);
+        if from.helpers.is_some() {
+            self.helpers = from.helpers.clone();
+        }
+        if from.base_url.is_some() {
+            self.base_url = from.base_url.clone();
+        }
+        if !from.paths.is_empty() {
+            self.paths = from.paths.clone();
+        }
+        self.rewrite_relative_import_extensions
+            .merge(&from.rewrite_relative_import_extensions);
     }
 }
 
@@ -764,6 +942,24 @@ impl Merge for Option<ModuleConfig> {
     }
 }
 
+impl Merge for Assumptions {
+    fn merge(&mut self, from: &Self) {
+        self.constant_super.merge(&from.constant_super);
+        self.ignore_function_length
+            .merge(&from.ignore_function_length);
+        self.iterable_is_array.merge(&from.iterable_is_array);
+        self.no_class_calls.merge(&from.no_class_calls);
+        self.no_document_all.merge(&from.no_document_all);
+        self.private_fields_as_properties
+            .merge(&from.private_fields_as_properties);
+        self.pure_getters.merge(&from.pure_getters);
+        self.set_public_class_fields
+            .merge(&from.set_public_class_fields);
+        self.set_spread_properties
+            .merge(&from.set_spread_properties);
+    }
+}
+
 impl Merge for bool {
     fn merge(&mut self, from: &Self) {
         *self |= *from
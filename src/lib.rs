@@ -14,10 +14,10 @@ use serde_json::error::Category;
 use std::{
     fs::{read_to_string, File},
     path::{Path, PathBuf},
-    sync::Arc,
+    sync::{Arc, Mutex},
 };
 use swc_common::{
-    comments::{Comment, Comments},
+    comments::{is_annotation, Comment, Comments},
     errors::Handler,
     input::StringInput,
     BytePos, FileName, Globals, SourceFile, SourceMap, Spanned, GLOBALS,
@@ -43,6 +43,11 @@ pub struct Compiler {
     pub cm: Arc<SourceMap>,
     pub handler: Arc<Handler>,
     comments: SwcComments,
+    /// Number of files being processed.
+    ///
+    /// Positions handed out by `Span::dummy_with_cmt` are reused once no file
+    /// is processed, as only 2^16 of them are reserved.
+    files_in_progress: Mutex<usize>,
 }
 
 #[derive(Debug, Serialize)]
@@ -190,6 +195,29 @@ impl Compiler {
         source_map: SourceMapsConfig,
         orig: Option<&sourcemap::SourceMap>,
         minify: bool,
+        target: JscTarget,
+    ) -> Result<TransformOutput, Error>
+    where
+        T: Node,
+    {
+        self.print_with_config(
+            node,
+            source_map,
+            orig,
+            swc_ecma_codegen::Config {
+                minify,
+                target,
+                ..Default::default()
+            },
+        )
+    }
+
+    fn print_with_config<T>(
+        &self,
+        node: &T,
+        source_map: SourceMapsConfig,
+        orig: Option<&sourcemap::SourceMap>,
+        cfg: swc_ecma_codegen::Config,
     ) -> Result<TransformOutput, Error>
     where
         T: Node,
@@ -201,8 +229,12 @@ impl Compiler {
                 let mut buf = vec![];
                 {
                     let mut emitter = Emitter {
-                        cfg: swc_ecma_codegen::Config { minify },
-                        comments: if minify { None } else { Some(&self.comments) },
+                        cfg,
+                        comments: if cfg.minify {
+                            None
+                        } else {
+                            Some(&self.comments)
+                        },
                         cm: self.cm.clone(),
                         wr: Box::new(swc_ecma_codegen::text_writer::JsWriter::new(
                             self.cm.clone(),
@@ -271,6 +303,7 @@ impl Compiler {
             handler,
             globals: Globals::new(),
             comments: Default::default(),
+            files_in_progress: Mutex::new(0),
         }
     }
 
@@ -429,28 +462,78 @@ impl Compiler {
         orig: Option<&sourcemap::SourceMap>,
         config: BuiltConfig<impl swc_ecma_visit::Fold>,
     ) -> Result<TransformOutput, Error> {
+        let _file = FileInProgress::new(self);
+
         self.run(|| {
             if config.minify {
+                // Annotations are kept for the optimizer.
                 let preserve_excl = |_: &BytePos, vc: &mut Vec<Comment>| -> bool {
-                    vc.retain(|c: &Comment| c.text.starts_with("!"));
+                    vc.retain(|c: &Comment| c.text.starts_with("!") || is_annotation(c, "PURE"));
                     !vc.is_empty()
                 };
                 self.comments.leading.retain(preserve_excl);
                 self.comments.trailing.retain(preserve_excl);
             }
             let mut pass = config.pass;
-            let program = helpers::HELPERS.set(&Helpers::new(config.external_helpers), || {
+            let helpers = Helpers::with_config(config.external_helpers, config.helpers);
+            let program = helpers::HELPERS.set(&helpers, || {
                 util::HANDLER.set(&self.handler, || {
                     // Fold module
                     program.fold_with(&mut pass)
                 })
             });
 
-            self.print(&program, config.source_maps, orig, config.minify)
+            self.print_with_config(
+                &program,
+                config.source_maps,
+                orig,
+                swc_ecma_codegen::Config {
+                    minify: config.minify,
+                    target: config.codegen_target,
+                    check_target: config.check_target,
+                    ..Default::default()
+                },
+            )
         })
     }
 }
 
+/// Marks a file as being processed by [Compiler::process_js_inner].
+struct FileInProgress<'a> {
+    compiler: &'a Compiler,
+}
+
+impl<'a> FileInProgress<'a> {
+    fn new(compiler: &'a Compiler) -> Self {
+        *compiler.files_in_progress.lock().unwrap() += 1;
+        FileInProgress { compiler }
+    }
+}
+
+impl Drop for FileInProgress<'_> {
+    fn drop(&mut self) {
+        let mut cnt = match self.compiler.files_in_progress.lock() {
+            Ok(cnt) => cnt,
+            Err(err) => err.into_inner(),
+        };
+        *cnt -= 1;
+        if *cnt != 0 {
+            return;
+        }
+
+        // Comments of synthesized nodes which were not emitted would be attached
+        // to the nodes of the next file.
+        let comments = &self.compiler.comments;
+        comments
+            .leading
+            .retain(|pos, _| !pos.is_reserved_for_comments());
+        comments
+            .trailing
+            .retain(|pos, _| !pos.is_reserved_for_comments());
+        self.compiler.globals.reset_dummy_cmt();
+    }
+}
+
 fn load_swcrc(path: &Path) -> Result<Rc, Error> {
     fn convert_json_err(e: serde_json::Error) -> Error {
         let line = e.line();
@@ -480,6 +563,14 @@ fn load_swcrc(path: &Path) -> Result<Rc, Error> {
         .map_err(convert_json_err)
 }
 
+/// Returns a long-form explanation of a diagnostic code emitted by the parser
+/// or by a transform.
+///
+/// Returns `None` if `code` is unknown.
+pub fn explain(code: &str) -> Option<&'static str> {
+    swc_ecma_parser::error::explain(code).or_else(|| swc_ecma_transforms::explain(code))
+}
+
 type CommentMap = Arc<DashMap<BytePos, Vec<Comment>>>;
 
 /// Multi-threaded implementation of [Comments]
@@ -514,6 +605,10 @@ impl Comments for SwcComments {
         self.leading.remove(&pos).map(|v| v.1)
     }
 
+    fn get_leading(&self, pos: BytePos) -> Option<Vec<Comment>> {
+        self.leading.get(&pos).map(|v| v.value().clone())
+    }
+
     fn add_trailing(&self, pos: BytePos, cmt: Comment) {
         self.trailing.entry(pos).or_default().push(cmt)
     }
@@ -537,4 +632,8 @@ impl Comments for SwcComments {
     fn take_trailing(&self, pos: BytePos) -> Option<Vec<Comment>> {
         self.trailing.remove(&pos).map(|v| v.1)
     }
+
+    fn get_trailing(&self, pos: BytePos) -> Option<Vec<Comment>> {
+        self.trailing.get(&pos).map(|v| v.value().clone())
+    }
 }
@@ -371,6 +371,26 @@ fn issue_605() {
     assert!(f.contains("Object.keys(_c)"));
 }
 
+/// Module syntax is not checked against an explicit `jsc.target`.
+#[test]
+fn es5_module() {
+    let f = file("tests/projects/es5-module/input.js").unwrap();
+    println!("{}", f);
+
+    assert!(f.contains("import foo from"));
+    assert!(f.contains("export var bar = function"));
+    assert!(f.contains("export default import("));
+}
+
+#[test]
+fn es5_commonjs_dynamic_import() {
+    let f = file("tests/projects/es5-commonjs/input.js").unwrap();
+    println!("{}", f);
+
+    assert!(f.contains("require("));
+    assert!(f.contains("import("));
+}
+
 #[test]
 fn await_expr() {
     let f = file("tests/projects/await-expression/input.js").unwrap();
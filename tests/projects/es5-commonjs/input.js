@@ -0,0 +1,4 @@
+import foo from './foo';
+
+export const bar = () => foo;
+export default import('./baz');
@@ -94,3 +94,45 @@ fn issue_834_3() {
         },
     );
 }
+
+/// Positions used for comments of synthesized nodes are reused for each file,
+/// so a long-running compiler does not run out of them.
+#[test]
+fn pure_annotations_of_many_classes() {
+    const CLASSES: usize = 25_000;
+
+    let src = (0..CLASSES)
+        .map(|i| format!("class A{} {{ foo() {{}} }}\n", i))
+        .collect::<String>();
+
+    Tester::new()
+        .print_errors(|cm, handler| {
+            let c = Compiler::new(cm.clone(), Arc::new(handler));
+
+            // Each class uses two positions, for the class and `_createClass`.
+            let counts = (0..3)
+                .map(|i| {
+                    let fm = cm.new_source_file(
+                        FileName::Real(format!("input-{}.js", i).into()),
+                        src.clone(),
+                    );
+                    let output = c
+                        .process_js_file(
+                            fm,
+                            &Options {
+                                swcrc: false,
+                                is_module: true,
+                                ..Default::default()
+                            },
+                        )
+                        .expect("failed to process file");
+
+                    output.code.matches("/*#__PURE__*/").count()
+                })
+                .collect::<Vec<_>>();
+
+            assert_eq!(counts, vec![2 * CLASSES; 3]);
+            Ok(())
+        })
+        .unwrap();
+}
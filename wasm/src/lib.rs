@@ -5,7 +5,7 @@ use std::{
     sync::{Arc, RwLock},
 };
 use swc::{
-    config::{Options, ParseOptions, SourceMapsConfig},
+    config::{JscTarget, Options, ParseOptions, SourceMapsConfig},
     Compiler,
 };
 use swc_common::{
@@ -55,6 +55,7 @@ pub fn print_sync(s: JsValue, opts: JsValue) -> Result<JsValue, JsValue> {
                 .unwrap_or(SourceMapsConfig::Bool(false)),
             None,
             opts.config.unwrap_or_default().minify.unwrap_or_default(),
+            JscTarget::Es2020,
         )
         .map_err(|err| format!("failed to print: {}\n{}", err, errors))?;
 